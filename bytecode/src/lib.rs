@@ -1 +1,234 @@
-// Type definitions for bytecode. Owned by no one. Pure contracts.
+//! # Score — Bytecode
+//!
+//! Type definitions for bytecode. Owned by no one. Pure contracts.
+//!
+//! Instruction set shared between Maestro (writes) and Sonata (reads).
+//! The VM is a stack machine: every expression pushes exactly one value
+//! onto the operand stack, and instructions pop their operands from it.
+
+use std::collections::HashMap;
+
+use elise_shared::shared_types::Span;
+
+/// Literal values referenced by `Instruction::Const`. Numbers are
+/// kept as written in the source code and parsed by the VM.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Constant {
+    Int(String),
+    Float(String),
+    String(String),
+    Bool(bool),
+    Null,
+}
+
+/// String function other than concatenation. Lengths and
+/// positions are counted in characters, not bytes.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StrFn {
+    Length,
+    Substring,
+    Split,
+    Join,
+    Trim,
+    Upper,
+    Lower,
+    StartsWith,
+    EndsWith,
+    Replace,
+    Matches,
+    Extract,
+    ExtractAll,
+}
+
+/// Primitive type `Instruction::Convert` converts a value to.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ConvertTo {
+    Int,
+    Float,
+    Str,
+    Bool,
+}
+
+/// What `Instruction::Convert` pushes if the value can't be converted.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum OnFailure {
+    // Fails the program at the span of the instruction.
+    Fail,
+    // Pushes the fallback value.
+    Fallback,
+    Null,
+}
+
+/// Function of Dates, Times and DateTimes.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DateFn {
+    Add,
+    Diff,
+    Trunc,
+    Format,
+}
+
+/// Aggregation over a List of values.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AggregateFn {
+    Count,
+    CountDistinct,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+/// Grouping referenced by `Instruction::GroupAggregate`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AggregateSpec {
+    // Fields of the rows that make up the key of a group.
+    pub keys: Vec<String>,
+    // Field of the resulting Dict, aggregation and the field
+    // of the rows it reads, which Count doesn't need.
+    pub aggregations: Vec<(String, AggregateFn, Option<String>)>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Instruction {
+    // Pushes a constant from the pool by its index.
+    Const(usize),
+    // Discards the value on top of the stack.
+    Pop,
+    // Reads a symbol's slot and pushes its value.
+    Load(usize),
+    // Writes the value on top of the stack into a symbol's slot.
+    // The value stays on the stack.
+    Store(usize),
+    // Continues execution from the instruction index.
+    Jump(usize),
+    // Pops a Bool and continues from the instruction index if it
+    // is false, otherwise continues with the next instruction.
+    JumpIfFalse(usize),
+    // Pop 2 operands, push the result.
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    // Pops 1 operand, pushes the result.
+    Neg,
+    // Pop 2 operands, push a Bool.
+    Eq,
+    Neq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    // Pops 2 Strings, pushes their concatenation.
+    Concat,
+    // Pops the arguments of the function in the order they were
+    // pushed, pushes the result. Omitted optional arguments are
+    // pushed as Null.
+    Str(StrFn),
+    // Pops the value and the second argument of the function,
+    // pushes the result.
+    Date(DateFn),
+    // Pops a fallback value for OnFailure::Fallback, then pops
+    // a value and pushes it converted to the type.
+    Convert(ConvertTo, OnFailure),
+    // Pops the number of values, pushes a List of them
+    // in the order they were pushed.
+    MakeList(usize),
+    // Pops a List, pushes its length as an Int.
+    Len,
+    // Pops an Int index and a List, pushes the item.
+    Index,
+    // Pops the number of key and value pairs, every key pushed
+    // before its value, pushes a Dict of them in that order.
+    MakeDict(usize),
+    // Pushes the data provided through the slot named by the
+    // String constant from the pool by its index.
+    LoadData(usize),
+    // Pops a key and a compound, pushes the value of a Dict by
+    // a String key or the item of a List by an Int index. Keys
+    // missing from a Dict push Null, since optional fields can
+    // be absent from the data, while an index out of the List
+    // fails at the span of the instruction.
+    Get,
+    // Pops a value and a List, pushes the List with the
+    // value appended.
+    Append,
+    // Pops 2 Lists, pushes them joined.
+    Extend,
+    // Pops a List of keys and a List of items, pushes the items
+    // ordered by their keys. Items with equal keys keep their order.
+    SortBy,
+    // Pop an Int and a List, push the List without the items
+    // after (Take) or before (Drop) the Int.
+    Take,
+    Drop,
+    // Pops a List of keys and a List of items, pushes a List of
+    // groups of items with equal keys. Items are put into a hash
+    // table by their keys in one pass, groups keep the order of
+    // their first items.
+    GroupBy,
+    // Pops a List, pushes the aggregated value. Sum and counting
+    // of an empty List push 0, the rest push Null.
+    Aggregate(AggregateFn),
+    // Pops a List of Dicts, pushes a List of Dicts grouped by the
+    // spec from the pool by its index. Every row updates the
+    // running aggregations of its group found in a hash table
+    // by the values of the key fields, so the rows are read once.
+    GroupAggregate(usize),
+}
+
+/// Compiled program. The value left on the stack after the last
+/// instruction is the result of the program.
+#[derive(Debug, PartialEq, Default)]
+pub struct Chunk {
+    pub instructions: Vec<Instruction>,
+    pub constants: Vec<Constant>,
+    pub aggregate_specs: Vec<AggregateSpec>,
+    // Spans of the source code of instructions that can fail at
+    // runtime by their indexes, so failures point at the code.
+    pub spans: HashMap<usize, Span>,
+}
+
+impl Chunk {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an instruction and returns its index.
+    pub fn emit(&mut self, instruction: Instruction) -> usize {
+        self.instructions.push(instruction);
+        self.instructions.len() - 1
+    }
+
+    /// Appends an instruction that can fail at runtime along with
+    /// the span of its source code and returns its index.
+    pub fn emit_with_span(&mut self, instruction: Instruction, span: Span) -> usize {
+        let idx = self.emit(instruction);
+        self.spans.insert(idx, span);
+        idx
+    }
+
+    /// Adds a constant to the pool and returns its index.
+    pub fn add_constant(&mut self, constant: Constant) -> usize {
+        self.constants.push(constant);
+        self.constants.len() - 1
+    }
+
+    /// Adds a grouping to the pool and returns its index.
+    pub fn add_aggregate_spec(&mut self, spec: AggregateSpec) -> usize {
+        self.aggregate_specs.push(spec);
+        self.aggregate_specs.len() - 1
+    }
+
+    /// Points a previously emitted jump to the current end of
+    /// the chunk, once the jump target is known.
+    pub fn patch_jump(&mut self, idx: usize) {
+        let target = self.instructions.len();
+
+        match &mut self.instructions[idx] {
+            Instruction::Jump(offset) | Instruction::JumpIfFalse(offset) => *offset = target,
+            instruction => panic!("Expected jump instruction, found {instruction:?}"),
+        }
+    }
+}
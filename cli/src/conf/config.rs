@@ -5,6 +5,8 @@ impl FileExt {
     pub const DATA_SCHEMA: &[&str] = &[".elt"];
     pub const CSV: &str = ".csv";
     pub const DATA: &[&str] = &[Self::CSV];
+    // Every file that shares the language syntax.
+    pub const SYNTAX: &[&str] = &[".eli", ".elt"];
 }
 
 pub struct ArgName;
@@ -16,6 +18,10 @@ impl ArgName {
     pub const EXECUTABLE: &str = "executable";
     pub const OUTPUT: &str = "output";
     pub const PRINT_BYTECODE: &str = "print-bytecode";
+    pub const DENY_WARNINGS: &str = "deny-warnings";
+    pub const FILE: &str = "file";
+    pub const CHECK: &str = "check";
+    pub const WHAT: &str = "what";
 }
 
 pub struct ArgValue;
@@ -26,18 +32,28 @@ impl ArgValue {
     pub const MODE_BUILD: &str = "build";
     pub const MODE_EXEC: &str = "exec";
     pub const MODE_VALIDATE: &str = "validate";
+    pub const MODE_FMT: &str = "fmt";
+    pub const MODE_DUMP: &str = "dump";
 
-    pub const MODES: [&str; 4] = [
+    pub const MODES: [&str; 6] = [
         Self::MODE_RUN,
         Self::MODE_BUILD,
         Self::MODE_EXEC,
         Self::MODE_VALIDATE,
+        Self::MODE_FMT,
+        Self::MODE_DUMP,
     ];
+
+    pub const DUMP_AST: &str = "ast";
+    pub const DUMP_SCHEMA: &str = "schema";
+    pub const DUMP_TARGETS: &[&str] = &[Self::DUMP_AST, Self::DUMP_SCHEMA];
 }
 
 pub enum ArgType {
     SourceFile(&'static [&'static str]),
     Boolean,
+    // One of the listed values.
+    OneOf(&'static [&'static str]),
     Any,
 }
 
@@ -79,6 +95,12 @@ pub const RUN_ARGS: &[Arg] = &[
         req: false,
         def: Some(ArgValue::BOOL_FALSE),
     },
+    Arg {
+        name: ArgName::DENY_WARNINGS,
+        ty: ArgType::Boolean,
+        req: false,
+        def: Some(ArgValue::BOOL_FALSE),
+    },
 ];
 
 pub const BUILD_ARGS: &[Arg] = &[
@@ -100,6 +122,12 @@ pub const BUILD_ARGS: &[Arg] = &[
         req: true,
         def: None,
     },
+    Arg {
+        name: ArgName::DENY_WARNINGS,
+        ty: ArgType::Boolean,
+        req: false,
+        def: Some(ArgValue::BOOL_FALSE),
+    },
 ];
 
 pub const EXEC_ARGS: &[Arg] = &[
@@ -131,3 +159,39 @@ pub const VALIDATE_ARGS: &[Arg] = &[
         def: None,
     },
 ];
+
+pub const FMT_ARGS: &[Arg] = &[
+    Arg {
+        name: ArgName::FILE,
+        ty: ArgType::SourceFile(FileExt::SYNTAX),
+        req: true,
+        def: None,
+    },
+    Arg {
+        name: ArgName::CHECK,
+        ty: ArgType::Boolean,
+        req: false,
+        def: Some(ArgValue::BOOL_FALSE),
+    },
+];
+
+pub const DUMP_ARGS: &[Arg] = &[
+    Arg {
+        name: ArgName::FILE,
+        ty: ArgType::SourceFile(FileExt::SYNTAX),
+        req: true,
+        def: None,
+    },
+    Arg {
+        name: ArgName::WHAT,
+        ty: ArgType::OneOf(ArgValue::DUMP_TARGETS),
+        req: false,
+        def: Some(ArgValue::DUMP_AST),
+    },
+    Arg {
+        name: ArgName::OUTPUT,
+        ty: ArgType::Any,
+        req: false,
+        def: None,
+    },
+];
@@ -8,7 +8,9 @@ pub mod config;
 
 use std::collections::HashMap;
 
-use config::{ArgName, ArgValue, BUILD_ARGS, EXEC_ARGS, RUN_ARGS, VALIDATE_ARGS};
+use config::{
+    ArgName, ArgValue, BUILD_ARGS, DUMP_ARGS, EXEC_ARGS, FMT_ARGS, RUN_ARGS, VALIDATE_ARGS,
+};
 
 use crate::conf::config::ArgType;
 
@@ -32,6 +34,8 @@ pub struct ModeRunConf {
     pub data_schema_path: String,
     pub print_bytecode: bool,
     pub output_path: Option<String>,
+    // Whether semantic warnings must fail the program.
+    pub deny_warnings: bool,
 }
 
 #[derive(Debug, PartialEq)]
@@ -39,6 +43,7 @@ pub struct ModeBuildConf {
     pub source_code_path: String,
     pub data_schema_path: String,
     pub executable_output_path: String,
+    pub deny_warnings: bool,
 }
 
 #[derive(Debug, PartialEq)]
@@ -53,12 +58,34 @@ pub struct ModeValidateConf {
     pub data_schema_path: String,
 }
 
+#[derive(Debug, PartialEq)]
+pub struct ModeFmtConf {
+    pub file_path: String,
+    pub check: bool,
+}
+
+/// What exactly has to be dumped.
+#[derive(Debug, PartialEq)]
+pub enum DumpTarget {
+    Ast,
+    Schema,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ModeDumpConf {
+    pub file_path: String,
+    pub what: DumpTarget,
+    pub output_path: Option<String>,
+}
+
 #[derive(Debug, PartialEq)]
 pub enum Conf {
     Run(ModeRunConf),
     Build(ModeBuildConf),
     Exec(ModeExecConf),
     Validate(ModeValidateConf),
+    Fmt(ModeFmtConf),
+    Dump(ModeDumpConf),
 }
 
 impl Conf {
@@ -153,6 +180,8 @@ impl Conf {
             ArgValue::MODE_BUILD => Ok(BUILD_ARGS),
             ArgValue::MODE_EXEC => Ok(EXEC_ARGS),
             ArgValue::MODE_VALIDATE => Ok(VALIDATE_ARGS),
+            ArgValue::MODE_FMT => Ok(FMT_ARGS),
+            ArgValue::MODE_DUMP => Ok(DUMP_ARGS),
             _ => Err(ConfErr::ArgInvalid(InvalidArg {
                 provided: mode.to_string(),
                 arg_name: ArgName::MODE.to_string(),
@@ -193,6 +222,15 @@ impl Conf {
                             res.insert(arg.name, value);
                         }
                     }
+                    ArgType::OneOf(values) => {
+                        if !values.contains(&user_arg) {
+                            return Err(ConfErr::ArgInvalid(InvalidArg {
+                                provided: user_arg.to_string(),
+                                arg_name: arg.name.to_string(),
+                            }));
+                        }
+                        res.insert(arg.name, user_arg);
+                    }
                     ArgType::Any => {
                         res.insert(arg.name, user_arg);
                     }
@@ -227,12 +265,14 @@ impl Conf {
                 data_schema_path: Self::arg_str(args.get(ArgName::DATA_SCHEMA)),
                 print_bytecode: Self::arg_bool(args.get(ArgName::PRINT_BYTECODE)),
                 output_path: Self::arg_any(args.get(ArgName::OUTPUT)),
+                deny_warnings: Self::arg_bool(args.get(ArgName::DENY_WARNINGS)),
             })),
 
             ArgValue::MODE_BUILD => Ok(Self::Build(ModeBuildConf {
                 source_code_path: Self::arg_str(args.get(ArgName::SOURCE_CODE)),
                 data_schema_path: Self::arg_str(args.get(ArgName::DATA_SCHEMA)),
                 executable_output_path: Self::arg_str(args.get(ArgName::OUTPUT)),
+                deny_warnings: Self::arg_bool(args.get(ArgName::DENY_WARNINGS)),
             })),
 
             ArgValue::MODE_EXEC => Ok(Self::Exec(ModeExecConf {
@@ -245,6 +285,20 @@ impl Conf {
                 data_schema_path: Self::arg_str(args.get(ArgName::DATA_SCHEMA)),
             })),
 
+            ArgValue::MODE_FMT => Ok(Self::Fmt(ModeFmtConf {
+                file_path: Self::arg_str(args.get(ArgName::FILE)),
+                check: Self::arg_bool(args.get(ArgName::CHECK)),
+            })),
+
+            ArgValue::MODE_DUMP => Ok(Self::Dump(ModeDumpConf {
+                file_path: Self::arg_str(args.get(ArgName::FILE)),
+                what: match args.get(ArgName::WHAT).copied() {
+                    Some(ArgValue::DUMP_SCHEMA) => DumpTarget::Schema,
+                    _ => DumpTarget::Ast,
+                },
+                output_path: Self::arg_any(args.get(ArgName::OUTPUT)),
+            })),
+
             _ => Err(ConfErr::ArgInvalid(InvalidArg {
                 provided: mode.to_string(),
                 arg_name: ArgName::MODE.to_string(),
@@ -263,7 +317,8 @@ impl Conf {
 mod tests {
     use crate::conf::config::ArgName;
     use crate::conf::{
-        Conf, ConfErr, InvalidArg, ModeBuildConf, ModeExecConf, ModeRunConf, ModeValidateConf,
+        Conf, ConfErr, DumpTarget, InvalidArg, ModeBuildConf, ModeDumpConf, ModeExecConf,
+        ModeFmtConf, ModeRunConf, ModeValidateConf,
     };
 
     #[test]
@@ -350,6 +405,7 @@ mod tests {
                 data_schema_path: "data.elt".to_string(),
                 print_bytecode: false,
                 output_path: Some("res.txt".to_string()),
+                deny_warnings: false,
             }))
         );
     }
@@ -371,6 +427,29 @@ mod tests {
                 data_schema_path: "data.elt".to_string(),
                 print_bytecode: true,
                 output_path: None,
+                deny_warnings: false,
+            }))
+        );
+    }
+
+    #[test]
+    fn run_should_construct_conf_with_deny_warnings_flag() {
+        let result = Conf::new(&[
+            "--mode=run".to_string(),
+            "--source-code=sample.eli".to_string(),
+            "--data=data.csv".to_string(),
+            "--data-schema=data.elt".to_string(),
+            "--deny-warnings".to_string(),
+        ]);
+        assert_eq!(
+            result,
+            Ok(Conf::Run(ModeRunConf {
+                source_code_path: "sample.eli".to_string(),
+                data_path: "data.csv".to_string(),
+                data_schema_path: "data.elt".to_string(),
+                print_bytecode: false,
+                output_path: None,
+                deny_warnings: true,
             }))
         );
     }
@@ -437,6 +516,27 @@ mod tests {
                 source_code_path: "sample.eli".to_string(),
                 executable_output_path: "sample.elb".to_string(),
                 data_schema_path: "data.elt".to_string(),
+                deny_warnings: false,
+            }))
+        );
+    }
+
+    #[test]
+    fn build_should_construct_conf_with_deny_warnings_flag() {
+        let result = Conf::new(&[
+            "--mode=build".to_string(),
+            "--source-code=sample.eli".to_string(),
+            "--data-schema=data.elt".to_string(),
+            "--output=sample.elb".to_string(),
+            "--deny-warnings=true".to_string(),
+        ]);
+        assert_eq!(
+            result,
+            Ok(Conf::Build(ModeBuildConf {
+                source_code_path: "sample.eli".to_string(),
+                executable_output_path: "sample.elb".to_string(),
+                data_schema_path: "data.elt".to_string(),
+                deny_warnings: true,
             }))
         );
     }
@@ -528,6 +628,115 @@ mod tests {
     // ==================================================================
     // VALIDATE MODE TESTS END
     // ==================================================================
+
+    // ==================================================================
+    // FMT MODE TESTS START
+    // ==================================================================
+
+    #[test]
+    fn fmt_should_require_file_flag() {
+        let result = Conf::new(&["--mode=fmt".to_string(), "--check".to_string()]);
+        assert_eq!(result, Err(ConfErr::ArgRequired(ArgName::FILE.to_string())));
+    }
+
+    #[test]
+    fn fmt_should_reject_unknown_file_ext() {
+        let result = Conf::new(&["--mode=fmt".to_string(), "--file=data.csv".to_string()]);
+        assert_eq!(result, Err(ConfErr::ExtInvalid("data.csv".to_string())));
+    }
+
+    #[test]
+    fn fmt_should_construct_conf() {
+        let result = Conf::new(&["--mode=fmt".to_string(), "--file=sample.elt".to_string()]);
+        assert_eq!(
+            result,
+            Ok(Conf::Fmt(ModeFmtConf {
+                file_path: "sample.elt".to_string(),
+                check: false,
+            }))
+        );
+    }
+
+    #[test]
+    fn fmt_should_construct_conf_with_check_flag() {
+        let result = Conf::new(&[
+            "--mode=fmt".to_string(),
+            "--file=sample.eli".to_string(),
+            "--check".to_string(),
+        ]);
+        assert_eq!(
+            result,
+            Ok(Conf::Fmt(ModeFmtConf {
+                file_path: "sample.eli".to_string(),
+                check: true,
+            }))
+        );
+    }
+
+    // ==================================================================
+    // FMT MODE TESTS END
+    // ==================================================================
+
+    // ==================================================================
+    // DUMP MODE TESTS START
+    // ==================================================================
+
+    #[test]
+    fn dump_should_require_file_flag() {
+        let result = Conf::new(&["--mode=dump".to_string(), "--what=ast".to_string()]);
+        assert_eq!(result, Err(ConfErr::ArgRequired(ArgName::FILE.to_string())));
+    }
+
+    #[test]
+    fn dump_should_reject_unknown_target() {
+        let result = Conf::new(&[
+            "--mode=dump".to_string(),
+            "--file=sample.eli".to_string(),
+            "--what=hir".to_string(),
+        ]);
+        assert_eq!(
+            result,
+            Err(ConfErr::ArgInvalid(InvalidArg {
+                provided: "hir".to_string(),
+                arg_name: ArgName::WHAT.to_string(),
+            }))
+        );
+    }
+
+    #[test]
+    fn dump_should_construct_conf_with_ast_by_default() {
+        let result = Conf::new(&["--mode=dump".to_string(), "--file=sample.eli".to_string()]);
+        assert_eq!(
+            result,
+            Ok(Conf::Dump(ModeDumpConf {
+                file_path: "sample.eli".to_string(),
+                what: DumpTarget::Ast,
+                output_path: None,
+            }))
+        );
+    }
+
+    #[test]
+    fn dump_should_construct_conf() {
+        let result = Conf::new(&[
+            "--mode=dump".to_string(),
+            "--file=sample.elt".to_string(),
+            "--what=schema".to_string(),
+            "--output=schema.json".to_string(),
+        ]);
+        assert_eq!(
+            result,
+            Ok(Conf::Dump(ModeDumpConf {
+                file_path: "sample.elt".to_string(),
+                what: DumpTarget::Schema,
+                output_path: Some("schema.json".to_string()),
+            }))
+        );
+    }
+
+    // ==================================================================
+    // DUMP MODE TESTS END
+    // ==================================================================
 }
 
 // ==================================================================
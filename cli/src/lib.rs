@@ -7,7 +7,10 @@
 pub mod conf;
 pub mod fsys;
 
-use conf::{ModeBuildConf, ModeExecConf, ModeRunConf, ModeValidateConf};
+use conf::{
+    DumpTarget, ModeBuildConf, ModeDumpConf, ModeExecConf, ModeFmtConf, ModeRunConf,
+    ModeValidateConf,
+};
 //use elise_data::{
 //    DataParseResult,
 //    data_binder::DataBinder,
@@ -17,10 +20,24 @@ use conf::{ModeBuildConf, ModeExecConf, ModeRunConf, ModeValidateConf};
 //    },
 //};
 
-use elise_data::{csv::csv_parser::CsvParser, schema_resolver::SchemaResolver};
+use elise_ast::{AstNode, dump::ast_dump};
+use elise_data::{
+    csv::{csv_parser::CsvParser, csv_validator::CsvValidator},
+    dump::schema_dump,
+    schema_resolver::{ResolvedSchema, SchemaImporter, SchemaResolver},
+};
+use elise_formatter::Cadenza;
 use elise_parser::Prelude;
-//use elise_semanalyzer::Harmony;
-use elise_shared::shared_errors::LangErr;
+use elise_semanalyzer::{HIR, Harmony};
+use elise_shared::shared_errors::{
+    LangErr,
+    errors_parser::ParserErr,
+    errors_schema_resolver::SchemaImportErr,
+    errors_semanalyzer::{SemanalyzerDiag, SemanalyzerWarn, Severity},
+};
+use fsys::read_file_bytes;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 /// Representation of the successful execution of the
@@ -31,6 +48,7 @@ pub struct RunResult<'a> {
     pub ms: u128,
     pub output: String,
     pub bytecode: String,
+    pub warnings: Vec<SemanalyzerWarn>,
 }
 
 /// Representation of the successful execution of the
@@ -40,6 +58,7 @@ pub struct BuildResult<'a> {
     pub config: &'a ModeBuildConf,
     pub ms: u128,
     pub executable_output: String,
+    pub warnings: Vec<SemanalyzerWarn>,
 }
 
 /// Representation of the successful execution of the
@@ -59,9 +78,116 @@ pub struct ValidateResult<'a> {
     pub ms: u128,
 }
 
+/// Representation of the successful execution of the
+/// program in 'FMT' mode.
+#[derive(Debug)]
+pub struct FmtResult<'a> {
+    pub config: &'a ModeFmtConf,
+    pub ms: u128,
+    pub output: String,
+    // Whether the original source code is already formatted.
+    pub formatted: bool,
+}
+
+/// Representation of the successful execution of the
+/// program in 'DUMP' mode.
+#[derive(Debug)]
+pub struct DumpResult<'a> {
+    pub config: &'a ModeDumpConf,
+    pub ms: u128,
+    // JSON document.
+    pub output: String,
+}
+
+/// Parses source code collecting every parser error instead
+/// of stopping at the first one.
+fn parse_recovering(source_code: &[u8]) -> Result<Vec<AstNode>, Vec<ParserErr>> {
+    let recovered = Prelude::new(source_code).parse_recovering();
+    if recovered.errors.is_empty() {
+        Ok(recovered.ast)
+    } else {
+        Err(recovered.errors)
+    }
+}
+
+/// Imports schema files from the file system.
+struct SchemaFsImporter;
+
+impl SchemaFsImporter {
+    // The same file must always get the same path, however
+    // it's imported, in order to detect import cycles.
+    fn canonicalize(path: PathBuf) -> String {
+        fs::canonicalize(&path)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    }
+}
+
+impl SchemaImporter for SchemaFsImporter {
+    fn resolve_path(&self, from: &str, path: &str) -> String {
+        let path = match Path::new(from).parent() {
+            Some(dir) => dir.join(path),
+            None => PathBuf::from(path),
+        };
+        Self::canonicalize(path)
+    }
+
+    fn load(&mut self, path: &str) -> Result<Vec<AstNode>, SchemaImportErr> {
+        let file = read_file_bytes(path).map_err(|err| SchemaImportErr::Unreadable {
+            reason: err.message,
+        })?;
+        parse_recovering(&file.content).map_err(SchemaImportErr::Parser)
+    }
+}
+
+/// Resolves schema of the file at the path, which can
+/// import other schema files relative to it.
+fn resolve_schema(schema_ast: &Vec<AstNode>, path: &str) -> Result<ResolvedSchema, LangErr> {
+    let path = SchemaFsImporter::canonicalize(PathBuf::from(path));
+    let mut importer = SchemaFsImporter;
+
+    SchemaResolver::new(schema_ast)
+        .with_importer(&path, &mut importer)
+        .resolve()
+        .map_err(LangErr::SchemaResolver)
+}
+
+/// Parses and analyzes source code collecting every semantic
+/// diagnostic. Warnings are handed back to the caller unless
+/// they are denied, in which case they fail the analysis.
+fn analyze(
+    source_code: &[u8],
+    schema: &ResolvedSchema,
+    deny_warnings: bool,
+) -> Result<(HIR, Vec<SemanalyzerWarn>), LangErr> {
+    let ast = parse_recovering(source_code).map_err(LangErr::ParserSource)?;
+    let recovered = Harmony::new(&ast, schema).analyze_recovering();
+
+    let has_errors = recovered
+        .diagnostics
+        .iter()
+        .any(|diag| diag.severity() == Severity::Error);
+
+    if has_errors || (deny_warnings && !recovered.diagnostics.is_empty()) {
+        return Err(LangErr::SemanticAnalyzer(recovered.diagnostics));
+    }
+
+    let warnings = recovered
+        .diagnostics
+        .into_iter()
+        .filter_map(|diag| match diag {
+            SemanalyzerDiag::Warn(warn) => Some(warn),
+            SemanalyzerDiag::Err(_) => None,
+        })
+        .collect();
+
+    Ok((recovered.hir, warnings))
+}
+
 /// Entry point for running the program in 'RUN' mode.
 pub fn run<'a>(
-    _source_code: &'a [u8],
+    source_code: &'a [u8],
     // TODO: Why it's not vec of bytes?
     data: &'a str,
     data_schema: &'a [u8],
@@ -71,19 +197,16 @@ pub fn run<'a>(
 
     println!("PARSED DATA: {:#?}", parsed_data);
 
-    let schema_ast = Prelude::new(data_schema)
-        .parse()
-        .map_err(LangErr::ParserSchema)
-        .unwrap();
+    let schema_ast = parse_recovering(data_schema).map_err(LangErr::ParserSchema)?;
 
-    let res = SchemaResolver::new(&schema_ast)
-        .resolve()
-        .map_err(LangErr::SchemaResolver)?;
+    let res = resolve_schema(&schema_ast, &config.data_schema_path)?;
 
     println!("RESOLVED SCHEMA: {:#?}", res);
 
     let start = Instant::now();
 
+    let (_hir, warnings) = analyze(source_code, &res, config.deny_warnings)?;
+
     // let (mut source_code_ast, mut schema_ast, mut parsed_data) = (None, None, None);
 
     // // Run in parallel since these processes don't depend on one another.
@@ -134,32 +257,34 @@ pub fn run<'a>(
     //     }
     // };
 
-    // let _hir = Harmony::new(&source_code_ast, &data_binding)
-    //     .analyze()
-    //     .map_err(LangErr::SemanticAnalyzer)?;
-
     Ok(RunResult {
         config,
         ms: start.elapsed().as_millis(),
         output: String::from("123"),
         bytecode: String::from("CALL a [1] [0]"),
+        warnings,
     })
 }
 
 /// Entry point for running the program in 'BUILD' mode.
 pub fn build<'a>(
-    _source_code: &'a [u8],
-    _data_schema: &'a [u8],
+    source_code: &'a [u8],
+    data_schema: &'a [u8],
     config: &'a ModeBuildConf,
 ) -> Result<BuildResult<'a>, LangErr> {
     let start = Instant::now();
 
-    println!("BUILD MODE");
+    let schema_ast = parse_recovering(data_schema).map_err(LangErr::ParserSchema)?;
+
+    let schema = resolve_schema(&schema_ast, &config.data_schema_path)?;
+
+    let (_hir, warnings) = analyze(source_code, &schema, config.deny_warnings)?;
 
     Ok(BuildResult {
         config,
         ms: start.elapsed().as_millis(),
         executable_output: String::from("CALL a [1] [0]"),
+        warnings,
     })
 }
 
@@ -182,16 +307,71 @@ pub fn exec<'a>(
 
 /// Entry point for running the program in 'VALIDATE' mode.
 pub fn validate<'a>(
-    _data: &'a str,
-    _data_schema: &'a [u8],
+    data: &'a str,
+    data_schema: &'a [u8],
     config: &'a ModeValidateConf,
 ) -> Result<ValidateResult<'a>, LangErr> {
     let start = Instant::now();
 
-    println!("VALIDATE MODE");
+    let rows = CsvParser::new(data).parse().map_err(LangErr::CsvParser)?;
+
+    let schema_ast = parse_recovering(data_schema).map_err(LangErr::ParserSchema)?;
+
+    let schema = resolve_schema(&schema_ast, &config.data_schema_path)?;
+
+    CsvValidator::new(&rows, &schema)
+        .validate()
+        .map_err(LangErr::CsvValidator)?;
 
     Ok(ValidateResult {
         config,
         ms: start.elapsed().as_millis(),
     })
 }
+
+/// Entry point for running the program in 'FMT' mode.
+pub fn fmt<'a>(source_code: &'a [u8], config: &'a ModeFmtConf) -> Result<FmtResult<'a>, LangErr> {
+    let start = Instant::now();
+
+    // Report every syntax error at once before formatting.
+    parse_recovering(source_code).map_err(LangErr::ParserSource)?;
+
+    let cst = Prelude::new(source_code)
+        .parse_cst()
+        .map_err(|e| LangErr::ParserSource(vec![e]))?;
+
+    let output = Cadenza::new(&cst).format();
+
+    Ok(FmtResult {
+        config,
+        ms: start.elapsed().as_millis(),
+        formatted: output.as_bytes() == source_code,
+        output,
+    })
+}
+
+/// Entry point for running the program in 'DUMP' mode.
+pub fn dump<'a>(
+    source_code: &'a [u8],
+    config: &'a ModeDumpConf,
+) -> Result<DumpResult<'a>, LangErr> {
+    let start = Instant::now();
+
+    let output = match config.what {
+        DumpTarget::Ast => {
+            let ast = parse_recovering(source_code).map_err(LangErr::ParserSource)?;
+            ast_dump(&ast)
+        }
+        DumpTarget::Schema => {
+            let schema_ast = parse_recovering(source_code).map_err(LangErr::ParserSchema)?;
+            let resolved_schema = resolve_schema(&schema_ast, &config.file_path)?;
+            schema_dump(&resolved_schema)
+        }
+    };
+
+    Ok(DumpResult {
+        config,
+        ms: start.elapsed().as_millis(),
+        output,
+    })
+}
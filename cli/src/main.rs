@@ -15,7 +15,9 @@
 
 pub mod out;
 
-use elise::conf::{Conf, ModeBuildConf, ModeExecConf, ModeRunConf, ModeValidateConf};
+use elise::conf::{
+    Conf, ModeBuildConf, ModeDumpConf, ModeExecConf, ModeFmtConf, ModeRunConf, ModeValidateConf,
+};
 use elise::fsys::{read_file_bytes, read_file_string, write_file};
 use elise_shared::shared_errors::LangErr;
 
@@ -25,21 +27,24 @@ use crate::out::msg_fsys;
 use crate::out::msg_modes;
 use crate::out::msg_parser;
 use crate::out::utils::{panic_hook, print_bytecode};
-use crate::out::{msg_common, msg_schema_resolver};
-use crate::out::{msg_conf, msg_csv_binder};
-use crate::out::{msg_csv_parser, msg_semanalyzer};
+use crate::out::{msg_common, msg_compiler, msg_schema_resolver};
+use crate::out::{msg_conf, msg_csv_binder, msg_csv_validator};
+use crate::out::{msg_csv_parser, msg_semanalyzer, msg_vm};
 
 fn handle_lang_err(lang_err: &LangErr, source_code: &[u8], schema_source_code: &[u8]) -> ! {
     use LangErr::*;
 
     match lang_err {
         Common(err) => msg_common::print_err(err),
-        ParserSource(err) => msg_parser::print_err(err, source_code),
-        ParserSchema(err) => msg_parser::print_err(err, schema_source_code),
+        ParserSource(errs) => msg_parser::print_errs(errs, source_code),
+        ParserSchema(errs) => msg_parser::print_errs(errs, schema_source_code),
         SchemaResolver(err) => msg_schema_resolver::print_err(err, schema_source_code),
-        SemanticAnalyzer(err) => msg_semanalyzer::print_err(err, source_code),
+        SemanticAnalyzer(diags) => msg_semanalyzer::print_diags(diags, source_code),
+        Compiler(err) => msg_compiler::print_err(err, source_code),
         CsvParser(err) => msg_csv_parser::print_err(err),
         CsvBinder(err) => msg_csv_binder::print_err(err),
+        CsvValidator(err) => msg_csv_validator::print_err(err),
+        Vm(err) => msg_vm::print_err(err, source_code),
     }
 
     std::process::exit(1);
@@ -69,6 +74,7 @@ fn cli_run(conf: &ModeRunConf) {
             let run_res = elise::run(&source_code, &data_desc.content, &schema_source_code, conf)
                 .unwrap_or_else(|e| handle_lang_err(&e, &source_code, &schema_source_code));
 
+            msg_semanalyzer::print_warns(&run_res.warnings, &source_code);
             msg_modes::print_run_result(&run_res.output, run_res.ms);
 
             if run_res.config.print_bytecode {
@@ -110,6 +116,8 @@ fn cli_build(conf: &ModeBuildConf) {
     let build_res = elise::build(&source_code, &schema_source_code, conf)
         .unwrap_or_else(|e| handle_lang_err(&e, &source_code, &schema_source_code));
 
+    msg_semanalyzer::print_warns(&build_res.warnings, &source_code);
+
     let out_path = &build_res.config.executable_output_path;
 
     match write_file(out_path, &build_res.executable_output) {
@@ -163,6 +171,62 @@ fn cli_validate(conf: &ModeValidateConf) {
     };
 }
 
+fn cli_fmt(conf: &ModeFmtConf) {
+    // We need to keep source code globally available in order to
+    // be able to pass it to the function that handles errors.
+    let source_code = match read_file_bytes(&conf.file_path) {
+        Ok(desc) => desc.content,
+        Err(e) => {
+            return msg_fsys::print_file_rw_err(&e.message, &e.path, true);
+        }
+    };
+
+    let fmt_res = elise::fmt(&source_code, conf)
+        .unwrap_or_else(|e| handle_lang_err(&e, &source_code, &source_code));
+
+    let path = &fmt_res.config.file_path;
+
+    if fmt_res.config.check {
+        msg_modes::print_fmt_check_result(path, fmt_res.formatted);
+        if !fmt_res.formatted {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if fmt_res.formatted {
+        return msg_modes::print_fmt_result(path, false, fmt_res.ms);
+    }
+
+    match write_file(path, &fmt_res.output) {
+        Ok(_) => msg_modes::print_fmt_result(path, true, fmt_res.ms),
+        Err(err) => msg_fsys::print_file_rw_err(&err.message, &err.path, false),
+    }
+}
+
+fn cli_dump(conf: &ModeDumpConf) {
+    // We need to keep source code globally available in order to
+    // be able to pass it to the function that handles errors.
+    let source_code = match read_file_bytes(&conf.file_path) {
+        Ok(desc) => desc.content,
+        Err(e) => {
+            return msg_fsys::print_file_rw_err(&e.message, &e.path, true);
+        }
+    };
+
+    let dump_res = elise::dump(&source_code, conf)
+        .unwrap_or_else(|e| handle_lang_err(&e, &source_code, &source_code));
+
+    match dump_res.config.output_path.as_ref() {
+        Some(path) => match write_file(path, &dump_res.output) {
+            Ok(_) => msg_fsys::print_saved_to(path),
+            Err(err) => msg_fsys::print_file_rw_err(&err.message, &err.path, false),
+        },
+        // Print nothing but JSON, so it can be piped to other tools.
+        None => msg_modes::print_dump_result(&dump_res.output),
+    }
+}
+
 fn main() {
     // Override default panic message.
     std::panic::set_hook(Box::new(|info| {
@@ -178,5 +242,7 @@ fn main() {
         Ok(Conf::Build(build_conf)) => cli_build(&build_conf),
         Ok(Conf::Exec(exec_conf)) => cli_exec(&exec_conf),
         Ok(Conf::Validate(validate_conf)) => cli_validate(&validate_conf),
+        Ok(Conf::Fmt(fmt_conf)) => cli_fmt(&fmt_conf),
+        Ok(Conf::Dump(dump_conf)) => cli_dump(&dump_conf),
     }
 }
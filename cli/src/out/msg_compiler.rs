@@ -0,0 +1,20 @@
+use elise_shared::shared_errors::errors_compiler::CompilerErr;
+
+use crate::out::utils::{
+    self, get_source_code_slice, print_err_source_code_pos, print_err_source_code_slice,
+};
+
+pub fn print_err(compiler_err: &CompilerErr, source_code: &[u8]) {
+    use CompilerErr::*;
+
+    let (info, span) = match compiler_err {
+        UnsupportedNode { span } => ("Expression can not be compiled yet".to_string(), span),
+    };
+
+    utils::print_err(&info, Some("Compilation error"));
+
+    if let Some(code) = get_source_code_slice(source_code, span.start) {
+        print_err_source_code_pos(code.row, code.col);
+        print_err_source_code_slice(&code.slice, code.col);
+    }
+}
@@ -6,7 +6,7 @@ pub fn print_err(err: &ConfErr) {
     let msg = match err {
         ConfErr::ExtInvalid(ext) => format!("Invalid extension: '{}'", ext),
         ConfErr::ArgInvalid(arg) => format!(
-            "Invalid argument. Expected '{}', got '{}'",
+            "Invalid value '{}' for argument '{}'",
             arg.provided, arg.arg_name
         ),
         ConfErr::ArgRequired(arg) => format!("Argument required: '{}'", arg),
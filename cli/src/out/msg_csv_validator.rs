@@ -0,0 +1,57 @@
+use crate::out::utils::{self};
+use elise_shared::shared_errors::errors_csv_validator::CsvValidatorErr;
+
+pub fn print_err(validator_err: &CsvValidatorErr) {
+    use CsvValidatorErr::*;
+    let label = Some("Validation error");
+
+    match validator_err {
+        InvalRowSchema => {
+            utils::print_err(
+                "Schema of CSV data must be a list of dictionaries, one for each row",
+                label,
+            );
+        }
+        MissingCol { col } => {
+            utils::print_err(&format!("Missing required column: {col}"), label);
+        }
+        MissingTypeDefinition { pos, col } => {
+            utils::print_err(&format!("Missing type definition for column: {col}"), label);
+            utils::print_err_source_code_pos(pos.row, pos.col);
+        }
+        TypeMismatch {
+            pos,
+            col,
+            expected,
+            found,
+        } => {
+            utils::print_err(&format!("Type mismatch in column: {col}"), label);
+            utils::print_err_source_code_pos(pos.row, pos.col);
+            utils::print_err_type_mismatch(expected, found);
+        }
+        ConstraintViolation {
+            pos,
+            col,
+            value,
+            constraint,
+        } => {
+            utils::print_err(
+                &format!("Value \"{value}\" of column {col} violates {constraint}"),
+                label,
+            );
+            utils::print_err_source_code_pos(pos.row, pos.col);
+        }
+        FormatMismatch {
+            pos,
+            col,
+            value,
+            format,
+        } => {
+            utils::print_err(
+                &format!("Value \"{value}\" of column {col} doesn't match format \"{format}\""),
+                label,
+            );
+            utils::print_err_source_code_pos(pos.row, pos.col);
+        }
+    };
+}
@@ -1,3 +1,5 @@
+use crate::out::utils;
+
 pub fn print_run_result(output: &str, ms: u128) {
     println!("Output: {}", output);
     println!("Execution time: {} ms", ms);
@@ -12,3 +14,27 @@ pub fn print_validate_result(ms: u128) {
     println!("Valid");
     println!("Execution time: {} ms", ms);
 }
+
+pub fn print_fmt_result(path: &str, changed: bool, ms: u128) {
+    if changed {
+        println!("Formatted: {}", path);
+    } else {
+        println!("Already formatted: {}", path);
+    }
+    println!("Execution time: {} ms", ms);
+}
+
+pub fn print_fmt_check_result(path: &str, formatted: bool) {
+    if formatted {
+        println!("Formatted: {}", path);
+    } else {
+        utils::print_err(
+            &format!("File is not formatted ({})", path),
+            Some("Formatter"),
+        );
+    }
+}
+
+pub fn print_dump_result(output: &str) {
+    println!("{}", output);
+}
@@ -1,8 +1,28 @@
 use crate::out::utils::{self, print_err_source_code_slice};
-use crate::out::utils::{get_source_code_slice, print_err_source_code_pos};
+use crate::out::utils::{get_source_code_slice, print_err_file, print_err_source_code_pos};
 use elise_shared::shared_errors::errors_parser::ParserErr;
 
+/// Prints every collected parser error in source order.
+pub fn print_errs(parser_errs: &[ParserErr], source_code: &[u8]) {
+    print_errs_in_file(parser_errs, source_code, None);
+}
+
+/// Prints parser errors of the file at the path, like an imported
+/// schema file, which is not the one given to the CLI.
+pub fn print_errs_in_file(parser_errs: &[ParserErr], source_code: &[u8], path: Option<&str>) {
+    let mut parser_errs: Vec<&ParserErr> = parser_errs.iter().collect();
+    parser_errs.sort_by_key(|err| err.info().pos);
+
+    for parser_err in parser_errs {
+        print_err_in_file(parser_err, source_code, path);
+    }
+}
+
 pub fn print_err(parser_err: &ParserErr, source_code: &[u8]) {
+    print_err_in_file(parser_err, source_code, None);
+}
+
+fn print_err_in_file(parser_err: &ParserErr, source_code: &[u8], path: Option<&str>) {
     use ParserErr::*;
 
     let info = match parser_err {
@@ -15,12 +35,19 @@ pub fn print_err(parser_err: &ParserErr, source_code: &[u8]) {
         InvalNum(err_info) => ("Invalid number", err_info),
         InvalStr(err_info) => ("Invalid string", err_info),
         UntermStr(err_info) => ("Unterminated string", err_info),
+        InvalUnicodeEsc(err_info) => ("Invalid unicode escape", err_info),
+        InvalHexEsc(err_info) => ("Invalid hex escape", err_info),
+        UntermComment(err_info) => ("Unterminated comment", err_info),
         InvalDictPair(err_info) => ("Invalid dictionary key value pair", err_info),
         InvalFnName(err_info) => ("Invalid function name", err_info),
     };
 
     utils::print_err(info.0, Some("Parser error"));
 
+    if let Some(path) = path {
+        print_err_file(path);
+    }
+
     if let Some(code) = &get_source_code_slice(source_code, info.1.pos) {
         print_err_source_code_pos(code.row, code.col);
         print_err_source_code_slice(&code.slice, code.col);
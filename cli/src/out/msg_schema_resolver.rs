@@ -1,17 +1,38 @@
-use elise_shared::shared_errors::errors_schema_resolver::SchemaResolverErr;
+use elise::fsys::read_file_bytes;
+use elise_shared::shared_errors::errors_schema_resolver::{SchemaImportErr, SchemaResolverErr};
 use elise_shared::shared_types::Span;
 
-use crate::out::utils;
+use crate::out::{msg_parser, utils};
 
 use crate::out::utils::{
-    get_source_code_slice, print_err_source_code_pos, print_err_source_code_slice,
+    get_source_code_slice, print_err_file, print_err_source_code_pos, print_err_source_code_slice,
 };
 
+fn read_imported_file(path: &str) -> Vec<u8> {
+    // File has been read during the resolution, so it can
+    // only be missing if it's been changed since then.
+    read_file_bytes(path)
+        .map(|file| file.content)
+        .unwrap_or_default()
+}
+
 pub fn print_err(schema_err: &SchemaResolverErr, schema_source_code: &[u8]) {
+    match schema_err {
+        // Spans of errors in imported files point to the
+        // source code of those files.
+        SchemaResolverErr::InFile { path, err } => {
+            print_err_in_file(err, &read_imported_file(path), Some(path))
+        }
+        err => print_err_in_file(err, schema_source_code, None),
+    }
+}
+
+fn print_err_in_file(schema_err: &SchemaResolverErr, source_code: &[u8], path: Option<&str>) {
     use SchemaResolverErr::*;
 
     let (msg, span): (&str, Option<&Span>) = match schema_err {
         Empty => ("Schema file must not be empty", None),
+        MissingRoot => ("Schema file must contain a \".schema\" definition", None),
 
         Unexp { span } => ("Unexpected expression", Some(span)),
 
@@ -48,14 +69,58 @@ pub fn print_err(schema_err: &SchemaResolverErr, schema_source_code: &[u8]) {
             "Union cannot be used as a direct child of another union",
             Some(span),
         ),
+        InvalConstraint { span } => ("Invalid constraint", Some(span)),
+        InvalPattern { pattern, span } => (
+            &format!("Invalid regular expression \"{pattern}\""),
+            Some(span),
+        ),
+        InvalFormat { format, span } => (&format!("Invalid format \"{format}\""), Some(span)),
+        FormatConflict { span } => (
+            "Alternatives of the union have the same type with different formats",
+            Some(span),
+        ),
+        TypeRedefinition { name, span } => {
+            (&format!("Type \"{name}\" is already defined"), Some(span))
+        }
+        TypeUndefined { name, span } => (&format!("Type \"{name}\" is not defined"), Some(span)),
+        TypeCycle { name, span } => (&format!("Type \"{name}\" references itself"), Some(span)),
+        Import {
+            path,
+            err: SchemaImportErr::Unreadable { reason },
+            span,
+        } => (&format!("Can not import \"{path}\". {reason}"), Some(span)),
+        Import {
+            path,
+            err: SchemaImportErr::Parser(_),
+            span,
+        } => (
+            &format!("Can not parse imported file \"{path}\""),
+            Some(span),
+        ),
+        ImportCycle { path, span } => (&format!("File \"{path}\" imports itself"), Some(span)),
+        // Errors are attributed to the innermost imported file only.
+        InFile { err, .. } => return print_err_in_file(err, source_code, path),
     };
 
     utils::print_err(msg, Some("Schema error"));
 
+    if let Some(path) = path {
+        print_err_file(path);
+    }
+
     if let Some(span) = span
-        && let Some(code) = get_source_code_slice(schema_source_code, span.start)
+        && let Some(code) = get_source_code_slice(source_code, span.start)
     {
         print_err_source_code_pos(code.row, code.col);
         print_err_source_code_slice(&code.slice, code.col);
     }
+
+    if let Import {
+        path,
+        err: SchemaImportErr::Parser(errs),
+        ..
+    } = schema_err
+    {
+        msg_parser::print_errs_in_file(errs, &read_imported_file(path), Some(path));
+    }
 }
@@ -1,9 +1,45 @@
-use elise_shared::shared_errors::errors_semanalyzer::SemanalyzerErr;
+use elise_shared::shared_errors::errors_semanalyzer::{
+    SemanalyzerDiag, SemanalyzerErr, SemanalyzerWarn,
+};
 
 use crate::out::utils::{
     self, get_source_code_slice, print_err_source_code_pos, print_err_source_code_slice,
 };
 
+/// Prints every collected diagnostic. Diagnostics are
+/// already sorted in source order by Harmony.
+pub fn print_diags(diags: &[SemanalyzerDiag], source_code: &[u8]) {
+    for diag in diags {
+        match diag {
+            SemanalyzerDiag::Err(err) => print_err(err, source_code),
+            SemanalyzerDiag::Warn(warn) => print_warn(warn, source_code),
+        }
+    }
+}
+
+pub fn print_warns(warns: &[SemanalyzerWarn], source_code: &[u8]) {
+    for warn in warns {
+        print_warn(warn, source_code);
+    }
+}
+
+pub fn print_warn(sema_warn: &SemanalyzerWarn, source_code: &[u8]) {
+    use SemanalyzerWarn::*;
+
+    let (info, span) = match sema_warn {
+        UnusedBinding { span } => ("Unused binding", span),
+        UnusedParam { span } => ("Unused function parameter", span),
+        Shadowing { span } => ("Identifier shadows the one from an outer scope", span),
+    };
+
+    utils::print_warn(info, Some("Semantic warning"));
+
+    if let Some(code) = get_source_code_slice(source_code, span.start) {
+        print_err_source_code_pos(code.row, code.col);
+        print_err_source_code_slice(&code.slice, code.col);
+    }
+}
+
 pub fn print_err(sema_err: &SemanalyzerErr, source_code: &[u8]) {
     use SemanalyzerErr::*;
 
@@ -54,6 +90,86 @@ pub fn print_err(sema_err: &SemanalyzerErr, source_code: &[u8]) {
             span,
         ),
 
+        BindingsEmpty { span } => ("Expected at least one binding".to_string(), span),
+        BindingValueMissing { span } => ("Missing value for binding".to_string(), span),
+        PatternTypeMismatch {
+            expected,
+            found,
+            span,
+        } => (
+            format!("Expected \"{expected}\" type to destructure, found \"{found}\""),
+            span,
+        ),
+        PatternLenMismatch {
+            expected,
+            found,
+            span,
+        } => (
+            format!("Expected a list of {expected} items to destructure, found {found} items"),
+            span,
+        ),
+
+        SlotUnknown { span } => ("Unknown slot".to_string(), span),
+        SchemaPathUndefined { path, span } => (
+            format!("Path {path} is not defined in the data schema"),
+            span,
+        ),
+
+        BranchTypeMismatch {
+            fn_name,
+            expected,
+            found,
+            span,
+        } => (
+            format!(
+                "Branches of the \"{fn_name}\" function have incompatible types. Expected: \"{expected}\", found: \"{found}\""
+            ),
+            span,
+        ),
+
+        CallbackArityMismatch {
+            fn_name,
+            expected,
+            found,
+            span,
+        } => (
+            format!(
+                "Function passed to the \"{fn_name}\" function must take {expected} parameter(s), found: {found}"
+            ),
+            span,
+        ),
+        CallbackReturnMismatch {
+            fn_name,
+            expected,
+            found,
+            span,
+        } => (
+            format!(
+                "Function passed to the \"{fn_name}\" function must return \"{expected}\", found \"{found}\""
+            ),
+            span,
+        ),
+
+        DivisionByZero { span } => ("Division by zero".to_string(), span),
+        ArithmeticOverflow { span } => ("Arithmetic overflow".to_string(), span),
+        InvalidRegex { span } => ("Invalid regular expression".to_string(), span),
+        InvalidDateFormat { span } => ("Invalid format of the date or time".to_string(), span),
+        InvalidDateUnit { span } => ("Invalid unit of the date or time".to_string(), span),
+
+        UnionNotNarrowed { span } => (
+            "Value can be of several types, narrow it down with \".narrow\" first".to_string(),
+            span,
+        ),
+        UnionAlternativeUndefined { alternative, span } => (
+            format!("Expected the value to have \"{alternative}\" type as an alternative"),
+            span,
+        ),
+
+        NotCallable { found, span } => (
+            format!("Expected \"Function\" to be called, found \"{found}\""),
+            span,
+        ),
+
         UnknownFunction { span } => ("Unknown function".to_string(), span),
 
         UnsupportedNode { span } => ("Unsupported expression".to_string(), span),
@@ -0,0 +1,61 @@
+use elise_shared::shared_errors::errors_vm::VmErr;
+
+use crate::out::utils::{
+    self, get_source_code_slice, print_err_data_pos, print_err_source_code_pos,
+    print_err_source_code_slice,
+};
+
+pub fn print_err(vm_err: &VmErr, source_code: &[u8]) {
+    use VmErr::*;
+
+    let (info, span, cell) = match vm_err {
+        InvalidRegex { pattern } => (
+            format!("Invalid regular expression \"{pattern}\""),
+            None,
+            None,
+        ),
+        InvalidDateFormat { format } => (
+            format!("Invalid format of the date or time \"{format}\""),
+            None,
+            None,
+        ),
+        InvalidDateUnit { unit } => (
+            format!("Invalid unit of the date or time \"{unit}\""),
+            None,
+            None,
+        ),
+        InvalidAggregateValue { aggregation, found } => (
+            format!("Can not aggregate \"{found}\" with \"{aggregation}\", expected \"Number\""),
+            None,
+            None,
+        ),
+        InvalidAggregateRow { found } => (
+            format!("Can not group \"{found}\", expected \"Dict\""),
+            None,
+            None,
+        ),
+        ConversionFailed {
+            expected,
+            found,
+            cell,
+            span,
+        } => (
+            format!("Can not convert {found} to \"{expected}\""),
+            Some(span),
+            cell.as_ref(),
+        ),
+    };
+
+    utils::print_err(&info, Some("Runtime error"));
+
+    if let Some(cell) = cell {
+        print_err_data_pos(cell.row, cell.col);
+    }
+
+    if let Some(span) = span
+        && let Some(code) = get_source_code_slice(source_code, span.start)
+    {
+        print_err_source_code_pos(code.row, code.col);
+        print_err_source_code_slice(&code.slice, code.col);
+    }
+}
@@ -82,11 +82,29 @@ pub fn print_err(message: &str, label: Option<&str>) {
     eprintln!("{}", error.red().bold());
 }
 
+pub fn print_warn(message: &str, label: Option<&str>) {
+    let label: &str = label.unwrap_or("Warning");
+    let warning = format!("{}. {}", label.yellow().bold(), message);
+    eprintln!("{}", warning.yellow().bold());
+}
+
 pub fn print_err_source_code_pos(row: usize, col: usize) {
     let location = format!("At {}:{}\n", row + 1, col + 1);
     eprintln!("{}", location.bold());
 }
 
+// Rows are counted from the first row of values, the same
+// way as in validation errors.
+pub fn print_err_data_pos(row: usize, col: usize) {
+    let location = format!("In data at row {}, column {}", row + 1, col + 1);
+    eprintln!("{}", location.bold());
+}
+
+pub fn print_err_file(path: &str) {
+    let location = format!("In {path}");
+    eprintln!("{}", location.bold());
+}
+
 pub fn print_err_type_mismatch(expected: &'static str, got: &'static str) {
     let msg = format!("Expected: {expected}, got: {got}\n");
     eprintln!("{}", msg.bold());
@@ -1 +1,533 @@
-// TODO
+//! # Maestro — Compiler
+//!
+//! Transforms a HIR into a bytecode `Chunk` by walking the AAST.
+//!
+//! ## Input
+//!   - HIR produced by Harmony
+//!
+//! ## Output
+//!   - Chunk { instructions, constants, aggregate_specs, spans }
+//!
+//! ## What Maestro does
+//!   - Emits instructions for a stack machine, so every expression
+//!     leaves exactly one value on the stack
+//!   - Lowers conditionals into jumps, so only the taken branch is evaluated
+//!   - Lowers collection functions into loops with inline function bodies
+//!   - Loads the data from its slot and applies keys of `.get` one by one
+//!   - Leaves grouping and aggregation to dedicated instructions
+//!   - Stores literal values in the constant pool
+//!
+//! ## What Maestro does NOT do
+//!   - Validate the AAST (Harmony has already established all semantic guarantees)
+//!   - Parse literal values (that is the VM's responsibility)
+
+use elise_bytecode::{
+    AggregateFn, AggregateSpec, Chunk, Constant, ConvertTo, DateFn, Instruction, OnFailure, StrFn,
+};
+use elise_semanalyzer::{
+    HIR,
+    aast::{AAstNode, CollectionOp, Conversion, ConversionMode, Op},
+};
+use elise_shared::shared_errors::errors_compiler::CompilerErr;
+
+// ==================================================================
+//
+//  COMPILER START
+//
+// ==================================================================
+
+pub struct Maestro<'a> {
+    pub hir: &'a HIR,
+    chunk: Chunk,
+    // Next slot for values that are not bound to symbols, like
+    // loop counters. Slots of symbols are their SymbolIds, so
+    // these ones start right after them.
+    next_slot: usize,
+}
+
+impl<'a> Maestro<'a> {
+    pub fn new(hir: &'a HIR) -> Self {
+        Self {
+            hir,
+            chunk: Chunk::new(),
+            next_slot: hir.symbol_table.symbols.len(),
+        }
+    }
+
+    pub fn compile(mut self) -> Result<Chunk, CompilerErr> {
+        let aast = &self.hir.aast;
+
+        // Program evaluates to its last expression.
+        for (idx, aast_node) in aast.iter().enumerate() {
+            self.compile_aast_node(aast_node)?;
+
+            if idx < aast.len() - 1 {
+                self.chunk.emit(Instruction::Pop);
+            }
+        }
+
+        Ok(self.chunk)
+    }
+
+    fn compile_aast_node(&mut self, aast_node: &AAstNode) -> Result<(), CompilerErr> {
+        match aast_node {
+            AAstNode::Int { value, .. } => self.compile_constant(Constant::Int(value.clone())),
+            AAstNode::Float { value, .. } => self.compile_constant(Constant::Float(value.clone())),
+            AAstNode::String { value, .. } => {
+                self.compile_constant(Constant::String(value.clone()))
+            }
+            AAstNode::Bool { value, .. } => self.compile_constant(Constant::Bool(*value)),
+            AAstNode::Null { .. } => self.compile_constant(Constant::Null),
+            AAstNode::SymbolRef { symbol_id, .. } => {
+                self.chunk.emit(Instruction::Load(symbol_id.0 as usize));
+            }
+            AAstNode::CallDefine {
+                symbol_id, value, ..
+            } => {
+                self.compile_aast_node(value)?;
+                self.chunk.emit(Instruction::Store(symbol_id.0 as usize));
+            }
+            AAstNode::CallLet { bindings, body, .. } => {
+                for (symbol_id, init) in bindings {
+                    self.compile_aast_node(init)?;
+                    self.chunk.emit(Instruction::Store(symbol_id.0 as usize));
+                    self.chunk.emit(Instruction::Pop);
+                }
+                self.compile_body(body)?;
+            }
+            AAstNode::CallOp { op, args, .. } => self.compile_op(*op, args)?,
+            AAstNode::CallCollection {
+                op, source, args, ..
+            } => self.compile_collection(*op, source, args)?,
+            AAstNode::CallConvert {
+                conversion,
+                mode,
+                value,
+                fallback,
+                span,
+                ..
+            } => {
+                self.compile_aast_node(value)?;
+                if let Some(fallback) = fallback {
+                    self.compile_aast_node(fallback)?;
+                }
+
+                let to = match conversion {
+                    Conversion::Int => ConvertTo::Int,
+                    Conversion::Float => ConvertTo::Float,
+                    Conversion::Str => ConvertTo::Str,
+                    Conversion::Bool => ConvertTo::Bool,
+                };
+                let on_failure = match mode {
+                    ConversionMode::Strict => OnFailure::Fail,
+                    ConversionMode::Fallback => OnFailure::Fallback,
+                    ConversionMode::Null => OnFailure::Null,
+                };
+                self.chunk
+                    .emit_with_span(Instruction::Convert(to, on_failure), span.clone());
+            }
+            AAstNode::CallAggregate {
+                source,
+                keys,
+                aggregations,
+                ..
+            } => {
+                self.compile_aast_node(source)?;
+                let spec = AggregateSpec {
+                    keys: keys.clone(),
+                    aggregations: aggregations
+                        .iter()
+                        .map(|(name, op, field)| {
+                            (name.clone(), Self::aggregate_fn(*op), field.clone())
+                        })
+                        .collect(),
+                };
+                let idx = self.chunk.add_aggregate_spec(spec);
+                self.chunk.emit(Instruction::GroupAggregate(idx));
+            }
+            AAstNode::List { items, .. } => {
+                for item in items {
+                    self.compile_aast_node(item)?;
+                }
+                self.chunk.emit(Instruction::MakeList(items.len()));
+            }
+            AAstNode::Dict { pairs, .. } => {
+                for (key, value) in pairs {
+                    self.compile_constant(Constant::String(key.clone()));
+                    self.compile_aast_node(value)?;
+                }
+                self.chunk.emit(Instruction::MakeDict(pairs.len()));
+            }
+            AAstNode::CallGet { target, keys, .. } => {
+                self.compile_aast_node(target)?;
+                self.compile_keys(keys)?;
+            }
+            AAstNode::CallGetSlot { slot, keys, .. } => {
+                let idx = self.chunk.add_constant(Constant::String(slot.clone()));
+                self.chunk.emit(Instruction::LoadData(idx));
+                self.compile_keys(keys)?;
+            }
+            AAstNode::CallIf {
+                cond,
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                self.compile_aast_node(cond)?;
+                let to_else = self.chunk.emit(Instruction::JumpIfFalse(0));
+                self.compile_aast_node(then_branch)?;
+                let to_end = self.chunk.emit(Instruction::Jump(0));
+                self.chunk.patch_jump(to_else);
+                self.compile_aast_node(else_branch)?;
+                self.chunk.patch_jump(to_end);
+            }
+            AAstNode::CallCond { arms, default, .. } => {
+                let mut to_end = vec![];
+
+                for (cond, value) in arms {
+                    self.compile_aast_node(cond)?;
+                    let to_next = self.chunk.emit(Instruction::JumpIfFalse(0));
+                    self.compile_aast_node(value)?;
+                    to_end.push(self.chunk.emit(Instruction::Jump(0)));
+                    self.chunk.patch_jump(to_next);
+                }
+
+                match default {
+                    Some(default) => self.compile_aast_node(default)?,
+                    None => self.compile_constant(Constant::Null),
+                }
+
+                for idx in to_end {
+                    self.chunk.patch_jump(idx);
+                }
+            }
+            AAstNode::CallWhen { cond, body, .. } => {
+                self.compile_aast_node(cond)?;
+                let to_else = self.chunk.emit(Instruction::JumpIfFalse(0));
+                self.compile_body(body)?;
+                let to_end = self.chunk.emit(Instruction::Jump(0));
+                self.chunk.patch_jump(to_else);
+                self.compile_constant(Constant::Null);
+                self.chunk.patch_jump(to_end);
+            }
+            _ => {
+                return Err(CompilerErr::UnsupportedNode {
+                    span: aast_node.span().clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies the keys to the compound on top of the stack,
+    /// so a failure points at the key that can't be applied.
+    fn compile_keys(&mut self, keys: &[Box<AAstNode>]) -> Result<(), CompilerErr> {
+        for key in keys {
+            self.compile_aast_node(key)?;
+            self.chunk
+                .emit_with_span(Instruction::Get, key.span().clone());
+        }
+
+        Ok(())
+    }
+
+    /// Writes the value on top of the stack into the slot
+    /// and discards it.
+    fn compile_store(&mut self, slot: usize) {
+        self.chunk.emit(Instruction::Store(slot));
+        self.chunk.emit(Instruction::Pop);
+    }
+
+    fn temp_slot(&mut self) -> usize {
+        self.next_slot += 1;
+        self.next_slot - 1
+    }
+
+    fn compile_constant(&mut self, constant: Constant) {
+        let idx = self.chunk.add_constant(constant);
+        self.chunk.emit(Instruction::Const(idx));
+    }
+
+    /// Body evaluates to its last expression, values of the
+    /// others are discarded.
+    fn compile_body(&mut self, body: &[Box<AAstNode>]) -> Result<(), CompilerErr> {
+        for (idx, aast_node) in body.iter().enumerate() {
+            self.compile_aast_node(aast_node)?;
+
+            if idx < body.len() - 1 {
+                self.chunk.emit(Instruction::Pop);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Functions passed to collection functions are inlined into
+    // the body of a loop over the List, so no call frames are
+    // involved. Params of the function are the loop variables.
+    fn compile_collection(
+        &mut self,
+        op: CollectionOp,
+        source: &AAstNode,
+        args: &[Box<AAstNode>],
+    ) -> Result<(), CompilerErr> {
+        self.compile_aast_node(source)?;
+
+        match op {
+            CollectionOp::Count => {
+                self.chunk.emit(Instruction::Len);
+                return Ok(());
+            }
+            CollectionOp::CountDistinct
+            | CollectionOp::Sum
+            | CollectionOp::Avg
+            | CollectionOp::Min
+            | CollectionOp::Max => {
+                self.chunk
+                    .emit(Instruction::Aggregate(Self::aggregate_fn(op)));
+                return Ok(());
+            }
+            CollectionOp::Take | CollectionOp::Drop => {
+                self.compile_aast_node(&args[0])?;
+                self.chunk.emit(match op {
+                    CollectionOp::Take => Instruction::Take,
+                    _ => Instruction::Drop,
+                });
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        // Only inline functions can be lowered, calls of
+        // functions bound to symbols need call frames.
+        let AAstNode::CallFn { params, body, .. } = &*args[0] else {
+            return Err(CompilerErr::UnsupportedNode {
+                span: args[0].span().clone(),
+            });
+        };
+
+        let source_slot = self.temp_slot();
+        self.compile_store(source_slot);
+
+        // The value the loop builds up: the accumulator param of
+        // `.reduce`, keys of `.sort-by` and `.group-by` or the
+        // resulting List.
+        let acc_slot = match op {
+            CollectionOp::Reduce => {
+                self.compile_aast_node(&args[1])?;
+                params[0].0 as usize
+            }
+            _ => {
+                self.chunk.emit(Instruction::MakeList(0));
+                self.temp_slot()
+            }
+        };
+        self.compile_store(acc_slot);
+
+        let item_slot = params.last().unwrap().0 as usize;
+        let (loop_start, to_end, idx_slot) = self.compile_loop_start(source_slot, item_slot);
+
+        match op {
+            CollectionOp::Reduce => {
+                self.compile_body(body)?;
+                self.compile_store(acc_slot);
+            }
+            CollectionOp::Filter => {
+                self.compile_body(body)?;
+                let to_next = self.chunk.emit(Instruction::JumpIfFalse(0));
+                self.chunk.emit(Instruction::Load(acc_slot));
+                self.chunk.emit(Instruction::Load(item_slot));
+                self.chunk.emit(Instruction::Append);
+                self.compile_store(acc_slot);
+                self.chunk.patch_jump(to_next);
+            }
+            _ => {
+                self.chunk.emit(Instruction::Load(acc_slot));
+                self.compile_body(body)?;
+                self.chunk.emit(match op {
+                    CollectionOp::FlatMap => Instruction::Extend,
+                    _ => Instruction::Append,
+                });
+                self.compile_store(acc_slot);
+            }
+        }
+
+        self.compile_loop_end(loop_start, to_end, idx_slot);
+
+        match op {
+            CollectionOp::SortBy | CollectionOp::GroupBy => {
+                self.chunk.emit(Instruction::Load(source_slot));
+                self.chunk.emit(Instruction::Load(acc_slot));
+                self.chunk.emit(match op {
+                    CollectionOp::SortBy => Instruction::SortBy,
+                    _ => Instruction::GroupBy,
+                });
+            }
+            _ => {
+                self.chunk.emit(Instruction::Load(acc_slot));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn aggregate_fn(op: CollectionOp) -> AggregateFn {
+        match op {
+            CollectionOp::Count => AggregateFn::Count,
+            CollectionOp::CountDistinct => AggregateFn::CountDistinct,
+            CollectionOp::Sum => AggregateFn::Sum,
+            CollectionOp::Avg => AggregateFn::Avg,
+            CollectionOp::Min => AggregateFn::Min,
+            CollectionOp::Max => AggregateFn::Max,
+            op => unreachable!("{} is not an aggregation", op.as_str()),
+        }
+    }
+
+    /// Emits the loop condition and loads the current item into
+    /// its slot. Returns the index of the loop start, the index
+    /// of the jump out of the loop and the slot of the counter.
+    fn compile_loop_start(
+        &mut self,
+        source_slot: usize,
+        item_slot: usize,
+    ) -> (usize, usize, usize) {
+        let idx_slot = self.temp_slot();
+        self.compile_constant(Constant::Int("0".to_string()));
+        self.compile_store(idx_slot);
+
+        let loop_start = self.chunk.instructions.len();
+        self.chunk.emit(Instruction::Load(idx_slot));
+        self.chunk.emit(Instruction::Load(source_slot));
+        self.chunk.emit(Instruction::Len);
+        self.chunk.emit(Instruction::Lt);
+        let to_end = self.chunk.emit(Instruction::JumpIfFalse(0));
+
+        self.chunk.emit(Instruction::Load(source_slot));
+        self.chunk.emit(Instruction::Load(idx_slot));
+        self.chunk.emit(Instruction::Index);
+        self.compile_store(item_slot);
+
+        (loop_start, to_end, idx_slot)
+    }
+
+    fn compile_loop_end(&mut self, loop_start: usize, to_end: usize, idx_slot: usize) {
+        self.chunk.emit(Instruction::Load(idx_slot));
+        self.compile_constant(Constant::Int("1".to_string()));
+        self.chunk.emit(Instruction::Add);
+        self.compile_store(idx_slot);
+        self.chunk.emit(Instruction::Jump(loop_start));
+        self.chunk.patch_jump(to_end);
+    }
+
+    // Variadic arithmetic is folded from left to right, so
+    // .sub(a b c) is emitted as (a - b) - c.
+    fn compile_op(&mut self, op: Op, args: &[Box<AAstNode>]) -> Result<(), CompilerErr> {
+        if let Some(str_fn) = Self::str_fn(op) {
+            return self.compile_str_fn(str_fn, args);
+        }
+
+        if let Some(date_fn) = Self::date_fn(op) {
+            return self.compile_date_fn(date_fn, args);
+        }
+
+        let instruction = match op {
+            Op::Add => Instruction::Add,
+            Op::Sub => Instruction::Sub,
+            Op::Mul => Instruction::Mul,
+            Op::Div => Instruction::Div,
+            Op::Mod => Instruction::Mod,
+            Op::Neg => Instruction::Neg,
+            Op::Eq => Instruction::Eq,
+            Op::Neq => Instruction::Neq,
+            Op::Lt => Instruction::Lt,
+            Op::Lte => Instruction::Lte,
+            Op::Gt => Instruction::Gt,
+            Op::Gte => Instruction::Gte,
+            Op::Concat => Instruction::Concat,
+            _ => unreachable!(),
+        };
+
+        self.compile_aast_node(&args[0])?;
+
+        if op == Op::Neg {
+            self.chunk.emit(instruction);
+            return Ok(());
+        }
+
+        for arg in args.iter().skip(1) {
+            self.compile_aast_node(arg)?;
+            self.chunk.emit(instruction);
+        }
+
+        Ok(())
+    }
+
+    fn compile_str_fn(&mut self, str_fn: StrFn, args: &[Box<AAstNode>]) -> Result<(), CompilerErr> {
+        for arg in args {
+            self.compile_aast_node(arg)?;
+        }
+
+        // End of the substring defaults to the end of the String.
+        if str_fn == StrFn::Substring && args.len() == 2 {
+            self.compile_constant(Constant::Null);
+        }
+
+        self.chunk.emit(Instruction::Str(str_fn));
+
+        Ok(())
+    }
+
+    fn compile_date_fn(
+        &mut self,
+        date_fn: DateFn,
+        args: &[Box<AAstNode>],
+    ) -> Result<(), CompilerErr> {
+        for arg in args {
+            self.compile_aast_node(arg)?;
+        }
+
+        self.chunk.emit(Instruction::Date(date_fn));
+
+        Ok(())
+    }
+
+    fn date_fn(op: Op) -> Option<DateFn> {
+        let date_fn = match op {
+            Op::DateAdd => DateFn::Add,
+            Op::DateDiff => DateFn::Diff,
+            Op::DateTrunc => DateFn::Trunc,
+            Op::DateFormat => DateFn::Format,
+            _ => return None,
+        };
+
+        Some(date_fn)
+    }
+
+    fn str_fn(op: Op) -> Option<StrFn> {
+        let str_fn = match op {
+            Op::Length => StrFn::Length,
+            Op::Substring => StrFn::Substring,
+            Op::Split => StrFn::Split,
+            Op::Join => StrFn::Join,
+            Op::Trim => StrFn::Trim,
+            Op::Upper => StrFn::Upper,
+            Op::Lower => StrFn::Lower,
+            Op::StartsWith => StrFn::StartsWith,
+            Op::EndsWith => StrFn::EndsWith,
+            Op::Replace => StrFn::Replace,
+            Op::Matches => StrFn::Matches,
+            Op::Extract => StrFn::Extract,
+            Op::ExtractAll => StrFn::ExtractAll,
+            _ => return None,
+        };
+
+        Some(str_fn)
+    }
+}
+
+// ==================================================================
+//
+//  COMPILER END
+//
+// ==================================================================
@@ -0,0 +1,34 @@
+// Every test file includes this module but uses only some of the helpers.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use elise_bytecode::Chunk;
+use elise_compiler::Maestro;
+use elise_data::schema_resolver::{ResolvedSchema, SchemaResolver};
+use elise_parser::Prelude;
+use elise_semanalyzer::Harmony;
+
+pub fn compile(source_code: &str) -> Chunk {
+    let schema = ResolvedSchema {
+        resolved_schema: HashMap::new(),
+    };
+
+    compile_with_schema(source_code, &schema)
+}
+
+/// Compiles the source code against the schema, given as
+/// the source code of a `.schema`.
+pub fn compile_against(source_code: &str, schema_source_code: &str) -> Chunk {
+    let schema_ast = Prelude::new(schema_source_code.as_bytes()).parse().unwrap();
+    let schema = SchemaResolver::new(&schema_ast).resolve().unwrap();
+
+    compile_with_schema(source_code, &schema)
+}
+
+fn compile_with_schema(source_code: &str, schema: &ResolvedSchema) -> Chunk {
+    let ast = Prelude::new(source_code.as_bytes()).parse().unwrap();
+    let hir = Harmony::new(&ast, schema).analyze().unwrap();
+
+    Maestro::new(&hir).compile().unwrap()
+}
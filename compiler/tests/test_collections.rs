@@ -0,0 +1,234 @@
+use elise_bytecode::{AggregateFn, AggregateSpec, Constant, Instruction::*};
+
+use crate::common::{compile, compile_against};
+
+mod common;
+
+#[test]
+fn test_compiles_list_literal() {
+    let chunk = compile("[1 2]");
+
+    assert_eq!(chunk.instructions, vec![Const(0), Const(1), MakeList(2)]);
+}
+
+#[test]
+fn test_lowers_map_into_loop() {
+    let chunk = compile(".map([1 2] .fn([x] .mul(x 2)))");
+
+    // Symbol `x` takes slot 0, the list, the result and
+    // the counter take the next ones.
+    assert_eq!(
+        chunk.instructions,
+        vec![
+            Const(0),
+            Const(1),
+            MakeList(2),
+            Store(1),
+            Pop,
+            MakeList(0),
+            Store(2),
+            Pop,
+            Const(2),
+            Store(3),
+            Pop,
+            // Loop start.
+            Load(3),
+            Load(1),
+            Len,
+            Lt,
+            JumpIfFalse(34),
+            Load(1),
+            Load(3),
+            Index,
+            Store(0),
+            Pop,
+            Load(2),
+            Load(0),
+            Const(3),
+            Mul,
+            Append,
+            Store(2),
+            Pop,
+            Load(3),
+            Const(4),
+            Add,
+            Store(3),
+            Pop,
+            Jump(11),
+            // Loop end.
+            Load(2),
+        ]
+    );
+    assert_eq!(chunk.constants[2], Constant::Int("0".to_string()));
+    assert_eq!(chunk.constants[4], Constant::Int("1".to_string()));
+}
+
+#[test]
+fn test_lowers_map_over_data_rows_into_loop() {
+    let chunk = compile_against(
+        r#".map(@data .fn([r] .get(r "amount")))"#,
+        r#".schema(.list(.dict("amount" .int())))"#,
+    );
+
+    assert_eq!(
+        chunk.instructions,
+        vec![
+            LoadData(0),
+            Store(1),
+            Pop,
+            MakeList(0),
+            Store(2),
+            Pop,
+            Const(1),
+            Store(3),
+            Pop,
+            // Loop start.
+            Load(3),
+            Load(1),
+            Len,
+            Lt,
+            JumpIfFalse(32),
+            Load(1),
+            Load(3),
+            Index,
+            Store(0),
+            Pop,
+            Load(2),
+            Load(0),
+            Const(2),
+            Get,
+            Append,
+            Store(2),
+            Pop,
+            Load(3),
+            Const(3),
+            Add,
+            Store(3),
+            Pop,
+            Jump(9),
+            // Loop end.
+            Load(2),
+        ]
+    );
+    assert_eq!(chunk.constants[2], Constant::String("amount".to_string()));
+}
+
+#[test]
+fn test_lowers_filter_into_conditional_append() {
+    let chunk = compile(".filter([1] .fn([x] .gt(x 0)))");
+
+    assert_eq!(
+        &chunk.instructions[23..30],
+        &[
+            JumpIfFalse(29),
+            Load(2),
+            Load(0),
+            Append,
+            Store(2),
+            Pop,
+            Load(3)
+        ]
+    );
+}
+
+#[test]
+fn test_lowers_reduce_into_accumulator_param() {
+    let chunk = compile(".reduce([1] .fn([acc x] .add(acc x)) 0)");
+
+    // Accumulator is the first param itself.
+    assert_eq!(
+        &chunk.instructions[..7],
+        &[
+            Const(0),
+            MakeList(1),
+            Store(2),
+            Pop,
+            Const(1),
+            Store(0),
+            Pop
+        ]
+    );
+    assert_eq!(*chunk.instructions.last().unwrap(), Load(0));
+}
+
+#[test]
+fn test_sorts_items_by_collected_keys() {
+    let chunk = compile(".sort-by([2 1] .fn([x] x))");
+
+    assert_eq!(
+        &chunk.instructions[chunk.instructions.len() - 3..],
+        &[Load(1), Load(2), SortBy]
+    );
+}
+
+#[test]
+fn test_compiles_count_and_drop_without_loops() {
+    assert_eq!(
+        compile(".count([1])").instructions,
+        vec![Const(0), MakeList(1), Len]
+    );
+    assert_eq!(
+        compile(".drop([1] 1)").instructions,
+        vec![Const(0), MakeList(1), Const(1), Drop]
+    );
+}
+
+#[test]
+fn test_groups_items_by_collected_keys() {
+    let chunk = compile(".group-by([2 1] .fn([x] x))");
+
+    assert_eq!(
+        &chunk.instructions[chunk.instructions.len() - 3..],
+        &[Load(1), Load(2), GroupBy]
+    );
+}
+
+#[test]
+fn test_compiles_aggregations_without_loops() {
+    assert_eq!(
+        compile(".sum([1])").instructions,
+        vec![Const(0), MakeList(1), Aggregate(AggregateFn::Sum)]
+    );
+    assert_eq!(
+        compile(".count-distinct([1])").instructions,
+        vec![Const(0), MakeList(1), Aggregate(AggregateFn::CountDistinct)]
+    );
+}
+
+#[test]
+fn test_compiles_aggregate_over_data_rows() {
+    let chunk = compile_against(
+        r#".aggregate(@data ["country"] {"total" .sum("amount")})"#,
+        r#".schema(.list(.dict("country" .string() "amount" .int())))"#,
+    );
+
+    assert_eq!(chunk.instructions, vec![LoadData(0), GroupAggregate(0)]);
+    assert_eq!(
+        chunk.aggregate_specs,
+        vec![AggregateSpec {
+            keys: vec!["country".to_string()],
+            aggregations: vec![(
+                "total".to_string(),
+                AggregateFn::Sum,
+                Some("amount".to_string())
+            )],
+        }]
+    );
+}
+
+#[test]
+fn test_compiles_aggregate_into_spec() {
+    let chunk = compile(r#".let([rows []] .aggregate(rows ["a"] {"n" .count() "s" .sum("a")}))"#);
+
+    assert_eq!(*chunk.instructions.last().unwrap(), GroupAggregate(0));
+    assert_eq!(
+        chunk.aggregate_specs,
+        vec![AggregateSpec {
+            keys: vec!["a".to_string()],
+            aggregations: vec![
+                ("n".to_string(), AggregateFn::Count, None),
+                ("s".to_string(), AggregateFn::Sum, Some("a".to_string())),
+            ],
+        }]
+    );
+}
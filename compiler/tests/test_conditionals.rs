@@ -0,0 +1,181 @@
+use elise_bytecode::{Constant, Instruction::*};
+
+use crate::common::compile;
+
+mod common;
+
+// Conditions are bound with `.let`, since conditionals
+// with literal conditions are folded by Harmony.
+
+#[test]
+fn test_compiles_if_into_jumps() {
+    let chunk = compile(".let([c true] .if(c 1 2))");
+
+    assert_eq!(
+        chunk.instructions,
+        vec![
+            Const(0),
+            Store(0),
+            Pop,
+            Load(0),
+            JumpIfFalse(7),
+            Const(1),
+            Jump(8),
+            Const(2),
+        ]
+    );
+    assert_eq!(
+        chunk.constants,
+        vec![
+            Constant::Bool(true),
+            Constant::Int("1".to_string()),
+            Constant::Int("2".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_compiles_nested_if() {
+    let chunk = compile(".let([n 1] .if(.lt(n 2) .if(.gt(n 0) 1 2) 3))");
+
+    assert_eq!(
+        chunk.instructions,
+        vec![
+            Const(0),
+            Store(0),
+            Pop,
+            Load(0),
+            Const(1),
+            Lt,
+            JumpIfFalse(15),
+            Load(0),
+            Const(2),
+            Gt,
+            JumpIfFalse(13),
+            Const(3),
+            Jump(14),
+            Const(4),
+            Jump(16),
+            Const(5),
+        ]
+    );
+}
+
+#[test]
+fn test_compiles_cond_with_default() {
+    let chunk = compile(".let([c false] .cond(c 1 .neq(c true) 2 3))");
+
+    assert_eq!(
+        chunk.instructions,
+        vec![
+            Const(0),
+            Store(0),
+            Pop,
+            Load(0),
+            JumpIfFalse(7),
+            Const(1),
+            Jump(14),
+            Load(0),
+            Const(2),
+            Neq,
+            JumpIfFalse(13),
+            Const(3),
+            Jump(14),
+            Const(4),
+        ]
+    );
+}
+
+#[test]
+fn test_compiles_cond_without_default_to_null() {
+    let chunk = compile(".let([c false] .cond(c 1))");
+
+    assert_eq!(
+        chunk.instructions,
+        vec![
+            Const(0),
+            Store(0),
+            Pop,
+            Load(0),
+            JumpIfFalse(7),
+            Const(1),
+            Jump(8),
+            Const(2),
+        ]
+    );
+    assert_eq!(chunk.constants[2], Constant::Null);
+}
+
+#[test]
+fn test_compiles_when_body_and_null() {
+    let chunk = compile(".let([c true] .when(c 1 2))");
+
+    assert_eq!(
+        chunk.instructions,
+        vec![
+            Const(0),
+            Store(0),
+            Pop,
+            Load(0),
+            JumpIfFalse(9),
+            Const(1),
+            Pop,
+            Const(2),
+            Jump(10),
+            Const(3),
+        ]
+    );
+    assert_eq!(chunk.constants[3], Constant::Null);
+}
+
+#[test]
+fn test_compiles_let_bound_condition() {
+    let chunk = compile(".let([x 1] .if(.gt(x 0) x .neg(x)))");
+
+    assert_eq!(
+        chunk.instructions,
+        vec![
+            Const(0),
+            Store(0),
+            Pop,
+            Load(0),
+            Const(1),
+            Gt,
+            JumpIfFalse(9),
+            Load(0),
+            Jump(11),
+            Load(0),
+            Neg,
+        ]
+    );
+}
+
+#[test]
+fn test_emits_only_taken_branch_of_folded_if() {
+    let chunk = compile(r#".if(.lt(1 2) "a" "b")"#);
+
+    assert_eq!(chunk.instructions, vec![Const(0)]);
+    assert_eq!(chunk.constants, vec![Constant::String("a".to_string())]);
+}
+
+#[test]
+fn test_discards_values_of_top_level_expressions() {
+    let chunk = compile(".define(X 1) .let([y 2] .add(y X 3))");
+
+    assert_eq!(
+        chunk.instructions,
+        vec![
+            Const(0),
+            Store(0),
+            Pop,
+            Const(1),
+            Store(1),
+            Pop,
+            Load(1),
+            Const(2),
+            Add,
+            Const(3),
+            Add,
+        ]
+    );
+}
@@ -0,0 +1,34 @@
+use elise_bytecode::{ConvertTo, Instruction::*, OnFailure};
+use elise_shared::shared_types::Span;
+
+use crate::common::compile;
+
+mod common;
+
+#[test]
+fn test_compiles_conversion_with_span() {
+    let chunk = compile(".add(1 .to-int(\"2\"))");
+
+    assert_eq!(
+        chunk.instructions,
+        vec![
+            Const(0),
+            Const(1),
+            Convert(ConvertTo::Int, OnFailure::Fail),
+            Add
+        ]
+    );
+    assert_eq!(chunk.spans.get(&2), Some(&Span { start: 7, end: 19 }));
+}
+
+#[test]
+fn test_pushes_fallback_after_value() {
+    assert_eq!(
+        compile(".to-float-or(\"a\" 0.5)").instructions,
+        vec![
+            Const(0),
+            Const(1),
+            Convert(ConvertTo::Float, OnFailure::Fallback)
+        ]
+    );
+}
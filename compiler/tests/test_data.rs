@@ -0,0 +1,60 @@
+use elise_bytecode::{Constant, Instruction::*};
+use elise_shared::shared_types::Span;
+
+use crate::common::{compile, compile_against};
+
+mod common;
+
+const SCHEMA: &str = r#"
+.schema(
+    .dict(
+        "rows" .list(
+                   .dict(
+                       "country" .string()
+                       "amount"  .int()))))
+"#;
+
+#[test]
+fn test_loads_data_from_its_slot() {
+    let chunk = compile_against("@data", SCHEMA);
+
+    assert_eq!(chunk.instructions, vec![LoadData(0)]);
+    assert_eq!(chunk.constants, vec![Constant::String("data".to_string())]);
+}
+
+#[test]
+fn test_applies_keys_of_data_one_by_one() {
+    let chunk = compile_against(r#".get(@data "rows" 0 "amount")"#, SCHEMA);
+
+    assert_eq!(
+        chunk.instructions,
+        vec![LoadData(0), Const(1), Get, Const(2), Get, Const(3), Get]
+    );
+    assert_eq!(chunk.constants[1], Constant::String("rows".to_string()));
+    assert_eq!(chunk.constants[2], Constant::Int("0".to_string()));
+    // Failures point at the key that can't be applied.
+    assert_eq!(chunk.spans[&2], Span { start: 11, end: 17 });
+    assert_eq!(chunk.spans[&4], Span { start: 18, end: 19 });
+}
+
+#[test]
+fn test_applies_keys_to_compound() {
+    let chunk = compile(r#".let([xs [1 2]] .get(xs 1))"#);
+
+    assert_eq!(
+        &chunk.instructions[chunk.instructions.len() - 3..],
+        &[Load(0), Const(2), Get]
+    );
+}
+
+#[test]
+fn test_compiles_dict_literal_with_keys_before_values() {
+    let chunk = compile(r#"{"a" 1 "b" .add(1 2)}"#);
+
+    assert_eq!(
+        chunk.instructions,
+        vec![Const(0), Const(1), Const(2), Const(3), MakeDict(2)]
+    );
+    assert_eq!(chunk.constants[0], Constant::String("a".to_string()));
+    assert_eq!(chunk.constants[3], Constant::Int("3".to_string()));
+}
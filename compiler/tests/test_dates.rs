@@ -0,0 +1,21 @@
+use elise_bytecode::{Constant, DateFn, Instruction::*};
+
+use crate::common::compile;
+
+mod common;
+
+#[test]
+fn test_compiles_date_fn_after_its_args() {
+    let chunk = compile(r#".map([] .fn([d] .date-add(.date-trunc(d "month") d)))"#);
+
+    // Symbol `d` takes slot 0.
+    assert!(chunk.instructions.windows(5).any(|window| window
+        == [
+            Load(0),
+            Const(1),
+            Date(DateFn::Trunc),
+            Load(0),
+            Date(DateFn::Add)
+        ]));
+    assert_eq!(chunk.constants[1], Constant::String("month".to_string()));
+}
@@ -0,0 +1,24 @@
+use elise_bytecode::{Constant, Instruction::*, StrFn};
+
+use crate::common::compile;
+
+mod common;
+
+#[test]
+fn test_compiles_string_fn_after_its_args() {
+    assert_eq!(
+        compile(r#".replace("ab" "a" "c")"#).instructions,
+        vec![Const(0), Const(1), Const(2), Str(StrFn::Replace)]
+    );
+}
+
+#[test]
+fn test_pushes_null_for_omitted_substring_end() {
+    let chunk = compile(r#".substring("abc" 1)"#);
+
+    assert_eq!(
+        chunk.instructions,
+        vec![Const(0), Const(1), Const(2), Str(StrFn::Substring)]
+    );
+    assert_eq!(chunk.constants[2], Constant::Null);
+}
@@ -0,0 +1,115 @@
+//! # AST dump
+//!
+//! Versioned JSON representation of the AST for external tools.
+//! Every node has `kind` and `span`. Primitives carry `value`,
+//! calls carry `lexeme`, dict pairs carry `key` and `key_span`.
+//! Compound nodes, calls and dict pairs carry `children`.
+
+use elise_shared::shared_json::{DumpKind, Json};
+
+use crate::AstNode;
+
+impl AstNode {
+    pub fn to_json(&self) -> Json {
+        let kind = ("kind", Json::str(self.as_str()));
+        let span = ("span", Json::from(self.span()));
+
+        match self {
+            AstNode::Call(call) => Json::object(vec![
+                kind,
+                ("lexeme", Json::str(&call.lexeme)),
+                span,
+                ("children", Self::children_to_json(&call.children)),
+            ]),
+            AstNode::Int(prim)
+            | AstNode::Float(prim)
+            | AstNode::String(prim)
+            | AstNode::Bool(prim)
+            | AstNode::Null(prim)
+            | AstNode::Identifier(prim)
+            | AstNode::Slot(prim) => {
+                Json::object(vec![kind, ("value", Json::str(&prim.value)), span])
+            }
+            AstNode::List(compound) | AstNode::Dict(compound) => Json::object(vec![
+                kind,
+                span,
+                ("children", Self::children_to_json(&compound.children)),
+            ]),
+            AstNode::DictPair(pair) => Json::object(vec![
+                kind,
+                ("key", Json::str(&pair.key)),
+                ("key_span", Json::from(&pair.key_span)),
+                span,
+                ("children", Json::Array(vec![pair.value.to_json()])),
+            ]),
+        }
+    }
+
+    fn children_to_json(children: &[Box<AstNode>]) -> Json {
+        Json::Array(children.iter().map(|child| child.to_json()).collect())
+    }
+}
+
+/// Versioned JSON document of the whole AST.
+pub fn ast_to_json(ast: &[AstNode]) -> Json {
+    Json::document(
+        DumpKind::AST,
+        "nodes",
+        Json::Array(ast.iter().map(AstNode::to_json).collect()),
+    )
+}
+
+/// Pretty printed JSON dump of the whole AST.
+pub fn ast_dump(ast: &[AstNode]) -> String {
+    ast_to_json(ast).to_pretty()
+}
+
+// ==================================================================
+//
+// TESTS START
+//
+// ==================================================================
+
+#[cfg(test)]
+mod tests {
+    use elise_shared::shared_types::Span;
+
+    use crate::{AstCall, AstCompound, AstKeyValuePair, AstNode, AstPrimitive, dump::ast_to_json};
+
+    #[test]
+    fn should_dump_ast() {
+        let ast = vec![AstNode::Call(AstCall {
+            lexeme: "print".to_string(),
+            span: Span { start: 0, end: 18 },
+            children: vec![Box::new(AstNode::Dict(AstCompound {
+                span: Span { start: 7, end: 17 },
+                children: vec![Box::new(AstNode::DictPair(AstKeyValuePair {
+                    key: "a".to_string(),
+                    key_span: Span { start: 8, end: 11 },
+                    value: Box::new(AstNode::Int(AstPrimitive {
+                        value: "1".to_string(),
+                        span: Span { start: 12, end: 13 },
+                    })),
+                    span: Span { start: 8, end: 13 },
+                }))],
+            }))],
+        })];
+
+        assert_eq!(
+            ast_to_json(&ast).to_compact(),
+            concat!(
+                r#"{"version":2,"kind":"ast","nodes":[{"kind":"Call","lexeme":"print","#,
+                r#""span":{"start":0,"end":18},"children":[{"kind":"Dict","#,
+                r#""span":{"start":7,"end":17},"children":[{"kind":"DictPair","key":"a","#,
+                r#""key_span":{"start":8,"end":11},"span":{"start":8,"end":13},"#,
+                r#""children":[{"kind":"Int","value":"1","span":{"start":12,"end":13}}]}]}]}]}"#
+            )
+        );
+    }
+}
+
+// ==================================================================
+//
+// TESTS END
+//
+// ==================================================================
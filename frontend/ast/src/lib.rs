@@ -3,10 +3,12 @@
 //! This module consists of AST related type definitions
 //! and implementations.
 
+pub mod dump;
+
 use elise_shared::{shared_node_names::NodeName, shared_types::Span};
 
 /// Represents value for a function call.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct AstCall {
     pub lexeme: String,
     pub span: Span,
@@ -15,7 +17,7 @@ pub struct AstCall {
 
 /// Represents a primitive value that does not
 /// have any nested values inside. Numbers, strings, bools etc.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct AstPrimitive {
     // Interpreted runtime string. Encoding aware.
     pub value: String,
@@ -26,7 +28,7 @@ pub struct AstPrimitive {
 
 /// Represents a value that consists of other values like
 /// lists, dictionaries or functions.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct AstCompound {
     // Slice of bytes.
     pub span: Span,
@@ -34,7 +36,7 @@ pub struct AstCompound {
 }
 
 /// Dictionary key-value pair representation.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct AstKeyValuePair {
     pub key: String,
     // Span for key itself since we don't want
@@ -47,7 +49,7 @@ pub struct AstKeyValuePair {
     pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum AstNode {
     Call(AstCall),
     Int(AstPrimitive),
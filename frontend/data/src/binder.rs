@@ -11,14 +11,14 @@
 
 use std::collections::HashMap;
 
-use crate::data_types::{DataType, ResolutionPath};
+use crate::{resolution_path::ResolutionPath, schema_resolver::SchemaDataType};
 
 /// Provides some basic information for about
 /// underlying data.
 #[derive(Debug, PartialEq)]
 pub struct DataDescriptor {
     // We don't keep parsed values in enum options.
-    pub ty: DataType,
+    pub ty: SchemaDataType,
     // Instead we just use `value` prop as String type
     // because our values will be serialized into bytecode
     // anyway, so there is no need to parse "true" to bool,
@@ -1,5 +1,8 @@
 use csv::{ErrorKind, ReaderBuilder};
-use elise_shared::{shared_errors::errors_csv_parser::CsvParserErr, shared_types::Keyword};
+use elise_shared::{
+    shared_errors::errors_csv_parser::CsvParserErr, shared_node_names::NodeName,
+    shared_types::Keyword,
+};
 
 // ==================================================================
 //
@@ -15,6 +18,17 @@ pub enum ParserDataType {
     Bool,
     Null,
 }
+impl ParserDataType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParserDataType::Int => NodeName::INT,
+            ParserDataType::Float => NodeName::FLOAT,
+            ParserDataType::String => NodeName::STRING,
+            ParserDataType::Bool => NodeName::BOOL,
+            ParserDataType::Null => NodeName::NULL,
+        }
+    }
+}
 
 pub struct CsvParser<'a> {
     data: &'a str,
@@ -0,0 +1,303 @@
+//! # CSV Validator
+//!
+//! Checks parsed CSV rows against the resolved schema. Rows of
+//! CSV are items of the root list of the schema, where each row
+//! is a dict of columns:
+//!
+//! .schema(
+//!    .list(
+//!       .dict(
+//!          "name" .string(.len(1 64))
+//!          "age"  .nullable(.int(.range(0 150))))))
+//!
+//! So the type of the column "age" is resolved with the path
+//! [Root, AbstractIndex, Field("age")]. Every cell must be of the
+//! type of its column and satisfy all constraints of the type.
+//! Cells of dates, times and durations must be written in the
+//! format of their column.
+
+use std::collections::HashMap;
+
+use elise_shared::shared_errors::{
+    errors_csv_binder::PosInfo, errors_csv_validator::CsvValidatorErr,
+};
+use elise_shared::shared_node_names::NodeName;
+use elise_shared::shared_temporal;
+use regex::Regex;
+
+use crate::{
+    csv::csv_parser::{CsvCol, CsvRow, ParserDataType},
+    resolution_path::{ResolutionPath, ResolutionPathSegment},
+    schema_resolver::{ResolvedSchema, SchemaConstraint, SchemaDataType, SchemaTypeDescriptor},
+};
+
+// ==================================================================
+//
+// VALIDATOR START
+//
+// ==================================================================
+
+pub struct CsvValidator<'a> {
+    rows: &'a [CsvRow],
+    schema: &'a ResolvedSchema,
+    // Patterns of the schema compiled once for all rows.
+    patterns: HashMap<&'a str, Regex>,
+}
+
+impl<'a> CsvValidator<'a> {
+    pub fn new(rows: &'a [CsvRow], schema: &'a ResolvedSchema) -> Self {
+        let mut patterns = HashMap::new();
+
+        for descriptor in schema.resolved_schema.values() {
+            for constraint in &descriptor.constraints {
+                if let SchemaConstraint::Pattern(pattern) = constraint {
+                    // The whole value must match the pattern. Patterns are
+                    // checked by the SchemaResolver, so anchoring them
+                    // can't make them invalid.
+                    let anchored = Regex::new(&format!("^(?:{pattern})$")).unwrap();
+                    patterns.insert(pattern.as_str(), anchored);
+                }
+            }
+        }
+
+        Self {
+            rows,
+            schema,
+            patterns,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), CsvValidatorErr> {
+        let root = self.schema.resolved_schema.get(&ResolutionPath::new());
+        let row_path = ResolutionPath::with_segments(vec![ResolutionPathSegment::AbstractIndex]);
+        let row = self.schema.resolved_schema.get(&row_path);
+
+        match (root.map(|d| &d.dtype), row.map(|d| &d.dtype)) {
+            (
+                Some(SchemaDataType::ListAbstract | SchemaDataType::ListFixed(_)),
+                Some(SchemaDataType::Dict),
+            ) => {}
+            _ => return Err(CsvValidatorErr::InvalRowSchema),
+        }
+
+        // Every row has the same columns, since row length
+        // consistency is handled by the CsvParser.
+        if let Some(first_row) = self.rows.first() {
+            self.validate_cols(&row_path, first_row)?;
+        }
+
+        for row in self.rows {
+            for col in &row.cols {
+                let mut col_path = row_path.clone();
+                col_path.push(ResolutionPathSegment::Field(col.name.clone()));
+
+                let descriptor = self.schema.resolved_schema.get(&col_path).ok_or_else(|| {
+                    CsvValidatorErr::MissingTypeDefinition {
+                        pos: Self::pos(col),
+                        col: col.name.clone(),
+                    }
+                })?;
+
+                self.validate_value(col, &col_path, descriptor)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn pos(col: &CsvCol) -> PosInfo {
+        PosInfo {
+            row: col.row,
+            col: col.col,
+        }
+    }
+
+    /// Checks that every column of the row schema which is
+    /// not optional is present in CSV.
+    fn validate_cols(
+        &self,
+        row_path: &ResolutionPath,
+        row: &CsvRow,
+    ) -> Result<(), CsvValidatorErr> {
+        let mut required: Vec<&String> = self
+            .schema
+            .resolved_schema
+            .iter()
+            .filter(|(path, descriptor)| {
+                path.len() == row_path.len() + 1
+                    && path.starts_with(row_path)
+                    && !descriptor.optional
+            })
+            .filter_map(|(path, _)| match path.last() {
+                Some(ResolutionPathSegment::Field(name)) => Some(name),
+                _ => None,
+            })
+            .collect();
+
+        // HashMap doesn't keep any order, so we report
+        // missing columns in alphabetical order.
+        required.sort();
+
+        for name in required {
+            if !row.cols.iter().any(|col| &col.name == name) {
+                return Err(CsvValidatorErr::MissingCol { col: name.clone() });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_value(
+        &self,
+        col: &CsvCol,
+        path: &ResolutionPath,
+        descriptor: &SchemaTypeDescriptor,
+    ) -> Result<(), CsvValidatorErr> {
+        if col.ty == ParserDataType::Null && descriptor.nullable {
+            return Ok(());
+        }
+
+        if descriptor.dtype == SchemaDataType::Union {
+            return self.validate_union(col, path);
+        }
+
+        if !Self::accepts(&descriptor.dtype, &col.ty) {
+            return Err(CsvValidatorErr::TypeMismatch {
+                pos: Self::pos(col),
+                col: col.name.clone(),
+                expected: descriptor.dtype.as_str(),
+                found: col.ty.as_str(),
+            });
+        }
+
+        if let Some(format) = descriptor.dtype.format()
+            && !Self::matches_format(&descriptor.dtype, &col.value)
+        {
+            return Err(CsvValidatorErr::FormatMismatch {
+                pos: Self::pos(col),
+                col: col.name.clone(),
+                value: col.value.clone(),
+                format: format.to_string(),
+            });
+        }
+
+        for constraint in &descriptor.constraints {
+            if !self.satisfies(constraint, &descriptor.dtype, &col.value) {
+                return Err(CsvValidatorErr::ConstraintViolation {
+                    pos: Self::pos(col),
+                    col: col.name.clone(),
+                    value: col.value.clone(),
+                    constraint: constraint.as_str(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// A value of a union must be valid for one of its alternatives.
+    /// If the value is of the type of some alternatives, the error of
+    /// the first of them is reported.
+    fn validate_union(&self, col: &CsvCol, path: &ResolutionPath) -> Result<(), CsvValidatorErr> {
+        let mut alternatives: Vec<_> = self
+            .schema
+            .resolved_schema
+            .iter()
+            .filter(|(alternative_path, _)| {
+                alternative_path.len() == path.len() + 1 && alternative_path.starts_with(path)
+            })
+            .collect();
+
+        // Keep reported errors stable.
+        alternatives.sort_by_key(|(_, descriptor)| descriptor.dtype.as_str());
+
+        let mut err = None;
+
+        for (alternative_path, descriptor) in alternatives {
+            match self.validate_value(col, alternative_path, descriptor) {
+                Ok(()) => return Ok(()),
+                Err(alternative_err) if Self::accepts(&descriptor.dtype, &col.ty) => {
+                    err.get_or_insert(alternative_err);
+                }
+                Err(_) => {}
+            }
+        }
+
+        Err(err.unwrap_or_else(|| CsvValidatorErr::TypeMismatch {
+            pos: Self::pos(col),
+            col: col.name.clone(),
+            expected: NodeName::UNION,
+            found: col.ty.as_str(),
+        }))
+    }
+
+    /// Whether a cell of the parsed type is a value of the schema type.
+    fn accepts(dtype: &SchemaDataType, ty: &ParserDataType) -> bool {
+        match dtype {
+            SchemaDataType::Int => *ty == ParserDataType::Int,
+            SchemaDataType::Float => matches!(ty, ParserDataType::Int | ParserDataType::Float),
+            // Any cell can be read as a string, like "42"
+            // in a column of zip codes.
+            SchemaDataType::String => *ty != ParserDataType::Null,
+            SchemaDataType::Bool => *ty == ParserDataType::Bool,
+            // Formats like "%Y%m%d" read as numbers, so they
+            // are checked by `matches_format` instead.
+            SchemaDataType::Date(_)
+            | SchemaDataType::Time(_)
+            | SchemaDataType::DateTime(_)
+            | SchemaDataType::Duration => *ty != ParserDataType::Null,
+            // CSV cells can't hold compound values.
+            SchemaDataType::ListAbstract
+            | SchemaDataType::ListFixed(_)
+            | SchemaDataType::Dict
+            | SchemaDataType::Union => false,
+        }
+    }
+
+    fn matches_format(dtype: &SchemaDataType, value: &str) -> bool {
+        match dtype.temporal() {
+            Some((kind, format)) => shared_temporal::parse(value, format, kind).is_some(),
+            None => shared_temporal::parse_duration(value).is_some(),
+        }
+    }
+
+    fn satisfies(
+        &self,
+        constraint: &SchemaConstraint,
+        dtype: &SchemaDataType,
+        value: &str,
+    ) -> bool {
+        match constraint {
+            SchemaConstraint::IntRange { min, max } => value
+                .parse::<i64>()
+                .is_ok_and(|number| *min <= number && number <= *max),
+            SchemaConstraint::FloatRange { min, max } => value
+                .parse::<f64>()
+                .is_ok_and(|number| *min <= number && number <= *max),
+            SchemaConstraint::Len { min, max } => {
+                let len = value.chars().count();
+                *min <= len && len <= *max
+            }
+            SchemaConstraint::Pattern(pattern) => self.patterns[pattern.as_str()].is_match(value),
+            SchemaConstraint::Enum(values) => values.iter().any(|enum_value| match dtype {
+                // Ints are compared as they are, so the ones beyond
+                // the precision of Floats stay apart.
+                SchemaDataType::Int => value
+                    .parse::<i64>()
+                    .is_ok_and(|number| enum_value.parse() == Ok(number)),
+                // Numbers are compared by their values, so 1.0 is the same as 1.
+                SchemaDataType::Float => {
+                    enum_value.parse::<f64>().ok() == value.parse::<f64>().ok()
+                }
+                SchemaDataType::Bool => enum_value.eq_ignore_ascii_case(value),
+                _ => enum_value == value,
+            }),
+        }
+    }
+}
+
+// ==================================================================
+//
+// VALIDATOR END
+//
+// ==================================================================
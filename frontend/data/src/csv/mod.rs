@@ -1,2 +1,3 @@
 //pub mod csv_binder;
 pub mod csv_parser;
+pub mod csv_validator;
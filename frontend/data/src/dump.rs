@@ -0,0 +1,152 @@
+//! # Resolved schema dump
+//!
+//! Versioned JSON representation of the resolved schema for
+//! external tools. Every resolution path is dumped as a list
+//! of segments together with its type descriptor. Paths are
+//! sorted, so the same schema always gives the same dump.
+
+use elise_shared::shared_json::{DumpKind, Json};
+
+use crate::{
+    resolution_path::{ResolutionPath, ResolutionPathSegment},
+    schema_resolver::{ResolvedSchema, SchemaConstraint, SchemaDataType, SchemaTypeDescriptor},
+};
+
+impl ResolutionPathSegment {
+    pub fn to_json(&self) -> Json {
+        match self {
+            ResolutionPathSegment::Root => Json::object(vec![("kind", Json::str("Root"))]),
+            ResolutionPathSegment::AbstractIndex => {
+                Json::object(vec![("kind", Json::str("AbstractIndex"))])
+            }
+            ResolutionPathSegment::Field(name) => Json::object(vec![
+                ("kind", Json::str("Field")),
+                ("name", Json::str(name)),
+            ]),
+            ResolutionPathSegment::Assertion(kind) => Json::object(vec![
+                ("kind", Json::str("Assertion")),
+                ("name", Json::str(kind)),
+            ]),
+        }
+    }
+}
+
+impl ResolutionPath {
+    pub fn to_json(&self) -> Json {
+        Json::Array(self.iter().map(ResolutionPathSegment::to_json).collect())
+    }
+}
+
+impl SchemaConstraint {
+    pub fn to_json(&self, dtype: &SchemaDataType) -> Json {
+        match self {
+            SchemaConstraint::IntRange { min, max } => Json::object(vec![
+                ("kind", Json::str("range")),
+                ("min", Json::Int(*min)),
+                ("max", Json::Int(*max)),
+            ]),
+            SchemaConstraint::FloatRange { min, max } => Json::object(vec![
+                ("kind", Json::str("range")),
+                ("min", Json::Float(*min)),
+                ("max", Json::Float(*max)),
+            ]),
+            SchemaConstraint::Len { min, max } => Json::object(vec![
+                ("kind", Json::str("len")),
+                ("min", Json::Int(*min as i64)),
+                ("max", Json::Int(*max as i64)),
+            ]),
+            SchemaConstraint::Pattern(pattern) => Json::object(vec![
+                ("kind", Json::str("pattern")),
+                ("pattern", Json::str(pattern)),
+            ]),
+            SchemaConstraint::Enum(values) => Json::object(vec![
+                ("kind", Json::str("enum")),
+                (
+                    "values",
+                    Json::Array(
+                        values
+                            .iter()
+                            .map(|value| Self::enum_value_to_json(value, dtype))
+                            .collect(),
+                    ),
+                ),
+            ]),
+        }
+    }
+
+    // Enum values are kept as written in the schema,
+    // so they are dumped as values of the constrained type.
+    fn enum_value_to_json(value: &str, dtype: &SchemaDataType) -> Json {
+        match dtype {
+            SchemaDataType::Int => value.parse().map_or(Json::str(value), Json::Int),
+            SchemaDataType::Float => value.parse().map_or(Json::str(value), Json::Float),
+            SchemaDataType::Bool => Json::Bool(value == "true"),
+            _ => Json::str(value),
+        }
+    }
+}
+
+impl SchemaTypeDescriptor {
+    pub fn to_json(&self) -> Json {
+        let fixed_len = match self.dtype {
+            SchemaDataType::ListFixed(len) => Json::Int(len as i64),
+            _ => Json::Null,
+        };
+
+        Json::object(vec![
+            ("dtype", Json::str(self.dtype.as_str())),
+            ("fixed_len", fixed_len),
+            ("format", self.dtype.format().map_or(Json::Null, Json::str)),
+            ("nullable", Json::Bool(self.nullable)),
+            ("optional", Json::Bool(self.optional)),
+            (
+                "constraints",
+                Json::Array(
+                    self.constraints
+                        .iter()
+                        .map(|constraint| constraint.to_json(&self.dtype))
+                        .collect(),
+                ),
+            ),
+        ])
+    }
+}
+
+/// Versioned JSON document of the resolved schema.
+pub fn schema_to_json(schema: &ResolvedSchema) -> Json {
+    let mut paths = schema.resolved_schema.iter().collect::<Vec<_>>();
+
+    // HashMap doesn't keep any order. Parent path always
+    // goes right before its nested paths.
+    paths.sort_by_cached_key(|(path, _)| {
+        path.iter()
+            .map(|segment| match segment {
+                ResolutionPathSegment::Root => (0, ""),
+                ResolutionPathSegment::AbstractIndex => (1, ""),
+                ResolutionPathSegment::Field(name) => (2, name.as_str()),
+                ResolutionPathSegment::Assertion(kind) => (3, kind.as_str()),
+            })
+            .collect::<Vec<_>>()
+    });
+
+    Json::document(
+        DumpKind::SCHEMA,
+        "paths",
+        Json::Array(
+            paths
+                .into_iter()
+                .map(|(path, descriptor)| {
+                    Json::object(vec![
+                        ("path", path.to_json()),
+                        ("type", descriptor.to_json()),
+                    ])
+                })
+                .collect(),
+        ),
+    )
+}
+
+/// Pretty printed JSON dump of the resolved schema.
+pub fn schema_dump(schema: &ResolvedSchema) -> String {
+    schema_to_json(schema).to_pretty()
+}
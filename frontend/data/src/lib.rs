@@ -2,8 +2,9 @@ use elise_shared::shared_errors::errors_csv_parser::CsvParserErr;
 
 use crate::csv::csv_parser::CsvRow;
 
-//pub mod binder;
+pub mod binder;
 pub mod csv;
+pub mod dump;
 pub mod resolution_path;
 pub mod schema_resolver;
 
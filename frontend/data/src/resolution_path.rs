@@ -12,6 +12,9 @@ pub enum ResolutionPathSegment {
     AbstractIndex,
     // Any field like dict key.
     Field(String),
+    // One alternative of a union, named after the kind
+    // of its type, like "Int" or "List".
+    Assertion(String),
 }
 impl ResolutionPathSegment {
     // For anything that requires string representation, like error reports.
@@ -20,6 +23,7 @@ impl ResolutionPathSegment {
             ResolutionPathSegment::Root => "Root".to_string(),
             ResolutionPathSegment::AbstractIndex => "AbstractIndex".to_string(),
             ResolutionPathSegment::Field(name) => format!("Field(\"{}\")", name),
+            ResolutionPathSegment::Assertion(kind) => format!("Assertion(\"{}\")", kind),
         }
     }
 }
@@ -33,13 +33,16 @@
 //! result.
 
 use std::collections::HashMap;
+use std::rc::Rc;
 
 use elise_ast::{AstCall, AstNode};
+use regex::Regex;
 
-use elise_shared::shared_errors::errors_schema_resolver::SchemaResolverErr;
-use elise_shared::shared_types::ArityMismatchKind;
+use elise_shared::shared_errors::errors_schema_resolver::{SchemaImportErr, SchemaResolverErr};
+use elise_shared::shared_types::{ArityMismatchKind, Span};
 
 use elise_shared::shared_node_names::NodeName;
+use elise_shared::shared_temporal::{self, DURATION_FORMAT, TemporalKind};
 
 use crate::resolution_path::{ResolutionPath, ResolutionPathSegment};
 
@@ -65,10 +68,18 @@ pub enum SchemaDataType {
     Float,
     String,
     Bool,
+    // Temporal types carry the format their values are
+    // written in the data with.
+    Date(String),
+    Time(String),
+    DateTime(String),
+    Duration,
     ListAbstract,
     ListFixed(usize),
     Dict,
-    // Union,
+    // Path resolves to one of several alternatives, each
+    // one continues under its own Assertion segment.
+    Union,
 }
 // TODO: Check if we need this.
 impl SchemaDataType {
@@ -78,10 +89,33 @@ impl SchemaDataType {
             SchemaDataType::Float => NodeName::FLOAT,
             SchemaDataType::String => NodeName::STRING,
             SchemaDataType::Bool => NodeName::BOOL,
+            SchemaDataType::Date(_) => NodeName::DATE,
+            SchemaDataType::Time(_) => NodeName::TIME,
+            SchemaDataType::DateTime(_) => NodeName::DATETIME,
+            SchemaDataType::Duration => NodeName::DURATION,
             SchemaDataType::ListAbstract => NodeName::LIST,
             SchemaDataType::ListFixed(_) => NodeName::LIST,
             SchemaDataType::Dict => NodeName::DICT,
-            // SchemaDataType::Union => NodeName::UNION,
+            SchemaDataType::Union => NodeName::UNION,
+        }
+    }
+
+    /// Kind of Date, Time and DateTime together with their format.
+    pub fn temporal(&self) -> Option<(TemporalKind, &str)> {
+        match self {
+            SchemaDataType::Date(format) => Some((TemporalKind::Date, format)),
+            SchemaDataType::Time(format) => Some((TemporalKind::Time, format)),
+            SchemaDataType::DateTime(format) => Some((TemporalKind::DateTime, format)),
+            _ => None,
+        }
+    }
+
+    /// Format values of the type are written in, durations
+    /// are always written in ISO 8601.
+    pub fn format(&self) -> Option<&str> {
+        match self {
+            SchemaDataType::Duration => Some(DURATION_FORMAT),
+            dtype => dtype.temporal().map(|(_, format)| format),
         }
     }
 }
@@ -108,6 +142,11 @@ impl SchemaFnLexeme {
     // in the future.
     pub const ROOT: &'static str = "schema";
 
+    // Named type definitions.
+    pub const TYPE: &'static str = "type";
+    pub const REF: &'static str = "ref";
+    pub const IMPORT: &'static str = "import";
+
     // Modifiers.
     pub const NULLABLE: &'static str = "nullable";
     pub const OPTIONAL: &'static str = "optional";
@@ -117,9 +156,20 @@ impl SchemaFnLexeme {
     pub const FLOAT: &'static str = "float";
     pub const STRING: &'static str = "string";
     pub const BOOL: &'static str = "bool";
+    pub const DATE: &'static str = "date";
+    pub const TIME: &'static str = "time";
+    pub const DATETIME: &'static str = "datetime";
+    pub const DURATION: &'static str = "duration";
     pub const DICT: &'static str = "dict";
     pub const LIST: &'static str = "list";
-    // pub const UNION: &'static str = "union";
+    pub const UNION: &'static str = "union";
+    // Also used as a constraint of primitives.
+    pub const ENUM: &'static str = "enum";
+
+    // Constraints.
+    pub const RANGE: &'static str = "range";
+    pub const LEN: &'static str = "len";
+    pub const PATTERN: &'static str = "pattern";
 }
 
 /// Argument length requirements for different type
@@ -127,10 +177,11 @@ impl SchemaFnLexeme {
 pub struct ArgLen;
 impl ArgLen {
     pub const ROOT: usize = 1;
-    // All primitives don't need any arguments for now.
-    // If needed, create a separate variable for each
-    // primitive and remove this PRIMITIVE variable.
-    pub const PRIMITIVE: usize = 0;
+
+    // Named type definitions.
+    pub const TYPE: usize = 2;
+    pub const REF: (usize, usize) = (1, 2);
+    pub const IMPORT: (usize, usize) = (1, 2);
 
     // Modifiers.
     pub const NULLABLE: usize = 1;
@@ -138,7 +189,17 @@ impl ArgLen {
 
     pub const LIST: (usize, usize) = (1, 2);
 
-    // pub const UNION_MIN: usize = 2;
+    // Date, time and datetime take an optional format.
+    pub const TEMPORAL: (usize, usize) = (0, 1);
+    pub const DURATION: usize = 0;
+
+    pub const UNION_MIN: usize = 2;
+    pub const ENUM_MIN: usize = 1;
+
+    // Constraints.
+    pub const RANGE: usize = 2;
+    pub const LEN: usize = 2;
+    pub const PATTERN: usize = 1;
 }
 
 // ==================================================================
@@ -225,6 +286,80 @@ struct Modifier {
 //
 // ==================================================================
 
+// ==================================================================
+//
+// CONSTRAINTS START
+//
+// Constraints are calls passed as arguments to primitive type
+// definitions. They restrict values of the type and are stored
+// on its descriptor. Every constraint of the type must hold for
+// a value. The list of available constraints:
+//
+// 1. .range(min max) - Int and Float only. Value must be within
+//                      the bounds, both bounds are inclusive.
+//                      Bounds of Int must be Int literals.
+//                      Ex: .int(.range(0 150));
+//
+// 2. .len(min max)   - String only. Number of characters of the
+//                      value must be within the bounds, both
+//                      bounds are inclusive. Ex: .string(.len(1 64));
+//
+// 3. .pattern("re")  - String only. The whole value must match
+//                      the regular expression.
+//                      Ex: .string(.pattern("[A-Z]{2}"));
+//
+// 4. .enum(lit+)     - Value must be one of the literals, which
+//                      must be of the type being constrained.
+//                      Ex: .string(.enum("a" "b")). It can also be
+//                      used as a type definition on its own, in
+//                      which case the type is the one of its
+//                      literals: .enum("a" "b") is the same as
+//                      .string(.enum("a" "b"));
+//
+// In both .range and .len min can't be greater than max.
+//
+// ==================================================================
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SchemaConstraint {
+    // Bounds of Int are kept as Ints, since Floats can't
+    // hold every Int beyond 2^53.
+    IntRange { min: i64, max: i64 },
+    FloatRange { min: f64, max: f64 },
+    Len { min: usize, max: usize },
+    Pattern(String),
+    // Values are kept as written in the schema.
+    Enum(Vec<String>),
+}
+impl SchemaConstraint {
+    pub fn as_str(&self) -> String {
+        match self {
+            SchemaConstraint::IntRange { min, max } => {
+                format!(".{}({min} {max})", SchemaFnLexeme::RANGE)
+            }
+            SchemaConstraint::FloatRange { min, max } => {
+                format!(".{}({min} {max})", SchemaFnLexeme::RANGE)
+            }
+            SchemaConstraint::Len { min, max } => {
+                format!(".{}({min} {max})", SchemaFnLexeme::LEN)
+            }
+            SchemaConstraint::Pattern(pattern) => {
+                format!(".{}({pattern:?})", SchemaFnLexeme::PATTERN)
+            }
+            SchemaConstraint::Enum(values) => {
+                let values: Vec<String> = values.iter().map(|value| format!("{value:?}")).collect();
+                format!(".{}({})", SchemaFnLexeme::ENUM, values.join(" "))
+            }
+        }
+    }
+}
+
+// ==================================================================
+//
+// CONSTRAINTS END
+//
+// ==================================================================
+
 // ==================================================================
 //
 // SCHEMA RESOLVER START
@@ -240,6 +375,8 @@ pub struct SchemaTypeDescriptor {
     pub nullable: bool,
     // Either type or field is missing.
     pub optional: bool,
+    // Restrictions of values of primitive types.
+    pub constraints: Vec<SchemaConstraint>,
 }
 impl SchemaTypeDescriptor {
     pub fn with_defaults(dtype: SchemaDataType) -> Self {
@@ -250,6 +387,7 @@ impl SchemaTypeDescriptor {
             // after we create type descriptor.
             nullable: false,
             optional: false,
+            constraints: vec![],
         }
     }
 }
@@ -261,12 +399,59 @@ pub struct ResolvedSchema {
     pub resolved_schema: TResolvedSchema,
 }
 
+/// Gives access to schema files imported with .import.
+pub trait SchemaImporter {
+    /// Path of the file imported with `path` from the file at
+    /// the `from` path. The same file must always get the same
+    /// path, no matter which file imports it.
+    fn resolve_path(&self, from: &str, path: &str) -> String;
+
+    /// AST of the file at the path given by `resolve_path`.
+    fn load(&mut self, path: &str) -> Result<Vec<AstNode>, SchemaImportErr>;
+}
+
+/// Named type that can be referenced in a schema file.
+#[derive(Debug, Clone)]
+struct NamedType {
+    // Index of the file the type is declared in.
+    file: usize,
+    // Name of the type in the file it is declared in.
+    name: String,
+    definition: Rc<AstNode>,
+}
+
+/// Schema file taking part in the resolution.
+#[derive(Debug)]
+struct SchemaFile {
+    path: String,
+    // Names of the types declared in the file itself,
+    // which are the ones other files can import.
+    declared: Vec<String>,
+    // Types that can be referenced in the file by their
+    // names, imported types included.
+    scope: HashMap<String, NamedType>,
+}
+impl SchemaFile {
+    fn new(path: String) -> Self {
+        Self {
+            path,
+            declared: vec![],
+            scope: HashMap::new(),
+        }
+    }
+}
+
 pub struct SchemaResolver<'a> {
     // AST of the schema definition file.
     schema_ast: &'a Vec<AstNode>,
     current_path: ResolutionPath,
     current_type: Option<SchemaDataType>,
     current_modifiers: Vec<Modifier>,
+    importer: Option<&'a mut dyn SchemaImporter>,
+    files: Vec<SchemaFile>,
+    current_file: usize,
+    importing_files: Vec<String>,
+    expanding_types: Vec<(usize, String)>,
 }
 
 impl<'a> SchemaResolver<'a> {
@@ -284,22 +469,41 @@ impl<'a> SchemaResolver<'a> {
             // Track current modifier in order to be able to provide metadata
             // for the type being resolved.
             current_modifiers: vec![],
+            // Imports are not allowed without an importer, since
+            // there is no way to access other files.
+            importer: None,
+            // Every file taking part in the resolution. The first
+            // one is always the schema definition file.
+            files: vec![SchemaFile::new(String::new())],
+            // Index of the file the nodes being resolved belong to.
+            current_file: 0,
+            // Paths of the files being imported right now, from the
+            // schema definition file to the innermost import.
+            importing_files: vec![],
+            // Types being expanded right now, from the outermost
+            // reference to the innermost one.
+            expanding_types: vec![],
         }
     }
 
+    /// Allows the schema definition file at the `path` to import
+    /// other files with the importer.
+    pub fn with_importer(mut self, path: &str, importer: &'a mut dyn SchemaImporter) -> Self {
+        self.files[0].path = path.to_string();
+        self.importer = Some(importer);
+        self
+    }
+
     pub fn resolve(&mut self) -> Result<ResolvedSchema, SchemaResolverErr> {
         // Do not allow schema to be empty.
-        let first_node = self.schema_ast.first().ok_or(SchemaResolverErr::Empty)?;
+        if self.schema_ast.is_empty() {
+            return Err(SchemaResolverErr::Empty);
+        }
 
-        // First node must always be a root function call.
-        let call = match first_node {
-            AstNode::Call(call) if call.lexeme == SchemaFnLexeme::ROOT => call,
-            node => {
-                return Err(SchemaResolverErr::Unexp {
-                    span: node.span().clone(),
-                });
-            }
-        };
+        self.importing_files.push(self.files[0].path.clone());
+        let root = self.declare_file(0, self.schema_ast)?;
+        let call = root.ok_or(SchemaResolverErr::MissingRoot)?;
+        self.check_types()?;
 
         match call.children.len() {
             ArgLen::ROOT => {
@@ -331,33 +535,33 @@ impl<'a> SchemaResolver<'a> {
                 SchemaFnLexeme::OPTIONAL => {
                     self.resolve_modifier(ModifierKind::Optional, call, resolved_schema)
                 }
-                SchemaFnLexeme::INT => self.resolve_primitive(
-                    call,
-                    SchemaDataType::Int,
-                    SchemaFnLexeme::INT,
-                    resolved_schema,
-                ),
-                SchemaFnLexeme::FLOAT => self.resolve_primitive(
-                    call,
-                    SchemaDataType::Float,
-                    SchemaFnLexeme::FLOAT,
-                    resolved_schema,
-                ),
-                SchemaFnLexeme::STRING => self.resolve_primitive(
-                    call,
-                    SchemaDataType::String,
-                    SchemaFnLexeme::STRING,
-                    resolved_schema,
-                ),
-                SchemaFnLexeme::BOOL => self.resolve_primitive(
-                    call,
-                    SchemaDataType::Bool,
-                    SchemaFnLexeme::BOOL,
-                    resolved_schema,
-                ),
+                SchemaFnLexeme::INT => {
+                    self.resolve_primitive(call, SchemaDataType::Int, resolved_schema)
+                }
+                SchemaFnLexeme::FLOAT => {
+                    self.resolve_primitive(call, SchemaDataType::Float, resolved_schema)
+                }
+                SchemaFnLexeme::STRING => {
+                    self.resolve_primitive(call, SchemaDataType::String, resolved_schema)
+                }
+                SchemaFnLexeme::BOOL => {
+                    self.resolve_primitive(call, SchemaDataType::Bool, resolved_schema)
+                }
+                SchemaFnLexeme::DATE => {
+                    self.resolve_temporal(call, TemporalKind::Date, resolved_schema)
+                }
+                SchemaFnLexeme::TIME => {
+                    self.resolve_temporal(call, TemporalKind::Time, resolved_schema)
+                }
+                SchemaFnLexeme::DATETIME => {
+                    self.resolve_temporal(call, TemporalKind::DateTime, resolved_schema)
+                }
+                SchemaFnLexeme::DURATION => self.resolve_duration(call, resolved_schema),
                 SchemaFnLexeme::DICT => self.resolve_dict(call, resolved_schema),
                 SchemaFnLexeme::LIST => self.resolve_list(call, resolved_schema),
-                // SchemaFnLexeme::UNION => self.resolve_union(call, resolved_schema),
+                SchemaFnLexeme::UNION => self.resolve_union(call, resolved_schema),
+                SchemaFnLexeme::ENUM => self.resolve_enum(call, resolved_schema),
+                SchemaFnLexeme::REF => self.resolve_ref(call, resolved_schema),
                 _ => Err(SchemaResolverErr::InvalTypeDef {
                     span: call.span.clone(),
                 }),
@@ -504,27 +708,66 @@ impl<'a> SchemaResolver<'a> {
     // ==================================================================
 
     /// We use the same function for all primitives since they all
-    /// adhere to the same semantics.
+    /// adhere to the same semantics. Every argument of a primitive
+    /// is a constraint.
     fn resolve_primitive(
         &mut self,
         call: &AstCall,
         dtype: SchemaDataType,
-        lexeme: &'static str,
         resolved_schema: &mut TResolvedSchema,
     ) -> Result<(), SchemaResolverErr> {
-        let args_len = call.children.len();
+        let constraints = call
+            .children
+            .iter()
+            .map(|arg| Self::resolve_constraint(arg, &dtype))
+            .collect::<Result<Vec<_>, _>>()?;
+
         self.current_type = Some(dtype);
+        self.commit_primitive(constraints, resolved_schema)
+    }
 
-        if args_len > 0 {
-            return Err(SchemaResolverErr::ArityMismatch {
-                fn_name: lexeme,
-                kind: ArityMismatchKind::Eq(ArgLen::PRIMITIVE),
-                found: args_len,
-                span: call.span.clone(),
-            });
-        }
+    /// Standalone .enum is a primitive of the type of its literals.
+    fn resolve_enum(
+        &mut self,
+        call: &AstCall,
+        resolved_schema: &mut TResolvedSchema,
+    ) -> Result<(), SchemaResolverErr> {
+        let dtype = match call.children.first().map(|arg| &**arg) {
+            Some(AstNode::Int(_)) => SchemaDataType::Int,
+            Some(AstNode::Float(_)) => SchemaDataType::Float,
+            Some(AstNode::String(_)) => SchemaDataType::String,
+            Some(AstNode::Bool(_)) => SchemaDataType::Bool,
+            Some(node) => {
+                return Err(SchemaResolverErr::InvalConstraint {
+                    span: node.span().clone(),
+                });
+            }
+            None => {
+                return Err(SchemaResolverErr::ArityMismatch {
+                    fn_name: SchemaFnLexeme::ENUM,
+                    kind: ArityMismatchKind::MoreEq(ArgLen::ENUM_MIN),
+                    found: 0,
+                    span: call.span.clone(),
+                });
+            }
+        };
 
+        let constraint = Self::resolve_constraint_enum(call, &dtype)?;
+
+        self.current_type = Some(dtype);
+        self.commit_primitive(vec![constraint], resolved_schema)
+    }
+
+    fn commit_primitive(
+        &mut self,
+        constraints: Vec<SchemaConstraint>,
+        resolved_schema: &mut TResolvedSchema,
+    ) -> Result<(), SchemaResolverErr> {
         self.commit(resolved_schema)?;
+        resolved_schema
+            .get_mut(&self.current_path)
+            .unwrap()
+            .constraints = constraints;
         // We always remove the last path segment after resolving primitives
         // regardless if they nested or not, because if they are nested,
         // then it removes nested path segment which is correct. If they are not
@@ -539,6 +782,270 @@ impl<'a> SchemaResolver<'a> {
     // PRIMITIVES END
     // ==================================================================
 
+    // ==================================================================
+    // TEMPORALS START
+    //
+    // .date(Format?), .time(Format?), .datetime(Format?)
+    // .duration()
+    //
+    // 1. Format is a String literal, see `shared_temporal` for the
+    //    fields it can have. Defaults are "%Y-%m-%d", "%H:%M:%S"
+    //    and "%Y-%m-%dT%H:%M:%S";
+    // 2. Format must have every field needed to read a value of
+    //    the type, seconds can be omitted;
+    // 3. Durations are always written in ISO 8601 as PnDTnHnMnS;
+    // 4. Temporal types take no constraints;
+    // ==================================================================
+
+    fn resolve_temporal(
+        &mut self,
+        call: &AstCall,
+        kind: TemporalKind,
+        resolved_schema: &mut TResolvedSchema,
+    ) -> Result<(), SchemaResolverErr> {
+        let lexeme = match kind {
+            TemporalKind::Date => SchemaFnLexeme::DATE,
+            TemporalKind::Time => SchemaFnLexeme::TIME,
+            TemporalKind::DateTime => SchemaFnLexeme::DATETIME,
+        };
+
+        if call.children.len() > ArgLen::TEMPORAL.1 {
+            return Err(SchemaResolverErr::ArityMismatch {
+                fn_name: lexeme,
+                kind: ArityMismatchKind::Range(ArgLen::TEMPORAL),
+                found: call.children.len(),
+                span: call.span.clone(),
+            });
+        }
+
+        let format = match call.children.first().map(|arg| &**arg) {
+            None => kind.default_format().to_string(),
+            Some(AstNode::String(prim)) if shared_temporal::can_parse(&prim.value, kind) => {
+                prim.value.clone()
+            }
+            Some(AstNode::String(prim)) => {
+                return Err(SchemaResolverErr::InvalFormat {
+                    format: prim.value.clone(),
+                    span: prim.span.clone(),
+                });
+            }
+            Some(node) => {
+                return Err(SchemaResolverErr::UndexpType {
+                    expected: NodeName::STRING.to_string(),
+                    found: node.as_str().to_string(),
+                    span: node.span().clone(),
+                });
+            }
+        };
+
+        self.current_type = Some(match kind {
+            TemporalKind::Date => SchemaDataType::Date(format),
+            TemporalKind::Time => SchemaDataType::Time(format),
+            TemporalKind::DateTime => SchemaDataType::DateTime(format),
+        });
+        self.commit_primitive(vec![], resolved_schema)
+    }
+
+    fn resolve_duration(
+        &mut self,
+        call: &AstCall,
+        resolved_schema: &mut TResolvedSchema,
+    ) -> Result<(), SchemaResolverErr> {
+        if call.children.len() != ArgLen::DURATION {
+            return Err(SchemaResolverErr::ArityMismatch {
+                fn_name: SchemaFnLexeme::DURATION,
+                kind: ArityMismatchKind::Eq(ArgLen::DURATION),
+                found: call.children.len(),
+                span: call.span.clone(),
+            });
+        }
+
+        self.current_type = Some(SchemaDataType::Duration);
+        self.commit_primitive(vec![], resolved_schema)
+    }
+
+    // ==================================================================
+    // TEMPORALS END
+    // ==================================================================
+
+    // ==================================================================
+    // CONSTRAINTS START
+    // ==================================================================
+
+    fn resolve_constraint(
+        node: &AstNode,
+        dtype: &SchemaDataType,
+    ) -> Result<SchemaConstraint, SchemaResolverErr> {
+        let call = match node {
+            AstNode::Call(call) => call,
+            node => {
+                return Err(SchemaResolverErr::InvalConstraint {
+                    span: node.span().clone(),
+                });
+            }
+        };
+
+        match (call.lexeme.as_str(), dtype) {
+            (SchemaFnLexeme::RANGE, SchemaDataType::Int) => {
+                Self::check_constraint_arity(call, SchemaFnLexeme::RANGE, ArgLen::RANGE)?;
+                let min = Self::resolve_int_range_bound(&call.children[0])?;
+                let max = Self::resolve_int_range_bound(&call.children[1])?;
+
+                if min > max {
+                    return Err(SchemaResolverErr::InvalConstraint {
+                        span: call.span.clone(),
+                    });
+                }
+                Ok(SchemaConstraint::IntRange { min, max })
+            }
+            (SchemaFnLexeme::RANGE, SchemaDataType::Float) => {
+                Self::check_constraint_arity(call, SchemaFnLexeme::RANGE, ArgLen::RANGE)?;
+                let min = Self::resolve_float_range_bound(&call.children[0])?;
+                let max = Self::resolve_float_range_bound(&call.children[1])?;
+
+                if min > max {
+                    return Err(SchemaResolverErr::InvalConstraint {
+                        span: call.span.clone(),
+                    });
+                }
+                Ok(SchemaConstraint::FloatRange { min, max })
+            }
+            (SchemaFnLexeme::LEN, SchemaDataType::String) => {
+                Self::check_constraint_arity(call, SchemaFnLexeme::LEN, ArgLen::LEN)?;
+                let min = Self::resolve_len_bound(&call.children[0])?;
+                let max = Self::resolve_len_bound(&call.children[1])?;
+
+                if min > max {
+                    return Err(SchemaResolverErr::InvalConstraint {
+                        span: call.span.clone(),
+                    });
+                }
+                Ok(SchemaConstraint::Len { min, max })
+            }
+            (SchemaFnLexeme::PATTERN, SchemaDataType::String) => {
+                Self::check_constraint_arity(call, SchemaFnLexeme::PATTERN, ArgLen::PATTERN)?;
+
+                match &*call.children[0] {
+                    AstNode::String(prim) => {
+                        // Compile it once here in order to report invalid
+                        // patterns against the schema source code.
+                        if Regex::new(&prim.value).is_err() {
+                            return Err(SchemaResolverErr::InvalPattern {
+                                pattern: prim.value.clone(),
+                                span: prim.span.clone(),
+                            });
+                        }
+                        Ok(SchemaConstraint::Pattern(prim.value.clone()))
+                    }
+                    node => Err(SchemaResolverErr::UndexpType {
+                        expected: NodeName::STRING.to_string(),
+                        found: node.as_str().to_string(),
+                        span: node.span().clone(),
+                    }),
+                }
+            }
+            (SchemaFnLexeme::ENUM, _) => Self::resolve_constraint_enum(call, dtype),
+            _ => Err(SchemaResolverErr::InvalConstraint {
+                span: call.span.clone(),
+            }),
+        }
+    }
+
+    fn check_constraint_arity(
+        call: &AstCall,
+        lexeme: &'static str,
+        expected: usize,
+    ) -> Result<(), SchemaResolverErr> {
+        if call.children.len() != expected {
+            return Err(SchemaResolverErr::ArityMismatch {
+                fn_name: lexeme,
+                kind: ArityMismatchKind::Eq(expected),
+                found: call.children.len(),
+                span: call.span.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    fn resolve_int_range_bound(node: &AstNode) -> Result<i64, SchemaResolverErr> {
+        match node {
+            AstNode::Int(prim) => {
+                prim.value
+                    .parse()
+                    .map_err(|_| SchemaResolverErr::InvalConstraint {
+                        span: prim.span.clone(),
+                    })
+            }
+            node => Err(SchemaResolverErr::UndexpType {
+                expected: SchemaDataType::Int.as_str().to_string(),
+                found: node.as_str().to_string(),
+                span: node.span().clone(),
+            }),
+        }
+    }
+
+    fn resolve_float_range_bound(node: &AstNode) -> Result<f64, SchemaResolverErr> {
+        match node {
+            AstNode::Int(prim) | AstNode::Float(prim) => Ok(prim.value.parse().unwrap()),
+            node => Err(SchemaResolverErr::UndexpType {
+                expected: SchemaDataType::Float.as_str().to_string(),
+                found: node.as_str().to_string(),
+                span: node.span().clone(),
+            }),
+        }
+    }
+
+    fn resolve_len_bound(node: &AstNode) -> Result<usize, SchemaResolverErr> {
+        match node {
+            AstNode::Int(prim) => {
+                prim.value
+                    .parse()
+                    .map_err(|_| SchemaResolverErr::InvalConstraint {
+                        span: prim.span.clone(),
+                    })
+            }
+            node => Err(SchemaResolverErr::UndexpType {
+                expected: NodeName::INT.to_string(),
+                found: node.as_str().to_string(),
+                span: node.span().clone(),
+            }),
+        }
+    }
+
+    fn resolve_constraint_enum(
+        call: &AstCall,
+        dtype: &SchemaDataType,
+    ) -> Result<SchemaConstraint, SchemaResolverErr> {
+        if call.children.len() < ArgLen::ENUM_MIN {
+            return Err(SchemaResolverErr::ArityMismatch {
+                fn_name: SchemaFnLexeme::ENUM,
+                kind: ArityMismatchKind::MoreEq(ArgLen::ENUM_MIN),
+                found: call.children.len(),
+                span: call.span.clone(),
+            });
+        }
+
+        call.children
+            .iter()
+            .map(|arg| match (&**arg, dtype) {
+                (AstNode::Int(prim), SchemaDataType::Int | SchemaDataType::Float)
+                | (AstNode::Float(prim), SchemaDataType::Float)
+                | (AstNode::String(prim), SchemaDataType::String)
+                | (AstNode::Bool(prim), SchemaDataType::Bool) => Ok(prim.value.clone()),
+                (node, dtype) => Err(SchemaResolverErr::UndexpType {
+                    expected: dtype.as_str().to_string(),
+                    found: node.as_str().to_string(),
+                    span: node.span().clone(),
+                }),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(SchemaConstraint::Enum)
+    }
+
+    // ==================================================================
+    // CONSTRAINTS END
+    // ==================================================================
+
     // ==================================================================
     // DICT START
     // ==================================================================
@@ -632,9 +1139,8 @@ impl<'a> SchemaResolver<'a> {
     }
 
     /// Lists are monomorphic because schema resolution is a single
-    /// deterministic AST walk producing one path -> type entry —
-    /// there is no representation for a path resolving
-    /// to more than one type.
+    /// deterministic AST walk producing one path -> type entry.
+    /// Items of different types are described with a union.
     fn resolve_list(
         &mut self,
         call: &AstCall,
@@ -683,203 +1189,650 @@ impl<'a> SchemaResolver<'a> {
     // ==================================================================
 
     // ==================================================================
-    // UNION START
+    // NAMED TYPES START
+    //
+    // .type(name Type), .ref(name) and .ref(namespace name)
+    //
+    // 1. .type takes an identifier and a type definition. It can
+    //    only be used at the top level next to .schema;
+    // 2. Type names must be unique within the file, imported
+    //    types included;
+    // 3. .ref takes an identifier of a type declared with .type
+    //    and can be used anywhere a type definition is expected.
+    //    Types imported into a namespace are referenced with the
+    //    namespace and the name;
+    // 4. .ref is expanded into the type definition at its path,
+    //    so the resolved schema is the same as if the definition
+    //    was written in place of the reference. Modifiers of the
+    //    reference apply to the definition;
+    // 5. A type can't reference itself, either directly or
+    //    through other types. The reference that closes the
+    //    cycle is reported;
+    // 6. Every declared definition is checked on its own, so errors
+    //    of unused types are reported too. Otherwise definitions are
+    //    resolved only where they are referenced;
     // ==================================================================
 
-    // "case3" .list(.union(
-    //                    .list(.dict(
-    //                            "some"  .int()
-    //                            "some2" .float()
-    //                    )),
-    //                    .list(.dict(
-    //                            "some3" .string()
-    //                            "some"  .list(.int())
-    //                    ))))
-    //                    .list(.dict(
-    //                            "some3" .float()
-    //                            "some"  .list(.string())
-    //                            "other" .string()
-    //                    ))))
+    fn type_name(node: &AstNode) -> Result<&String, SchemaResolverErr> {
+        match node {
+            AstNode::Identifier(prim) => Ok(&prim.value),
+            node => Err(SchemaResolverErr::UndexpType {
+                expected: NodeName::IDENTIFIER.to_string(),
+                found: node.as_str().to_string(),
+                span: node.span().clone(),
+            }),
+        }
+    }
+
+    /// Name of the type in the scope of the file it is imported into.
+    fn qualified_type_name(namespace: Option<&String>, name: &String) -> String {
+        match namespace {
+            // Identifiers can't contain dots, so qualified names
+            // never clash with the names of the file itself.
+            Some(namespace) => format!("{namespace}.{name}"),
+            None => name.clone(),
+        }
+    }
+
+    fn declare_type(&mut self, file: usize, call: &AstCall) -> Result<(), SchemaResolverErr> {
+        if call.children.len() != ArgLen::TYPE {
+            return Err(SchemaResolverErr::ArityMismatch {
+                fn_name: SchemaFnLexeme::TYPE,
+                kind: ArityMismatchKind::Eq(ArgLen::TYPE),
+                found: call.children.len(),
+                span: call.span.clone(),
+            });
+        }
+
+        let name = Self::type_name(&call.children[0])?;
+        let schema_file = &mut self.files[file];
+
+        if schema_file.scope.contains_key(name) {
+            return Err(SchemaResolverErr::TypeRedefinition {
+                name: name.clone(),
+                span: call.children[0].span().clone(),
+            });
+        }
+
+        schema_file.declared.push(name.clone());
+        schema_file.scope.insert(
+            name.clone(),
+            NamedType {
+                file,
+                name: name.clone(),
+                // Definitions outlive the AST of imported files.
+                definition: Rc::new((*call.children[1]).clone()),
+            },
+        );
+        Ok(())
+    }
+
+    /// Resolves every type declared in the files taking part in the
+    /// resolution into a table of its own, which is thrown away.
+    fn check_types(&mut self) -> Result<(), SchemaResolverErr> {
+        for file in 0..self.files.len() {
+            for name in self.files[file].declared.clone() {
+                let definition = self.files[file].scope[&name].definition.clone();
+
+                self.current_file = file;
+                self.current_path = ResolutionPath::new();
+                self.current_type = None;
+                self.current_modifiers.clear();
+                self.expanding_types.push((file, name));
+
+                let result = self.resolve_from_node(&definition, &mut HashMap::new());
+
+                self.expanding_types.pop();
+                self.current_file = 0;
+
+                // The schema definition file is reported without a path.
+                result.map_err(|err| match file {
+                    0 => err,
+                    file => self.in_file(file, err),
+                })?;
+            }
+        }
+
+        self.current_path = ResolutionPath::new();
+        self.current_type = None;
+        self.current_modifiers.clear();
+        Ok(())
+    }
+
+    /// Name of the type referenced by the `.ref` call in the `file`,
+    /// as it is written, and the type itself.
+    fn referenced_type(
+        &self,
+        file: usize,
+        call: &AstCall,
+    ) -> Result<(String, NamedType), SchemaResolverErr> {
+        let args_len = call.children.len();
+
+        if args_len < ArgLen::REF.0 || args_len > ArgLen::REF.1 {
+            return Err(SchemaResolverErr::ArityMismatch {
+                fn_name: SchemaFnLexeme::REF,
+                kind: ArityMismatchKind::Range(ArgLen::REF),
+                found: args_len,
+                span: call.span.clone(),
+            });
+        }
+
+        let name = Self::type_name(call.children.last().unwrap())?;
+        let name = if args_len == ArgLen::REF.1 {
+            let namespace = Self::type_name(&call.children[0])?;
+            Self::qualified_type_name(Some(namespace), name)
+        } else {
+            name.clone()
+        };
+
+        match self.files[file].scope.get(&name) {
+            Some(named_type) => Ok((name, named_type.clone())),
+            None => Err(SchemaResolverErr::TypeUndefined {
+                name,
+                span: call.span.clone(),
+            }),
+        }
+    }
+
+    /// Whether the node of the `file` is a union, either written in
+    /// place or expanded from references.
+    fn is_union(&self, file: usize, node: &AstNode, seen: &mut Vec<(usize, String)>) -> bool {
+        let AstNode::Call(call) = node else {
+            return false;
+        };
+
+        match call.lexeme.as_str() {
+            SchemaFnLexeme::UNION => true,
+            SchemaFnLexeme::REF => {
+                // Invalid references are reported once they are resolved.
+                let Ok((_, named_type)) = self.referenced_type(file, call) else {
+                    return false;
+                };
+                let identity = (named_type.file, named_type.name.clone());
+
+                if seen.contains(&identity) {
+                    return false;
+                }
+
+                seen.push(identity);
+                self.is_union(named_type.file, &named_type.definition, seen)
+            }
+            _ => false,
+        }
+    }
+
+    fn resolve_ref(
+        &mut self,
+        call: &AstCall,
+        resolved_schema: &mut TResolvedSchema,
+    ) -> Result<(), SchemaResolverErr> {
+        let (name, named_type) = self.referenced_type(self.current_file, call)?;
+        let identity = (named_type.file, named_type.name);
+
+        if self.expanding_types.contains(&identity) {
+            return Err(SchemaResolverErr::TypeCycle {
+                name,
+                span: call.span.clone(),
+            });
+        }
+
+        // Definition is resolved in the scope of its own file.
+        let referencing_file = std::mem::replace(&mut self.current_file, named_type.file);
+        self.expanding_types.push(identity);
+
+        let result = self.resolve_from_node(&named_type.definition, resolved_schema);
+
+        self.expanding_types.pop();
+        self.current_file = referencing_file;
+
+        if named_type.file != referencing_file {
+            return result.map_err(|err| self.in_file(named_type.file, err));
+        }
+        result
+    }
+
+    // ==================================================================
+    // NAMED TYPES END
+    // ==================================================================
+
+    // ==================================================================
+    // IMPORTS START
     //
-    // [Root] -> Dict
-
-    // [Root, "case3"] -> ListAbstract
-
-    // HashMap {
-    //     (Root, "case3", AbstractIndex, AbstractIndex) => Dict,
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some")) => Int,
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some2")) => Float,
-    // }
-
-    // HashMap {
-    //     (Root, "case3", AbstractIndex, AbstractIndex) => Dict
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some")) => List
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some"), AbstractIndex) => Int
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some3")) => String
-    // }
-
-    // Remove common key-value pairs and insert them into main:
-
-    // HashMap {
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some")) => Int,
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some2")) => Float,
-    // }
-
-    // HashMap {
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some")) => List
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some"), AbstractIndex) => Int
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some3")) => String
-    // }
-
-    // HashMap {
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some")) => List
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some"), AbstractIndex) => String
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some3")) => Float
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("other")) => String
-    // }
-
-    // 1. Create a HashMap<key, [usize; N]> where N is a number of tables;
-    // 2. Iterate over each table key:
-    //    if key is not in hashmap, then insert it with key => [0, 0, 0]
-    //    and increment the number on the index of the current table by one;
-    //    if key is inside the hashmap, then increment the number on the index
-    //    of the current table by one;
+    // .import("path") and .import("path" namespace)
     //
-    // This gives us information about how many keys are present inside each table and how
-    // many times.
+    // 1. .import takes a path of the schema file relative to the
+    //    importing file, and optionally an identifier of the
+    //    namespace. It can only be used at the top level;
+    // 2. Imported files only declare types and import other files.
+    //    They can't have .schema;
+    // 3. Types declared in the imported file can be referenced by
+    //    their names in the importing file, or by the namespace and
+    //    the name if the namespace is given. Types the imported file
+    //    imports itself are not imported;
+    // 4. A file can't import itself, either directly or through
+    //    other files. The import that closes the cycle is reported;
+    // 5. Each file is loaded once, no matter how many files import
+    //    it;
+    // 6. Errors of imported files are reported with the path of
+    //    the file, since their spans point to its source code;
+    // ==================================================================
+
+    /// Declares top level definitions of the file and gives
+    /// back its root function call.
+    fn declare_file<'b>(
+        &mut self,
+        file: usize,
+        ast: &'b [AstNode],
+    ) -> Result<Option<&'b AstCall>, SchemaResolverErr> {
+        let mut root: Option<&AstCall> = None;
+
+        // Top level nodes are imports, named type definitions and a
+        // single root function call. All types are declared before
+        // the root is resolved, so they can be referenced in any order.
+        for node in ast {
+            match node {
+                AstNode::Call(call) if call.lexeme == SchemaFnLexeme::ROOT && root.is_none() => {
+                    root = Some(call);
+                }
+                AstNode::Call(call) if call.lexeme == SchemaFnLexeme::TYPE => {
+                    self.declare_type(file, call)?;
+                }
+                AstNode::Call(call) if call.lexeme == SchemaFnLexeme::IMPORT => {
+                    self.declare_import(file, call)?;
+                }
+                node => {
+                    return Err(SchemaResolverErr::Unexp {
+                        span: node.span().clone(),
+                    });
+                }
+            }
+        }
 
-    // Needs to be transformed into:
+        Ok(root)
+    }
+
+    fn declare_import(&mut self, file: usize, call: &AstCall) -> Result<(), SchemaResolverErr> {
+        let args_len = call.children.len();
+
+        if args_len < ArgLen::IMPORT.0 || args_len > ArgLen::IMPORT.1 {
+            return Err(SchemaResolverErr::ArityMismatch {
+                fn_name: SchemaFnLexeme::IMPORT,
+                kind: ArityMismatchKind::Range(ArgLen::IMPORT),
+                found: args_len,
+                span: call.span.clone(),
+            });
+        }
+
+        let import_path = match &*call.children[0] {
+            AstNode::String(prim) => &prim.value,
+            node => {
+                return Err(SchemaResolverErr::UndexpType {
+                    expected: NodeName::STRING.to_string(),
+                    found: node.as_str().to_string(),
+                    span: node.span().clone(),
+                });
+            }
+        };
+
+        let namespace = match call.children.get(1) {
+            Some(node) => Some(Self::type_name(node)?),
+            None => None,
+        };
+
+        let Some(importer) = self.importer.as_deref_mut() else {
+            return Err(SchemaResolverErr::Unexp {
+                span: call.span.clone(),
+            });
+        };
+
+        let path = importer.resolve_path(&self.files[file].path, import_path);
+
+        if self.importing_files.contains(&path) {
+            return Err(SchemaResolverErr::ImportCycle {
+                path,
+                span: call.span.clone(),
+            });
+        }
+
+        let imported = match self.files.iter().position(|loaded| loaded.path == path) {
+            Some(imported) => imported,
+            None => {
+                let ast = importer
+                    .load(&path)
+                    .map_err(|err| SchemaResolverErr::Import {
+                        path: path.clone(),
+                        err,
+                        span: call.span.clone(),
+                    })?;
+                self.load_file(path, &ast)?
+            }
+        };
+
+        let schema_file = &self.files[imported];
+        let named_types: Vec<(String, NamedType)> = schema_file
+            .declared
+            .iter()
+            .map(|name| {
+                (
+                    Self::qualified_type_name(namespace, name),
+                    schema_file.scope[name].clone(),
+                )
+            })
+            .collect();
+
+        for (name, named_type) in named_types {
+            if self.files[file].scope.contains_key(&name) {
+                return Err(SchemaResolverErr::TypeRedefinition {
+                    name,
+                    span: call.span.clone(),
+                });
+            }
+            self.files[file].scope.insert(name, named_type);
+        }
+
+        Ok(())
+    }
+
+    fn load_file(&mut self, path: String, ast: &[AstNode]) -> Result<usize, SchemaResolverErr> {
+        let file = self.files.len();
+        self.files.push(SchemaFile::new(path.clone()));
+        self.importing_files.push(path);
+
+        let result = match self.declare_file(file, ast) {
+            Ok(Some(root)) => Err(SchemaResolverErr::Unexp {
+                span: root.span.clone(),
+            }),
+            Ok(None) => Ok(file),
+            Err(err) => Err(err),
+        };
+
+        self.importing_files.pop();
+        result.map_err(|err| self.in_file(file, err))
+    }
+
+    /// Attributes the error to the imported file, unless it's
+    /// already attributed to a file imported by that file.
+    fn in_file(&self, file: usize, err: SchemaResolverErr) -> SchemaResolverErr {
+        match err {
+            SchemaResolverErr::InFile { .. } => err,
+            err => SchemaResolverErr::InFile {
+                path: self.files[file].path.clone(),
+                err: Box::new(err),
+            },
+        }
+    }
+
+    // ==================================================================
+    // IMPORTS END
+    // ==================================================================
+
+    // ==================================================================
+    // UNION START
+    //
+    // .union(Type Type+)
+    //
+    // 1. Min 2 arguments, each of them is a type definition;
+    // 2. Union can't be a direct child of another union, neither
+    //    written in place nor referenced with .ref;
+    // 3. Modifiers of the union apply to the union itself;
+    // 4. Each branch is resolved into its own table starting
+    //    from the same path;
+    // 5. Branch tables are merged from the union path down. While
+    //    all branches have the same kind of type at a path, the
+    //    path is shared by them (common prefix), so
+    //    .union(.list(.int()) .list(.string())) is a list
+    //    whose items are a union;
+    // 6. At the first path where kinds differ the path resolves
+    //    to Union, and each alternative continues under the
+    //    Assertion segment named after its kind:
     //
-    // HashMap {
-    //     (Root, "case3", AbstractIndex, AbstractIndex) => Dict,
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some"), Assertion(Int)) => Int
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some"), Assertion(List)) => List
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some2")) => Float
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some"), Assertion(List), AbstractIndex, Assertion(Int)) => Int
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some"), Assertion(List), AbstractIndex, Assertion(String)) => String
-    //     (Root, "case3", AbstractIndex, AbstractIndex, Field("some3")) => String
-    // }
-
-    // fn resolve_union(
-    //     &mut self,
-    //     call: &AstCall,
-    //     resolved_schema: &mut TResolvedSchema,
-    // ) -> Result<(), SchemaResolverErr> {
-    //     let args_len = call.children.len();
-
-    //     if args_len < ArgLen::UNION_MIN {
-    //         return Err(SchemaResolverErr::ArityMismatch {
-    //             fn_name: SchemaFnLexeme::UNION,
-    //             kind: ArityMismatchKind::MoreEq(ArgLen::UNION_MIN),
-    //             found: args_len,
-    //             span: call.span.clone(),
-    //         });
-    //     }
-
-    //     // Disallow direct usage of union inside the union.
-    //     for child in &call.children {
-    //         if let AstNode::Call(inner) = &**child
-    //             && inner.lexeme == SchemaFnLexeme::UNION
-    //         {
-    //             return Err(SchemaResolverErr::NoUnionOfUnion {
-    //                 span: inner.span.clone(),
-    //             });
-    //         }
-    //     }
-
-    //     // Capture global state at the moment of the branches resolution start
-    //     // since each of the branches needs to start its own resolution from
-    //     // the same state.
-    //     let captured_path = self.current_path.clone();
-    //     let captured_modifiers = self.current_modifiers.clone();
-
-    //     // Main {
-    //     //     (Root) => Dict
-    //     //     (Root, "case3") => List
-    //     // }
-    //     //
-    //     // [
-    //     //     HashMap {
-    //     //         (Root, "case3", Index) => List
-    //     //         (Root, "case3", Index, Index) => Dict
-    //     //         (Root, "case3", Index, Index, "some") => Int
-    //     //         (Root, "case3", Index, Index, "some2") => Float
-    //     //     }
-    //     //
-    //     //     HashMap {
-    //     //        (Root, "case3", Index) => List
-    //     //        (Root, "case3", Index, Index) => Dict
-    //     //        (Root, "case3", Index, Index, "some") => List
-    //     //        (Root, "case3", Index, Index, "some", Index) => Int
-    //     //        (Root, "case3", Index, Index, "some3") => String
-    //     //     }
-    //     // ]
-
-    //     // New resolution table for each Union branch.
-    //     let mut resolution_tables: Vec<TResolvedSchema> = Vec::with_capacity(call.children.len());
-
-    //     for child in &call.children {
-    //         // TODO: Works but cloning on each iteration again is not good.
-    //         // Although there is less likely to be a case where we have
-    //         // hundreds of Union branches, but I think we need to check
-    //         // if we can find another solution here.
-    //         self.current_path = captured_path.clone();
-    //         self.current_modifiers = captured_modifiers.clone();
-    //         let mut table: TResolvedSchema = HashMap::new();
-    //         self.resolve_from_node(child, &mut table)?;
-    //         resolution_tables.push(table);
-    //     }
-
-    //     // Remove the same key-value pairs from all union branch tables
-    //     // only if each key-value pair is present in each table.
-
-    //     let smallest_table_index = resolution_tables
-    //         .iter()
-    //         .enumerate()
-    //         // Enumerate gives us a tuple with (index, map), min_by_key
-    //         // returns the item that yields the smallest number in predicate.
-    //         .min_by_key(|(_, map)| map.len())
-    //         // Extract only index.
-    //         .map(|(i, _)| i)
-    //         .unwrap();
-
-    //     // We start from the smallest table since we want to remove the key-value pairs
-    //     // that are present in ALL union branch tables, so the subset of all potential
-    //     // entries is a smallest one.
-    //     let smallest_table = &resolution_tables[smallest_table_index];
-
-    //     // Key-value pairs that are present in ALL union branch tables.
-    //     let common_key_values: Vec<(ResolutionPath, SchemaTypeDescriptor)> = smallest_table
-    //         .iter()
-    //         .filter(|(key, value)| {
-    //             resolution_tables
-    //                 .iter()
-    //                 .enumerate()
-    //                 // Skip smallest table since we're already iterating over its values.
-    //                 .filter(|(index, _)| *index != smallest_table_index)
-    //                 // Tests if all elements (tables) match the predicate.
-    //                 // In our case, each table must have the same key-value in order to be removed
-    //                 // and inserted into the main resolution table.
-    //                 .all(|(_, table)| table.get(*key) == Some(*value))
-    //         })
-    //         .map(|(key, value)| (key.clone(), value.clone()))
-    //         .collect();
-
-    //     // Remove common key-value pairs from all Union branch tables
-    //     // and insert them into the main resolution table since there is
-    //     // no type ambiguity in this case.
-    //     for (key, value) in common_key_values {
-    //         for table in resolution_tables.iter_mut() {
-    //             table.remove(&key);
-    //         }
-    //         resolved_schema.insert(key, value);
-    //     }
-
-    //     //let _without_common_prefix = Self::remove_common_path_segments(&resolution_tables);
-    //     println!("-------- Children tables: {:#?}", resolution_tables);
-
-    //     Ok(())
-    // }
+    //    [Root, Field("id")] => Union
+    //    [Root, Field("id"), Assertion("Int")] => Int
+    //    [Root, Field("id"), Assertion("String")] => String
+    //
+    // 7. Dict fields that are missing in some of the branches
+    //    are optional;
+    // 8. Branches that share a path of a temporal type must
+    //    have the same format there;
+    // ==================================================================
+
+    fn resolve_union(
+        &mut self,
+        call: &AstCall,
+        resolved_schema: &mut TResolvedSchema,
+    ) -> Result<(), SchemaResolverErr> {
+        let args_len = call.children.len();
+
+        if args_len < ArgLen::UNION_MIN {
+            return Err(SchemaResolverErr::ArityMismatch {
+                fn_name: SchemaFnLexeme::UNION,
+                kind: ArityMismatchKind::MoreEq(ArgLen::UNION_MIN),
+                found: args_len,
+                span: call.span.clone(),
+            });
+        }
+
+        // Disallow direct usage of union inside the union, references
+        // included since they are expanded in place.
+        for child in &call.children {
+            if self.is_union(self.current_file, child, &mut vec![]) {
+                return Err(SchemaResolverErr::NoUnionOfUnion {
+                    span: child.span().clone(),
+                });
+            }
+        }
+
+        // Modifiers are consumed by the union, so they don't
+        // leak into the root types of the branches.
+        let mut union_descriptor = SchemaTypeDescriptor::with_defaults(SchemaDataType::Union);
+        self.appy_modifiers(&mut union_descriptor);
+
+        // Capture global state at the moment of the branches resolution start
+        // since each of the branches needs to start its own resolution from
+        // the same state.
+        let captured_path = self.current_path.clone();
+        let captured_modifiers = self.current_modifiers.clone();
+
+        // New resolution table for each Union branch.
+        let mut branches: Vec<TResolvedSchema> = Vec::with_capacity(args_len);
+
+        for child in &call.children {
+            self.current_path = captured_path.clone();
+            self.current_modifiers = captured_modifiers.clone();
+            let mut table: TResolvedSchema = HashMap::new();
+            self.resolve_from_node(child, &mut table)?;
+            branches.push(table);
+        }
+
+        Self::merge_branches(
+            &captured_path,
+            branches,
+            union_descriptor.optional,
+            &call.span,
+            resolved_schema,
+        )?;
+
+        if union_descriptor.nullable {
+            resolved_schema.get_mut(&captured_path).unwrap().nullable = true;
+        }
+
+        self.current_path = captured_path;
+        self.current_modifiers = captured_modifiers;
+        self.current_type = Some(SchemaDataType::Union);
+        // The same as any other type definition, union removes
+        // the path segment that leads to it.
+        self.current_path.pop();
+
+        Ok(())
+    }
+
+    /// Merges tables of the branches that all define the `path`
+    /// into the main resolution table.
+    fn merge_branches(
+        path: &ResolutionPath,
+        branches: Vec<TResolvedSchema>,
+        // Some branches don't define the path at all.
+        missing: bool,
+        span: &Span,
+        resolved_schema: &mut TResolvedSchema,
+    ) -> Result<(), SchemaResolverErr> {
+        // Alternatives of a nested union take part in the merge
+        // as separate branches.
+        let branches: Vec<TResolvedSchema> = branches
+            .into_iter()
+            .flat_map(|table| Self::expand_union(path, table))
+            .collect();
+
+        // Kinds in order of their first appearance, so the merge
+        // doesn't depend on the order of the hash map.
+        let mut kinds: Vec<&'static str> = vec![];
+
+        for table in &branches {
+            let kind = table.get(path).unwrap().dtype.as_str();
+            if !kinds.contains(&kind) {
+                kinds.push(kind);
+            }
+        }
+
+        if kinds.len() > 1 {
+            let descriptors = branches.iter().map(|table| table.get(path).unwrap());
+            let mut descriptor = Self::merge_descriptors(descriptors, missing);
+            descriptor.dtype = SchemaDataType::Union;
+            resolved_schema.insert(path.clone(), descriptor);
+
+            for kind in kinds {
+                let mut alternative_path = path.clone();
+                alternative_path.push(ResolutionPathSegment::Assertion(kind.to_string()));
+
+                let alternatives = branches
+                    .iter()
+                    .filter(|table| table.get(path).unwrap().dtype.as_str() == kind)
+                    .map(|table| Self::rebase(table, path, &alternative_path))
+                    .collect();
+
+                Self::merge_branches(
+                    &alternative_path,
+                    alternatives,
+                    false,
+                    span,
+                    resolved_schema,
+                )?;
+            }
+
+            return Ok(());
+        }
+
+        let mut formats = branches
+            .iter()
+            .filter_map(|table| table.get(path).unwrap().dtype.temporal());
+
+        if let Some(first) = formats.next()
+            && formats.any(|format| format != first)
+        {
+            return Err(SchemaResolverErr::FormatConflict { span: span.clone() });
+        }
+
+        let descriptors = branches.iter().map(|table| table.get(path).unwrap());
+        resolved_schema.insert(path.clone(), Self::merge_descriptors(descriptors, missing));
+
+        // Direct children in order of their first appearance.
+        let mut children: Vec<ResolutionPath> = vec![];
+
+        for table in &branches {
+            for key in table.keys() {
+                if key.len() == path.len() + 1 && key.starts_with(path) && !children.contains(key) {
+                    children.push(key.clone());
+                }
+            }
+        }
+
+        for child in children {
+            let child_branches: Vec<TResolvedSchema> = branches
+                .iter()
+                .filter(|table| table.contains_key(&child))
+                .map(|table| {
+                    table
+                        .iter()
+                        .filter(|(key, _)| key.starts_with(&child))
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect()
+                })
+                .collect();
+
+            let missing = child_branches.len() < branches.len();
+            Self::merge_branches(&child, child_branches, missing, span, resolved_schema)?;
+        }
+
+        Ok(())
+    }
+
+    /// Descriptor of the path that is shared by the branches.
+    fn merge_descriptors<'b>(
+        mut descriptors: impl Iterator<Item = &'b SchemaTypeDescriptor>,
+        missing: bool,
+    ) -> SchemaTypeDescriptor {
+        let mut merged = descriptors.next().unwrap().clone();
+
+        for descriptor in descriptors {
+            // Only lists of different sizes can have the same
+            // kind of type but different data types.
+            if merged.dtype != descriptor.dtype {
+                merged.dtype = SchemaDataType::ListAbstract;
+            }
+            merged.nullable |= descriptor.nullable;
+            merged.optional |= descriptor.optional;
+            // A value of the shared path satisfies any of the branches,
+            // so only constraints common to all of them are kept.
+            merged
+                .constraints
+                .retain(|constraint| descriptor.constraints.contains(constraint));
+        }
+
+        merged.optional |= missing;
+        merged
+    }
+
+    /// Splits the table into alternatives if the `path` resolves
+    /// to a union, so each alternative is a table on its own.
+    fn expand_union(path: &ResolutionPath, table: TResolvedSchema) -> Vec<TResolvedSchema> {
+        let union_descriptor = table.get(path).unwrap().clone();
+
+        if union_descriptor.dtype != SchemaDataType::Union {
+            return vec![table];
+        }
+
+        table
+            .keys()
+            .filter(|key| key.len() == path.len() + 1 && key.starts_with(path))
+            .map(|alternative_path| {
+                let mut alternative = Self::rebase(&table, alternative_path, path);
+                let descriptor = alternative.get_mut(path).unwrap();
+                descriptor.nullable |= union_descriptor.nullable;
+                descriptor.optional |= union_descriptor.optional;
+                alternative
+            })
+            .collect()
+    }
+
+    /// Moves entries of the table that start with the `from` path
+    /// under the `to` path. Other entries are dropped.
+    fn rebase(
+        table: &TResolvedSchema,
+        from: &ResolutionPath,
+        to: &ResolutionPath,
+    ) -> TResolvedSchema {
+        table
+            .iter()
+            .filter(|(key, _)| key.starts_with(from))
+            .map(|(key, value)| {
+                let mut rebased = to.clone();
+                for segment in &key[from.len()..] {
+                    rebased.push(segment.clone());
+                }
+                (rebased, value.clone())
+            })
+            .collect()
+    }
 
     // ==================================================================
     // UNION END
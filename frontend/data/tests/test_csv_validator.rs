@@ -0,0 +1,303 @@
+use elise_data::{
+    csv::{csv_parser::CsvParser, csv_validator::CsvValidator},
+    schema_resolver::SchemaResolver,
+};
+use elise_shared::shared_errors::{
+    errors_csv_binder::PosInfo, errors_csv_validator::CsvValidatorErr,
+};
+
+use crate::common::parse;
+
+mod common;
+
+const SCHEMA: &str = r#"
+.schema(
+    .list(
+        .dict(
+            "name"   .string(.len(1 8) .pattern("[A-Z][a-z]+"))
+            "age"    .nullable(.int(.range(0 150)))
+            "status" .enum("active" "banned")
+            "score"  .optional(.float(.range(0 1)))
+            "code"   .union(.int(.enum(1 2)) .string(.len(2 2))))))
+"#;
+
+const TEMPORAL_SCHEMA: &str = r#"
+.schema(
+    .list(
+        .dict(
+            "day"   .date("%d.%m.%Y")
+            "at"    .nullable(.datetime())
+            "opens" .time("%H:%M")
+            "took"  .duration()
+            "since" .union(.date("%Y%m%d") .bool()))))
+"#;
+
+fn validate(schema: &str, data: &str) -> Result<(), CsvValidatorErr> {
+    let ast = parse(schema);
+    let schema = SchemaResolver::new(&ast).resolve().unwrap();
+    let rows = CsvParser::new(data).parse().unwrap();
+
+    CsvValidator::new(&rows, &schema).validate()
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn should_accept_values_satisfying_constraints() {
+    let data = "name,age,status,score,code\nAnn,0,active,0.5,1\nBob,150,banned,1,ab";
+    assert_eq!(validate(SCHEMA, data), Ok(()));
+}
+
+#[test]
+fn should_accept_null_of_nullable_and_missing_optional_column() {
+    let data = "name,age,status,code\nAnn,null,active,2";
+    assert_eq!(validate(SCHEMA, data), Ok(()));
+}
+
+#[test]
+fn should_accept_numbers_as_strings() {
+    let schema = r#".schema(.list(.dict("zip" .string(.pattern("[0-9]{5}")))))"#;
+    assert_eq!(validate(schema, "zip\n01234\n98765"), Ok(()));
+}
+
+#[test]
+fn should_accept_temporal_values_in_their_formats() {
+    let data = "day,at,opens,took,since\n\
+                29.02.2024,2024-02-29T23:59:59,09:30,P1DT2H,20240229\n\
+                01.01.1970,null,00:00,-PT15M,false";
+    assert_eq!(validate(TEMPORAL_SCHEMA, data), Ok(()));
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn should_return_error_if_schema_is_not_list_of_rows() {
+    assert_eq!(
+        validate(r#".schema(.dict("a" .int()))"#, "a\n1"),
+        Err(CsvValidatorErr::InvalRowSchema)
+    );
+    assert_eq!(
+        validate(".schema(.list(.int()))", "a\n1"),
+        Err(CsvValidatorErr::InvalRowSchema)
+    );
+}
+
+#[test]
+fn should_return_error_if_required_column_is_missing() {
+    assert_eq!(
+        validate(SCHEMA, "name,status,code\nAnn,active,1"),
+        Err(CsvValidatorErr::MissingCol {
+            col: "age".to_string()
+        })
+    );
+}
+
+#[test]
+fn should_return_error_if_column_is_not_defined() {
+    assert_eq!(
+        validate(SCHEMA, "name,age,status,code,zip\nAnn,1,active,1,123"),
+        Err(CsvValidatorErr::MissingTypeDefinition {
+            pos: PosInfo { row: 0, col: 4 },
+            col: "zip".to_string()
+        })
+    );
+}
+
+#[test]
+fn should_return_error_if_value_has_type_of_other_column() {
+    assert_eq!(
+        validate(SCHEMA, "name,age,status,code\nAnn,1.5,active,1"),
+        Err(CsvValidatorErr::TypeMismatch {
+            pos: PosInfo { row: 0, col: 1 },
+            col: "age".to_string(),
+            expected: "Int",
+            found: "Float",
+        })
+    );
+    assert_eq!(
+        validate(SCHEMA, "name,age,status,code\nAnn,1,null,1"),
+        Err(CsvValidatorErr::TypeMismatch {
+            pos: PosInfo { row: 0, col: 2 },
+            col: "status".to_string(),
+            expected: "String",
+            found: "Null",
+        })
+    );
+}
+
+#[test]
+fn should_return_error_with_violated_constraint_at_row_and_column() {
+    let cases = vec![
+        ("Ann,151,active,1", 1, "age", "151", ".range(0 150)"),
+        ("Ann,-1,active,1", 1, "age", "-1", ".range(0 150)"),
+        ("Annabella,1,active,1", 0, "name", "Annabella", ".len(1 8)"),
+        (
+            "ann,1,active,1",
+            0,
+            "name",
+            "ann",
+            ".pattern(\"[A-Z][a-z]+\")",
+        ),
+        (
+            "Ann,1,paused,1",
+            2,
+            "status",
+            "paused",
+            r#".enum("active" "banned")"#,
+        ),
+    ];
+
+    for (row, col, name, value, constraint) in cases {
+        let data = format!("name,age,status,code\nBob,1,active,1\n{row}");
+        assert_eq!(
+            validate(SCHEMA, &data),
+            Err(CsvValidatorErr::ConstraintViolation {
+                pos: PosInfo { row: 1, col },
+                col: name.to_string(),
+                value: value.to_string(),
+                constraint: constraint.to_string(),
+            })
+        );
+    }
+}
+
+#[test]
+fn should_compare_ints_beyond_float_precision_exactly() {
+    let schema = r#"
+    .schema(
+        .list(
+            .dict(
+                "id"   .int(.range(0 9007199254740992))
+                "kind" .int(.enum(9007199254740992)))))
+    "#;
+
+    assert_eq!(
+        validate(schema, "id,kind\n9007199254740992,9007199254740992"),
+        Ok(())
+    );
+    assert_eq!(
+        validate(schema, "id,kind\n9007199254740993,9007199254740992"),
+        Err(CsvValidatorErr::ConstraintViolation {
+            pos: PosInfo { row: 0, col: 0 },
+            col: "id".to_string(),
+            value: "9007199254740993".to_string(),
+            constraint: ".range(0 9007199254740992)".to_string(),
+        })
+    );
+    assert_eq!(
+        validate(schema, "id,kind\n1,9007199254740993"),
+        Err(CsvValidatorErr::ConstraintViolation {
+            pos: PosInfo { row: 0, col: 1 },
+            col: "kind".to_string(),
+            value: "9007199254740993".to_string(),
+            constraint: r#".enum("9007199254740992")"#.to_string(),
+        })
+    );
+}
+
+#[test]
+fn should_return_error_of_union_alternative_of_value_type() {
+    assert_eq!(
+        validate(SCHEMA, "name,age,status,code\nAnn,1,active,3"),
+        Err(CsvValidatorErr::ConstraintViolation {
+            pos: PosInfo { row: 0, col: 3 },
+            col: "code".to_string(),
+            value: "3".to_string(),
+            constraint: r#".enum("1" "2")"#.to_string(),
+        })
+    );
+    assert_eq!(
+        validate(SCHEMA, "name,age,status,code\nAnn,1,active,true"),
+        Err(CsvValidatorErr::ConstraintViolation {
+            pos: PosInfo { row: 0, col: 3 },
+            col: "code".to_string(),
+            value: "true".to_string(),
+            constraint: ".len(2 2)".to_string(),
+        })
+    );
+}
+
+#[test]
+fn should_return_error_if_temporal_value_doesnt_match_format() {
+    let cases = vec![
+        (
+            "2024-02-29,null,09:30,P1D,",
+            0,
+            "day",
+            "2024-02-29",
+            "%d.%m.%Y",
+        ),
+        (
+            "29.02.2023,null,09:30,P1D,",
+            0,
+            "day",
+            "29.02.2023",
+            "%d.%m.%Y",
+        ),
+        (
+            "29.02.2024,2024-02-29 10:00:00,09:30,P1D,",
+            1,
+            "at",
+            "2024-02-29 10:00:00",
+            "%Y-%m-%dT%H:%M:%S",
+        ),
+        ("29.02.2024,null,24:00,P1D,", 2, "opens", "24:00", "%H:%M"),
+        ("29.02.2024,null,9:30,P1D,", 2, "opens", "9:30", "%H:%M"),
+        (
+            "29.02.2024,null,09:30,1 day,",
+            3,
+            "took",
+            "1 day",
+            "PnDTnHnMnS",
+        ),
+    ];
+
+    for (row, col, name, value, format) in cases {
+        let data = format!("day,at,opens,took,since\n{row}");
+        assert_eq!(
+            validate(TEMPORAL_SCHEMA, &data),
+            Err(CsvValidatorErr::FormatMismatch {
+                pos: PosInfo { row: 0, col },
+                col: name.to_string(),
+                value: value.to_string(),
+                format: format.to_string(),
+            })
+        );
+    }
+}
+
+#[test]
+fn should_return_error_of_temporal_alternative_of_union() {
+    assert_eq!(
+        validate(
+            TEMPORAL_SCHEMA,
+            "day,at,opens,took,since\n01.01.2024,null,09:30,P1D,2024"
+        ),
+        Err(CsvValidatorErr::FormatMismatch {
+            pos: PosInfo { row: 0, col: 4 },
+            col: "since".to_string(),
+            value: "2024".to_string(),
+            format: "%Y%m%d".to_string(),
+        })
+    );
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
@@ -0,0 +1,78 @@
+use elise_data::{dump::schema_to_json, schema_resolver::SchemaResolver};
+
+use crate::common::parse;
+
+mod common;
+
+#[test]
+fn should_dump_resolved_schema_in_stable_order() {
+    let ast = parse(
+        r#".schema(.dict(
+            "tags" .list(.string())
+            "id"   .int()
+            "pos"  .nullable(.list(.float() 2))))"#,
+    );
+    let expected = [
+        r#"{"version":2,"kind":"schema","paths":["#,
+        r#"{"path":[{"kind":"Root"}],"type":{"dtype":"Dict","fixed_len":null,"format":null,"nullable":false,"optional":false,"constraints":[]}},"#,
+        r#"{"path":[{"kind":"Root"},{"kind":"Field","name":"id"}],"type":{"dtype":"Int","fixed_len":null,"format":null,"nullable":false,"optional":false,"constraints":[]}},"#,
+        r#"{"path":[{"kind":"Root"},{"kind":"Field","name":"pos"}],"type":{"dtype":"List","fixed_len":2,"format":null,"nullable":true,"optional":false,"constraints":[]}},"#,
+        r#"{"path":[{"kind":"Root"},{"kind":"Field","name":"pos"},{"kind":"AbstractIndex"}],"type":{"dtype":"Float","fixed_len":null,"format":null,"nullable":false,"optional":false,"constraints":[]}},"#,
+        r#"{"path":[{"kind":"Root"},{"kind":"Field","name":"tags"}],"type":{"dtype":"List","fixed_len":null,"format":null,"nullable":false,"optional":false,"constraints":[]}},"#,
+        r#"{"path":[{"kind":"Root"},{"kind":"Field","name":"tags"},{"kind":"AbstractIndex"}],"type":{"dtype":"String","fixed_len":null,"format":null,"nullable":false,"optional":false,"constraints":[]}}"#,
+        r#"]}"#,
+    ]
+    .concat();
+
+    // Every resolution gets its own HashMap with a different order.
+    for _ in 0..3 {
+        let resolved_schema = SchemaResolver::new(&ast).resolve().unwrap();
+        assert_eq!(schema_to_json(&resolved_schema).to_compact(), expected);
+    }
+}
+
+// Snapshot of every part of the dumped structure. If it changes,
+// `DUMP_VERSION` must be bumped together with it.
+#[test]
+fn should_dump_every_part_of_type_descriptor() {
+    let ast = parse(
+        r#".schema(.dict(
+            "at"   .date("%d.%m.%Y")
+            "code" .union(.int(.range(1 9)) .string(.enum("a" "b")))))"#,
+    );
+    let expected = [
+        r#"{"version":2,"kind":"schema","paths":["#,
+        r#"{"path":[{"kind":"Root"}],"type":{"dtype":"Dict","fixed_len":null,"format":null,"nullable":false,"optional":false,"constraints":[]}},"#,
+        r#"{"path":[{"kind":"Root"},{"kind":"Field","name":"at"}],"type":{"dtype":"Date","fixed_len":null,"format":"%d.%m.%Y","nullable":false,"optional":false,"constraints":[]}},"#,
+        r#"{"path":[{"kind":"Root"},{"kind":"Field","name":"code"}],"type":{"dtype":"Union","fixed_len":null,"format":null,"nullable":false,"optional":false,"constraints":[]}},"#,
+        r#"{"path":[{"kind":"Root"},{"kind":"Field","name":"code"},{"kind":"Assertion","name":"Int"}],"type":{"dtype":"Int","fixed_len":null,"format":null,"nullable":false,"optional":false,"constraints":[{"kind":"range","min":1,"max":9}]}},"#,
+        r#"{"path":[{"kind":"Root"},{"kind":"Field","name":"code"},{"kind":"Assertion","name":"String"}],"type":{"dtype":"String","fixed_len":null,"format":null,"nullable":false,"optional":false,"constraints":[{"kind":"enum","values":["a","b"]}]}}"#,
+        r#"]}"#,
+    ]
+    .concat();
+
+    let resolved_schema = SchemaResolver::new(&ast).resolve().unwrap();
+    assert_eq!(schema_to_json(&resolved_schema).to_compact(), expected);
+}
+
+#[test]
+fn should_dump_constraints_as_objects() {
+    let ast = parse(
+        r#".schema(.dict(
+            "age"   .int(.range(0 150) .enum(1 2))
+            "name"  .string(.len(1 64) .pattern("^[a-z]+$"))
+            "score" .float(.range(0.5 2.5))))"#,
+    );
+    let expected = [
+        r#"{"version":2,"kind":"schema","paths":["#,
+        r#"{"path":[{"kind":"Root"}],"type":{"dtype":"Dict","fixed_len":null,"format":null,"nullable":false,"optional":false,"constraints":[]}},"#,
+        r#"{"path":[{"kind":"Root"},{"kind":"Field","name":"age"}],"type":{"dtype":"Int","fixed_len":null,"format":null,"nullable":false,"optional":false,"constraints":[{"kind":"range","min":0,"max":150},{"kind":"enum","values":[1,2]}]}},"#,
+        r#"{"path":[{"kind":"Root"},{"kind":"Field","name":"name"}],"type":{"dtype":"String","fixed_len":null,"format":null,"nullable":false,"optional":false,"constraints":[{"kind":"len","min":1,"max":64},{"kind":"pattern","pattern":"^[a-z]+$"}]}},"#,
+        r#"{"path":[{"kind":"Root"},{"kind":"Field","name":"score"}],"type":{"dtype":"Float","fixed_len":null,"format":null,"nullable":false,"optional":false,"constraints":[{"kind":"range","min":0.5,"max":2.5}]}}"#,
+        r#"]}"#,
+    ]
+    .concat();
+
+    let resolved_schema = SchemaResolver::new(&ast).resolve().unwrap();
+    assert_eq!(schema_to_json(&resolved_schema).to_compact(), expected);
+}
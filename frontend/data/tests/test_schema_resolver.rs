@@ -1,13 +1,23 @@
 use elise_data::{
-    resolution_path::{ResolutionPath, ResolutionPathSegment::*},
+    resolution_path::{
+        ResolutionPath,
+        ResolutionPathSegment::{self, *},
+    },
     schema_resolver::{
-        ArgLen, SchemaDataType, SchemaFnLexeme, SchemaResolver, SchemaTypeDescriptor,
+        ArgLen, ResolvedSchema, SchemaConstraint, SchemaDataType, SchemaFnLexeme, SchemaImporter,
+        SchemaResolver, SchemaTypeDescriptor,
     },
 };
 use elise_shared::{
-    shared_errors::errors_schema_resolver::SchemaResolverErr, shared_types::ArityMismatchKind,
+    shared_errors::errors_schema_resolver::{SchemaImportErr, SchemaResolverErr},
+    shared_types::{ArityMismatchKind, Span},
 };
 
+use std::collections::HashMap;
+
+use elise_ast::AstNode;
+use elise_parser::Prelude;
+
 use crate::common::parse;
 
 mod common;
@@ -88,30 +98,21 @@ fn should_return_error_if_root_arg_len_is_more_than_1() {
 // ==================================================================
 
 #[test]
-fn should_return_error_if_primitive_has_arguments() {
+fn should_return_error_if_primitive_has_non_constraint_arguments() {
     let inputs = vec![
-        (".int(.int())", SchemaFnLexeme::INT),
-        (".float(.float())", SchemaFnLexeme::FLOAT),
-        (".string(.string())", SchemaFnLexeme::STRING),
-        (".bool(.bool())", SchemaFnLexeme::BOOL),
+        (".int(.int())", Span { start: 13, end: 19 }),
+        (".float(1)", Span { start: 15, end: 16 }),
+        (".string(\"a\")", Span { start: 16, end: 19 }),
+        (".bool(.bool())", Span { start: 14, end: 21 }),
     ];
 
-    for input in inputs {
-        let ast = parse(&format!(".schema({})", input.0));
+    for (input, span) in inputs {
+        let ast = parse(&format!(".schema({input})"));
         let resolved_schema = SchemaResolver::new(&ast).resolve();
-        match resolved_schema {
-            Err(SchemaResolverErr::ArityMismatch {
-                fn_name,
-                kind,
-                found,
-                ..
-            }) => {
-                assert_eq!(fn_name, input.1);
-                assert_eq!(kind, ArityMismatchKind::Eq(ArgLen::PRIMITIVE));
-                assert_eq!(found, 1);
-            }
-            other => panic!("expected ArityMismatch, got {:?}", other),
-        }
+        assert_eq!(
+            resolved_schema,
+            Err(SchemaResolverErr::InvalConstraint { span })
+        );
     }
 }
 
@@ -205,29 +206,638 @@ fn should_return_error_if_modifiers_have_invalid_arity() {
 // OPTIONAL START
 
 #[test]
-fn should_return_error_if_optional_modifier_applied_to_list_item() {
+fn should_return_error_if_optional_modifier_applied_to_list_item() {
+    let inputs = vec![
+        ".list(.nullable(.optional(.int())))",
+        ".list(.optional(.int()))",
+        ".list(.list(.optional(.int())))",
+        r#".dict("x" .list(.optional(.int())))"#,
+    ];
+
+    for input in inputs {
+        let ast = parse(&format!(".schema({})", input));
+        let resolved_schema = SchemaResolver::new(&ast).resolve();
+
+        assert!(matches!(
+            resolved_schema,
+            Err(SchemaResolverErr::InvalUseOfModifier { .. })
+        ));
+    }
+}
+
+// OPTIONAL END
+
+// ==================================================================
+// MODIFIERS ERROR CASES END
+// ==================================================================
+
+// ==================================================================
+// UNION ERROR CASES START
+// ==================================================================
+
+#[test]
+fn should_return_error_if_union_has_less_than_2_args() {
+    let ast = parse(".schema(.union(.int()))");
+    let resolved_schema = SchemaResolver::new(&ast).resolve();
+    assert!(matches!(
+        resolved_schema,
+        Err(SchemaResolverErr::ArityMismatch {
+            fn_name: SchemaFnLexeme::UNION,
+            kind: ArityMismatchKind::MoreEq(ArgLen::UNION_MIN),
+            found: 1,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn should_return_error_if_union_is_direct_child_of_union() {
+    let ast = parse(".schema(.union(.int() .union(.string() .bool())))");
+    let resolved_schema = SchemaResolver::new(&ast).resolve();
+    assert_eq!(
+        resolved_schema,
+        Err(SchemaResolverErr::NoUnionOfUnion {
+            span: Span { start: 22, end: 47 }
+        })
+    );
+}
+
+#[test]
+fn should_return_error_if_union_is_referenced_by_union() {
+    let inputs = vec![
+        (
+            ".type(u .union(.int() .string())) .schema(.union(.ref(u) .float()))",
+            Span { start: 49, end: 56 },
+        ),
+        (
+            ".type(u .union(.int() .string())) .type(v .ref(u)) .schema(.union(.float() .ref(v)))",
+            Span { start: 75, end: 82 },
+        ),
+    ];
+
+    for (input, span) in inputs {
+        let ast = parse(input);
+        let resolved_schema = SchemaResolver::new(&ast).resolve();
+        assert_eq!(
+            resolved_schema,
+            Err(SchemaResolverErr::NoUnionOfUnion { span })
+        );
+    }
+}
+
+// ==================================================================
+// UNION ERROR CASES END
+// ==================================================================
+
+// ==================================================================
+// CONSTRAINTS ERROR CASES START
+// ==================================================================
+
+#[test]
+fn should_return_error_if_constraint_does_not_apply_to_type() {
+    let inputs = vec![
+        (".string(.range(0 1))", Span { start: 16, end: 27 }),
+        (".int(.len(0 1))", Span { start: 13, end: 22 }),
+        (".float(.pattern(\"a\"))", Span { start: 15, end: 28 }),
+        (".bool(.range(0 1))", Span { start: 14, end: 25 }),
+        (".int(.unknown())", Span { start: 13, end: 23 }),
+    ];
+
+    for (input, span) in inputs {
+        let ast = parse(&format!(".schema({input})"));
+        let resolved_schema = SchemaResolver::new(&ast).resolve();
+        assert_eq!(
+            resolved_schema,
+            Err(SchemaResolverErr::InvalConstraint { span })
+        );
+    }
+}
+
+#[test]
+fn should_return_error_if_min_is_greater_than_max() {
+    let inputs = vec![
+        (".int(.range(2 1))", Span { start: 13, end: 24 }),
+        (".string(.len(3 1))", Span { start: 16, end: 25 }),
+    ];
+
+    for (input, span) in inputs {
+        let ast = parse(&format!(".schema({input})"));
+        let resolved_schema = SchemaResolver::new(&ast).resolve();
+        assert_eq!(
+            resolved_schema,
+            Err(SchemaResolverErr::InvalConstraint { span })
+        );
+    }
+}
+
+#[test]
+fn should_return_error_if_constraint_has_invalid_arity() {
+    let inputs = vec![
+        (
+            ".int(.range(1))",
+            SchemaFnLexeme::RANGE,
+            ArityMismatchKind::Eq(ArgLen::RANGE),
+        ),
+        (
+            ".string(.len(1 2 3))",
+            SchemaFnLexeme::LEN,
+            ArityMismatchKind::Eq(ArgLen::LEN),
+        ),
+        (
+            ".string(.pattern())",
+            SchemaFnLexeme::PATTERN,
+            ArityMismatchKind::Eq(ArgLen::PATTERN),
+        ),
+        (
+            ".int(.enum())",
+            SchemaFnLexeme::ENUM,
+            ArityMismatchKind::MoreEq(ArgLen::ENUM_MIN),
+        ),
+        (
+            ".enum()",
+            SchemaFnLexeme::ENUM,
+            ArityMismatchKind::MoreEq(ArgLen::ENUM_MIN),
+        ),
+    ];
+
+    for (input, lexeme, arity) in inputs {
+        let ast = parse(&format!(".schema({input})"));
+        let resolved_schema = SchemaResolver::new(&ast).resolve();
+        match resolved_schema {
+            Err(SchemaResolverErr::ArityMismatch { fn_name, kind, .. }) => {
+                assert_eq!(fn_name, lexeme);
+                assert_eq!(kind, arity);
+            }
+            other => panic!("expected ArityMismatch, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn should_return_error_if_constraint_value_has_unexpected_type() {
+    let inputs = vec![
+        (".int(.range(0 1.5))", "Int", "Float"),
+        (".string(.len(0 \"a\"))", "Int", "String"),
+        (".string(.pattern(1))", "String", "Int"),
+        (".int(.enum(1 \"a\"))", "Int", "String"),
+        (".enum(\"a\" 1)", "String", "Int"),
+    ];
+
+    for (input, expected_type, found_type) in inputs {
+        let ast = parse(&format!(".schema({input})"));
+        let resolved_schema = SchemaResolver::new(&ast).resolve();
+        match resolved_schema {
+            Err(SchemaResolverErr::UndexpType {
+                expected, found, ..
+            }) => {
+                assert_eq!(expected, expected_type);
+                assert_eq!(found, found_type);
+            }
+            other => panic!("expected UndexpType, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn should_return_error_if_pattern_is_invalid_regex() {
+    let ast = parse(r#".schema(.string(.pattern("[a-")))"#);
+    let resolved_schema = SchemaResolver::new(&ast).resolve();
+    assert_eq!(
+        resolved_schema,
+        Err(SchemaResolverErr::InvalPattern {
+            pattern: "[a-".to_string(),
+            span: Span { start: 25, end: 30 }
+        })
+    );
+}
+
+#[test]
+fn should_return_error_if_len_bound_is_negative() {
+    let ast = parse(".schema(.string(.len(-1 2)))");
+    let resolved_schema = SchemaResolver::new(&ast).resolve();
+    assert_eq!(
+        resolved_schema,
+        Err(SchemaResolverErr::InvalConstraint {
+            span: Span { start: 21, end: 23 }
+        })
+    );
+}
+
+// ==================================================================
+// CONSTRAINTS ERROR CASES END
+// ==================================================================
+
+// ==================================================================
+// TEMPORAL TYPES ERROR CASES START
+// ==================================================================
+
+#[test]
+fn should_return_error_if_format_cant_read_values() {
+    let inputs = vec![
+        r#".date("%Y-%m")"#,
+        r#".date("%Y-%m-%d %H")"#,
+        r#".time("%H")"#,
+        r#".datetime("%Y-%m-%d")"#,
+        r#".date("%Y-%m-%d-%Y")"#,
+        r#".date("%y-%m-%d")"#,
+    ];
+
+    for input in inputs {
+        let ast = parse(&format!(".schema({input})"));
+        let resolved_schema = SchemaResolver::new(&ast).resolve();
+        assert!(
+            matches!(resolved_schema, Err(SchemaResolverErr::InvalFormat { .. })),
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn should_return_error_with_invalid_format_at_its_span() {
+    let ast = parse(r#".schema(.time("%H-%Y"))"#);
+    let resolved_schema = SchemaResolver::new(&ast).resolve();
+    assert_eq!(
+        resolved_schema,
+        Err(SchemaResolverErr::InvalFormat {
+            format: "%H-%Y".to_string(),
+            span: Span { start: 14, end: 21 }
+        })
+    );
+}
+
+#[test]
+fn should_return_error_if_format_is_not_string() {
+    let inputs = vec![".date(1)", ".datetime(.range(1 2))"];
+
+    for input in inputs {
+        let ast = parse(&format!(".schema({input})"));
+        let resolved_schema = SchemaResolver::new(&ast).resolve();
+        assert!(
+            matches!(resolved_schema, Err(SchemaResolverErr::UndexpType { .. })),
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn should_return_error_if_temporal_arg_len_is_invalid() {
+    let ast = parse(r#".schema(.date("%Y-%m-%d" "%d.%m.%Y"))"#);
+    let resolved_schema = SchemaResolver::new(&ast).resolve();
+    assert!(matches!(
+        resolved_schema,
+        Err(SchemaResolverErr::ArityMismatch {
+            fn_name: SchemaFnLexeme::DATE,
+            kind: ArityMismatchKind::Range(ArgLen::TEMPORAL),
+            found: 2,
+            ..
+        })
+    ));
+
+    let ast = parse(r#".schema(.duration("PnD"))"#);
+    let resolved_schema = SchemaResolver::new(&ast).resolve();
+    assert!(matches!(
+        resolved_schema,
+        Err(SchemaResolverErr::ArityMismatch {
+            fn_name: SchemaFnLexeme::DURATION,
+            kind: ArityMismatchKind::Eq(ArgLen::DURATION),
+            found: 1,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn should_return_error_if_union_branches_have_different_formats() {
+    let ast = parse(r#".schema(.union(.list(.date()) .list(.date("%d.%m.%Y"))))"#);
+    let resolved_schema = SchemaResolver::new(&ast).resolve();
+    assert_eq!(
+        resolved_schema,
+        Err(SchemaResolverErr::FormatConflict {
+            span: Span { start: 8, end: 55 }
+        })
+    );
+}
+
+// ==================================================================
+// TEMPORAL TYPES ERROR CASES END
+// ==================================================================
+
+// ==================================================================
+// NAMED TYPES ERROR CASES START
+// ==================================================================
+
+#[test]
+fn should_return_error_if_root_is_missing() {
+    let ast = parse(".type(id .int())");
+    let resolved_schema = SchemaResolver::new(&ast).resolve();
+    assert_eq!(resolved_schema, Err(SchemaResolverErr::MissingRoot));
+}
+
+#[test]
+fn should_return_error_if_root_is_defined_twice() {
+    let ast = parse(".schema(.int()) .schema(.int())");
+    let resolved_schema = SchemaResolver::new(&ast).resolve();
+    assert_eq!(
+        resolved_schema,
+        Err(SchemaResolverErr::Unexp {
+            span: Span { start: 16, end: 31 }
+        })
+    );
+}
+
+#[test]
+fn should_return_error_if_type_is_redefined() {
+    let ast = parse(".type(id .int()) .type(id .string()) .schema(.ref(id))");
+    let resolved_schema = SchemaResolver::new(&ast).resolve();
+    assert_eq!(
+        resolved_schema,
+        Err(SchemaResolverErr::TypeRedefinition {
+            name: "id".to_string(),
+            span: Span { start: 23, end: 25 }
+        })
+    );
+}
+
+#[test]
+fn should_return_error_if_type_is_undefined() {
+    let ast = parse(r#".schema(.dict("id" .ref(id)))"#);
+    let resolved_schema = SchemaResolver::new(&ast).resolve();
+    assert_eq!(
+        resolved_schema,
+        Err(SchemaResolverErr::TypeUndefined {
+            name: "id".to_string(),
+            span: Span { start: 19, end: 27 }
+        })
+    );
+}
+
+#[test]
+fn should_return_error_if_type_definitions_are_invalid() {
+    let inputs = vec![
+        (
+            ".type(id) .schema(.int())",
+            SchemaFnLexeme::TYPE,
+            ArityMismatchKind::Eq(ArgLen::TYPE),
+        ),
+        (
+            ".type(id .int()) .schema(.ref())",
+            SchemaFnLexeme::REF,
+            ArityMismatchKind::Range(ArgLen::REF),
+        ),
+        (
+            ".type(id .int()) .schema(.ref(a b c))",
+            SchemaFnLexeme::REF,
+            ArityMismatchKind::Range(ArgLen::REF),
+        ),
+    ];
+
+    for (input, lexeme, arity) in inputs {
+        let ast = parse(input);
+        let resolved_schema = SchemaResolver::new(&ast).resolve();
+        match resolved_schema {
+            Err(SchemaResolverErr::ArityMismatch { fn_name, kind, .. }) => {
+                assert_eq!(fn_name, lexeme);
+                assert_eq!(kind, arity);
+            }
+            other => panic!("expected ArityMismatch, got {:?}", other),
+        }
+    }
+
+    let ast = parse(r#".type("id" .int()) .schema(.int())"#);
+    assert!(matches!(
+        SchemaResolver::new(&ast).resolve(),
+        Err(SchemaResolverErr::UndexpType { .. })
+    ));
+}
+
+#[test]
+fn should_return_error_if_type_is_used_outside_top_level() {
+    let ast = parse(r#".schema(.dict("id" .type(id .int())))"#);
+    let resolved_schema = SchemaResolver::new(&ast).resolve();
+    assert_eq!(
+        resolved_schema,
+        Err(SchemaResolverErr::InvalTypeDef {
+            span: Span { start: 19, end: 35 }
+        })
+    );
+}
+
+#[test]
+fn should_return_error_at_reference_closing_type_cycle() {
+    let inputs = vec![
+        (
+            r#".type(node .dict("next" .ref(node))) .schema(.ref(node))"#,
+            "node",
+            Span { start: 24, end: 34 },
+        ),
+        (
+            r#".type(a .list(.ref(b))) .type(b .nullable(.ref(a))) .schema(.ref(a))"#,
+            "a",
+            Span { start: 42, end: 49 },
+        ),
+    ];
+
+    for (input, name, span) in inputs {
+        let ast = parse(input);
+        let resolved_schema = SchemaResolver::new(&ast).resolve();
+        assert_eq!(
+            resolved_schema,
+            Err(SchemaResolverErr::TypeCycle {
+                name: name.to_string(),
+                span
+            })
+        );
+    }
+}
+
+#[test]
+fn should_return_error_of_unused_types() {
+    let ast = parse(".type(a .ref(a)) .schema(.int())");
+    assert_eq!(
+        SchemaResolver::new(&ast).resolve(),
+        Err(SchemaResolverErr::TypeCycle {
+            name: "a".to_string(),
+            span: Span { start: 8, end: 15 }
+        })
+    );
+
+    let ast = parse(".type(a .ref(missing)) .schema(.int())");
+    assert_eq!(
+        SchemaResolver::new(&ast).resolve(),
+        Err(SchemaResolverErr::TypeUndefined {
+            name: "missing".to_string(),
+            span: Span { start: 8, end: 21 }
+        })
+    );
+
+    let mut importer = MemImporter::new(vec![("common.elt", ".type(a .list(.ref(b)))")]);
+    assert_eq!(
+        resolve_with_imports(r#".import("common.elt") .schema(.int())"#, &mut importer),
+        Err(SchemaResolverErr::InFile {
+            path: "common.elt".to_string(),
+            err: Box::new(SchemaResolverErr::TypeUndefined {
+                name: "b".to_string(),
+                span: Span { start: 14, end: 21 }
+            })
+        })
+    );
+}
+
+// ==================================================================
+// NAMED TYPES ERROR CASES END
+// ==================================================================
+
+// ==================================================================
+// IMPORTS ERROR CASES START
+// ==================================================================
+
+#[test]
+fn should_return_error_if_import_is_not_available() {
+    let ast = parse(r#".import("common.elt") .schema(.int())"#);
+    let resolved_schema = SchemaResolver::new(&ast).resolve();
+    assert_eq!(
+        resolved_schema,
+        Err(SchemaResolverErr::Unexp {
+            span: Span { start: 0, end: 21 }
+        })
+    );
+}
+
+#[test]
+fn should_return_error_if_import_is_invalid() {
     let inputs = vec![
-        ".list(.nullable(.optional(.int())))",
-        ".list(.optional(.int()))",
-        ".list(.list(.optional(.int())))",
-        r#".dict("x" .list(.optional(.int())))"#,
+        r#".import() .schema(.int())"#,
+        r#".import(common) .schema(.int())"#,
+        r#".import("common.elt" "common") .schema(.int())"#,
     ];
 
     for input in inputs {
-        let ast = parse(&format!(".schema({})", input));
-        let resolved_schema = SchemaResolver::new(&ast).resolve();
-
+        let mut importer = MemImporter::new(vec![("common.elt", ".type(id .int())")]);
         assert!(matches!(
-            resolved_schema,
-            Err(SchemaResolverErr::InvalUseOfModifier { .. })
+            resolve_with_imports(input, &mut importer),
+            Err(SchemaResolverErr::ArityMismatch { .. } | SchemaResolverErr::UndexpType { .. })
         ));
     }
 }
 
-// OPTIONAL END
+#[test]
+fn should_return_error_if_imported_file_can_not_be_loaded() {
+    let mut importer = MemImporter::new(vec![("lib/broken.elt", ".type(id")]);
+    let resolved_schema = resolve_with_imports(
+        r#".import("lib/missing.elt") .schema(.int())"#,
+        &mut importer,
+    );
+    assert!(matches!(
+        resolved_schema,
+        Err(SchemaResolverErr::Import {
+            path,
+            err: SchemaImportErr::Unreadable { .. },
+            span: Span { start: 0, end: 26 },
+        }) if path == "lib/missing.elt"
+    ));
+
+    let resolved_schema = resolve_with_imports(
+        r#".import("lib/broken.elt") .schema(.int())"#,
+        &mut importer,
+    );
+    assert!(matches!(
+        resolved_schema,
+        Err(SchemaResolverErr::Import {
+            err: SchemaImportErr::Parser(_),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn should_return_error_at_import_closing_import_cycle() {
+    let mut importer = MemImporter::new(vec![
+        ("lib/a.elt", r#".import("b.elt")"#),
+        ("lib/b.elt", r#".type(id .int()) .import("../main.elt")"#),
+    ]);
+    let resolved_schema =
+        resolve_with_imports(r#".import("lib/a.elt") .schema(.int())"#, &mut importer);
+    assert_eq!(
+        resolved_schema,
+        Err(SchemaResolverErr::InFile {
+            path: "lib/b.elt".to_string(),
+            err: Box::new(SchemaResolverErr::ImportCycle {
+                path: "main.elt".to_string(),
+                span: Span { start: 17, end: 39 }
+            })
+        })
+    );
+}
+
+#[test]
+fn should_return_error_of_imported_file_with_its_path() {
+    let mut importer = MemImporter::new(vec![
+        ("common.elt", r#".type(id .int(.len(1 2)))"#),
+        ("other.elt", r#".schema(.int())"#),
+    ]);
+
+    // Errors of imported types are reported where they are declared.
+    let resolved_schema =
+        resolve_with_imports(r#".import("common.elt") .schema(.ref(id))"#, &mut importer);
+    assert_eq!(
+        resolved_schema,
+        Err(SchemaResolverErr::InFile {
+            path: "common.elt".to_string(),
+            err: Box::new(SchemaResolverErr::InvalConstraint {
+                span: Span { start: 14, end: 23 }
+            })
+        })
+    );
+
+    // Imported files can't have root.
+    let resolved_schema =
+        resolve_with_imports(r#".import("other.elt") .schema(.int())"#, &mut importer);
+    assert_eq!(
+        resolved_schema,
+        Err(SchemaResolverErr::InFile {
+            path: "other.elt".to_string(),
+            err: Box::new(SchemaResolverErr::Unexp {
+                span: Span { start: 0, end: 15 }
+            })
+        })
+    );
+}
+
+#[test]
+fn should_return_error_if_imported_type_clashes() {
+    let mut importer = MemImporter::new(vec![
+        ("a.elt", ".type(id .int())"),
+        ("b.elt", ".type(id .string())"),
+    ]);
+    let resolved_schema = resolve_with_imports(
+        r#".import("a.elt") .import("b.elt") .schema(.ref(id))"#,
+        &mut importer,
+    );
+    assert_eq!(
+        resolved_schema,
+        Err(SchemaResolverErr::TypeRedefinition {
+            name: "id".to_string(),
+            span: Span { start: 17, end: 33 }
+        })
+    );
+}
+
+#[test]
+fn should_not_import_types_imported_by_imported_file() {
+    let mut importer = MemImporter::new(vec![
+        ("a.elt", r#".import("b.elt")"#),
+        ("b.elt", ".type(id .int())"),
+    ]);
+    let resolved_schema =
+        resolve_with_imports(r#".import("a.elt") .schema(.ref(id))"#, &mut importer);
+    assert!(matches!(
+        resolved_schema,
+        Err(SchemaResolverErr::TypeUndefined { .. })
+    ));
+}
 
 // ==================================================================
-// MODIFIERS ERROR CASES END
+// IMPORTS ERROR CASES END
 // ==================================================================
 
 // ==================================================================
@@ -268,6 +878,7 @@ fn should_resolve_single_primitive() {
                 dtype: input.1,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             }
         );
     }
@@ -302,6 +913,7 @@ fn should_resolve_single_compound() {
                 dtype: input.1,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             }
         );
     }
@@ -342,6 +954,7 @@ fn should_resolve_one_nullable_child() {
                 dtype: input.1,
                 nullable: true,
                 optional: false,
+                constraints: vec![],
             }
         );
     }
@@ -380,6 +993,7 @@ fn should_resolve_nested_nullables() {
                 dtype: SchemaDataType::Dict,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -388,6 +1002,7 @@ fn should_resolve_nested_nullables() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -396,6 +1011,7 @@ fn should_resolve_nested_nullables() {
                 dtype: SchemaDataType::String,
                 nullable: true,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -404,6 +1020,7 @@ fn should_resolve_nested_nullables() {
                 dtype: SchemaDataType::Dict,
                 nullable: true,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -415,6 +1032,7 @@ fn should_resolve_nested_nullables() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -426,6 +1044,7 @@ fn should_resolve_nested_nullables() {
                 dtype: SchemaDataType::Int,
                 nullable: true,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -437,6 +1056,7 @@ fn should_resolve_nested_nullables() {
                 dtype: SchemaDataType::Dict,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -449,6 +1069,7 @@ fn should_resolve_nested_nullables() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -461,6 +1082,7 @@ fn should_resolve_nested_nullables() {
                 dtype: SchemaDataType::String,
                 nullable: true,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -469,6 +1091,7 @@ fn should_resolve_nested_nullables() {
                 dtype: SchemaDataType::Float,
                 nullable: true,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -477,6 +1100,7 @@ fn should_resolve_nested_nullables() {
                 dtype: SchemaDataType::Int,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
     ];
@@ -518,6 +1142,7 @@ fn should_resolve_one_optional_child() {
                 dtype: input.1,
                 nullable: false,
                 optional: true,
+                constraints: vec![],
             }
         );
     }
@@ -556,6 +1181,7 @@ fn should_resolve_nested_optionals() {
                 dtype: SchemaDataType::Dict,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -564,6 +1190,7 @@ fn should_resolve_nested_optionals() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -572,6 +1199,7 @@ fn should_resolve_nested_optionals() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: true,
+                constraints: vec![],
             },
         ),
         (
@@ -580,6 +1208,7 @@ fn should_resolve_nested_optionals() {
                 dtype: SchemaDataType::Dict,
                 nullable: false,
                 optional: true,
+                constraints: vec![],
             },
         ),
         (
@@ -591,6 +1220,7 @@ fn should_resolve_nested_optionals() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -602,6 +1232,7 @@ fn should_resolve_nested_optionals() {
                 dtype: SchemaDataType::Int,
                 nullable: false,
                 optional: true,
+                constraints: vec![],
             },
         ),
         (
@@ -613,6 +1244,7 @@ fn should_resolve_nested_optionals() {
                 dtype: SchemaDataType::Dict,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -625,6 +1257,7 @@ fn should_resolve_nested_optionals() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -637,6 +1270,7 @@ fn should_resolve_nested_optionals() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: true,
+                constraints: vec![],
             },
         ),
         (
@@ -645,6 +1279,7 @@ fn should_resolve_nested_optionals() {
                 dtype: SchemaDataType::Float,
                 nullable: false,
                 optional: true,
+                constraints: vec![],
             },
         ),
         (
@@ -653,6 +1288,7 @@ fn should_resolve_nested_optionals() {
                 dtype: SchemaDataType::Int,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
     ];
@@ -686,6 +1322,7 @@ fn should_resolve_optional_with_nullable() {
                 dtype: SchemaDataType::Dict,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -694,6 +1331,7 @@ fn should_resolve_optional_with_nullable() {
                 dtype: SchemaDataType::String,
                 nullable: true,
                 optional: true,
+                constraints: vec![],
             },
         ),
         (
@@ -702,6 +1340,7 @@ fn should_resolve_optional_with_nullable() {
                 dtype: SchemaDataType::Int,
                 nullable: true,
                 optional: true,
+                constraints: vec![],
             },
         ),
     ];
@@ -747,6 +1386,7 @@ fn should_resolve_one_level_dict() {
                 dtype: SchemaDataType::Dict,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -755,6 +1395,7 @@ fn should_resolve_one_level_dict() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -763,6 +1404,7 @@ fn should_resolve_one_level_dict() {
                 dtype: SchemaDataType::Int,
                 nullable: true,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -771,6 +1413,7 @@ fn should_resolve_one_level_dict() {
                 dtype: SchemaDataType::Float,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -779,6 +1422,7 @@ fn should_resolve_one_level_dict() {
                 dtype: SchemaDataType::Bool,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
     ];
@@ -819,6 +1463,7 @@ fn should_resolve_one_level_abstract_list() {
                     dtype: SchemaDataType::ListAbstract,
                     nullable: false,
                     optional: false,
+                    constraints: vec![],
                 },
             ),
             (
@@ -827,6 +1472,7 @@ fn should_resolve_one_level_abstract_list() {
                     dtype: input.1,
                     nullable: false,
                     optional: false,
+                    constraints: vec![],
                 },
             ),
         ];
@@ -860,6 +1506,7 @@ fn should_resolve_one_level_fixed_list() {
                     dtype: SchemaDataType::ListFixed(2),
                     nullable: false,
                     optional: false,
+                    constraints: vec![],
                 },
             ),
             (
@@ -868,6 +1515,7 @@ fn should_resolve_one_level_fixed_list() {
                     dtype: input.1,
                     nullable: false,
                     optional: false,
+                    constraints: vec![],
                 },
             ),
         ];
@@ -885,6 +1533,587 @@ fn should_resolve_one_level_fixed_list() {
 // LIST SUCCESS CASES END
 // ==================================================================
 
+// ==================================================================
+// UNION SUCCESS CASES START
+// ==================================================================
+
+fn descriptor(dtype: SchemaDataType) -> SchemaTypeDescriptor {
+    SchemaTypeDescriptor::with_defaults(dtype)
+}
+
+fn alternative(kind: &str) -> ResolutionPathSegment {
+    Assertion(kind.to_string())
+}
+
+#[test]
+fn should_resolve_union_of_primitives_into_alternatives() {
+    let ast = parse(r#".schema(.dict("id" .union(.int() .string())))"#);
+    let resolved_schema = SchemaResolver::new(&ast).resolve().unwrap();
+
+    let id = Field("id".to_string());
+    let cases = vec![
+        (ResolutionPath::new(), descriptor(SchemaDataType::Dict)),
+        (
+            ResolutionPath::with_segments(vec![id.clone()]),
+            descriptor(SchemaDataType::Union),
+        ),
+        (
+            ResolutionPath::with_segments(vec![id.clone(), alternative("Int")]),
+            descriptor(SchemaDataType::Int),
+        ),
+        (
+            ResolutionPath::with_segments(vec![id, alternative("String")]),
+            descriptor(SchemaDataType::String),
+        ),
+    ];
+
+    assert_eq!(resolved_schema.resolved_schema.len(), cases.len());
+    for case in cases {
+        assert_eq!(
+            *resolved_schema.resolved_schema.get(&case.0).unwrap(),
+            case.1
+        );
+    }
+}
+
+#[test]
+fn should_share_common_prefix_of_union_branches() {
+    let ast = parse(".schema(.union(.list(.int()) .list(.string() 2)))");
+    let resolved_schema = SchemaResolver::new(&ast).resolve().unwrap();
+
+    let cases = vec![
+        // Lists of different sizes are still lists.
+        (
+            ResolutionPath::new(),
+            descriptor(SchemaDataType::ListAbstract),
+        ),
+        (
+            ResolutionPath::with_segments(vec![AbstractIndex]),
+            descriptor(SchemaDataType::Union),
+        ),
+        (
+            ResolutionPath::with_segments(vec![AbstractIndex, alternative("Int")]),
+            descriptor(SchemaDataType::Int),
+        ),
+        (
+            ResolutionPath::with_segments(vec![AbstractIndex, alternative("String")]),
+            descriptor(SchemaDataType::String),
+        ),
+    ];
+
+    assert_eq!(resolved_schema.resolved_schema.len(), cases.len());
+    for case in cases {
+        assert_eq!(
+            *resolved_schema.resolved_schema.get(&case.0).unwrap(),
+            case.1
+        );
+    }
+}
+
+#[test]
+fn should_merge_dict_branches_by_fields() {
+    let s = r#"
+        .schema(
+            .union(
+                .dict("some" .int()         "some2" .float())
+                .dict("some" .list(.int())  "some3" .string())))
+    "#;
+    let ast = parse(s);
+    let resolved_schema = SchemaResolver::new(&ast).resolve().unwrap();
+
+    let some = Field("some".to_string());
+    let optional = |dtype| SchemaTypeDescriptor {
+        optional: true,
+        ..descriptor(dtype)
+    };
+    let cases = vec![
+        (ResolutionPath::new(), descriptor(SchemaDataType::Dict)),
+        (
+            ResolutionPath::with_segments(vec![some.clone()]),
+            descriptor(SchemaDataType::Union),
+        ),
+        (
+            ResolutionPath::with_segments(vec![some.clone(), alternative("Int")]),
+            descriptor(SchemaDataType::Int),
+        ),
+        (
+            ResolutionPath::with_segments(vec![some.clone(), alternative("List")]),
+            descriptor(SchemaDataType::ListAbstract),
+        ),
+        (
+            ResolutionPath::with_segments(vec![some, alternative("List"), AbstractIndex]),
+            descriptor(SchemaDataType::Int),
+        ),
+        // Fields that only some of the branches have can be missing.
+        (
+            ResolutionPath::with_segments(vec![Field("some2".to_string())]),
+            optional(SchemaDataType::Float),
+        ),
+        (
+            ResolutionPath::with_segments(vec![Field("some3".to_string())]),
+            optional(SchemaDataType::String),
+        ),
+    ];
+
+    assert_eq!(resolved_schema.resolved_schema.len(), cases.len());
+    for case in cases {
+        assert_eq!(
+            *resolved_schema.resolved_schema.get(&case.0).unwrap(),
+            case.1
+        );
+    }
+}
+
+#[test]
+fn should_apply_modifiers_to_union_itself() {
+    let ast = parse(r#".schema(.dict("id" .optional(.union(.int() .string())) "n" .int()))"#);
+    let resolved_schema = SchemaResolver::new(&ast).resolve().unwrap();
+
+    let id = Field("id".to_string());
+    assert_eq!(
+        *resolved_schema
+            .resolved_schema
+            .get(&ResolutionPath::with_segments(vec![id.clone()]))
+            .unwrap(),
+        SchemaTypeDescriptor {
+            dtype: SchemaDataType::Union,
+            nullable: false,
+            optional: true,
+            constraints: vec![],
+        }
+    );
+    assert_eq!(
+        *resolved_schema
+            .resolved_schema
+            .get(&ResolutionPath::with_segments(vec![id, alternative("Int")]))
+            .unwrap(),
+        descriptor(SchemaDataType::Int)
+    );
+    // Path of the next field is not affected by the union.
+    assert_eq!(
+        *resolved_schema
+            .resolved_schema
+            .get(&ResolutionPath::with_segments(vec![Field("n".to_string())]))
+            .unwrap(),
+        descriptor(SchemaDataType::Int)
+    );
+}
+
+#[test]
+fn should_flatten_nested_unions_of_common_prefix() {
+    let ast = parse(".schema(.union(.list(.union(.int() .string())) .list(.bool())))");
+    let resolved_schema = SchemaResolver::new(&ast).resolve().unwrap();
+
+    for kind in ["Int", "String", "Bool"] {
+        assert!(
+            resolved_schema
+                .resolved_schema
+                .contains_key(&ResolutionPath::with_segments(vec![
+                    AbstractIndex,
+                    alternative(kind)
+                ]))
+        );
+    }
+    assert_eq!(resolved_schema.resolved_schema.len(), 5);
+}
+
+// ==================================================================
+// UNION SUCCESS CASES END
+// ==================================================================
+
+// ==================================================================
+// CONSTRAINTS SUCCESS CASES START
+// ==================================================================
+
+fn constraints_of(src: &str) -> Vec<SchemaConstraint> {
+    let ast = parse(&format!(".schema({src})"));
+    let resolved_schema = SchemaResolver::new(&ast).resolve().unwrap();
+    resolved_schema
+        .resolved_schema
+        .get(&ResolutionPath::new())
+        .unwrap()
+        .constraints
+        .clone()
+}
+
+#[test]
+fn should_resolve_constraints_of_primitives() {
+    assert_eq!(
+        constraints_of(".int(.range(-10 150))"),
+        vec![SchemaConstraint::IntRange { min: -10, max: 150 }]
+    );
+    assert_eq!(
+        constraints_of(".float(.range(0 1.5))"),
+        vec![SchemaConstraint::FloatRange { min: 0.0, max: 1.5 }]
+    );
+    assert_eq!(
+        constraints_of(r#".string(.len(1 64) .pattern("[A-Z]+"))"#),
+        vec![
+            SchemaConstraint::Len { min: 1, max: 64 },
+            SchemaConstraint::Pattern("[A-Z]+".to_string())
+        ]
+    );
+    assert_eq!(
+        constraints_of(".float(.enum(1 2.5))"),
+        vec![SchemaConstraint::Enum(vec![
+            "1".to_string(),
+            "2.5".to_string()
+        ])]
+    );
+}
+
+#[test]
+fn should_resolve_standalone_enum_to_type_of_its_values() {
+    let inputs = vec![
+        (r#".enum("a" "b")"#, SchemaDataType::String, vec!["a", "b"]),
+        (".enum(1 2)", SchemaDataType::Int, vec!["1", "2"]),
+        (".enum(true)", SchemaDataType::Bool, vec!["true"]),
+    ];
+
+    for (input, dtype, values) in inputs {
+        let ast = parse(&format!(".schema(.dict(\"status\" .nullable({input})))"));
+        let resolved_schema = SchemaResolver::new(&ast).resolve().unwrap();
+
+        assert_eq!(
+            *resolved_schema
+                .resolved_schema
+                .get(&ResolutionPath::with_segments(vec![Field(
+                    "status".to_string()
+                )]))
+                .unwrap(),
+            SchemaTypeDescriptor {
+                dtype,
+                nullable: true,
+                optional: false,
+                constraints: vec![SchemaConstraint::Enum(
+                    values.into_iter().map(String::from).collect()
+                )],
+            }
+        );
+    }
+}
+
+#[test]
+fn should_keep_constraints_common_to_union_branches() {
+    let ast =
+        parse(".schema(.union(.list(.int(.range(0 9) .enum(1 2))) .list(.int(.enum(1 2)) 2)))");
+    let resolved_schema = SchemaResolver::new(&ast).resolve().unwrap();
+
+    assert_eq!(
+        resolved_schema
+            .resolved_schema
+            .get(&ResolutionPath::with_segments(vec![AbstractIndex]))
+            .unwrap()
+            .constraints,
+        vec![SchemaConstraint::Enum(vec![
+            "1".to_string(),
+            "2".to_string()
+        ])]
+    );
+}
+
+// ==================================================================
+// CONSTRAINTS SUCCESS CASES END
+// ==================================================================
+
+// ==================================================================
+// TEMPORAL TYPES SUCCESS CASES START
+// ==================================================================
+
+fn dtype_of(src: &str) -> SchemaDataType {
+    let ast = parse(&format!(".schema({src})"));
+    let resolved_schema = SchemaResolver::new(&ast).resolve().unwrap();
+    resolved_schema
+        .resolved_schema
+        .get(&ResolutionPath::new())
+        .unwrap()
+        .dtype
+        .clone()
+}
+
+#[test]
+fn should_resolve_temporal_types_with_default_formats() {
+    assert_eq!(
+        dtype_of(".date()"),
+        SchemaDataType::Date("%Y-%m-%d".to_string())
+    );
+    assert_eq!(
+        dtype_of(".time()"),
+        SchemaDataType::Time("%H:%M:%S".to_string())
+    );
+    assert_eq!(
+        dtype_of(".datetime()"),
+        SchemaDataType::DateTime("%Y-%m-%dT%H:%M:%S".to_string())
+    );
+    assert_eq!(dtype_of(".duration()"), SchemaDataType::Duration);
+}
+
+#[test]
+fn should_resolve_temporal_types_with_given_formats() {
+    assert_eq!(
+        dtype_of(r#".date("%d.%m.%Y")"#),
+        SchemaDataType::Date("%d.%m.%Y".to_string())
+    );
+    assert_eq!(
+        dtype_of(r#".time("%Hh%M")"#),
+        SchemaDataType::Time("%Hh%M".to_string())
+    );
+    assert_eq!(
+        dtype_of(r#".datetime("%Y%m%d %H:%M")"#),
+        SchemaDataType::DateTime("%Y%m%d %H:%M".to_string())
+    );
+}
+
+#[test]
+fn should_resolve_union_of_temporal_types_into_alternatives() {
+    let ast = parse(r#".schema(.dict("at" .union(.date() .datetime() .nullable(.int()))))"#);
+    let resolved_schema = SchemaResolver::new(&ast).resolve().unwrap();
+    let at = ResolutionPath::with_segments(vec![Field("at".to_string())]);
+
+    assert_eq!(
+        resolved_schema.resolved_schema.get(&at).unwrap().dtype,
+        SchemaDataType::Union
+    );
+
+    for (alternative, dtype) in [
+        ("Date", SchemaDataType::Date("%Y-%m-%d".to_string())),
+        (
+            "DateTime",
+            SchemaDataType::DateTime("%Y-%m-%dT%H:%M:%S".to_string()),
+        ),
+        ("Int", SchemaDataType::Int),
+    ] {
+        let mut path = at.clone();
+        path.push(Assertion(alternative.to_string()));
+        assert_eq!(
+            resolved_schema.resolved_schema.get(&path).unwrap().dtype,
+            dtype
+        );
+    }
+}
+
+#[test]
+fn should_merge_union_branches_with_same_format() {
+    let ast = parse(r#".schema(.union(.list(.date("%d.%m.%Y")) .list(.date("%d.%m.%Y") 2)))"#);
+    let resolved_schema = SchemaResolver::new(&ast).resolve().unwrap();
+
+    assert_eq!(
+        resolved_schema
+            .resolved_schema
+            .get(&ResolutionPath::with_segments(vec![AbstractIndex]))
+            .unwrap()
+            .dtype,
+        SchemaDataType::Date("%d.%m.%Y".to_string())
+    );
+}
+
+// ==================================================================
+// TEMPORAL TYPES SUCCESS CASES END
+// ==================================================================
+
+// ==================================================================
+// NAMED TYPES SUCCESS CASES START
+// ==================================================================
+
+#[test]
+fn should_expand_references_as_hand_expanded_schema() {
+    let inputs = vec![
+        (
+            r#"
+.type(address .dict("street" .string() "house" .int(.range(1 999))))
+.schema(
+    .dict(
+        "home"   .ref(address)
+        "work"   .nullable(.ref(address))
+        "extra"  .list(.ref(address))))
+"#,
+            r#"
+.schema(
+    .dict(
+        "home"   .dict("street" .string() "house" .int(.range(1 999)))
+        "work"   .nullable(.dict("street" .string() "house" .int(.range(1 999))))
+        "extra"  .list(.dict("street" .string() "house" .int(.range(1 999))))))
+"#,
+        ),
+        // Types can reference types declared after them.
+        (
+            r#"
+.schema(.list(.ref(person)))
+.type(person .dict("name" .string() "address" .optional(.ref(address))))
+.type(address .union(.ref(street) .int()))
+.type(street .string(.len(1 32)))
+"#,
+            r#"
+.schema(
+    .list(
+        .dict(
+            "name"    .string()
+            "address" .optional(.union(.string(.len(1 32)) .int())))))
+"#,
+        ),
+    ];
+
+    for (aliased, expanded) in inputs {
+        let aliased = SchemaResolver::new(&parse(aliased)).resolve().unwrap();
+        let expanded = SchemaResolver::new(&parse(expanded)).resolve().unwrap();
+        assert_eq!(aliased, expanded);
+    }
+}
+
+#[test]
+fn should_ignore_unused_types() {
+    let ast = parse(".type(unused .int()) .schema(.string())");
+    let resolved_schema = SchemaResolver::new(&ast).resolve().unwrap();
+    assert_eq!(resolved_schema.resolved_schema.len(), 1);
+}
+
+// ==================================================================
+// NAMED TYPES SUCCESS CASES END
+// ==================================================================
+
+// ==================================================================
+// IMPORTS SUCCESS CASES START
+// ==================================================================
+
+/// Importer of in-memory files, where paths are relative
+/// to the directory of the importing file.
+struct MemImporter {
+    files: HashMap<String, String>,
+    loads: usize,
+}
+
+impl MemImporter {
+    fn new(files: Vec<(&str, &str)>) -> Self {
+        Self {
+            files: files
+                .into_iter()
+                .map(|(path, source)| (path.to_string(), source.to_string()))
+                .collect(),
+            loads: 0,
+        }
+    }
+}
+
+impl SchemaImporter for MemImporter {
+    fn resolve_path(&self, from: &str, path: &str) -> String {
+        let mut segments: Vec<&str> = from.split('/').collect();
+        segments.pop();
+
+        for segment in path.split('/') {
+            match segment {
+                ".." => {
+                    segments.pop();
+                }
+                segment => segments.push(segment),
+            }
+        }
+        segments.join("/")
+    }
+
+    fn load(&mut self, path: &str) -> Result<Vec<AstNode>, SchemaImportErr> {
+        self.loads += 1;
+        let source = self.files.get(path).ok_or(SchemaImportErr::Unreadable {
+            reason: "No such file".to_string(),
+        })?;
+        Prelude::new(source.as_bytes())
+            .parse()
+            .map_err(|err| SchemaImportErr::Parser(vec![err]))
+    }
+}
+
+fn resolve_with_imports(
+    src: &str,
+    importer: &mut MemImporter,
+) -> Result<ResolvedSchema, SchemaResolverErr> {
+    let ast = parse(src);
+    SchemaResolver::new(&ast)
+        .with_importer("main.elt", importer)
+        .resolve()
+}
+
+#[test]
+fn should_resolve_imported_types_as_hand_expanded_schema() {
+    let mut importer = MemImporter::new(vec![
+        (
+            "types/common.elt",
+            r#"
+.import("geo.elt" geo)
+.type(person .dict("name" .ref(name) "home" .ref(geo address)))
+.type(name .string(.len(1 64)))
+"#,
+        ),
+        (
+            "types/geo.elt",
+            r#".type(address .dict("street" .string() "house" .int()))"#,
+        ),
+    ]);
+
+    let aliased = resolve_with_imports(
+        r#"
+.import("types/common.elt")
+.import("types/geo.elt" geo)
+.schema(.dict("owner" .ref(person) "office" .nullable(.ref(geo address))))
+"#,
+        &mut importer,
+    )
+    .unwrap();
+
+    let expanded = SchemaResolver::new(&parse(
+        r#"
+.schema(
+    .dict(
+        "owner"  .dict(
+                    "name" .string(.len(1 64))
+                    "home" .dict("street" .string() "house" .int()))
+        "office" .nullable(.dict("street" .string() "house" .int()))))
+"#,
+    ))
+    .resolve()
+    .unwrap();
+
+    assert_eq!(aliased, expanded);
+    // geo.elt is imported twice, but loaded once.
+    assert_eq!(importer.loads, 2);
+}
+
+#[test]
+fn should_keep_names_of_imported_file_in_its_own_scope() {
+    // Both files have their own "id", so the namespace
+    // is the only way to reference the imported one.
+    let mut importer = MemImporter::new(vec![(
+        "common.elt",
+        ".type(id .string()) .type(key .ref(id))",
+    )]);
+
+    let resolved_schema = resolve_with_imports(
+        r#"
+.import("common.elt" common)
+.type(id .int())
+.schema(.dict("a" .ref(id) "b" .ref(common id) "c" .ref(common key)))
+"#,
+        &mut importer,
+    )
+    .unwrap();
+
+    let dtype_of = |field: &str| {
+        resolved_schema
+            .resolved_schema
+            .get(&ResolutionPath::with_segments(vec![Field(
+                field.to_string(),
+            )]))
+            .unwrap()
+            .dtype
+            .clone()
+    };
+    assert_eq!(dtype_of("a"), SchemaDataType::Int);
+    assert_eq!(dtype_of("b"), SchemaDataType::String);
+    assert_eq!(dtype_of("c"), SchemaDataType::String);
+}
+
+// ==================================================================
+// IMPORTS SUCCESS CASES END
+// ==================================================================
+
 // ==================================================================
 // COMPLEX SCHEMAS SUCCESS CASES START
 // ==================================================================
@@ -928,6 +2157,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::ListAbstract,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -936,6 +2166,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::Dict,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -944,6 +2175,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::Int,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -952,6 +2184,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -960,6 +2193,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::Int,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -968,6 +2202,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::ListFixed(3),
                 nullable: true,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -980,6 +2215,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -988,6 +2224,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::Dict,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1000,6 +2237,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1012,6 +2250,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1024,6 +2263,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::Int,
                 nullable: true,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1036,6 +2276,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::Int,
                 nullable: true,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1044,6 +2285,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::ListAbstract,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1056,6 +2298,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::ListFixed(2),
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1069,6 +2312,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::Float,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1077,6 +2321,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::Bool,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1085,6 +2330,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::Dict,
                 nullable: true,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1097,6 +2343,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::Int,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1109,6 +2356,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1121,6 +2369,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::ListAbstract,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1134,6 +2383,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::String,
                 nullable: true,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1146,6 +2396,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::Dict,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1159,6 +2410,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1172,6 +2424,7 @@ fn should_resolve_complex_schema_with_nullables() {
                 dtype: SchemaDataType::String,
                 nullable: true,
                 optional: false,
+                constraints: vec![],
             },
         ),
     ];
@@ -1219,6 +2472,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::ListAbstract,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1227,6 +2481,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::Dict,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1235,6 +2490,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::Int,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1243,6 +2499,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::ListFixed(3),
                 nullable: false,
                 optional: true,
+                constraints: vec![],
             },
         ),
         (
@@ -1255,6 +2512,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1263,6 +2521,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::Dict,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1275,6 +2534,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1287,6 +2547,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::Int,
                 nullable: false,
                 optional: true,
+                constraints: vec![],
             },
         ),
         (
@@ -1299,6 +2560,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::Int,
                 nullable: false,
                 optional: true,
+                constraints: vec![],
             },
         ),
         (
@@ -1307,6 +2569,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::ListAbstract,
                 nullable: false,
                 optional: true,
+                constraints: vec![],
             },
         ),
         (
@@ -1319,6 +2582,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::ListFixed(2),
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1332,6 +2596,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::Float,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1340,6 +2605,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::Bool,
                 nullable: false,
                 optional: true,
+                constraints: vec![],
             },
         ),
         (
@@ -1348,6 +2614,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::Dict,
                 nullable: false,
                 optional: true,
+                constraints: vec![],
             },
         ),
         (
@@ -1360,6 +2627,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1372,6 +2640,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::ListAbstract,
                 nullable: false,
                 optional: true,
+                constraints: vec![],
             },
         ),
         (
@@ -1385,6 +2654,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1397,6 +2667,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::Dict,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1410,6 +2681,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: false,
+                constraints: vec![],
             },
         ),
         (
@@ -1423,6 +2695,7 @@ fn should_resolve_complex_schema_with_optionals() {
                 dtype: SchemaDataType::String,
                 nullable: false,
                 optional: true,
+                constraints: vec![],
             },
         ),
     ];
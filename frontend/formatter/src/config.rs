@@ -0,0 +1,19 @@
+pub struct FmtConfig;
+impl FmtConfig {
+    // Number of spaces for nested call arguments.
+    pub const INDENT: usize = 4;
+    // Node is broken into multiple lines if it doesn't fit.
+    pub const MAX_WIDTH: usize = 80;
+}
+
+/// Calls which arguments are key-value pairs.
+pub struct FmtPairedCall;
+impl FmtPairedCall {
+    pub const DICT: &'static str = "dict";
+}
+
+/// Calls which first argument is a list of bindings.
+pub struct FmtBindingCall;
+impl FmtBindingCall {
+    pub const LET: &'static str = "let";
+}
@@ -0,0 +1,661 @@
+//! # Formatter
+//!
+//! Cadenza takes a lossless CST and rewrites it into the canonical
+//! layout. The only things it keeps from the original layout are
+//! comments and single blank lines between top-level nodes.
+//!
+//! Layout rules:
+//!     1. Node is printed on a single line if it fits into
+//!        `FmtConfig::MAX_WIDTH` and doesn't contain comments.
+//!     2. Broken call puts every argument on its own line indented
+//!        by `FmtConfig::INDENT` relative to the call itself.
+//!        Call with a single argument hugs it instead, unless the
+//!        call is at the top level.
+//!     3. Broken list or dict puts every item on its own line
+//!        aligned right after the opening bracket.
+//!     4. Key-value pairs (dicts, `.dict` calls and `.let` bindings)
+//!        are printed one pair per line with aligned values as soon
+//!        as there are at least two of them.
+//!     5. Closing brackets are attached to the last item unless
+//!        it ends with a comment. Broken `.dict` call hugged by its
+//!        parent closes on its own line `FmtConfig::INDENT` columns
+//!        to the left of the call, so the end of the pairs stands
+//!        out, see `sample.elt`.
+
+pub mod config;
+
+use elise_parser::cst::{Cst, CstCall, CstElement, CstNode, CstTrivia, CstTriviaKind};
+
+use crate::config::{FmtBindingCall, FmtConfig, FmtPairedCall};
+
+// ==================================================================
+//
+// DATA TYPES START
+//
+// ==================================================================
+
+/// Node together with comments around it.
+struct Item<'a> {
+    // Comments on their own lines right before the node.
+    leading: Vec<&'a CstTrivia>,
+    node: &'a CstNode,
+    // Comment on the same line right after the node.
+    trailing: Option<&'a CstTrivia>,
+    // Whether there was a blank line before the item.
+    blank_before: bool,
+}
+
+/// Children of a container split into items.
+struct Items<'a> {
+    items: Vec<Item<'a>>,
+    // Comments after the last item.
+    dangling: Vec<&'a CstTrivia>,
+}
+
+impl Items<'_> {
+    fn has_comments(&self) -> bool {
+        !self.dangling.is_empty()
+            || self
+                .items
+                .iter()
+                .any(|item| !item.leading.is_empty() || item.trailing.is_some())
+    }
+}
+
+/// Everything that goes on its own line in a broken layout.
+struct Row<'a> {
+    leading: Vec<&'a CstTrivia>,
+    key: Option<String>,
+    // Comments between the key and the value.
+    key_comments: Vec<&'a CstTrivia>,
+    value: &'a CstNode,
+    value_paired: bool,
+    trailing: Option<&'a CstTrivia>,
+}
+
+// ==================================================================
+//
+// DATA TYPES END
+//
+// ==================================================================
+
+pub struct Cadenza<'a> {
+    cst: &'a Cst,
+}
+
+impl<'a> Cadenza<'a> {
+    pub fn new(cst: &'a Cst) -> Self {
+        Self { cst }
+    }
+
+    /// Returns source code in the canonical layout.
+    pub fn format(&self) -> String {
+        let Items { items, dangling } = Self::items(&self.cst.children);
+        let mut out = String::new();
+
+        for (i, item) in items.into_iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+                if item.blank_before {
+                    out.push('\n');
+                }
+            }
+            let row = Self::row_from_item(item, false);
+            out.push_str(&Self::row_fmt(&row, 0, 0));
+        }
+
+        for comment in dangling {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&Self::comment_text(comment));
+        }
+
+        if !out.is_empty() {
+            out.push('\n');
+        }
+
+        out
+    }
+
+    // ==================================================================
+    //
+    // ITEMS START
+    //
+    // ==================================================================
+
+    /// Attaches comments to the nodes they belong to. Comment on the
+    /// same line with the previous node is its trailing comment,
+    /// every other comment leads the next node.
+    fn items<'b>(elements: &'b [CstElement]) -> Items<'b> {
+        let mut items: Vec<Item> = vec![];
+        let mut pending: Vec<&CstTrivia> = vec![];
+        let mut new_lines = 0;
+        let mut blank_before = false;
+
+        for element in elements {
+            match element {
+                CstElement::Trivia(trivia) if trivia.is_comment() => {
+                    let is_trailing = pending.is_empty()
+                        && new_lines == 0
+                        && items.last().is_some_and(|item| item.trailing.is_none());
+
+                    if is_trailing && let Some(last) = items.last_mut() {
+                        last.trailing = Some(trivia);
+                    } else {
+                        if pending.is_empty() {
+                            blank_before = new_lines > 1;
+                        }
+                        pending.push(trivia);
+                    }
+                    new_lines = 0;
+                }
+                CstElement::Trivia(trivia) => new_lines += trivia.new_lines(),
+                CstElement::Node(node) => {
+                    if pending.is_empty() {
+                        blank_before = new_lines > 1;
+                    }
+                    items.push(Item {
+                        leading: std::mem::take(&mut pending),
+                        node,
+                        trailing: None,
+                        blank_before,
+                    });
+                    new_lines = 0;
+                }
+            }
+        }
+
+        Items {
+            items,
+            dangling: pending,
+        }
+    }
+
+    // ==================================================================
+    //
+    // ITEMS END
+    //
+    // ==================================================================
+
+    // ==================================================================
+    //
+    // FLAT START
+    //
+    // Single line layout. None if the node can't be printed
+    // on a single line regardless of the width.
+    //
+    // ==================================================================
+
+    fn flat(node: &CstNode, paired: bool) -> Option<String> {
+        match node {
+            // Multi-line strings can't be a part of a single line.
+            CstNode::Leaf(leaf) if leaf.text.contains('\n') => None,
+            CstNode::Leaf(leaf) => Some(leaf.text.clone()),
+            CstNode::Call(call) => {
+                let children =
+                    Self::flat_children(&call.children, Self::call_is_paired(call), |i| {
+                        Self::call_child_is_paired(call, i)
+                    })?;
+                Some(format!(".{}({})", call.name, children.join(" ")))
+            }
+            CstNode::List(list) => {
+                let children = Self::flat_children(&list.children, paired, |_| false)?;
+                Some(format!("[{}]", children.join(" ")))
+            }
+            CstNode::Dict(dict) => {
+                // Every dict child is a pair already.
+                let children = Self::flat_children(&dict.children, false, |_| false)?;
+                if children.len() > 1 {
+                    return None;
+                }
+                Some(format!("{{{}}}", children.join(" ")))
+            }
+            CstNode::DictPair(pair) => {
+                if pair.trivia.iter().any(|trivia| trivia.is_comment()) {
+                    return None;
+                }
+                let value = Self::flat(&pair.value, false)?;
+                Some(format!("{} {}", pair.key.text, value))
+            }
+        }
+    }
+
+    fn flat_children(
+        elements: &[CstElement],
+        paired: bool,
+        child_is_paired: impl Fn(usize) -> bool,
+    ) -> Option<Vec<String>> {
+        let items = Self::items(elements);
+
+        // Pairs have to be aligned on their own lines.
+        if items.has_comments() || (paired && items.items.len() > 2) {
+            return None;
+        }
+
+        items
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| Self::flat(item.node, child_is_paired(i)))
+            .collect()
+    }
+
+    // ==================================================================
+    //
+    // FLAT END
+    //
+    // ==================================================================
+
+    // ==================================================================
+    //
+    // BROKEN START
+    //
+    // Multi line layout. First line is never indented since
+    // it continues the line of the parent node.
+    //
+    // ==================================================================
+
+    /// Single line layout if it fits into the line at the `col`.
+    fn fitting_flat(node: &CstNode, col: usize, paired: bool) -> Option<String> {
+        Self::flat(node, paired).filter(|flat| col + Self::width(flat) <= FmtConfig::MAX_WIDTH)
+    }
+
+    fn node_fmt(node: &CstNode, col: usize, paired: bool) -> String {
+        if let Some(flat) = Self::fitting_flat(node, col, paired) {
+            return flat;
+        }
+
+        match node {
+            CstNode::Leaf(leaf) => leaf.text.clone(),
+            CstNode::Call(call) => Self::call_fmt(call, col, false),
+            CstNode::List(list) => {
+                let items = Self::items(&list.children);
+                let rows = Self::rows(items.items, paired, |_| false);
+                Self::compound_fmt("[", "]", &rows, &items.dangling, col)
+            }
+            CstNode::Dict(dict) => {
+                let items = Self::items(&dict.children);
+                let rows = items
+                    .items
+                    .into_iter()
+                    .map(|item| Self::row_from_item(item, false))
+                    .collect::<Vec<_>>();
+                Self::compound_fmt("{", "}", &rows, &items.dangling, col)
+            }
+            CstNode::DictPair(pair) => {
+                let key_col = col + Self::width(&pair.key.text) + 1;
+                format!(
+                    "{} {}",
+                    pair.key.text,
+                    Self::node_fmt(&pair.value, key_col, false)
+                )
+            }
+        }
+    }
+
+    fn call_fmt(call: &CstCall, col: usize, hugged: bool) -> String {
+        let open = format!(".{}(", call.name);
+        let items = Self::items(&call.children);
+
+        // Top level calls, like `.schema`, start at column 0.
+        if items.items.len() == 1 && !items.has_comments() && col > 0 {
+            let child_col = col + Self::width(&open);
+            let child_paired = Self::call_child_is_paired(call, 0);
+            let node = items.items[0].node;
+            let child = match (Self::fitting_flat(node, child_col, child_paired), node) {
+                (Some(flat), _) => flat,
+                (None, CstNode::Call(child)) => Self::call_fmt(child, child_col, true),
+                (None, node) => Self::node_fmt(node, child_col, child_paired),
+            };
+            return format!("{open}{child})");
+        }
+
+        let dangling = items.dangling;
+        let rows = Self::rows(items.items, Self::call_is_paired(call), |i| {
+            Self::call_child_is_paired(call, i)
+        });
+        let child_col = col + FmtConfig::INDENT;
+        let key_width = Self::key_width(&rows);
+        let mut out = open;
+
+        for row in &rows {
+            Self::new_line(&mut out, child_col);
+            out.push_str(&Self::row_fmt(row, child_col, key_width));
+        }
+        for comment in &dangling {
+            Self::new_line(&mut out, child_col);
+            out.push_str(&Self::comment_text(comment));
+        }
+
+        if hugged && Self::call_is_paired(call) && !Self::ends_with_comment(&rows, &dangling) {
+            Self::new_line(&mut out, col.saturating_sub(FmtConfig::INDENT));
+        }
+
+        Self::close(&mut out, ")", &rows, &dangling, col);
+        out
+    }
+
+    fn compound_fmt(
+        open: &str,
+        close: &str,
+        rows: &[Row],
+        dangling: &[&CstTrivia],
+        col: usize,
+    ) -> String {
+        let child_col = col + Self::width(open);
+        let key_width = Self::key_width(rows);
+        let mut out = open.to_string();
+
+        for (i, row) in rows.iter().enumerate() {
+            if i > 0 {
+                Self::new_line(&mut out, child_col);
+            }
+            out.push_str(&Self::row_fmt(row, child_col, key_width));
+        }
+        for (i, comment) in dangling.iter().enumerate() {
+            if i > 0 || !rows.is_empty() {
+                Self::new_line(&mut out, child_col);
+            }
+            out.push_str(&Self::comment_text(comment));
+        }
+
+        Self::close(&mut out, close, rows, dangling, col);
+        out
+    }
+
+    /// Closing bracket can't follow a comment on the same line.
+    fn close(out: &mut String, close: &str, rows: &[Row], dangling: &[&CstTrivia], col: usize) {
+        if Self::ends_with_comment(rows, dangling) {
+            Self::new_line(out, col);
+        }
+        out.push_str(close);
+    }
+
+    fn ends_with_comment(rows: &[Row], dangling: &[&CstTrivia]) -> bool {
+        !dangling.is_empty() || rows.last().is_some_and(|row| row.trailing.is_some())
+    }
+
+    // ==================================================================
+    //
+    // BROKEN END
+    //
+    // ==================================================================
+
+    // ==================================================================
+    //
+    // ROWS START
+    //
+    // ==================================================================
+
+    fn row_from_item<'b>(item: Item<'b>, value_paired: bool) -> Row<'b> {
+        match item.node {
+            CstNode::DictPair(pair) => Row {
+                leading: item.leading,
+                key: Some(pair.key.text.clone()),
+                key_comments: pair.trivia.iter().filter(|t| t.is_comment()).collect(),
+                value: &pair.value,
+                value_paired: false,
+                trailing: item.trailing,
+            },
+            node => Row {
+                leading: item.leading,
+                key: None,
+                key_comments: vec![],
+                value: node,
+                value_paired,
+                trailing: item.trailing,
+            },
+        }
+    }
+
+    /// Groups items into key-value rows if they are paired and
+    /// nothing stands between keys and values. Otherwise every
+    /// item gets its own row.
+    fn rows<'b>(
+        items: Vec<Item<'b>>,
+        paired: bool,
+        child_is_paired: impl Fn(usize) -> bool,
+    ) -> Vec<Row<'b>> {
+        let pairable = paired
+            && items.len().is_multiple_of(2)
+            && items.chunks(2).all(|pair| {
+                pair[0].trailing.is_none()
+                    && pair[1].leading.is_empty()
+                    && Self::flat(pair[0].node, false).is_some()
+            });
+
+        if !pairable {
+            return items
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| Self::row_from_item(item, child_is_paired(i)))
+                .collect();
+        }
+
+        let mut rows = vec![];
+        let mut items = items.into_iter();
+
+        while let (Some(key), Some(value)) = (items.next(), items.next()) {
+            rows.push(Row {
+                leading: key.leading,
+                key: Self::flat(key.node, false),
+                key_comments: vec![],
+                value: value.node,
+                value_paired: false,
+                trailing: value.trailing,
+            });
+        }
+
+        rows
+    }
+
+    fn row_fmt(row: &Row, col: usize, key_width: usize) -> String {
+        let mut out = String::new();
+
+        for comment in &row.leading {
+            out.push_str(&Self::comment_text(comment));
+            Self::new_line(&mut out, col);
+        }
+
+        match &row.key {
+            Some(key) => {
+                let value_col = col + key_width + 1;
+                out.push_str(key);
+                if row.key_comments.is_empty() {
+                    out.push_str(&" ".repeat(key_width - Self::width(key) + 1));
+                } else {
+                    for comment in &row.key_comments {
+                        out.push(' ');
+                        out.push_str(&Self::comment_text(comment));
+                    }
+                    Self::new_line(&mut out, value_col);
+                }
+                out.push_str(&Self::node_fmt(row.value, value_col, false));
+            }
+            None => out.push_str(&Self::node_fmt(row.value, col, row.value_paired)),
+        }
+
+        if let Some(comment) = row.trailing {
+            out.push(' ');
+            out.push_str(&Self::comment_text(comment));
+        }
+
+        out
+    }
+
+    fn key_width(rows: &[Row]) -> usize {
+        rows.iter()
+            .filter_map(|row| row.key.as_ref())
+            .map(|key| Self::width(key))
+            .max()
+            .unwrap_or(0)
+    }
+
+    // ==================================================================
+    //
+    // ROWS END
+    //
+    // ==================================================================
+
+    // ==================================================================
+    //
+    // UTILS START
+    //
+    // ==================================================================
+
+    fn call_is_paired(call: &CstCall) -> bool {
+        call.name == FmtPairedCall::DICT
+    }
+
+    fn call_child_is_paired(call: &CstCall, index: usize) -> bool {
+        call.name == FmtBindingCall::LET && index == 0
+    }
+
+    fn comment_text(comment: &CstTrivia) -> String {
+        match comment.kind {
+            CstTriviaKind::LineComment => comment.text.trim_end().to_string(),
+            _ => comment.text.clone(),
+        }
+    }
+
+    fn new_line(out: &mut String, col: usize) {
+        out.push('\n');
+        out.push_str(&" ".repeat(col));
+    }
+
+    fn width(s: &str) -> usize {
+        s.chars().count()
+    }
+
+    // ==================================================================
+    //
+    // UTILS END
+    //
+    // ==================================================================
+}
+
+// ==================================================================
+//
+// TESTS START
+//
+// ==================================================================
+
+#[cfg(test)]
+mod tests {
+    use elise_parser::Prelude;
+
+    use crate::Cadenza;
+
+    fn fmt(input: &str) -> String {
+        let cst = Prelude::new(input.as_bytes()).parse_cst().unwrap();
+        Cadenza::new(&cst).format()
+    }
+
+    #[test]
+    fn should_normalize_separators() {
+        assert_eq!(fmt("  .add (1,2   ,3)  "), ".add(1 2 3)\n");
+        assert_eq!(fmt("[ 1 ,2 ]\n\n\n\n{ \"a\"   1 }"), "[1 2]\n\n{\"a\" 1}\n");
+        assert_eq!(fmt(""), "");
+    }
+
+    #[test]
+    fn should_break_long_calls() {
+        let input = format!(".print(\"{}\" \"{}\")", "a".repeat(40), "b".repeat(40));
+        assert_eq!(
+            fmt(&input),
+            format!(
+                ".print(\n    \"{}\"\n    \"{}\")\n",
+                "a".repeat(40),
+                "b".repeat(40)
+            )
+        );
+    }
+
+    #[test]
+    fn should_align_dict_pairs() {
+        assert_eq!(
+            fmt("{\"a\" 1, \"long\" [1 2]}"),
+            "{\"a\"    1\n \"long\" [1 2]}\n"
+        );
+    }
+
+    #[test]
+    fn should_align_schema_dict_pairs() {
+        let input = ".schema(.dict(\"name\" .string() \"age\" .int() \"tags\" .list(.dict(\"id\" .int() \"title\" .string()))))";
+        let expected = r#".schema(
+    .dict(
+        "name" .string()
+        "age"  .int()
+        "tags" .list(.dict(
+                         "id"    .int()
+                         "title" .string()
+                 ))))
+"#;
+        assert_eq!(fmt(input), expected);
+    }
+
+    #[test]
+    fn should_keep_sample_schema_unchanged() {
+        let sample = include_str!("../../../sample.elt");
+        assert_eq!(fmt(sample), sample);
+    }
+
+    #[test]
+    fn should_align_let_bindings() {
+        assert_eq!(fmt(".let([x 1] x)"), ".let([x 1] x)\n");
+        assert_eq!(
+            fmt(".let([x 1, long 2] .add(x long))"),
+            ".let(\n    [x    1\n     long 2]\n    .add(x long))\n"
+        );
+    }
+
+    #[test]
+    fn should_keep_comments() {
+        assert_eq!(
+            fmt("; head\n.add(1 ; one\n 2)   ; tail   "),
+            "; head\n.add(\n    1 ; one\n    2) ; tail\n"
+        );
+        assert_eq!(fmt("[1\n; last\n]"), "[1\n ; last\n]\n");
+        assert_eq!(fmt("{\"a\" #| a |# 1}"), "{\"a\" #| a |#\n     1}\n");
+    }
+
+    #[test]
+    fn should_close_dict_right_after_values() {
+        let inputs = vec!["{\"a\" 1 }", "{\"a\" 1.5 }", "{\"a\" b }", "{\"a\" @b }"];
+        for input in inputs {
+            let once = fmt(input);
+            assert_eq!(once, format!("{}}}\n", input.trim_end_matches(" }")));
+            assert_eq!(fmt(&once), once);
+        }
+    }
+
+    #[test]
+    fn should_keep_multiline_strings() {
+        assert_eq!(
+            fmt(".query(\"\"\"\nSELECT *\n  FROM t\"\"\" 1)"),
+            ".query(\n    \"\"\"\nSELECT *\n  FROM t\"\"\"\n    1)\n"
+        );
+    }
+
+    #[test]
+    fn should_be_idempotent() {
+        let inputs = vec![
+            ".schema(\n    .dict(\n        \"case3\" .list(.list(.dict(\n            \"some\"  .int()\n            \"some2\" .float()\n        )))))",
+            "; a\n\n.let ([x 1 y 2] ; bindings\n  #| sum |# .add(x y)) ; tail\n;end",
+            "{\"a\" {\"b\" 1 \"c\" [1 2 3]} \"d\" ; c\n null}",
+            ".dict(\"a\" ; key\n .int() \"b\" .int())",
+            ".let([q \"\"\"\n  a\n\"\"\" r r\"\\d\"] .print(q r))",
+        ];
+        for input in inputs {
+            let once = fmt(input);
+            assert_eq!(fmt(&once), once, "Not idempotent for: {input}");
+        }
+    }
+}
+
+// ==================================================================
+//
+// TESTS END
+//
+// ==================================================================
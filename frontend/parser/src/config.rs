@@ -9,6 +9,13 @@ impl CharCode {
     pub const LEFT_CUR_BRACKET: u8 = b'{';
     pub const RIGHT_CUR_BRACKET: u8 = b'}';
     pub const MINUS: u8 = b'-';
+    pub const PLUS: u8 = b'+';
+    pub const UNDERSCORE: u8 = b'_';
     pub const COMMA: u8 = b',';
     pub const DOUBLE_QT: u8 = b'"';
+    pub const BACKSLASH: u8 = b'\\';
+    pub const RAW_STR_PREFIX: u8 = b'r';
+    pub const LINE_COMMENT: u8 = b';';
+    pub const BLOCK_COMMENT_OUTER: u8 = b'#';
+    pub const BLOCK_COMMENT_INNER: u8 = b'|';
 }
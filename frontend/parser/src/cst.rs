@@ -0,0 +1,515 @@
+//! # Concrete Syntax Tree (CST)
+//!
+//! AST drops everything that has no meaning for evaluation: separators,
+//! commas, comments and layout. This is exactly what tooling like the
+//! formatter needs to keep, so CST is a lossless representation of the
+//! source code. Concatenating text of every CST element in order always
+//! gives back the original source code byte by byte.
+//!
+//! CST is built from the AST and the source code it was parsed from.
+//! Spans of AST nodes tell us where each node is, so everything in
+//! between sibling nodes is trivia (separators, commas and comments).
+//! This way CST can never disagree with the AST about the structure.
+
+use elise_ast::{AstCall, AstCompound, AstKeyValuePair, AstNode, AstPrimitive};
+use elise_shared::{shared_errors::errors_parser::ParserErr, shared_types::Span};
+
+use crate::{Prelude, config::CharCode};
+
+// ==================================================================
+//
+// DATA TYPES START
+//
+// ==================================================================
+
+/// Everything that does not affect evaluation.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CstTriviaKind {
+    // Spaces, tabs and new lines.
+    Whitespace,
+    Comma,
+    LineComment,
+    BlockComment,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CstTrivia {
+    pub kind: CstTriviaKind,
+    pub text: String,
+    pub span: Span,
+}
+
+impl CstTrivia {
+    pub fn is_comment(&self) -> bool {
+        matches!(
+            self.kind,
+            CstTriviaKind::LineComment | CstTriviaKind::BlockComment
+        )
+    }
+
+    /// Number of new lines inside the trivia.
+    pub fn new_lines(&self) -> usize {
+        self.text.bytes().filter(|c| *c == b'\n').count()
+    }
+}
+
+/// Kinds of nodes that do not have any nested nodes.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CstLeafKind {
+    Int,
+    Float,
+    String,
+    Bool,
+    Null,
+    Identifier,
+    Slot,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CstLeaf {
+    pub kind: CstLeafKind,
+    // Original source text, so string literals keep their escapes.
+    pub text: String,
+    pub span: Span,
+}
+
+/// Opening or closing part of a compound node or a call.
+#[derive(Debug, PartialEq)]
+pub struct CstToken {
+    pub text: String,
+    pub span: Span,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CstCall {
+    pub name: String,
+    // Call prefix, name, any separators after the name and an open paren.
+    pub open: CstToken,
+    pub children: Vec<CstElement>,
+    pub close: CstToken,
+    pub span: Span,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CstCompound {
+    pub open: CstToken,
+    pub children: Vec<CstElement>,
+    pub close: CstToken,
+    pub span: Span,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CstDictPair {
+    pub key: CstLeaf,
+    // Trivia between the key and the value.
+    pub trivia: Vec<CstTrivia>,
+    pub value: Box<CstNode>,
+    pub span: Span,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CstNode {
+    Call(CstCall),
+    List(CstCompound),
+    Dict(CstCompound),
+    DictPair(CstDictPair),
+    Leaf(CstLeaf),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CstElement {
+    Node(CstNode),
+    Trivia(CstTrivia),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Cst {
+    pub children: Vec<CstElement>,
+}
+
+// ==================================================================
+//
+// DATA TYPES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+// TEXT START
+//
+// Restores the original source code from the CST.
+//
+// ==================================================================
+
+impl CstNode {
+    pub fn span(&self) -> &Span {
+        match self {
+            CstNode::Call(call) => &call.span,
+            CstNode::List(compound) | CstNode::Dict(compound) => &compound.span,
+            CstNode::DictPair(pair) => &pair.span,
+            CstNode::Leaf(leaf) => &leaf.span,
+        }
+    }
+
+    pub fn write_text(&self, out: &mut String) {
+        match self {
+            CstNode::Call(call) => {
+                out.push_str(&call.open.text);
+                CstElement::write_all(&call.children, out);
+                out.push_str(&call.close.text);
+            }
+            CstNode::List(compound) | CstNode::Dict(compound) => {
+                out.push_str(&compound.open.text);
+                CstElement::write_all(&compound.children, out);
+                out.push_str(&compound.close.text);
+            }
+            CstNode::DictPair(pair) => {
+                out.push_str(&pair.key.text);
+                for trivia in &pair.trivia {
+                    out.push_str(&trivia.text);
+                }
+                pair.value.write_text(out);
+            }
+            CstNode::Leaf(leaf) => out.push_str(&leaf.text),
+        }
+    }
+}
+
+impl CstElement {
+    fn write_all(elements: &[CstElement], out: &mut String) {
+        for element in elements {
+            match element {
+                CstElement::Node(node) => node.write_text(out),
+                CstElement::Trivia(trivia) => out.push_str(&trivia.text),
+            }
+        }
+    }
+}
+
+impl Cst {
+    /// Lossless source code of the whole tree.
+    pub fn text(&self) -> String {
+        let mut out = String::new();
+        CstElement::write_all(&self.children, &mut out);
+        out
+    }
+}
+
+// ==================================================================
+//
+// TEXT END
+//
+// ==================================================================
+
+// ==================================================================
+//
+// BUILDER START
+//
+// ==================================================================
+
+impl<'a> Prelude<'a> {
+    /// Parses source code into a lossless CST.
+    pub fn parse_cst(&mut self) -> Result<Cst, ParserErr> {
+        let ast = self.parse()?;
+        let mut children = vec![];
+        let mut pos = 0;
+
+        for node in &ast {
+            self.cst_push_trivia(pos, node.span().start, &mut children);
+            children.push(CstElement::Node(self.cst_node(node)));
+            pos = node.span().end;
+        }
+        self.cst_push_trivia(pos, self.source_code.len(), &mut children);
+
+        Ok(Cst { children })
+    }
+
+    fn cst_text(&self, start: usize, end: usize) -> String {
+        String::from_utf8_lossy(&self.source_code[start..end]).into_owned()
+    }
+
+    fn cst_token(&self, start: usize, end: usize) -> CstToken {
+        CstToken {
+            text: self.cst_text(start, end),
+            span: Span { start, end },
+        }
+    }
+
+    fn cst_node(&self, node: &AstNode) -> CstNode {
+        match node {
+            AstNode::Call(call) => CstNode::Call(self.cst_call(call)),
+            AstNode::List(compound) => CstNode::List(self.cst_compound(compound)),
+            AstNode::Dict(compound) => CstNode::Dict(self.cst_compound(compound)),
+            AstNode::DictPair(pair) => CstNode::DictPair(self.cst_dict_pair(pair)),
+            AstNode::Int(prim) => CstNode::Leaf(self.cst_leaf(CstLeafKind::Int, prim)),
+            AstNode::Float(prim) => CstNode::Leaf(self.cst_leaf(CstLeafKind::Float, prim)),
+            AstNode::String(prim) => CstNode::Leaf(self.cst_leaf(CstLeafKind::String, prim)),
+            AstNode::Bool(prim) => CstNode::Leaf(self.cst_leaf(CstLeafKind::Bool, prim)),
+            AstNode::Null(prim) => CstNode::Leaf(self.cst_leaf(CstLeafKind::Null, prim)),
+            AstNode::Identifier(prim) => {
+                CstNode::Leaf(self.cst_leaf(CstLeafKind::Identifier, prim))
+            }
+            AstNode::Slot(prim) => CstNode::Leaf(self.cst_leaf(CstLeafKind::Slot, prim)),
+        }
+    }
+
+    fn cst_leaf(&self, kind: CstLeafKind, prim: &AstPrimitive) -> CstLeaf {
+        CstLeaf {
+            kind,
+            text: self.cst_text(prim.span.start, prim.span.end),
+            span: prim.span.clone(),
+        }
+    }
+
+    /// Children are placed between `open_end` and `close_start`
+    /// together with the trivia around them.
+    fn cst_children(
+        &self,
+        children: &[Box<AstNode>],
+        open_end: usize,
+        close_start: usize,
+    ) -> Vec<CstElement> {
+        let mut elements = vec![];
+        let mut pos = open_end;
+
+        for child in children {
+            self.cst_push_trivia(pos, child.span().start, &mut elements);
+            elements.push(CstElement::Node(self.cst_node(child)));
+            pos = child.span().end;
+        }
+        self.cst_push_trivia(pos, close_start, &mut elements);
+
+        elements
+    }
+
+    fn cst_call(&self, call: &AstCall) -> CstCall {
+        let Span { start, end } = call.span;
+        // Call name can't contain an open paren, so the first one
+        // is always the one that starts the arguments.
+        let open_end = self.source_code[start..end]
+            .iter()
+            .position(|c| *c == CharCode::LEFT_PAREN)
+            .map(|pos| start + pos + 1)
+            .unwrap_or(end);
+        let close_start = end - 1;
+
+        CstCall {
+            name: call.lexeme.clone(),
+            open: self.cst_token(start, open_end),
+            children: self.cst_children(&call.children, open_end, close_start),
+            close: self.cst_token(close_start, end),
+            span: call.span.clone(),
+        }
+    }
+
+    fn cst_compound(&self, compound: &AstCompound) -> CstCompound {
+        let Span { start, end } = compound.span;
+        let (open_end, close_start) = (start + 1, end - 1);
+
+        CstCompound {
+            open: self.cst_token(start, open_end),
+            children: self.cst_children(&compound.children, open_end, close_start),
+            close: self.cst_token(close_start, end),
+            span: compound.span.clone(),
+        }
+    }
+
+    fn cst_dict_pair(&self, pair: &AstKeyValuePair) -> CstDictPair {
+        let mut elements = vec![];
+        self.cst_push_trivia(pair.key_span.end, pair.value.span().start, &mut elements);
+
+        let trivia = elements
+            .into_iter()
+            .filter_map(|element| match element {
+                CstElement::Trivia(trivia) => Some(trivia),
+                CstElement::Node(_) => None,
+            })
+            .collect();
+
+        CstDictPair {
+            key: CstLeaf {
+                kind: CstLeafKind::String,
+                text: self.cst_text(pair.key_span.start, pair.key_span.end),
+                span: pair.key_span.clone(),
+            },
+            trivia,
+            value: Box::new(self.cst_node(&pair.value)),
+            span: pair.span.clone(),
+        }
+    }
+
+    /// Splits the gap between two nodes into trivia. The gap
+    /// can only contain separators, commas and comments since
+    /// everything else would have been parsed into a node.
+    fn cst_push_trivia(&self, start: usize, end: usize, elements: &mut Vec<CstElement>) {
+        let mut pos = start;
+
+        while pos < end {
+            let c = self.source_code[pos];
+            let next = self.source_code.get(pos + 1).copied();
+
+            let (kind, trivia_end) = if c == CharCode::COMMA {
+                (CstTriviaKind::Comma, pos + 1)
+            } else if Self::comment_is_line_start(&c) {
+                (
+                    CstTriviaKind::LineComment,
+                    self.recover_skip_line_comment(pos),
+                )
+            } else if Self::comment_is_block_start(&c, next) {
+                (
+                    CstTriviaKind::BlockComment,
+                    self.recover_skip_block_comment(pos),
+                )
+            } else {
+                let mut trivia_end = pos;
+                while trivia_end < end
+                    && Self::is_separator(&self.source_code[trivia_end])
+                    && self.source_code[trivia_end] != CharCode::COMMA
+                {
+                    trivia_end += 1;
+                }
+                // Never get stuck on unexpected bytes.
+                (CstTriviaKind::Whitespace, trivia_end.max(pos + 1))
+            };
+
+            let trivia_end = trivia_end.min(end);
+
+            elements.push(CstElement::Trivia(CstTrivia {
+                kind,
+                text: self.cst_text(pos, trivia_end),
+                span: Span {
+                    start: pos,
+                    end: trivia_end,
+                },
+            }));
+
+            pos = trivia_end;
+        }
+    }
+}
+
+// ==================================================================
+//
+// BUILDER END
+//
+// ==================================================================
+
+// ==================================================================
+//
+// TESTS START
+//
+// ==================================================================
+
+#[cfg(test)]
+mod tests {
+    use elise_shared::shared_types::Span;
+
+    use crate::{
+        Prelude,
+        cst::{CstElement, CstLeaf, CstLeafKind, CstNode, CstTrivia, CstTriviaKind},
+    };
+
+    #[test]
+    fn should_restore_source_code() {
+        let inputs = vec![
+            "",
+            "  \n",
+            "1",
+            ".add(1, 2)",
+            ".add (1 .mul(2 3))\n",
+            "; comment\n.a() #| block |# .b()",
+            "[1, \"a\\\"b\" null]\n\n\n{ \"a\" ; key\n 1 }",
+            ".schema(\n    .dict(\n        \"name\"  .string()\n        \"age\"   .int()))\n",
+            ".let ([x 1, y 2]\n  #| sum |# .add(x y)) ; tail",
+            "\"😄\" @data",
+            "r\"\\d\" \"\"\"\n  multi\n\"\"\" \"\\u{E9}\"",
+        ];
+        for input in inputs {
+            let cst = Prelude::new(input.as_bytes()).parse_cst().unwrap();
+            assert_eq!(cst.text(), input);
+        }
+    }
+
+    #[test]
+    fn should_keep_trivia_between_nodes() {
+        let cst = Prelude::new("1, ;c\n2".as_bytes()).parse_cst().unwrap();
+        assert_eq!(
+            cst.children,
+            vec![
+                CstElement::Node(CstNode::Leaf(CstLeaf {
+                    kind: CstLeafKind::Int,
+                    text: "1".to_string(),
+                    span: Span { start: 0, end: 1 },
+                })),
+                CstElement::Trivia(CstTrivia {
+                    kind: CstTriviaKind::Comma,
+                    text: ",".to_string(),
+                    span: Span { start: 1, end: 2 },
+                }),
+                CstElement::Trivia(CstTrivia {
+                    kind: CstTriviaKind::Whitespace,
+                    text: " ".to_string(),
+                    span: Span { start: 2, end: 3 },
+                }),
+                CstElement::Trivia(CstTrivia {
+                    kind: CstTriviaKind::LineComment,
+                    text: ";c".to_string(),
+                    span: Span { start: 3, end: 5 },
+                }),
+                CstElement::Trivia(CstTrivia {
+                    kind: CstTriviaKind::Whitespace,
+                    text: "\n".to_string(),
+                    span: Span { start: 5, end: 6 },
+                }),
+                CstElement::Node(CstNode::Leaf(CstLeaf {
+                    kind: CstLeafKind::Int,
+                    text: "2".to_string(),
+                    span: Span { start: 6, end: 7 },
+                })),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_split_call_into_open_children_and_close() {
+        let cst = Prelude::new(".add (1)".as_bytes()).parse_cst().unwrap();
+        let Some(CstElement::Node(CstNode::Call(call))) = cst.children.first() else {
+            panic!("Expected call");
+        };
+        assert_eq!(call.name, "add");
+        assert_eq!(call.open.text, ".add (");
+        assert_eq!(call.children.len(), 1);
+        assert_eq!(call.close.text, ")");
+    }
+
+    #[test]
+    fn should_keep_trivia_inside_dict_pairs() {
+        let cst = Prelude::new("{\"a\" #|c|# 1}".as_bytes())
+            .parse_cst()
+            .unwrap();
+        let Some(CstElement::Node(CstNode::Dict(dict))) = cst.children.first() else {
+            panic!("Expected dict");
+        };
+        let Some(CstElement::Node(CstNode::DictPair(pair))) = dict.children.first() else {
+            panic!("Expected dict pair");
+        };
+        assert_eq!(pair.key.text, "\"a\"");
+        assert_eq!(
+            pair.trivia
+                .iter()
+                .map(|trivia| trivia.kind)
+                .collect::<Vec<_>>(),
+            vec![
+                CstTriviaKind::Whitespace,
+                CstTriviaKind::BlockComment,
+                CstTriviaKind::Whitespace
+            ]
+        );
+    }
+}
+
+// ==================================================================
+//
+// TESTS END
+//
+// ==================================================================
@@ -1,4 +1,5 @@
 pub mod config;
+pub mod cst;
 
 use elise_shared::shared_types::{Keyword, Span};
 use std::str::from_utf8;
@@ -21,11 +22,29 @@ enum DfaNumState {
     Sign,
     Zero,
     Int,
+    // Digit separator inside the integer part.
+    IntSep,
     Frac,
+    FracSep,
     Dot,
     Scient,
-    ScientMinus,
+    ScientSign,
+    ScientSep,
     Expon,
+    // Right after 0x, 0b or 0o prefix. Holds the radix.
+    Radix(u32),
+    RadixInt(u32),
+    RadixSep(u32),
+}
+
+/// Result of parsing in recovering mode.
+#[derive(Debug, PartialEq)]
+pub struct RecoveredParse {
+    // Every node that was parsed. Nodes that contained errors
+    // are either skipped or keep only their valid children.
+    pub ast: Vec<AstNode>,
+    // Every error that was collected, in source order.
+    pub errors: Vec<ParserErr>,
 }
 
 pub struct Prelude<'a> {
@@ -36,6 +55,10 @@ pub struct Prelude<'a> {
     tok_pos: usize,
     // Track open and closed brackets via stack.
     depth_stack: Vec<u8>,
+    // Whether errors must be collected instead of stopping
+    // the parsing at the first one.
+    recovering: bool,
+    errors: Vec<ParserErr>,
 }
 
 // Top-Down, Leftmost, Non-backtracking, Recursive
@@ -46,6 +69,8 @@ impl<'a> Prelude<'a> {
             tok_pos: 0,
             // For tracking open/closed parens.
             depth_stack: vec![],
+            recovering: false,
+            errors: vec![],
         }
     }
 
@@ -55,35 +80,72 @@ impl<'a> Prelude<'a> {
         let mut ast: Vec<AstNode> = vec![];
 
         while let Some(c) = self.peek() {
-            if let Some(node) = self.get_node_from_char(&c)? {
+            if let Some(node) = self.child_consume(&c)? {
                 ast.push(node);
             }
         }
 
         if !self.depth_stack.is_empty() {
+            if self.recovering {
+                self.errors.push(self.fail(ParserErr::UnexpEoFile));
+                return Ok(ast);
+            }
             return Err(self.fail(ParserErr::UnexpEoFile));
         }
 
         Ok(ast)
     }
 
+    /// Same as `parse` but does not stop at the first error. Instead,
+    /// it collects every error and resynchronizes at the next balanced
+    /// closing bracket, separator or top-level call, so the caller gets
+    /// all errors at once together with a partial AST.
+    pub fn parse_recovering(&mut self) -> RecoveredParse {
+        self.recovering = true;
+
+        // Every error is collected in recovering mode,
+        // so parsing itself never fails.
+        let ast = self.parse().unwrap_or_default();
+
+        RecoveredParse {
+            ast,
+            errors: std::mem::take(&mut self.errors),
+        }
+    }
+
     fn fail(&self, variant: fn(ParserErrInfo) -> ParserErr) -> ParserErr {
         variant(ParserErrInfo { pos: self.tok_pos })
     }
 
+    /// Returns the error back in regular mode. In recovering mode the
+    /// error is collected and the caller continues as if nothing happened.
+    fn fail_or_collect(
+        &mut self,
+        variant: fn(ParserErrInfo) -> ParserErr,
+    ) -> Result<(), ParserErr> {
+        let err = self.fail(variant);
+        if self.recovering {
+            self.errors.push(err);
+            return Ok(());
+        }
+        Err(err)
+    }
+
     /// This function was decomposed from parse function in order
     /// to be able to handle AstNode differently in some cases.
     fn get_node_from_char(&mut self, c: &u8) -> Result<Option<AstNode>, ParserErr> {
         if Self::is_separator(c) {
             self.advance();
             Ok(None)
+        } else if self.comment_is_start(c) {
+            self.comment_consume()
         } else if self.call_is_start(c) {
             self.call_consume()
         } else if self.slot_is_start(c) {
             self.slot_consume()
         } else if Self::number_is_start(c) {
             self.number_consume()
-        } else if Self::string_is_start(c) {
+        } else if Self::string_is_start(c) || Self::string_is_raw_start(c, self.peek_next()) {
             self.string_consume()
         } else if self.list_is_start(c) {
             self.list_consume()
@@ -99,6 +161,173 @@ impl<'a> Prelude<'a> {
         }
     }
 
+    /// Consumes a child node of any compound value or a top level node.
+    /// In recovering mode an error inside the child is collected and the
+    /// parser skips the broken part of the source code.
+    fn child_consume(&mut self, c: &u8) -> Result<Option<AstNode>, ParserErr> {
+        let child_start = self.tok_pos;
+        let depth = self.depth_stack.len();
+
+        match self.get_node_from_char(c) {
+            Err(err) if self.recovering => {
+                self.errors.push(err);
+                // Brackets that were opened by the broken child are
+                // tracked by the recovery itself.
+                self.depth_stack.truncate(depth);
+                self.recover(child_start, depth == 0);
+                Ok(None)
+            }
+            result => result,
+        }
+    }
+
+    // ==================================================================
+    // RECOVERY START
+    //
+    // Recovery re-scans the broken child from its start, so it knows how
+    // many brackets are still open at the error position. Then it skips
+    // forward to the first synchronization point after the error:
+    //
+    // 1. The position right after the bracket that closes every
+    //    bracket opened by the broken child;
+    // 2. A separator or a closing bracket of the parent when there
+    //    are no open brackets;
+    // 3. A call that starts at the beginning of a line, but only for
+    //    top level nodes, so one unclosed call does not hide errors
+    //    of all the following ones.
+    //
+    // String literals and comments are skipped while scanning so their
+    // contents are never treated as brackets.
+    // ==================================================================
+
+    fn recover(&mut self, child_start: usize, top_level: bool) {
+        let error_pos = self.tok_pos;
+        let mut pos = child_start;
+        let mut open: usize = 0;
+
+        while let Some(&c) = self.source_code.get(pos) {
+            let synced = pos >= error_pos && pos > child_start;
+
+            if synced && open == 0 {
+                let line_start = self.source_code.get(pos - 1) == Some(&b'\n');
+                if Self::is_separator(&c)
+                    || Self::recover_is_closing(&c)
+                    || (top_level && line_start && c == CharCode::CALL_PREFIX)
+                {
+                    break;
+                }
+            }
+
+            if Self::string_is_start(&c)
+                || Self::string_is_raw_start(&c, self.source_code.get(pos + 1).copied())
+            {
+                pos = self.recover_skip_string(pos);
+                continue;
+            }
+            if Self::comment_is_line_start(&c) {
+                pos = self.recover_skip_line_comment(pos);
+                continue;
+            }
+            if Self::comment_is_block_start(&c, self.source_code.get(pos + 1).copied()) {
+                pos = self.recover_skip_block_comment(pos);
+                continue;
+            }
+
+            if Self::recover_is_opening(&c) {
+                open += 1;
+            } else if Self::recover_is_closing(&c) {
+                if open == 0 {
+                    // Stray closing bracket inside the broken child itself.
+                    pos += 1;
+                    continue;
+                }
+                open -= 1;
+                if open == 0 && pos + 1 > error_pos {
+                    pos += 1;
+                    break;
+                }
+            }
+
+            pos += 1;
+        }
+
+        // Always move forward, otherwise the same
+        // error is going to be reported forever.
+        self.tok_pos = pos.max(child_start + 1);
+    }
+
+    fn recover_is_opening(c: &u8) -> bool {
+        matches!(
+            *c,
+            CharCode::LEFT_PAREN | CharCode::LEFT_SQR_BRACKET | CharCode::LEFT_CUR_BRACKET
+        )
+    }
+
+    fn recover_is_closing(c: &u8) -> bool {
+        matches!(
+            *c,
+            CharCode::RIGHT_PAREN | CharCode::RIGHT_SQR_BRACKET | CharCode::RIGHT_CUR_BRACKET
+        )
+    }
+
+    /// Returns position right after the string literal that starts at `pos`.
+    /// New line terminates a single line string as well since it's forbidden inside it.
+    fn recover_skip_string(&self, pos: usize) -> usize {
+        let raw = self.source_code[pos] == CharCode::RAW_STR_PREFIX;
+        let mut pos = if raw { pos + 1 } else { pos };
+        let multiline = self.string_is_triple_quote(pos);
+        pos += if multiline { 3 } else { 1 };
+
+        while let Some(&c) = self.source_code.get(pos) {
+            if multiline && self.string_is_triple_quote(pos) {
+                return pos + 3;
+            }
+            pos += 1;
+            if !raw && Self::string_is_escape(&c) {
+                pos += 1;
+            } else if !multiline && (Self::string_is_end(&c) || Self::string_is_forbidden_char(&c))
+            {
+                break;
+            }
+        }
+        pos.min(self.source_code.len())
+    }
+
+    fn recover_skip_line_comment(&self, pos: usize) -> usize {
+        let mut pos = pos;
+        while let Some(&c) = self.source_code.get(pos) {
+            if c == b'\n' {
+                break;
+            }
+            pos += 1;
+        }
+        pos
+    }
+
+    fn recover_skip_block_comment(&self, pos: usize) -> usize {
+        let mut pos = pos + 2;
+        let mut depth: usize = 1;
+        while let Some(&c) = self.source_code.get(pos) {
+            let next = self.source_code.get(pos + 1).copied();
+            if Self::comment_is_block_start(&c, next) {
+                depth += 1;
+                pos += 1;
+            } else if Self::comment_is_block_end(&c, next) {
+                depth -= 1;
+                pos += 1;
+                if depth == 0 {
+                    return pos + 1;
+                }
+            }
+            pos += 1;
+        }
+        pos
+    }
+
+    // ==================================================================
+    // RECOVERY END
+    // ==================================================================
+
     // ==================================================================
     // TOKEN UTILITIES START
     // ==================================================================
@@ -110,6 +339,10 @@ impl<'a> Prelude<'a> {
         self.source_code.get(self.tok_pos).copied()
     }
 
+    fn peek_next(&self) -> Option<u8> {
+        self.source_code.get(self.tok_pos + 1).copied()
+    }
+
     fn advance(&mut self) -> Option<u8> {
         let tok = self.peek();
         self.tok_pos += 1;
@@ -124,6 +357,79 @@ impl<'a> Prelude<'a> {
     // TOKEN UTILITIES END
     // ==================================================================
 
+    // ==================================================================
+    // COMMENT START
+    //
+    // Comments are skipped the same way as separators, so they can be
+    // placed anywhere a separator is allowed. Two forms are supported:
+    //
+    // ; Line comment that lasts until the end of the line.
+    //
+    // #| Block comment that lasts until the closing mark.
+    //    Block comments can be nested. |#
+    // ==================================================================
+
+    fn comment_is_line_start(c: &u8) -> bool {
+        *c == CharCode::LINE_COMMENT
+    }
+
+    fn comment_is_block_start(c: &u8, next: Option<u8>) -> bool {
+        *c == CharCode::BLOCK_COMMENT_OUTER && next == Some(CharCode::BLOCK_COMMENT_INNER)
+    }
+
+    fn comment_is_block_end(c: &u8, next: Option<u8>) -> bool {
+        *c == CharCode::BLOCK_COMMENT_INNER && next == Some(CharCode::BLOCK_COMMENT_OUTER)
+    }
+
+    /// Comment start terminates any token before it, so
+    /// `12;note` is a number followed by a comment.
+    fn comment_is_start(&self, c: &u8) -> bool {
+        Self::comment_is_line_start(c) || Self::comment_is_block_start(c, self.peek_next())
+    }
+
+    /// Skips a comment without producing any node. New line character
+    /// that terminates a line comment is left for the separator handling.
+    fn comment_consume(&mut self) -> Result<Option<AstNode>, ParserErr> {
+        if let Some(c) = self.peek()
+            && Self::comment_is_line_start(&c)
+        {
+            while let Some(c) = self.peek() {
+                if c == b'\n' {
+                    break;
+                }
+                self.advance();
+            }
+            return Ok(None);
+        }
+
+        // Skip opening mark.
+        self.advance();
+        self.advance();
+
+        let mut depth: usize = 1;
+
+        while let Some(c) = self.peek() {
+            if Self::comment_is_block_start(&c, self.peek_next()) {
+                depth += 1;
+                self.advance();
+            } else if Self::comment_is_block_end(&c, self.peek_next()) {
+                depth -= 1;
+                self.advance();
+                if depth == 0 {
+                    self.advance();
+                    return Ok(None);
+                }
+            }
+            self.advance();
+        }
+
+        Err(self.fail(ParserErr::UntermComment))
+    }
+
+    // ==================================================================
+    // COMMENT END
+    // ==================================================================
+
     // ==================================================================
     // NUMBER START
     // ==================================================================
@@ -133,13 +439,98 @@ impl<'a> Prelude<'a> {
     }
 
     fn number_is_start(c: &u8) -> bool {
-        Self::number_is_digit(c) || *c == CharCode::MINUS
+        Self::number_is_digit(c) || *c == CharCode::MINUS || *c == CharCode::PLUS
     }
 
+    // Numbers, identifiers and slots can be the last value of a dict,
+    // like in `{"a" 1}` printed by the formatter, so the closing
+    // curly bracket ends them the same way as other brackets.
     fn number_is_end(c: &u8) -> bool {
-        Self::is_separator(c) || *c == CharCode::RIGHT_PAREN || *c == CharCode::RIGHT_SQR_BRACKET
+        Self::is_separator(c)
+            || *c == CharCode::RIGHT_PAREN
+            || *c == CharCode::RIGHT_SQR_BRACKET
+            || *c == CharCode::RIGHT_CUR_BRACKET
+    }
+
+    fn number_radix(c: &u8) -> Option<u32> {
+        match c {
+            b'x' | b'X' => Some(16),
+            b'b' | b'B' => Some(2),
+            b'o' | b'O' => Some(8),
+            _ => None,
+        }
+    }
+
+    fn number_is_radix_digit(c: &u8, radix: u32) -> bool {
+        (*c as char).is_digit(radix)
     }
 
+    /// Builds normalized integer value: optional minus followed by
+    /// decimal digits without separators. Fails at the exact digit
+    /// that makes the value overflow i64.
+    fn number_int_normalize(&self, start: usize, end: usize) -> Result<String, ParserErr> {
+        let token = &self.source_code[start..end];
+        let negative = token.first() == Some(&CharCode::MINUS);
+
+        // Magnitude of i64::MIN is one more than i64::MAX.
+        let limit = if negative {
+            i64::MIN.unsigned_abs()
+        } else {
+            i64::MAX as u64
+        };
+
+        let mut pos = start;
+        while matches!(self.source_code[pos], CharCode::MINUS | CharCode::PLUS) {
+            pos += 1;
+        }
+
+        let mut radix = 10;
+        if end - pos > 2
+            && self.source_code[pos] == b'0'
+            && let Some(r) = Self::number_radix(&self.source_code[pos + 1])
+        {
+            radix = r;
+            pos += 2;
+        }
+
+        let mut magnitude: u64 = 0;
+        for (i, c) in self.source_code[pos..end].iter().enumerate() {
+            if *c == CharCode::UNDERSCORE {
+                continue;
+            }
+            // Validity of every digit is guaranteed by DFA.
+            let digit = (*c as char).to_digit(radix).unwrap_or_default() as u64;
+            magnitude = magnitude
+                .checked_mul(radix as u64)
+                .and_then(|m| m.checked_add(digit))
+                .filter(|m| *m <= limit)
+                .ok_or(ParserErr::InvalNum(ParserErrInfo { pos: pos + i }))?;
+        }
+
+        Ok(if negative {
+            format!("-{magnitude}")
+        } else {
+            magnitude.to_string()
+        })
+    }
+
+    /// Builds normalized float value without plus signs and separators.
+    fn number_float_normalize(&self, start: usize, end: usize) -> Result<String, ParserErr> {
+        let value = from_utf8(&self.source_code[start..end])
+            .map_err(|_| self.fail(ParserErr::InvalNum))?
+            .chars()
+            .filter(|c| *c != CharCode::PLUS as char && *c != CharCode::UNDERSCORE as char)
+            .collect();
+        Ok(value)
+    }
+
+    /// Supported forms:
+    ///     -12, +12, 1_000_000       - decimal integers
+    ///     0xFF, 0b1010, 0o17, -0x1  - hex, binary and octal integers
+    ///     1.5, -1_000.25, 1e+8      - floats
+    ///
+    /// Integer value is normalized into decimal digits, so 0xFF
+    /// becomes 255. Digit separator is allowed between digits only.
     fn number_consume(&mut self) -> Result<Option<AstNode>, ParserErr> {
         let mut state = DfaNumState::Start;
         let tok_start = self.tok_pos;
@@ -148,7 +539,7 @@ impl<'a> Prelude<'a> {
             use DfaNumState::*;
 
             state = match (&state, c) {
-                (Start, CharCode::MINUS) => {
+                (Start, CharCode::MINUS | CharCode::PLUS) => {
                     self.advance();
                     Sign
                 }
@@ -160,18 +551,42 @@ impl<'a> Prelude<'a> {
                     self.advance();
                     Int
                 }
-                (Int, b'0'..=b'9') => {
+                (Zero, c) if Self::number_radix(&c).is_some() => {
+                    self.advance();
+                    Radix(Self::number_radix(&c).unwrap_or(10))
+                }
+                (Radix(radix) | RadixInt(radix) | RadixSep(radix), c)
+                    if Self::number_is_radix_digit(&c, *radix) =>
+                {
+                    let radix = *radix;
+                    self.advance();
+                    RadixInt(radix)
+                }
+                (RadixInt(radix), CharCode::UNDERSCORE) => {
+                    let radix = *radix;
+                    self.advance();
+                    RadixSep(radix)
+                }
+                (Int | IntSep, b'0'..=b'9') => {
                     self.advance();
                     Int
                 }
+                (Int, CharCode::UNDERSCORE) => {
+                    self.advance();
+                    IntSep
+                }
                 (Zero | Int, b'.') => {
                     self.advance();
                     Dot
                 }
-                (Dot | Frac, b'0'..=b'9') => {
+                (Dot | Frac | FracSep, b'0'..=b'9') => {
                     self.advance();
                     Frac
                 }
+                (Frac, CharCode::UNDERSCORE) => {
+                    self.advance();
+                    FracSep
+                }
                 (Zero | Int | Frac, b'e' | b'E') => {
                     self.advance();
                     Expon
@@ -180,15 +595,19 @@ impl<'a> Prelude<'a> {
                     self.advance();
                     Scient
                 }
-                (Expon, CharCode::MINUS) => {
+                (Expon, CharCode::MINUS | CharCode::PLUS) => {
                     self.advance();
-                    ScientMinus
+                    ScientSign
                 }
-                (ScientMinus | Scient, b'0'..=b'9') => {
+                (ScientSign | Scient | ScientSep, b'0'..=b'9') => {
                     self.advance();
                     Scient
                 }
-                (_, c) if Self::number_is_end(&c) => break,
+                (Scient, CharCode::UNDERSCORE) => {
+                    self.advance();
+                    ScientSep
+                }
+                (_, c) if Self::number_is_end(&c) || self.comment_is_start(&c) => break,
                 _ => {
                     return Err(self.fail(ParserErr::InvalNum));
                 }
@@ -196,23 +615,22 @@ impl<'a> Prelude<'a> {
         }
 
         let tok_end = self.tok_pos;
-        let value = from_utf8(&self.source_code[tok_start..tok_end]);
-
-        if value.is_err() {
-            return Err(self.fail(ParserErr::InvalNum));
-        }
-
-        let primitive = AstPrimitive {
-            value: value.unwrap().to_string(),
-            span: Span {
-                start: tok_start,
-                end: tok_end,
-            },
+        let span = Span {
+            start: tok_start,
+            end: tok_end,
         };
 
         match state {
-            DfaNumState::Zero | DfaNumState::Int => Ok(Some(AstNode::Int(primitive))),
-            DfaNumState::Frac | DfaNumState::Scient => Ok(Some(AstNode::Float(primitive))),
+            DfaNumState::Zero | DfaNumState::Int | DfaNumState::RadixInt(_) => {
+                Ok(Some(AstNode::Int(AstPrimitive {
+                    value: self.number_int_normalize(tok_start, tok_end)?,
+                    span,
+                })))
+            }
+            DfaNumState::Frac | DfaNumState::Scient => Ok(Some(AstNode::Float(AstPrimitive {
+                value: self.number_float_normalize(tok_start, tok_end)?,
+                span,
+            }))),
             _ => Err(self.fail(ParserErr::InvalNum)),
         }
     }
@@ -238,7 +656,18 @@ impl<'a> Prelude<'a> {
     }
 
     fn string_is_escape(char: &u8) -> bool {
-        *char == b'\\'
+        *char == CharCode::BACKSLASH
+    }
+
+    fn string_is_raw_start(c: &u8, next: Option<u8>) -> bool {
+        *c == CharCode::RAW_STR_PREFIX && next == Some(CharCode::DOUBLE_QT)
+    }
+
+    /// Multi-line strings are wrapped in triple quotes.
+    fn string_is_triple_quote(&self, pos: usize) -> bool {
+        self.source_code
+            .get(pos..pos + 3)
+            .is_some_and(|quotes| quotes.iter().all(Self::string_is_end))
     }
 
     fn string_decode_escape(char: Option<u8>) -> Option<u8> {
@@ -254,38 +683,155 @@ impl<'a> Prelude<'a> {
         })
     }
 
+    /// Consumes `\u{...}` escape without the leading back slash.
+    /// Must contain from 1 to 6 hex digits of a valid unicode scalar value.
+    fn string_unicode_escape_consume(&mut self, escape_start: usize) -> Result<char, ParserErr> {
+        let err = || ParserErr::InvalUnicodeEsc(ParserErrInfo { pos: escape_start });
+
+        // Skip 'u'.
+        self.advance();
+        if self.peek() != Some(CharCode::LEFT_CUR_BRACKET) {
+            return Err(err());
+        }
+        self.advance();
+
+        let digits_start = self.tok_pos;
+        while let Some(c) = self.peek()
+            && c.is_ascii_hexdigit()
+        {
+            self.advance();
+        }
+        let digits = &self.source_code[digits_start..self.tok_pos];
+
+        if digits.is_empty() || digits.len() > 6 || self.peek() != Some(CharCode::RIGHT_CUR_BRACKET)
+        {
+            return Err(err());
+        }
+        self.advance();
+
+        // Digits are guaranteed to be valid hex.
+        let code =
+            u32::from_str_radix(from_utf8(digits).unwrap_or_default(), 16).map_err(|_| err())?;
+
+        char::from_u32(code).ok_or_else(err)
+    }
+
+    /// Consumes `\xNN` escape without the leading back slash.
+    /// Limited to ASCII since any other single byte is not valid UTF-8.
+    fn string_hex_escape_consume(&mut self, escape_start: usize) -> Result<u8, ParserErr> {
+        let err = || ParserErr::InvalHexEsc(ParserErrInfo { pos: escape_start });
+
+        // Skip 'x'.
+        self.advance();
+        let digits = self
+            .source_code
+            .get(self.tok_pos..self.tok_pos + 2)
+            .filter(|digits| digits.iter().all(u8::is_ascii_hexdigit))
+            .ok_or_else(err)?;
+
+        let code =
+            u8::from_str_radix(from_utf8(digits).unwrap_or_default(), 16).map_err(|_| err())?;
+
+        if !code.is_ascii() {
+            return Err(err());
+        }
+
+        self.advance();
+        self.advance();
+        Ok(code)
+    }
+
+    /// Consumes an escape sequence starting with a back slash
+    /// and pushes decoded bytes to the slice.
+    fn string_escape_consume(&mut self, slice: &mut Vec<u8>) -> Result<(), ParserErr> {
+        let escape_start = self.tok_pos;
+        // Skip escaping back slash.
+        self.advance();
+
+        match self.peek() {
+            Some(b'u') => {
+                let c = self.string_unicode_escape_consume(escape_start)?;
+                slice.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+            }
+            Some(b'x') => {
+                let c = self.string_hex_escape_consume(escape_start)?;
+                slice.push(c);
+            }
+            next => {
+                if let Some(esc) = Self::string_decode_escape(next) {
+                    slice.push(esc);
+                    self.advance();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Consumes a string literal preserving UTF-8 encoding.
     /// Regardless of the contents, Span will always point
     /// to the start and end position of bytes instead of
     /// encoded characters.
+    ///
+    /// Supported forms:
+    ///     "..."       - single line string with escapes
+    ///     """..."""   - multi-line string with escapes
+    ///     r"..."      - single line raw string without escapes
+    ///     r"""..."""  - multi-line raw string without escapes
+    ///
+    /// New line right after the opening triple quotes is skipped,
+    /// so multi-line content can start on its own line.
     fn string_consume(&mut self) -> Result<Option<AstNode>, ParserErr> {
         // Capture start pos before advancing forward in order to
         // construct valid Span.
         let start = self.tok_pos;
         let mut closed = false;
         let mut slice: Vec<u8> = vec![];
+
+        let raw = self.peek() == Some(CharCode::RAW_STR_PREFIX);
+        if raw {
+            self.advance();
+        }
+
+        let multiline = self.string_is_triple_quote(self.tok_pos);
+        let quotes = if multiline { 3 } else { 1 };
+
         // Skip open quotes.
-        self.advance();
+        for _ in 0..quotes {
+            self.advance();
+        }
+
+        if multiline {
+            if self.peek() == Some(b'\r') && self.peek_next() == Some(b'\n') {
+                self.advance();
+            }
+            if self.peek() == Some(b'\n') {
+                self.advance();
+            }
+        }
 
         while let Some(c) = self.peek() {
-            let mut next_byte = c;
+            let is_end = if multiline {
+                self.string_is_triple_quote(self.tok_pos)
+            } else {
+                Self::string_is_end(&c)
+            };
 
-            if Self::string_is_end(&c) {
+            if is_end {
                 closed = true;
-                self.advance();
+                for _ in 0..quotes {
+                    self.advance();
+                }
                 break;
             }
-            if Self::string_is_forbidden_char(&c) {
+            if !multiline && Self::string_is_forbidden_char(&c) {
                 return Err(self.fail(ParserErr::InvalStr));
             }
-            if Self::string_is_escape(&c) {
-                // Skip escaping back slash.
-                self.advance();
-                if let Some(esc) = Self::string_decode_escape(self.peek()) {
-                    next_byte = esc;
-                }
+            if !raw && Self::string_is_escape(&c) {
+                self.string_escape_consume(&mut slice)?;
+                continue;
             }
-            slice.push(next_byte);
+            slice.push(c);
             self.advance();
         }
 
@@ -317,7 +863,10 @@ impl<'a> Prelude<'a> {
     }
 
     fn identifier_is_end(c: &u8) -> bool {
-        Self::is_separator(c) || *c == CharCode::RIGHT_PAREN || *c == CharCode::RIGHT_SQR_BRACKET
+        Self::is_separator(c)
+            || *c == CharCode::RIGHT_PAREN
+            || *c == CharCode::RIGHT_SQR_BRACKET
+            || *c == CharCode::RIGHT_CUR_BRACKET
     }
 
     fn identifier_is_valid(s: &str) -> bool {
@@ -336,7 +885,7 @@ impl<'a> Prelude<'a> {
         let start = self.tok_pos;
 
         while let Some(c) = self.peek() {
-            if Self::identifier_is_end(&c) {
+            if Self::identifier_is_end(&c) || self.comment_is_start(&c) {
                 break;
             } else {
                 self.advance();
@@ -407,7 +956,7 @@ impl<'a> Prelude<'a> {
                     self.advance();
                     break;
                 }
-                if let Some(node) = self.get_node_from_char(&c)? {
+                if let Some(node) = self.child_consume(&c)? {
                     children.push(Box::new(node));
                 }
             } else {
@@ -459,12 +1008,15 @@ impl<'a> Prelude<'a> {
         let mut key: Option<String> = None;
         let mut key_start = 0;
         let mut key_end = 0;
+        // Used only in recovering mode in order to skip a value
+        // of the broken key, so it's not treated as a key itself.
+        let mut skip_value = false;
 
         while let Some(c) = self.peek() {
             if let Ok(eo_dict) = self.dict_check_end(&c) {
                 if eo_dict {
                     if key.is_some() {
-                        return Err(self.fail(ParserErr::InvalDictPair));
+                        self.fail_or_collect(ParserErr::InvalDictPair)?;
                     }
                     self.advance();
                     break;
@@ -473,8 +1025,20 @@ impl<'a> Prelude<'a> {
                 return Err(self.fail(ParserErr::UnexpEoDict));
             }
 
-            if let Some(node) = self.get_node_from_char(&c)? {
-                if key.is_none() {
+            let errors_len = self.errors.len();
+            let node = self.child_consume(&c)?;
+
+            if self.errors.len() > errors_len {
+                // Either key or value is broken, so the whole pair is dropped.
+                skip_value = key.is_none();
+                key = None;
+                continue;
+            }
+
+            if let Some(node) = node {
+                if skip_value {
+                    skip_value = false;
+                } else if key.is_none() {
                     match node {
                         AstNode::String(primitive) => {
                             key_start = primitive.span.start;
@@ -482,7 +1046,8 @@ impl<'a> Prelude<'a> {
                             key = Some(primitive.value);
                         }
                         _ => {
-                            return Err(self.fail(ParserErr::UnexpDictKey));
+                            self.fail_or_collect(ParserErr::UnexpDictKey)?;
+                            skip_value = true;
                         }
                     }
                 } else {
@@ -535,7 +1100,9 @@ impl<'a> Prelude<'a> {
     }
 
     fn call_validate_name(&self, name: &str) -> Result<String, ParserErr> {
-        if !name.is_empty() && Self::identifier_is_valid(name) {
+        let is_thread = name == Keyword::THREAD_FIRST || name == Keyword::THREAD_LAST;
+
+        if is_thread || (!name.is_empty() && Self::identifier_is_valid(name)) {
             Ok(name.to_string())
         } else {
             Err(self.fail(ParserErr::InvalFnName))
@@ -579,7 +1146,7 @@ impl<'a> Prelude<'a> {
                 break;
             }
 
-            if let Some(node) = self.get_node_from_char(&c)? {
+            if let Some(node) = self.child_consume(&c)? {
                 children.push(Box::new(node));
             }
         }
@@ -612,7 +1179,10 @@ impl<'a> Prelude<'a> {
     }
 
     fn slot_is_end(c: &u8) -> bool {
-        Self::is_separator(c) || *c == CharCode::RIGHT_PAREN || *c == CharCode::RIGHT_SQR_BRACKET
+        Self::is_separator(c)
+            || *c == CharCode::RIGHT_PAREN
+            || *c == CharCode::RIGHT_SQR_BRACKET
+            || *c == CharCode::RIGHT_CUR_BRACKET
     }
 
     fn slot_consume(&mut self) -> Result<Option<AstNode>, ParserErr> {
@@ -624,7 +1194,7 @@ impl<'a> Prelude<'a> {
         let slot_name_start = self.tok_pos;
 
         while let Some(c) = self.peek() {
-            if Self::slot_is_end(&c) {
+            if Self::slot_is_end(&c) || self.comment_is_start(&c) {
                 break;
             } else {
                 self.advance();
@@ -667,10 +1237,13 @@ impl<'a> Prelude<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{AstCall, AstCompound, AstNode, AstPrimitive, Prelude, Span};
+    use crate::{AstCall, AstCompound, AstNode, AstPrimitive, Prelude, RecoveredParse, Span};
     use elise_ast::AstKeyValuePair;
     use elise_shared::shared_errors::errors_parser::{ParserErr, ParserErrInfo};
 
+    // Source code, position of the error and its constructor.
+    type ErrCase = (&'static str, usize, fn(ParserErrInfo) -> ParserErr);
+
     // ==================================================================
     // NUMBER TESTS START
     // ==================================================================
@@ -801,6 +1374,121 @@ mod tests {
         }
     }
 
+    #[test]
+    fn number_should_parse_extended_integers() {
+        let numbers = vec![
+            ("+12", "12"),
+            ("+0", "0"),
+            ("1_000_000", "1000000"),
+            ("-1_2_3", "-123"),
+            ("0xFF", "255"),
+            ("0Xff_ff", "65535"),
+            ("-0x1", "-1"),
+            ("+0b1010", "10"),
+            ("0b1111_0000", "240"),
+            ("0o17", "15"),
+            ("0x00", "0"),
+            ("9223372036854775807", "9223372036854775807"),
+            ("-9223372036854775808", "-9223372036854775808"),
+            ("0x7FFF_FFFF_FFFF_FFFF", "9223372036854775807"),
+            ("-0x8000000000000000", "-9223372036854775808"),
+        ];
+        for (number, value) in numbers {
+            let ast = Prelude::new(number.as_bytes()).parse();
+            assert_eq!(
+                ast,
+                Ok(vec![AstNode::Int(AstPrimitive {
+                    value: value.to_string(),
+                    span: Span {
+                        start: 0,
+                        end: number.len()
+                    },
+                })])
+            );
+        }
+    }
+
+    #[test]
+    fn number_should_parse_extended_floats() {
+        let numbers = vec![
+            ("+1.5", "1.5"),
+            ("1_000.000_1", "1000.0001"),
+            ("1e+8", "1e8"),
+            ("-2.5E-1_0", "-2.5E-10"),
+        ];
+        for (number, value) in numbers {
+            let ast = Prelude::new(number.as_bytes()).parse();
+            assert_eq!(
+                ast,
+                Ok(vec![AstNode::Float(AstPrimitive {
+                    value: value.to_string(),
+                    span: Span {
+                        start: 0,
+                        end: number.len()
+                    },
+                })])
+            );
+        }
+    }
+
+    #[test]
+    fn number_should_not_allow_invalid_extended_forms() {
+        let forbidded_tokens = vec![
+            ("0x", 2),
+            ("0xG1", 2),
+            ("0b102", 4),
+            ("0o8", 2),
+            ("0x1.5", 3),
+            ("-0b", 3),
+            ("1__0", 2),
+            ("1_", 2),
+            ("_1", 0),
+            ("1._5", 2),
+            ("1_.5", 2),
+            ("0_1", 1),
+            ("0x_1", 2),
+            ("+-1", 1),
+            ("1e_5", 2),
+            ("1e+", 3),
+        ];
+
+        for (token, pos) in forbidded_tokens {
+            let expected = if token == "_1" {
+                ParserErr::UnexpTok(ParserErrInfo { pos })
+            } else {
+                ParserErr::InvalNum(ParserErrInfo { pos })
+            };
+            assert_eq!(
+                Prelude::new(token.as_bytes()).parse(),
+                Err(expected),
+                "{token}"
+            );
+        }
+    }
+
+    #[test]
+    fn number_should_point_at_overflowing_digit() {
+        let forbidded_tokens = vec![
+            ("9223372036854775808", 18),
+            ("-9223372036854775809", 19),
+            ("+92233720368547758070", 20),
+            ("9_223_372_036_854_775_808", 24),
+            ("0x8000000000000000", 17),
+            (
+                "0b1_0000000000000000000000000000000000000000000000000000000000000000",
+                66,
+            ),
+        ];
+
+        for (token, pos) in forbidded_tokens {
+            assert_eq!(
+                Prelude::new(token.as_bytes()).parse(),
+                Err(ParserErr::InvalNum(ParserErrInfo { pos })),
+                "{token}"
+            );
+        }
+    }
+
     #[test]
     fn number_should_parse_numbers_that_are_separated() {
         let ast = Prelude::new(
@@ -961,6 +1649,145 @@ mod tests {
         }
     }
 
+    #[test]
+    fn string_should_parse_unicode_and_hex_escapes() {
+        let strings = vec![
+            (r#""caf\u{E9}""#, "café", 11),
+            (r#""\u{00e9}\u{1F604}""#, "é😄", 19),
+            (r#""\u{0}""#, "\0", 7),
+            (r#""a\x09b""#, "a\tb", 8),
+            (r#""\x41\x7F""#, "A\x7F", 10),
+        ];
+        for (string, expected, end) in strings {
+            let ast = Prelude::new(string.as_bytes()).parse();
+            assert_eq!(
+                ast,
+                Ok(vec![AstNode::String(AstPrimitive {
+                    value: expected.to_string(),
+                    span: Span { start: 0, end },
+                })])
+            );
+        }
+    }
+
+    #[test]
+    fn string_should_not_allow_invalid_unicode_escapes() {
+        let strings = vec![
+            (r#""\u00E9""#, 1),
+            (r#""ab\u{}""#, 3),
+            (r#""\u{1234567}""#, 1),
+            (r#""\u{D800}""#, 1),
+            (r#""\u{110000}""#, 1),
+            (r#""\u{12g}""#, 1),
+            (r#""\u{12"#, 1),
+        ];
+        for (string, pos) in strings {
+            assert_eq!(
+                Prelude::new(string.as_bytes()).parse(),
+                Err(ParserErr::InvalUnicodeEsc(ParserErrInfo { pos }))
+            );
+        }
+    }
+
+    #[test]
+    fn string_should_not_allow_invalid_hex_escapes() {
+        let strings = vec![
+            (r#""\x4""#, 1),
+            (r#""a\xG1""#, 2),
+            (r#""\x80""#, 1),
+            (r#""\xFF""#, 1),
+        ];
+        for (string, pos) in strings {
+            assert_eq!(
+                Prelude::new(string.as_bytes()).parse(),
+                Err(ParserErr::InvalHexEsc(ParserErrInfo { pos }))
+            );
+        }
+    }
+
+    #[test]
+    fn string_should_parse_raw() {
+        let strings = vec![
+            (r#"r"C:\path\n""#, r"C:\path\n", 12),
+            (r#"r"\u{E9}""#, r"\u{E9}", 9),
+            (r#"r"""#, "", 3),
+        ];
+        for (string, expected, end) in strings {
+            let ast = Prelude::new(string.as_bytes()).parse();
+            assert_eq!(
+                ast,
+                Ok(vec![AstNode::String(AstPrimitive {
+                    value: expected.to_string(),
+                    span: Span { start: 0, end },
+                })])
+            );
+        }
+    }
+
+    #[test]
+    fn string_should_not_confuse_identifier_with_raw_prefix() {
+        let ast = Prelude::new("r \"a\"".as_bytes()).parse();
+        assert_eq!(
+            ast,
+            Ok(vec![
+                AstNode::Identifier(AstPrimitive {
+                    value: "r".to_string(),
+                    span: Span { start: 0, end: 1 },
+                }),
+                AstNode::String(AstPrimitive {
+                    value: "a".to_string(),
+                    span: Span { start: 2, end: 5 },
+                }),
+            ])
+        );
+    }
+
+    #[test]
+    fn string_should_parse_multiline() {
+        let strings = vec![
+            (
+                "\"\"\"\nSELECT *\n  FROM t\n\"\"\"",
+                "SELECT *\n  FROM t\n",
+                25,
+            ),
+            ("\"\"\"a \"quoted\" \\t\"\"\"", "a \"quoted\" \t", 19),
+            ("\"\"\"\r\nline\"\"\"", "line", 12),
+            ("r\"\"\"\n\\n\n\"\"\"", "\\n\n", 11),
+            ("\"\"\"\"\"\"", "", 6),
+        ];
+        for (string, expected, end) in strings {
+            let ast = Prelude::new(string.as_bytes()).parse();
+            assert_eq!(
+                ast,
+                Ok(vec![AstNode::String(AstPrimitive {
+                    value: expected.to_string(),
+                    span: Span { start: 0, end },
+                })])
+            );
+        }
+    }
+
+    #[test]
+    fn string_should_not_allow_unterminated_multiline() {
+        let strings = vec![("\"\"\"abc\"\"", 8), ("r\"\"\"\n", 5)];
+        for (string, pos) in strings {
+            assert_eq!(
+                Prelude::new(string.as_bytes()).parse(),
+                Err(ParserErr::UntermStr(ParserErrInfo { pos }))
+            );
+        }
+    }
+
+    #[test]
+    fn string_should_recover_after_raw_and_multiline() {
+        let res = Prelude::new("[r\"\\ )\" \"\"\"\n]\n\"\"\" 1a] 2".as_bytes()).parse_recovering();
+        assert_eq!(
+            res.errors,
+            vec![ParserErr::InvalNum(ParserErrInfo { pos: 19 })]
+        );
+        assert_eq!(res.ast.len(), 2);
+    }
+
     // ==================================================================
     // STRING TESTS END
     // ==================================================================
@@ -1023,7 +1850,7 @@ mod tests {
 
     #[test]
     fn identifier_should_reject_invalid_names() {
-        let identifiers: Vec<(&str, usize, fn(ParserErrInfo) -> ParserErr)> = vec![
+        let identifiers: Vec<ErrCase> = vec![
             ("1asd", 1, ParserErr::InvalNum),
             ("!asd", 0, ParserErr::UnexpTok),
             ("#asd", 0, ParserErr::UnexpTok),
@@ -1035,7 +1862,7 @@ mod tests {
             ("-asd", 1, ParserErr::InvalNum),
             ("_asd", 0, ParserErr::UnexpTok),
             ("=asd", 0, ParserErr::UnexpTok),
-            ("+asd", 0, ParserErr::UnexpTok),
+            ("+asd", 1, ParserErr::InvalNum),
             ("?asd", 0, ParserErr::UnexpTok),
             ("?asd", 0, ParserErr::UnexpTok),
             (">asd", 0, ParserErr::UnexpTok),
@@ -1265,6 +2092,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn dict_should_allow_closing_bracket_right_after_value() {
+        let inputs = vec![
+            ("{\"a\" 1}", "1"),
+            ("{\"a\" 1.5}", "1.5"),
+            ("{\"a\" b}", "b"),
+            ("{\"a\" @b}", "b"),
+        ];
+        for (input, value) in inputs {
+            let Ok(ast) = Prelude::new(input.as_bytes()).parse() else {
+                panic!("Expected {input} to be parsed");
+            };
+            let AstNode::Dict(dict) = &ast[0] else {
+                panic!("Expected dict");
+            };
+            let AstNode::DictPair(pair) = dict.children[0].as_ref() else {
+                panic!("Expected dict pair");
+            };
+            match pair.value.as_ref() {
+                AstNode::Int(p) | AstNode::Float(p) | AstNode::Identifier(p) | AstNode::Slot(p) => {
+                    assert_eq!(p.value, value)
+                }
+                _ => panic!("Unexpected value"),
+            }
+        }
+    }
+
     #[test]
     fn dict_should_not_allow_invalid_pair() {
         let code = "{ \"a\" 1, \"b\" }";
@@ -1294,7 +2148,7 @@ mod tests {
 
     #[test]
     fn dict_should_not_allow_non_closed() {
-        let inputs: Vec<(&str, usize, fn(ParserErrInfo) -> ParserErr)> = vec![
+        let inputs: Vec<ErrCase> = vec![
             ("{ \"a\" 1 }}", 9, ParserErr::UnexpTok),
             ("{{ \"1\" \"2\" }", 12, ParserErr::UnexpDictKey),
         ];
@@ -1437,6 +2291,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn call_should_allow_threading_names() {
+        for name in ["->", "->>"] {
+            let code = format!(".{name}(a .f())");
+            let result = Prelude::new(code.as_bytes()).parse().unwrap();
+
+            assert!(matches!(&result[0], AstNode::Call(call) if call.lexeme == name));
+        }
+    }
+
+    #[test]
+    fn call_should_reject_other_arrows() {
+        for name in ["-", "->>>", "<-"] {
+            let code = format!(".{name}()");
+
+            assert!(matches!(
+                Prelude::new(code.as_bytes()).parse(),
+                Err(ParserErr::InvalFnName(_))
+            ));
+        }
+    }
+
     #[test]
     fn call_should_not_allow_standalone_parens() {
         let code = "()";
@@ -1523,13 +2399,330 @@ mod tests {
     // SLOT TESTS END
     // ==================================================================
 
+    // ==================================================================
+    // COMMENT TESTS START
+    // ==================================================================
+
+    #[test]
+    fn comment_should_skip_line_comment() {
+        let inputs = vec![
+            "; comment",
+            ";",
+            ";; comment ; with ; semicolons",
+            "; comment\n; another comment\n",
+        ];
+        for input in inputs {
+            assert_eq!(Prelude::new(input.as_bytes()).parse(), Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn comment_should_skip_block_comment() {
+        let inputs = vec![
+            "#||#",
+            "#| comment |#",
+            "#| multi\n line\n comment |#",
+            "#| nested #| block |# comment |#",
+            "#| contains ; and | and # |#",
+        ];
+        for input in inputs {
+            assert_eq!(Prelude::new(input.as_bytes()).parse(), Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn comment_should_not_allow_unterminated_block_comment() {
+        let inputs = vec![("#| comment", 10), ("#| nested #| comment |#", 23)];
+        for (input, pos) in inputs {
+            assert_eq!(
+                Prelude::new(input.as_bytes()).parse(),
+                Err(ParserErr::UntermComment(ParserErrInfo { pos }))
+            );
+        }
+    }
+
+    #[test]
+    fn comment_should_terminate_preceding_token() {
+        let inputs = vec![
+            ("12;c", AstNode::Int as fn(AstPrimitive) -> AstNode, "12", 2),
+            ("1.5#|c|#", AstNode::Float, "1.5", 3),
+            ("name;c", AstNode::Identifier, "name", 4),
+            ("true#|c|#", AstNode::Bool, "true", 4),
+        ];
+        for (input, node, value, end) in inputs {
+            assert_eq!(
+                Prelude::new(input.as_bytes()).parse(),
+                Ok(vec![node(AstPrimitive {
+                    value: value.to_string(),
+                    span: Span { start: 0, end },
+                })])
+            );
+        }
+    }
+
+    #[test]
+    fn comment_should_not_be_part_of_string() {
+        let ast = Prelude::new(r#""; not #| a comment""#.as_bytes()).parse();
+        assert_eq!(
+            ast,
+            Ok(vec![AstNode::String(AstPrimitive {
+                value: "; not #| a comment".to_string(),
+                span: Span { start: 0, end: 20 },
+            })])
+        );
+    }
+
+    #[test]
+    fn comment_should_be_skipped_inside_compounds_and_calls() {
+        let code = ".add(#| lhs |# 1 ; rhs follows\n [2 ; item\n] { ; key\n \"a\" #| value |# 3 })";
+        let ast = Prelude::new(code.as_bytes()).parse();
+        assert_eq!(
+            ast,
+            Ok(vec![AstNode::Call(AstCall {
+                lexeme: "add".to_string(),
+                span: Span { start: 0, end: 73 },
+                children: vec![
+                    Box::new(AstNode::Int(AstPrimitive {
+                        value: "1".to_string(),
+                        span: Span { start: 15, end: 16 },
+                    })),
+                    Box::new(AstNode::List(AstCompound {
+                        span: Span { start: 32, end: 43 },
+                        children: vec![Box::new(AstNode::Int(AstPrimitive {
+                            value: "2".to_string(),
+                            span: Span { start: 33, end: 34 },
+                        }))],
+                    })),
+                    Box::new(AstNode::Dict(AstCompound {
+                        span: Span { start: 44, end: 72 },
+                        children: vec![Box::new(AstNode::DictPair(AstKeyValuePair {
+                            key: "a".to_string(),
+                            key_span: Span { start: 53, end: 56 },
+                            value: Box::new(AstNode::Int(AstPrimitive {
+                                value: "3".to_string(),
+                                span: Span { start: 69, end: 70 },
+                            })),
+                            span: Span { start: 53, end: 70 },
+                        }))],
+                    })),
+                ],
+            })])
+        );
+    }
+
+    #[test]
+    fn comment_should_be_skipped_between_top_level_nodes() {
+        let ast = Prelude::new("; header\n.a() #| between |# .b()".as_bytes()).parse();
+        assert_eq!(
+            ast,
+            Ok(vec![
+                AstNode::Call(AstCall {
+                    lexeme: "a".to_string(),
+                    span: Span { start: 9, end: 13 },
+                    children: vec![],
+                }),
+                AstNode::Call(AstCall {
+                    lexeme: "b".to_string(),
+                    span: Span { start: 28, end: 32 },
+                    children: vec![],
+                }),
+            ])
+        );
+    }
+
+    // ==================================================================
+    // COMMENT TESTS END
+    // ==================================================================
+
+    // ==================================================================
+    // RECOVERY TESTS START
+    // ==================================================================
+
+    #[test]
+    fn recovery_should_return_ast_if_no_errors() {
+        let code = ".a(1) [2]";
+        assert_eq!(
+            Prelude::new(code.as_bytes()).parse_recovering(),
+            RecoveredParse {
+                ast: Prelude::new(code.as_bytes()).parse().unwrap(),
+                errors: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn recovery_should_collect_all_errors_in_source_order() {
+        let code = ".a(1x 2) .b(2)\n.c(\"ok\" 3y)";
+        let result = Prelude::new(code.as_bytes()).parse_recovering();
+        assert_eq!(
+            result.errors,
+            vec![
+                ParserErr::InvalNum(ParserErrInfo { pos: 4 }),
+                ParserErr::InvalNum(ParserErrInfo { pos: 24 }),
+            ]
+        );
+        assert_eq!(
+            result.ast,
+            vec![
+                AstNode::Call(AstCall {
+                    lexeme: "a".to_string(),
+                    span: Span { start: 0, end: 8 },
+                    children: vec![Box::new(AstNode::Int(AstPrimitive {
+                        value: "2".to_string(),
+                        span: Span { start: 6, end: 7 },
+                    }))],
+                }),
+                AstNode::Call(AstCall {
+                    lexeme: "b".to_string(),
+                    span: Span { start: 9, end: 14 },
+                    children: vec![Box::new(AstNode::Int(AstPrimitive {
+                        value: "2".to_string(),
+                        span: Span { start: 12, end: 13 },
+                    }))],
+                }),
+                AstNode::Call(AstCall {
+                    lexeme: "c".to_string(),
+                    span: Span { start: 15, end: 26 },
+                    children: vec![Box::new(AstNode::String(AstPrimitive {
+                        value: "ok".to_string(),
+                        span: Span { start: 18, end: 22 },
+                    }))],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn recovery_should_resync_after_balanced_brackets() {
+        let code = ".a(.1b(1 [2 3]) 4)";
+        let result = Prelude::new(code.as_bytes()).parse_recovering();
+        assert_eq!(
+            result.errors,
+            vec![ParserErr::InvalFnName(ParserErrInfo { pos: 6 })]
+        );
+        assert_eq!(
+            result.ast,
+            vec![AstNode::Call(AstCall {
+                lexeme: "a".to_string(),
+                span: Span { start: 0, end: 18 },
+                children: vec![Box::new(AstNode::Int(AstPrimitive {
+                    value: "4".to_string(),
+                    span: Span { start: 16, end: 17 },
+                }))],
+            })]
+        );
+    }
+
+    #[test]
+    fn recovery_should_drop_broken_dict_pairs() {
+        let code = "{\"a\" 1x \"b\" 2 1 2 \"c\"}";
+        let result = Prelude::new(code.as_bytes()).parse_recovering();
+        assert_eq!(
+            result.errors,
+            vec![
+                ParserErr::InvalNum(ParserErrInfo { pos: 6 }),
+                ParserErr::UnexpDictKey(ParserErrInfo { pos: 15 }),
+                ParserErr::InvalDictPair(ParserErrInfo { pos: 21 }),
+            ]
+        );
+        assert_eq!(
+            result.ast,
+            vec![AstNode::Dict(AstCompound {
+                span: Span { start: 0, end: 22 },
+                children: vec![Box::new(AstNode::DictPair(AstKeyValuePair {
+                    key: "b".to_string(),
+                    key_span: Span { start: 8, end: 11 },
+                    value: Box::new(AstNode::Int(AstPrimitive {
+                        value: "2".to_string(),
+                        span: Span { start: 12, end: 13 },
+                    })),
+                    span: Span { start: 8, end: 13 },
+                }))],
+            })]
+        );
+    }
+
+    #[test]
+    fn recovery_should_resync_at_top_level_call() {
+        let code = "\"abc\n.b(1)";
+        let result = Prelude::new(code.as_bytes()).parse_recovering();
+        assert_eq!(
+            result.errors,
+            vec![ParserErr::InvalStr(ParserErrInfo { pos: 4 })]
+        );
+        assert_eq!(
+            result.ast,
+            vec![AstNode::Call(AstCall {
+                lexeme: "b".to_string(),
+                span: Span { start: 5, end: 10 },
+                children: vec![Box::new(AstNode::Int(AstPrimitive {
+                    value: "1".to_string(),
+                    span: Span { start: 8, end: 9 },
+                }))],
+            })]
+        );
+    }
+
+    #[test]
+    fn recovery_should_skip_stray_closing_brackets() {
+        let code = ".a()) ] .b()";
+        let result = Prelude::new(code.as_bytes()).parse_recovering();
+        assert_eq!(
+            result.errors,
+            vec![
+                ParserErr::UnexpTok(ParserErrInfo { pos: 4 }),
+                ParserErr::UnexpTok(ParserErrInfo { pos: 6 }),
+            ]
+        );
+        assert_eq!(result.ast.len(), 2);
+    }
+
+    #[test]
+    fn recovery_should_report_unexpected_end_of_file() {
+        let code = "[1 2x 3] [4";
+        let result = Prelude::new(code.as_bytes()).parse_recovering();
+        assert_eq!(
+            result.errors,
+            vec![
+                ParserErr::InvalNum(ParserErrInfo { pos: 4 }),
+                ParserErr::UnexpEoFile(ParserErrInfo { pos: 11 }),
+            ]
+        );
+        assert_eq!(result.ast.len(), 2);
+    }
+
+    #[test]
+    fn recovery_should_ignore_brackets_in_strings_and_comments() {
+        let code = ".a(.1b(\")\" #| ) |# ; )\n 1) 2)";
+        let result = Prelude::new(code.as_bytes()).parse_recovering();
+        assert_eq!(
+            result.errors,
+            vec![ParserErr::InvalFnName(ParserErrInfo { pos: 6 })]
+        );
+        assert_eq!(
+            result.ast,
+            vec![AstNode::Call(AstCall {
+                lexeme: "a".to_string(),
+                span: Span { start: 0, end: 29 },
+                children: vec![Box::new(AstNode::Int(AstPrimitive {
+                    value: "2".to_string(),
+                    span: Span { start: 27, end: 28 },
+                }))],
+            })]
+        );
+    }
+
+    // ==================================================================
+    // RECOVERY TESTS END
+    // ==================================================================
+
     // ==================================================================
     // DEPTH TESTS START
     // ==================================================================
 
     #[test]
     fn depth_should_reject_invalid() {
-        let depth_cases: Vec<(&str, usize, fn(ParserErrInfo) -> ParserErr)> = vec![
+        let depth_cases: Vec<ErrCase> = vec![
             (".a())", 4, ParserErr::UnexpTok),
             (".a(()", 3, ParserErr::UnexpTok),
             (".a().a()))", 8, ParserErr::UnexpTok),
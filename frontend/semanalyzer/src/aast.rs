@@ -7,15 +7,252 @@
 //!
 //! The AAST is a compile-time only structure, discarded after bytecode emission.
 
+use elise_data::resolution_path::ResolutionPath;
 use elise_shared::{shared_node_names::NodeName, shared_types::Span};
 
-use crate::symbol_table::SymbolId;
+use crate::{
+    config::{FnArith, FnCmp, FnColl, FnConv, FnDate, FnStr},
+    data_types::{LangPrimitiveType, LangType},
+    symbol_table::SymbolId,
+};
+
+/// Built-in operations that are emitted as dedicated
+/// instructions instead of function calls.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Neg,
+    Eq,
+    Neq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    Concat,
+    Length,
+    Substring,
+    Split,
+    Join,
+    Trim,
+    Upper,
+    Lower,
+    StartsWith,
+    EndsWith,
+    Replace,
+    Matches,
+    Extract,
+    ExtractAll,
+    DateAdd,
+    DateDiff,
+    DateTrunc,
+    DateFormat,
+}
+
+impl Op {
+    pub fn from_lexeme(lexeme: &str) -> Option<Self> {
+        match lexeme {
+            FnArith::ADD => Some(Op::Add),
+            FnArith::SUB => Some(Op::Sub),
+            FnArith::MUL => Some(Op::Mul),
+            FnArith::DIV => Some(Op::Div),
+            FnArith::MOD => Some(Op::Mod),
+            FnArith::NEG => Some(Op::Neg),
+            FnCmp::EQ => Some(Op::Eq),
+            FnCmp::NEQ => Some(Op::Neq),
+            FnCmp::LT => Some(Op::Lt),
+            FnCmp::LTE => Some(Op::Lte),
+            FnCmp::GT => Some(Op::Gt),
+            FnCmp::GTE => Some(Op::Gte),
+            FnStr::CONCAT => Some(Op::Concat),
+            FnStr::LENGTH => Some(Op::Length),
+            FnStr::SUBSTRING => Some(Op::Substring),
+            FnStr::SPLIT => Some(Op::Split),
+            FnStr::JOIN => Some(Op::Join),
+            FnStr::TRIM => Some(Op::Trim),
+            FnStr::UPPER => Some(Op::Upper),
+            FnStr::LOWER => Some(Op::Lower),
+            FnStr::STARTS_WITH => Some(Op::StartsWith),
+            FnStr::ENDS_WITH => Some(Op::EndsWith),
+            FnStr::REPLACE => Some(Op::Replace),
+            FnStr::MATCHES => Some(Op::Matches),
+            FnStr::EXTRACT => Some(Op::Extract),
+            FnStr::EXTRACT_ALL => Some(Op::ExtractAll),
+            FnDate::ADD => Some(Op::DateAdd),
+            FnDate::DIFF => Some(Op::DateDiff),
+            FnDate::TRUNC => Some(Op::DateTrunc),
+            FnDate::FORMAT => Some(Op::DateFormat),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Op::Add => FnArith::ADD,
+            Op::Sub => FnArith::SUB,
+            Op::Mul => FnArith::MUL,
+            Op::Div => FnArith::DIV,
+            Op::Mod => FnArith::MOD,
+            Op::Neg => FnArith::NEG,
+            Op::Eq => FnCmp::EQ,
+            Op::Neq => FnCmp::NEQ,
+            Op::Lt => FnCmp::LT,
+            Op::Lte => FnCmp::LTE,
+            Op::Gt => FnCmp::GT,
+            Op::Gte => FnCmp::GTE,
+            Op::Concat => FnStr::CONCAT,
+            Op::Length => FnStr::LENGTH,
+            Op::Substring => FnStr::SUBSTRING,
+            Op::Split => FnStr::SPLIT,
+            Op::Join => FnStr::JOIN,
+            Op::Trim => FnStr::TRIM,
+            Op::Upper => FnStr::UPPER,
+            Op::Lower => FnStr::LOWER,
+            Op::StartsWith => FnStr::STARTS_WITH,
+            Op::EndsWith => FnStr::ENDS_WITH,
+            Op::Replace => FnStr::REPLACE,
+            Op::Matches => FnStr::MATCHES,
+            Op::Extract => FnStr::EXTRACT,
+            Op::ExtractAll => FnStr::EXTRACT_ALL,
+            Op::DateAdd => FnDate::ADD,
+            Op::DateDiff => FnDate::DIFF,
+            Op::DateTrunc => FnDate::TRUNC,
+            Op::DateFormat => FnDate::FORMAT,
+        }
+    }
+}
+
+/// Built-in functions over Lists that are emitted as loops
+/// instead of function calls.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CollectionOp {
+    Map,
+    Filter,
+    Reduce,
+    SortBy,
+    Take,
+    Drop,
+    Count,
+    FlatMap,
+    GroupBy,
+    Sum,
+    Avg,
+    Min,
+    Max,
+    CountDistinct,
+}
+
+impl CollectionOp {
+    pub fn from_lexeme(lexeme: &str) -> Option<Self> {
+        match lexeme {
+            FnColl::MAP => Some(CollectionOp::Map),
+            FnColl::FILTER => Some(CollectionOp::Filter),
+            FnColl::REDUCE => Some(CollectionOp::Reduce),
+            FnColl::SORT_BY => Some(CollectionOp::SortBy),
+            FnColl::TAKE => Some(CollectionOp::Take),
+            FnColl::DROP => Some(CollectionOp::Drop),
+            FnColl::COUNT => Some(CollectionOp::Count),
+            FnColl::FLAT_MAP => Some(CollectionOp::FlatMap),
+            FnColl::GROUP_BY => Some(CollectionOp::GroupBy),
+            FnColl::SUM => Some(CollectionOp::Sum),
+            FnColl::AVG => Some(CollectionOp::Avg),
+            FnColl::MIN => Some(CollectionOp::Min),
+            FnColl::MAX => Some(CollectionOp::Max),
+            FnColl::COUNT_DISTINCT => Some(CollectionOp::CountDistinct),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CollectionOp::Map => FnColl::MAP,
+            CollectionOp::Filter => FnColl::FILTER,
+            CollectionOp::Reduce => FnColl::REDUCE,
+            CollectionOp::SortBy => FnColl::SORT_BY,
+            CollectionOp::Take => FnColl::TAKE,
+            CollectionOp::Drop => FnColl::DROP,
+            CollectionOp::Count => FnColl::COUNT,
+            CollectionOp::FlatMap => FnColl::FLAT_MAP,
+            CollectionOp::GroupBy => FnColl::GROUP_BY,
+            CollectionOp::Sum => FnColl::SUM,
+            CollectionOp::Avg => FnColl::AVG,
+            CollectionOp::Min => FnColl::MIN,
+            CollectionOp::Max => FnColl::MAX,
+            CollectionOp::CountDistinct => FnColl::COUNT_DISTINCT,
+        }
+    }
+}
+
+/// Primitive type a value is converted to.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Conversion {
+    Int,
+    Float,
+    Str,
+    Bool,
+}
+
+/// What a conversion results in if the value can't be converted.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ConversionMode {
+    // Fails the program.
+    Strict,
+    // Results in the fallback value.
+    Fallback,
+    // Results in Null.
+    Null,
+}
+
+impl Conversion {
+    /// Splits the lexeme of a conversion like `to-int-or-null`
+    /// into the conversion and its mode.
+    pub fn from_lexeme(lexeme: &str) -> Option<(Self, ConversionMode)> {
+        let (name, mode) = if let Some(name) = lexeme.strip_suffix(FnConv::NULL_SUFFIX) {
+            (name, ConversionMode::Null)
+        } else if let Some(name) = lexeme.strip_suffix(FnConv::FALLBACK_SUFFIX) {
+            (name, ConversionMode::Fallback)
+        } else {
+            (lexeme, ConversionMode::Strict)
+        };
+
+        let conversion = match name {
+            FnConv::TO_INT => Conversion::Int,
+            FnConv::TO_FLOAT => Conversion::Float,
+            FnConv::TO_STR => Conversion::Str,
+            FnConv::TO_BOOL => Conversion::Bool,
+            _ => return None,
+        };
+
+        Some((conversion, mode))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Conversion::Int => FnConv::TO_INT,
+            Conversion::Float => FnConv::TO_FLOAT,
+            Conversion::Str => FnConv::TO_STR,
+            Conversion::Bool => FnConv::TO_BOOL,
+        }
+    }
+
+    pub fn target(&self) -> LangType {
+        LangType::Primitive(match self {
+            Conversion::Int => LangPrimitiveType::Int,
+            Conversion::Float => LangPrimitiveType::Float,
+            Conversion::Str => LangPrimitiveType::String,
+            Conversion::Bool => LangPrimitiveType::Bool,
+        })
+    }
+}
 
 /// AAstNode must store primitive values as String type instead of
 /// parsed values since emitter only needs to know the type in order
 /// to emit a correct opcode. Parsing to correct value must be done
 /// only during VM bytecode execution.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum AAstNode {
     CallDefine {
         symbol_id: SymbolId,
@@ -27,6 +264,117 @@ pub enum AAstNode {
         body: Vec<Box<AAstNode>>,
         span: Span,
     },
+    CallFn {
+        // Set only for the named form that binds the
+        // function to an identifier in the enclosing scope.
+        symbol_id: Option<SymbolId>,
+        params: Vec<SymbolId>,
+        // Outer symbols referenced from the body that
+        // must be bundled with the function value.
+        captures: Vec<SymbolId>,
+        body: Vec<Box<AAstNode>>,
+        span: Span,
+    },
+    // Built-in operation like arithmetic or comparison.
+    CallOp {
+        op: Op,
+        args: Vec<Box<AAstNode>>,
+        ty: LangType,
+        span: Span,
+    },
+    // Built-in function over a List.
+    CallCollection {
+        op: CollectionOp,
+        source: Box<AAstNode>,
+        // The rest of arguments: a function, a number of
+        // items or an initial value.
+        args: Vec<Box<AAstNode>>,
+        ty: LangType,
+        span: Span,
+    },
+    // Groups rows of a List by key fields and turns every
+    // group into a Dict of the keys and aggregated fields.
+    CallAggregate {
+        source: Box<AAstNode>,
+        keys: Vec<String>,
+        // Field of the result, aggregation and the field of
+        // a row it reads, which `.count` doesn't have.
+        aggregations: Vec<(String, CollectionOp, Option<String>)>,
+        ty: LangType,
+        span: Span,
+    },
+    // Conversion of a primitive value to another primitive type.
+    CallConvert {
+        conversion: Conversion,
+        mode: ConversionMode,
+        value: Box<AAstNode>,
+        // Present only in the Fallback mode.
+        fallback: Option<Box<AAstNode>>,
+        ty: LangType,
+        // Whether the conversion can result in Null.
+        nullable: bool,
+        span: Span,
+    },
+    // Value of a union that is asserted to be of one
+    // of its alternatives.
+    CallNarrow {
+        value: Box<AAstNode>,
+        // Name of the alternative, like "Int" or "Dict".
+        alternative: String,
+        ty: LangType,
+        span: Span,
+    },
+    // Only one of the branches is evaluated at runtime.
+    CallIf {
+        cond: Box<AAstNode>,
+        then_branch: Box<AAstNode>,
+        else_branch: Box<AAstNode>,
+        ty: LangType,
+        span: Span,
+    },
+    CallCond {
+        // Pairs of a condition and a value, tested in order.
+        arms: Vec<(Box<AAstNode>, Box<AAstNode>)>,
+        // Evaluates to Null if not provided.
+        default: Option<Box<AAstNode>>,
+        ty: LangType,
+        span: Span,
+    },
+    CallWhen {
+        cond: Box<AAstNode>,
+        body: Vec<Box<AAstNode>>,
+        ty: LangType,
+        span: Span,
+    },
+    // Access to a value of a compound or a function result.
+    CallGet {
+        target: Box<AAstNode>,
+        keys: Vec<Box<AAstNode>>,
+        ty: LangType,
+        span: Span,
+    },
+    // Access to the external data provided through a slot.
+    // Type information is derived from the data schema.
+    CallGetSlot {
+        slot: String,
+        // Schema path of the accessed value where any index
+        // is represented as an abstract one.
+        path: ResolutionPath,
+        // Keys are kept since indexes can be evaluated
+        // only at runtime.
+        keys: Vec<Box<AAstNode>>,
+        ty: LangType,
+        nullable: bool,
+        optional: bool,
+        span: Span,
+    },
+    // Call of a function bound to an identifier.
+    Call {
+        symbol_id: SymbolId,
+        depth: usize,
+        args: Vec<Box<AAstNode>>,
+        span: Span,
+    },
     SymbolRef {
         symbol_id: SymbolId,
         span: Span,
@@ -51,6 +399,14 @@ pub enum AAstNode {
     Null {
         span: Span,
     },
+    List {
+        items: Vec<Box<AAstNode>>,
+        span: Span,
+    },
+    Dict {
+        pairs: Vec<(String, Box<AAstNode>)>,
+        span: Span,
+    },
 }
 
 // String representations for AAstNode's in order to be able to
@@ -60,12 +416,26 @@ impl AAstNode {
         match self {
             AAstNode::CallDefine { span, .. }
             | AAstNode::CallLet { span, .. }
+            | AAstNode::CallFn { span, .. }
+            | AAstNode::CallOp { span, .. }
+            | AAstNode::CallCollection { span, .. }
+            | AAstNode::CallAggregate { span, .. }
+            | AAstNode::CallConvert { span, .. }
+            | AAstNode::CallNarrow { span, .. }
+            | AAstNode::CallIf { span, .. }
+            | AAstNode::CallCond { span, .. }
+            | AAstNode::CallWhen { span, .. }
+            | AAstNode::CallGet { span, .. }
+            | AAstNode::CallGetSlot { span, .. }
+            | AAstNode::Call { span, .. }
             | AAstNode::SymbolRef { span, .. }
             | AAstNode::Int { span, .. }
             | AAstNode::Float { span, .. }
             | AAstNode::String { span, .. }
             | AAstNode::Bool { span, .. }
-            | AAstNode::Null { span, .. } => span,
+            | AAstNode::Null { span, .. }
+            | AAstNode::List { span, .. }
+            | AAstNode::Dict { span, .. } => span,
         }
     }
 
@@ -73,12 +443,26 @@ impl AAstNode {
         match self {
             AAstNode::CallDefine { .. } => NodeName::CALL_DEFINE,
             AAstNode::CallLet { .. } => NodeName::CALL_LET,
+            AAstNode::CallFn { .. } => NodeName::CALL_FN,
+            AAstNode::CallOp { .. } => NodeName::CALL_OP,
+            AAstNode::CallCollection { .. } => NodeName::CALL_COLLECTION,
+            AAstNode::CallAggregate { .. } => NodeName::CALL_AGGREGATE,
+            AAstNode::CallConvert { .. } => NodeName::CALL_CONVERT,
+            AAstNode::CallNarrow { .. } => NodeName::CALL_NARROW,
+            AAstNode::CallIf { .. } => NodeName::CALL_IF,
+            AAstNode::CallCond { .. } => NodeName::CALL_COND,
+            AAstNode::CallWhen { .. } => NodeName::CALL_WHEN,
+            AAstNode::CallGet { .. } => NodeName::CALL_GET,
+            AAstNode::CallGetSlot { .. } => NodeName::CALL_GET_SLOT,
+            AAstNode::Call { .. } => NodeName::CALL,
             AAstNode::SymbolRef { .. } => NodeName::SYMBOL,
             AAstNode::Int { .. } => NodeName::INT,
             AAstNode::Float { .. } => NodeName::FLOAT,
             AAstNode::String { .. } => NodeName::STRING,
             AAstNode::Bool { .. } => NodeName::BOOL,
             AAstNode::Null { .. } => NodeName::NULL,
+            AAstNode::List { .. } => NodeName::LIST,
+            AAstNode::Dict { .. } => NodeName::DICT,
         }
     }
 }
@@ -1,11 +1,187 @@
+use elise_shared::shared_types::Keyword;
+
 pub struct FnDefine;
 impl FnDefine {
     pub const LEXEME: &'static str = "define";
     pub const ARGS_LEN: usize = 2;
 }
 
+/// Threading forms rewritten into nested calls.
+pub struct FnThread;
+impl FnThread {
+    pub const FIRST: &'static str = Keyword::THREAD_FIRST;
+    pub const LAST: &'static str = Keyword::THREAD_LAST;
+    // The threaded expression. Calls are optional.
+    pub const MIN_ARGS_LEN: usize = 1;
+}
+
 pub struct FnLet;
 impl FnLet {
     pub const LEXEME: &'static str = "let";
     pub const MIN_ARGS_LEN: usize = 2;
 }
+
+pub struct FnIf;
+impl FnIf {
+    pub const LEXEME: &'static str = "if";
+    pub const ARGS_LEN: usize = 3;
+}
+
+pub struct FnCond;
+impl FnCond {
+    pub const LEXEME: &'static str = "cond";
+    pub const MIN_ARGS_LEN: usize = 2;
+}
+
+pub struct FnWhen;
+impl FnWhen {
+    pub const LEXEME: &'static str = "when";
+    pub const MIN_ARGS_LEN: usize = 2;
+}
+
+/// Arithmetic functions. All of them are variadic except `.neg`.
+pub struct FnArith;
+impl FnArith {
+    pub const ADD: &'static str = "add";
+    pub const SUB: &'static str = "sub";
+    pub const MUL: &'static str = "mul";
+    pub const DIV: &'static str = "div";
+    pub const MOD: &'static str = "mod";
+    pub const NEG: &'static str = "neg";
+    pub const MIN_ARGS_LEN: usize = 2;
+    pub const NEG_ARGS_LEN: usize = 1;
+}
+
+/// String functions. Lengths and positions are counted
+/// in characters, not bytes.
+pub struct FnStr;
+impl FnStr {
+    pub const CONCAT: &'static str = "concat";
+    pub const LENGTH: &'static str = "length";
+    pub const SUBSTRING: &'static str = "substring";
+    pub const SPLIT: &'static str = "split";
+    pub const JOIN: &'static str = "join";
+    pub const TRIM: &'static str = "trim";
+    pub const UPPER: &'static str = "upper";
+    pub const LOWER: &'static str = "lower";
+    pub const STARTS_WITH: &'static str = "starts-with";
+    pub const ENDS_WITH: &'static str = "ends-with";
+    pub const REPLACE: &'static str = "replace";
+    pub const MATCHES: &'static str = "matches";
+    pub const EXTRACT: &'static str = "extract";
+    pub const EXTRACT_ALL: &'static str = "extract-all";
+    pub const CONCAT_MIN_ARGS_LEN: usize = 2;
+    // String only.
+    pub const UNARY_ARGS_LEN: usize = 1;
+    // String and another String, a pattern or a separator.
+    pub const BINARY_ARGS_LEN: usize = 2;
+    // String, start and an optional end.
+    pub const SUBSTRING_MIN_ARGS_LEN: usize = 2;
+    pub const SUBSTRING_MAX_ARGS_LEN: usize = 3;
+    // String, the replaced String and the replacement.
+    pub const REPLACE_ARGS_LEN: usize = 3;
+}
+
+/// Functions of dates and times. Durations are counted in seconds.
+pub struct FnDate;
+impl FnDate {
+    pub const ADD: &'static str = "date-add";
+    pub const DIFF: &'static str = "date-diff";
+    pub const TRUNC: &'static str = "date-trunc";
+    pub const FORMAT: &'static str = "date-format";
+    // Value and a Duration, another value, a unit or a format.
+    pub const ARGS_LEN: usize = 2;
+}
+
+/// Conversions between primitive types. Every conversion has a
+/// variant with a fallback for values that can't be converted and
+/// a variant that results in Null for them.
+pub struct FnConv;
+impl FnConv {
+    pub const TO_INT: &'static str = "to-int";
+    pub const TO_FLOAT: &'static str = "to-float";
+    pub const TO_STR: &'static str = "to-str";
+    pub const TO_BOOL: &'static str = "to-bool";
+    pub const FALLBACK_SUFFIX: &'static str = "-or";
+    pub const NULL_SUFFIX: &'static str = "-or-null";
+    pub const ARGS_LEN: usize = 1;
+    // Value and the fallback.
+    pub const FALLBACK_ARGS_LEN: usize = 2;
+}
+
+pub struct FnNarrow;
+impl FnNarrow {
+    pub const LEXEME: &'static str = "narrow";
+    // Value and the name of its type.
+    pub const ARGS_LEN: usize = 2;
+}
+
+/// Comparison functions. All of them are binary.
+pub struct FnCmp;
+impl FnCmp {
+    pub const EQ: &'static str = "eq";
+    pub const NEQ: &'static str = "neq";
+    pub const LT: &'static str = "lt";
+    pub const LTE: &'static str = "lte";
+    pub const GT: &'static str = "gt";
+    pub const GTE: &'static str = "gte";
+    pub const ARGS_LEN: usize = 2;
+}
+
+/// Collection functions. The first argument is always a List.
+pub struct FnColl;
+impl FnColl {
+    pub const MAP: &'static str = "map";
+    pub const FILTER: &'static str = "filter";
+    pub const REDUCE: &'static str = "reduce";
+    pub const SORT_BY: &'static str = "sort-by";
+    pub const TAKE: &'static str = "take";
+    pub const DROP: &'static str = "drop";
+    pub const COUNT: &'static str = "count";
+    pub const FLAT_MAP: &'static str = "flat-map";
+    pub const GROUP_BY: &'static str = "group-by";
+    pub const SUM: &'static str = "sum";
+    pub const AVG: &'static str = "avg";
+    pub const MIN: &'static str = "min";
+    pub const MAX: &'static str = "max";
+    pub const COUNT_DISTINCT: &'static str = "count-distinct";
+    // List and a function or a number of items.
+    pub const ARGS_LEN: usize = 2;
+    // List, a function and an initial value.
+    pub const REDUCE_ARGS_LEN: usize = 3;
+    // List only.
+    pub const LIST_ARGS_LEN: usize = 1;
+}
+
+pub struct FnAggregate;
+impl FnAggregate {
+    pub const LEXEME: &'static str = "aggregate";
+    // List, List of key fields and Dict of aggregations.
+    pub const ARGS_LEN: usize = 3;
+    // Field of a row for every aggregation except `.count`.
+    pub const FIELD_ARGS_LEN: usize = 1;
+}
+
+pub struct FnGet;
+impl FnGet {
+    pub const LEXEME: &'static str = "get";
+    // Source of the value and at least one key.
+    pub const MIN_ARGS_LEN: usize = 2;
+    // Keys of a slot are optional, since the slot is a value itself.
+    pub const SLOT_MIN_ARGS_LEN: usize = 1;
+}
+
+/// Names of the slots that provide external data.
+pub struct SlotName;
+impl SlotName {
+    pub const DATA: &'static str = "data";
+}
+
+pub struct FnFn;
+impl FnFn {
+    pub const LEXEME: &'static str = "fn";
+    // Params list and at least one body expression.
+    pub const MIN_ARGS_LEN: usize = 2;
+    // Name, params list and at least one body expression.
+    pub const MIN_NAMED_ARGS_LEN: usize = 3;
+}
@@ -0,0 +1,423 @@
+//! # Constant Folder
+//!
+//! Evaluates parts of the AAST whose operands are all known at compile
+//! time and replaces them with literals. Runs after the annotation, so
+//! every semantic guarantee (arity, argument types) is already
+//! established and only values are left to check.
+//!
+//! Folds:
+//!   - Arithmetic, comparisons and string concatenation of literals
+//!   - Conditionals with a literal condition into the taken branch
+//!   - References to `.define` constants into their literal values
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use elise_shared::{shared_errors::errors_semanalyzer::SemanalyzerErr, shared_types::Span};
+
+use crate::{
+    aast::{AAstNode, Op},
+    symbol_table::SymbolId,
+};
+
+/// Value of a literal node that can be computed with.
+#[derive(Debug, Clone)]
+enum Literal {
+    Int(i64),
+    Float(f64),
+    String(String),
+    Bool(bool),
+    Null,
+}
+
+impl Literal {
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Literal::Int(value) => Some(*value as f64),
+            Literal::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ConstFolder {
+    // Values of `.define` constants that are inlined at
+    // every reference.
+    constants: HashMap<SymbolId, Literal>,
+}
+
+impl ConstFolder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Folds nodes in place.
+    pub fn fold(&mut self, aast: &mut [AAstNode]) -> Result<(), SemanalyzerErr> {
+        for aast_node in aast.iter_mut() {
+            self.fold_node(aast_node)?;
+        }
+
+        Ok(())
+    }
+
+    fn fold_all(&mut self, aast_nodes: &mut [Box<AAstNode>]) -> Result<(), SemanalyzerErr> {
+        for aast_node in aast_nodes.iter_mut() {
+            self.fold_node(aast_node)?;
+        }
+
+        Ok(())
+    }
+
+    fn fold_node(&mut self, aast_node: &mut AAstNode) -> Result<(), SemanalyzerErr> {
+        let folded = match aast_node {
+            AAstNode::CallDefine {
+                symbol_id, value, ..
+            } => {
+                self.fold_node(value)?;
+
+                if let Some(literal) = Self::literal_of(value) {
+                    self.constants.insert(*symbol_id, literal);
+                }
+
+                None
+            }
+            AAstNode::CallLet { bindings, body, .. } => {
+                for (_, init) in bindings.iter_mut() {
+                    self.fold_node(init)?;
+                }
+                self.fold_all(body)?;
+                None
+            }
+            AAstNode::CallFn { body, .. } => {
+                self.fold_all(body)?;
+                None
+            }
+            AAstNode::CallOp { op, args, span, .. } => {
+                self.fold_all(args)?;
+                Self::fold_op(*op, args, span)?
+            }
+            AAstNode::CallIf {
+                cond,
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                self.fold_node(cond)?;
+
+                // Branch that is never taken is dropped without
+                // folding, so it can not fail the compilation.
+                match Self::literal_of(cond) {
+                    Some(Literal::Bool(true)) => Some(self.take_folded(then_branch)?),
+                    Some(Literal::Bool(false)) => Some(self.take_folded(else_branch)?),
+                    _ => {
+                        self.fold_node(then_branch)?;
+                        self.fold_node(else_branch)?;
+                        None
+                    }
+                }
+            }
+            AAstNode::CallCond {
+                arms,
+                default,
+                span,
+                ..
+            } => self.fold_cond(arms, default, span)?,
+            AAstNode::CallWhen {
+                cond, body, span, ..
+            } => {
+                self.fold_node(cond)?;
+
+                match Self::literal_of(cond) {
+                    Some(Literal::Bool(false)) => Some(AAstNode::Null { span: span.clone() }),
+                    Some(Literal::Bool(true)) if body.len() == 1 => {
+                        Some(self.take_folded(&mut body[0])?)
+                    }
+                    _ => {
+                        self.fold_all(body)?;
+                        None
+                    }
+                }
+            }
+            AAstNode::CallCollection { source, args, .. } => {
+                self.fold_node(source)?;
+                self.fold_all(args)?;
+                None
+            }
+            AAstNode::CallConvert {
+                value, fallback, ..
+            } => {
+                self.fold_node(value)?;
+                if let Some(fallback) = fallback {
+                    self.fold_node(fallback)?;
+                }
+                None
+            }
+            AAstNode::CallNarrow { value, .. } => {
+                self.fold_node(value)?;
+                None
+            }
+            AAstNode::CallAggregate { source, .. } => {
+                self.fold_node(source)?;
+                None
+            }
+            AAstNode::CallGet { target, keys, .. } => {
+                self.fold_node(target)?;
+                self.fold_all(keys)?;
+                None
+            }
+            AAstNode::CallGetSlot { keys, .. } => {
+                self.fold_all(keys)?;
+                None
+            }
+            AAstNode::Call { args, .. } => {
+                self.fold_all(args)?;
+                None
+            }
+            AAstNode::List { items, .. } => {
+                self.fold_all(items)?;
+                None
+            }
+            AAstNode::Dict { pairs, .. } => {
+                for (_, value) in pairs.iter_mut() {
+                    self.fold_node(value)?;
+                }
+                None
+            }
+            AAstNode::SymbolRef {
+                symbol_id, span, ..
+            } => self
+                .constants
+                .get(symbol_id)
+                .map(|literal| Self::node_of(literal.clone(), span.clone())),
+            AAstNode::Int { .. }
+            | AAstNode::Float { .. }
+            | AAstNode::String { .. }
+            | AAstNode::Bool { .. }
+            | AAstNode::Null { .. } => None,
+        };
+
+        if let Some(folded) = folded {
+            *aast_node = folded;
+        }
+
+        Ok(())
+    }
+
+    /// Folds a node and moves it out of its parent, which is
+    /// about to be replaced by it.
+    fn take_folded(&mut self, aast_node: &mut AAstNode) -> Result<AAstNode, SemanalyzerErr> {
+        self.fold_node(aast_node)?;
+
+        let span = aast_node.span().clone();
+        Ok(std::mem::replace(aast_node, AAstNode::Null { span }))
+    }
+
+    /// Arms with a false condition are dropped. The first arm with
+    /// a true condition ends the `.cond`, so its value becomes the
+    /// default one.
+    fn fold_cond(
+        &mut self,
+        arms: &mut Vec<(Box<AAstNode>, Box<AAstNode>)>,
+        default: &mut Option<Box<AAstNode>>,
+        span: &Span,
+    ) -> Result<Option<AAstNode>, SemanalyzerErr> {
+        let mut folded_arms: Vec<(Box<AAstNode>, Box<AAstNode>)> = vec![];
+
+        for (mut cond, mut value) in arms.drain(..) {
+            self.fold_node(&mut cond)?;
+
+            match Self::literal_of(&cond) {
+                Some(Literal::Bool(false)) => continue,
+                Some(Literal::Bool(true)) => {
+                    *default = Some(value);
+                    break;
+                }
+                _ => {
+                    self.fold_node(&mut value)?;
+                    folded_arms.push((cond, value));
+                }
+            }
+        }
+
+        *arms = folded_arms;
+
+        if let Some(default) = default {
+            self.fold_node(default)?;
+        }
+
+        if !arms.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(match default.take() {
+            Some(default) => *default,
+            None => AAstNode::Null { span: span.clone() },
+        }))
+    }
+
+    fn fold_op(
+        op: Op,
+        args: &[Box<AAstNode>],
+        span: &Span,
+    ) -> Result<Option<AAstNode>, SemanalyzerErr> {
+        let Some(literals) = args
+            .iter()
+            .map(|arg| Self::literal_of(arg))
+            .collect::<Option<Vec<Literal>>>()
+        else {
+            return Ok(None);
+        };
+
+        let literal = match op {
+            // String functions are left to the VM.
+            Op::Length
+            | Op::Substring
+            | Op::Split
+            | Op::Join
+            | Op::Trim
+            | Op::Upper
+            | Op::Lower
+            | Op::StartsWith
+            | Op::EndsWith
+            | Op::Replace
+            | Op::Matches
+            | Op::Extract
+            | Op::ExtractAll => return Ok(None),
+            // So are functions of dates, which are never literals.
+            Op::DateAdd | Op::DateDiff | Op::DateTrunc | Op::DateFormat => return Ok(None),
+            Op::Add | Op::Sub | Op::Mul | Op::Div | Op::Mod => {
+                let mut literals = literals.into_iter();
+                let first = literals.next().unwrap();
+
+                literals.try_fold(first, |acc, literal| {
+                    Self::eval_arithmetic(op, acc, literal, span)
+                })?
+            }
+            Op::Neg => match &literals[0] {
+                Literal::Int(value) => Literal::Int(
+                    value
+                        .checked_neg()
+                        .ok_or(SemanalyzerErr::ArithmeticOverflow { span: span.clone() })?,
+                ),
+                Literal::Float(value) => Literal::Float(-value),
+                _ => unreachable!(),
+            },
+            Op::Eq => Literal::Bool(Self::eval_eq(&literals[0], &literals[1])),
+            Op::Neq => Literal::Bool(!Self::eval_eq(&literals[0], &literals[1])),
+            Op::Lt | Op::Lte | Op::Gt | Op::Gte => {
+                let ordering = Self::eval_cmp(&literals[0], &literals[1]);
+
+                Literal::Bool(match op {
+                    Op::Lt => ordering == Some(Ordering::Less),
+                    Op::Lte => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+                    Op::Gt => ordering == Some(Ordering::Greater),
+                    _ => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+                })
+            }
+            Op::Concat => Literal::String(
+                literals
+                    .into_iter()
+                    .map(|literal| match literal {
+                        Literal::String(value) => value,
+                        _ => unreachable!(),
+                    })
+                    .collect(),
+            ),
+        };
+
+        Ok(Some(Self::node_of(literal, span.clone())))
+    }
+
+    fn eval_arithmetic(
+        op: Op,
+        lhs: Literal,
+        rhs: Literal,
+        span: &Span,
+    ) -> Result<Literal, SemanalyzerErr> {
+        let overflow = || SemanalyzerErr::ArithmeticOverflow { span: span.clone() };
+        let division_by_zero = || SemanalyzerErr::DivisionByZero { span: span.clone() };
+
+        if let (Literal::Int(lhs), Literal::Int(rhs)) = (&lhs, &rhs) {
+            let result = match op {
+                Op::Add => lhs.checked_add(*rhs),
+                Op::Sub => lhs.checked_sub(*rhs),
+                Op::Mul => lhs.checked_mul(*rhs),
+                Op::Div | Op::Mod if *rhs == 0 => return Err(division_by_zero()),
+                Op::Div => lhs.checked_div(*rhs),
+                _ => lhs.checked_rem(*rhs),
+            };
+
+            return result.map(Literal::Int).ok_or_else(overflow);
+        }
+
+        // Any Float promotes the whole operation to Float.
+        let (lhs, rhs) = (lhs.as_f64().unwrap(), rhs.as_f64().unwrap());
+
+        let result = match op {
+            Op::Add => lhs + rhs,
+            Op::Sub => lhs - rhs,
+            Op::Mul => lhs * rhs,
+            Op::Div | Op::Mod if rhs == 0.0 => return Err(division_by_zero()),
+            Op::Div => lhs / rhs,
+            _ => lhs % rhs,
+        };
+
+        if result.is_finite() {
+            Ok(Literal::Float(result))
+        } else {
+            Err(overflow())
+        }
+    }
+
+    /// Both operands are numbers, which is checked by Harmony.
+    fn eval_cmp(lhs: &Literal, rhs: &Literal) -> Option<Ordering> {
+        match (lhs, rhs) {
+            (Literal::Int(lhs), Literal::Int(rhs)) => Some(lhs.cmp(rhs)),
+            _ => lhs.as_f64()?.partial_cmp(&rhs.as_f64()?),
+        }
+    }
+
+    /// Numbers are compared by value regardless of their type, any
+    /// other values are equal only if they have the same type.
+    fn eval_eq(lhs: &Literal, rhs: &Literal) -> bool {
+        match (lhs, rhs) {
+            (Literal::Int(lhs), Literal::Int(rhs)) => lhs == rhs,
+            (Literal::String(lhs), Literal::String(rhs)) => lhs == rhs,
+            (Literal::Bool(lhs), Literal::Bool(rhs)) => lhs == rhs,
+            (Literal::Null, Literal::Null) => true,
+            _ => match (lhs.as_f64(), rhs.as_f64()) {
+                (Some(lhs), Some(rhs)) => lhs == rhs,
+                _ => false,
+            },
+        }
+    }
+
+    fn literal_of(aast_node: &AAstNode) -> Option<Literal> {
+        match aast_node {
+            AAstNode::Int { value, .. } => value.parse().ok().map(Literal::Int),
+            AAstNode::Float { value, .. } => value.parse().ok().map(Literal::Float),
+            AAstNode::String { value, .. } => Some(Literal::String(value.clone())),
+            AAstNode::Bool { value, .. } => Some(Literal::Bool(*value)),
+            AAstNode::Null { .. } => Some(Literal::Null),
+            _ => None,
+        }
+    }
+
+    fn node_of(literal: Literal, span: Span) -> AAstNode {
+        match literal {
+            Literal::Int(value) => AAstNode::Int {
+                value: value.to_string(),
+                span,
+            },
+            // Debug representation always keeps a fraction or an
+            // exponent, so the value stays a Float for the VM.
+            Literal::Float(value) => AAstNode::Float {
+                value: format!("{value:?}"),
+                span,
+            },
+            Literal::String(value) => AAstNode::String { value, span },
+            Literal::Bool(value) => AAstNode::Bool { value, span },
+            Literal::Null => AAstNode::Null { span },
+        }
+    }
+}
@@ -1,12 +1,16 @@
 use elise_shared::shared_node_names::NodeName;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum LangPrimitiveType {
     Int,
     Float,
     String,
     Bool,
     Null,
+    Date,
+    Time,
+    DateTime,
+    Duration,
 }
 
 // Map types to string representation that we can use
@@ -19,19 +23,227 @@ impl LangPrimitiveType {
             LangPrimitiveType::String => NodeName::STRING,
             LangPrimitiveType::Bool => NodeName::BOOL,
             LangPrimitiveType::Null => NodeName::NULL,
+            LangPrimitiveType::Date => NodeName::DATE,
+            LangPrimitiveType::Time => NodeName::TIME,
+            LangPrimitiveType::DateTime => NodeName::DATETIME,
+            LangPrimitiveType::Duration => NodeName::DURATION,
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum LangType {
     Primitive(LangPrimitiveType),
+    // Type of the items. Unknown if items have different types.
+    List(Box<LangType>),
+    // Type of the values. Unknown if values have different types.
+    Dict(Box<LangType>),
+    Function {
+        params: Vec<LangType>,
+        ret: Box<LangType>,
+    },
+    // Data value that can be of any of the alternatives, it
+    // must be narrowed down before it is used.
+    Union(Vec<LangType>),
+    // Value of the type or Null. Null is checked at runtime the
+    // same way as for nullable data fields, so the value can be
+    // used wherever a value of the type can.
+    Nullable(Box<LangType>),
+    // Type that can be known only at runtime, like types
+    // of function parameters.
+    Unknown,
 }
 
 impl LangType {
     pub fn as_str(&self) -> &'static str {
         match self {
-            LangType::Primitive(_) => NodeName::PRIMITIVE,
+            LangType::Primitive(primitive) => primitive.as_str(),
+            LangType::List(_) => NodeName::LIST,
+            LangType::Dict(_) => NodeName::DICT,
+            LangType::Function { .. } => NodeName::FUNCTION,
+            LangType::Union(_) => NodeName::UNION,
+            LangType::Nullable(ty) => ty.as_str(),
+            LangType::Unknown => NodeName::UNKNOWN,
         }
     }
+
+    /// Wraps the type into Nullable. Null and Unknown values can
+    /// be Null already, so they are kept as they are.
+    pub fn nullable(self) -> LangType {
+        match self {
+            LangType::Nullable(_)
+            | LangType::Primitive(LangPrimitiveType::Null)
+            | LangType::Unknown => self,
+            ty => LangType::Nullable(Box::new(ty)),
+        }
+    }
+
+    /// Type of the values that are not Null.
+    pub fn non_null(&self) -> &LangType {
+        match self {
+            LangType::Nullable(ty) => ty,
+            ty => ty,
+        }
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        matches!(
+            self,
+            LangType::Nullable(_) | LangType::Primitive(LangPrimitiveType::Null)
+        )
+    }
+
+    /// Checks whether a value of the `found` type can be used where
+    /// this type is expected. Unknown types can be checked only
+    /// at runtime, so they are always accepted.
+    pub fn accepts(&self, found: &LangType) -> bool {
+        match (self.non_null(), found.non_null()) {
+            (LangType::Unknown, _) | (_, LangType::Unknown) => true,
+            (LangType::Primitive(expected), LangType::Primitive(found)) => expected == found,
+            (LangType::List(expected), LangType::List(found))
+            | (LangType::Dict(expected), LangType::Dict(found)) => expected.accepts(found),
+            (
+                LangType::Function {
+                    params: expected, ..
+                },
+                LangType::Function { params: found, .. },
+            ) => expected.len() == found.len(),
+            (LangType::Union(expected), LangType::Union(found)) => found
+                .iter()
+                .all(|found| expected.iter().any(|expected| expected.accepts(found))),
+            _ => false,
+        }
+    }
+
+    /// Derives a type that covers values of both types, used for
+    /// results of branching. Null unifies with anything into
+    /// a Nullable type. Returns None if types are incompatible.
+    pub fn unify(&self, other: &LangType) -> Option<LangType> {
+        use LangPrimitiveType::{Float, Int, Null};
+
+        match (self, other) {
+            _ if self == other => Some(self.clone()),
+            (LangType::Unknown, _) | (_, LangType::Unknown) => Some(LangType::Unknown),
+            (LangType::Nullable(ty), other) | (other, LangType::Nullable(ty)) => {
+                ty.unify(other).map(LangType::nullable)
+            }
+            (LangType::Primitive(Null), ty) | (ty, LangType::Primitive(Null)) => {
+                Some(ty.clone().nullable())
+            }
+            (LangType::Primitive(Int), LangType::Primitive(Float))
+            | (LangType::Primitive(Float), LangType::Primitive(Int)) => {
+                Some(LangType::Primitive(Float))
+            }
+            (LangType::List(_), LangType::List(_)) => {
+                Some(LangType::List(Box::new(LangType::Unknown)))
+            }
+            (LangType::Dict(_), LangType::Dict(_)) => {
+                Some(LangType::Dict(Box::new(LangType::Unknown)))
+            }
+            (LangType::Function { params, .. }, LangType::Function { params: other, .. })
+                if params.len() == other.len() =>
+            {
+                Some(LangType::Function {
+                    params: vec![LangType::Unknown; params.len()],
+                    ret: Box::new(LangType::Unknown),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_types::{LangPrimitiveType, LangType};
+
+    fn int() -> LangType {
+        LangType::Primitive(LangPrimitiveType::Int)
+    }
+
+    fn func(params_len: usize) -> LangType {
+        LangType::Function {
+            params: vec![LangType::Unknown; params_len],
+            ret: Box::new(LangType::Unknown),
+        }
+    }
+
+    #[test]
+    fn should_accept_same_primitive() {
+        assert!(int().accepts(&int()));
+        assert!(!int().accepts(&LangType::Primitive(LangPrimitiveType::Float)));
+    }
+
+    #[test]
+    fn should_accept_unknown_both_ways() {
+        assert!(LangType::Unknown.accepts(&int()));
+        assert!(int().accepts(&LangType::Unknown));
+    }
+
+    #[test]
+    fn should_accept_compound_by_item_type() {
+        let list = LangType::List(Box::new(int()));
+        assert!(list.accepts(&LangType::List(Box::new(int()))));
+        assert!(!list.accepts(&LangType::Dict(Box::new(int()))));
+        assert!(!list.accepts(&LangType::List(Box::new(func(0)))));
+    }
+
+    #[test]
+    fn should_unify_branch_types() {
+        let float = LangType::Primitive(LangPrimitiveType::Float);
+        let null = LangType::Primitive(LangPrimitiveType::Null);
+        let string = LangType::Primitive(LangPrimitiveType::String);
+
+        assert_eq!(int().unify(&int()), Some(int()));
+        assert_eq!(int().unify(&float), Some(float.clone()));
+        assert_eq!(
+            null.unify(&string),
+            Some(LangType::Nullable(Box::new(string.clone())))
+        );
+        assert_eq!(string.unify(&LangType::Unknown), Some(LangType::Unknown));
+        assert_eq!(func(1).unify(&func(2)), None);
+        assert_eq!(int().unify(&string), None);
+    }
+
+    #[test]
+    fn should_keep_nullability_through_unification() {
+        let null = LangType::Primitive(LangPrimitiveType::Null);
+        let float = LangType::Primitive(LangPrimitiveType::Float);
+        let nullable_int = int().unify(&null).unwrap();
+
+        assert!(nullable_int.is_nullable());
+        assert_eq!(nullable_int.non_null(), &int());
+        assert_eq!(
+            nullable_int.unify(&float),
+            Some(LangType::Nullable(Box::new(float)))
+        );
+        assert_eq!(nullable_int.unify(&null), Some(nullable_int.clone()));
+        assert_eq!(null.unify(&LangType::Unknown), Some(LangType::Unknown));
+    }
+
+    #[test]
+    fn should_accept_nullable_as_its_type() {
+        let nullable_int = LangType::Nullable(Box::new(int()));
+
+        assert!(int().accepts(&nullable_int));
+        assert!(nullable_int.accepts(&int()));
+        assert!(!LangType::Primitive(LangPrimitiveType::String).accepts(&nullable_int));
+    }
+
+    #[test]
+    fn should_not_accept_union_as_alternative() {
+        let string = LangType::Primitive(LangPrimitiveType::String);
+        let union = LangType::Union(vec![int(), string.clone()]);
+
+        assert!(!int().accepts(&union));
+        assert!(union.accepts(&LangType::Union(vec![string])));
+        assert!(LangType::Unknown.accepts(&union));
+    }
+
+    #[test]
+    fn should_accept_function_with_same_arity() {
+        assert!(func(2).accepts(&func(2)));
+        assert!(!func(2).accepts(&func(1)));
+        assert!(!func(0).accepts(&int()));
+    }
 }
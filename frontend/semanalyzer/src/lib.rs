@@ -5,7 +5,7 @@
 //!
 //! ## Input
 //!   - AST produced by the parser
-//!   - DataBindingTable produced by the Binder (validated data + schema)
+//!   - ResolvedSchema produced by the SchemaResolver (types of the data)
 //!
 //! ## Output
 //!   - HIR { SymbolTable, AAST }
@@ -15,7 +15,8 @@
 //!   - Validates language rules (arity, type constraints, redefinition etc.)
 //!   - Annotates AST nodes with type information derived from schema and literals
 //!   - Folds constants where all operands are known at compile time
-//!   - Resolves data references against DataBindingTable to derive types
+//!   - Resolves data references against ResolvedSchema to derive types
+//!   - Warns about unused bindings and params, and about shadowing
 //!
 //! ## What Harmony does NOT do
 //!   - Store runtime values in the SymbolTable (type only, value lives in AAST)
@@ -26,361 +27,3057 @@
 //! By the time HIR reaches the emitter, all semantic guarantees are established
 //! and the emitter can trust the AAST without re-validation.
 
-//pub mod aast;
-//pub mod config;
-//pub mod data_types;
-//pub mod scope_stack;
-//pub mod symbol_table;
-//
-//use elise_ast::{AstCall, AstNode, AstPrimitive};
-//use elise_data::binder::DataBindingTable;
-//use elise_shared::{
-//    shared_errors::errors_semanalyzer::{ArityMismatchKind, SemanalyzerErr},
-//    shared_node_names::NodeName,
-//    shared_types::{Keyword, Span},
-//};
-//
-//use crate::{
-//    aast::AAstNode,
-//    config::{FnDefine, FnLet},
-//    data_types::{LangPrimitiveType, LangType},
-//    scope_stack::ScopeStack,
-//    symbol_table::SymbolTable,
-//};
-//
-//// ==================================================================
-////
-////  SEMANALYZER START
-////
-//// ==================================================================
-//
-//#[derive(Debug)]
-//pub struct HIR {
-//    pub symbol_table: SymbolTable,
-//    pub aast: Vec<AAstNode>,
-//}
-//
-//pub struct Harmony<'a> {
-//    pub ast: &'a Vec<AstNode>,
-//    pub data_binding_table: &'a DataBindingTable,
-//    pub scope_stack: ScopeStack,
-//}
-//
-//impl<'a> Harmony<'a> {
-//    pub fn new(ast: &'a Vec<AstNode>, data_binding_table: &'a DataBindingTable) -> Self {
-//        // In order to have a global scope we push a new one
-//        // before analyzing AST, so the first stack frame is
-//        // our genesis scope.
-//        let mut scope_stack = ScopeStack::new();
-//        scope_stack.push();
-//        Self {
-//            ast,
-//            data_binding_table,
-//            scope_stack,
-//        }
-//    }
-//
-//    pub fn analyze(&mut self) -> Result<HIR, SemanalyzerErr> {
-//        let mut symbol_table = SymbolTable::new();
-//        let mut aast: Vec<AAstNode> = vec![];
-//
-//        for ast_node in self.ast {
-//            let aast_node = self.annotate_ast_node(ast_node, &mut symbol_table)?;
-//            aast.push(aast_node);
-//        }
-//
-//        Ok(HIR { symbol_table, aast })
-//    }
-//
-//    fn annotate_ast_node(
-//        &mut self,
-//        ast_node: &AstNode,
-//        symbol_table: &mut SymbolTable,
-//    ) -> Result<AAstNode, SemanalyzerErr> {
-//        match ast_node {
-//            AstNode::Int(primitive) => Self::annotate_int(primitive),
-//            AstNode::Float(primitive) => Self::annotate_float(primitive),
-//            AstNode::String(primitive) => Self::annotate_string(primitive),
-//            AstNode::Bool(primitive) => Self::annotate_bool(primitive),
-//            AstNode::Null(primitive) => Self::annotate_null(primitive),
-//            AstNode::Identifier(primitive) => self.annotate_identifier_reference(primitive),
-//            AstNode::Call(call) => self.annotate_call(call, symbol_table),
-//            _ => Err(SemanalyzerErr::UnsupportedNode {
-//                span: ast_node.span().clone(),
-//            }),
-//        }
-//    }
-//
-//    // ==================================================================
-//    // ANNOTATE DEFINE CALL START
-//    //
-//    // .define (Identifier LangPrimitiveType)
-//    //
-//    // 1. Has only 2 arguments;
-//    // 2. First argument is always an identifier;
-//    // 3. Second argument is always primitive type;
-//    // 4. Never creates a new scope stack record;
-//    // 5. Defines symbols in the current scope stack;
-//    // 6. Does not remove any scope stack entries;
-//    // ==================================================================
-//
-//    fn annotate_define_call(
-//        &mut self,
-//        call: &AstCall,
-//        symbol_table: &mut SymbolTable,
-//    ) -> Result<AAstNode, SemanalyzerErr> {
-//        if call.children.len() != FnDefine::ARGS_LEN {
-//            return Err(SemanalyzerErr::ArityMismatch {
-//                fn_name: FnDefine::LEXEME,
-//                found: call.children.len(),
-//                span: call.span.clone(),
-//                kind: ArityMismatchKind::Eq(FnDefine::ARGS_LEN),
-//            });
-//        }
-//
-//        let first_arg = &**call.children.first().unwrap();
-//        let second_arg = &**call.children.last().unwrap();
-//
-//        let (ident_type, aast_node) = match second_arg {
-//            AstNode::Int(prim) => (LangPrimitiveType::Int, Self::annotate_int(prim)?),
-//            AstNode::Float(prim) => (LangPrimitiveType::Float, Self::annotate_float(prim)?),
-//            AstNode::String(prim) => (LangPrimitiveType::String, Self::annotate_string(prim)?),
-//            AstNode::Bool(prim) => (LangPrimitiveType::Bool, Self::annotate_bool(prim)?),
-//            AstNode::Null(prim) => (LangPrimitiveType::Null, Self::annotate_null(prim)?),
-//            _ => {
-//                return Err(SemanalyzerErr::ArgTypeMismatch {
-//                    fn_name: FnDefine::LEXEME,
-//                    position: 1,
-//                    expected: NodeName::PRIMITIVE,
-//                    found: second_arg.as_str(),
-//                    span: second_arg.span().clone(),
-//                });
-//            }
-//        };
-//
-//        let AstNode::Identifier(primitive) = first_arg else {
-//            return Err(SemanalyzerErr::ArgKindMismatch {
-//                fn_name: FnDefine::LEXEME,
-//                position: 0,
-//                expected: NodeName::IDENTIFIER,
-//                found: first_arg.as_str(),
-//                span: first_arg.span().clone(),
-//            });
-//        };
-//
-//        if self.scope_stack.resolve(&primitive.value).is_some() {
-//            return Err(SemanalyzerErr::SymbolDuplicate {
-//                span: call.span.clone(),
-//            });
-//        }
-//
-//        let symbol_id =
-//            symbol_table.fresh(primitive.value.clone(), LangType::Primitive(ident_type));
-//
-//        self.scope_stack.define(primitive.value.clone(), symbol_id);
-//
-//        Ok(AAstNode::CallDefine {
-//            symbol_id,
-//            value: Box::new(aast_node),
-//            span: call.span.clone(),
-//        })
-//    }
-//
-//    // ==================================================================
-//    // ANNOTATE DEFINE CALL END
-//    // ==================================================================
-//
-//    // ==================================================================
-//    // ANNOTATE LET CALL START
-//    //
-//    // .let ([(Identifier Expression)+] Expression+)
-//    //
-//    // 1. Min 2 arguments;
-//    // 2. First argument is always a list;
-//    // 3. Odd items in the list are always identifiers;
-//    // 4. Even items in the list are always expressions
-//    //    that must be evaluated first;
-//    // 5. The result of evaluation is always a result of
-//    //    the last evaluated expression;
-//    // 6. Creates its own scope stack when enters;
-//    // 7. Removes its own scope stack when evaluation finishes;
-//    // 8. Does not allow symbol re-bindings;
-//    // 9. Can access outer scope;
-//    // ==================================================================
-//
-//    fn annotate_let_call(
-//        &mut self,
-//        call: &AstCall,
-//        _symbol_table: &mut SymbolTable,
-//    ) -> Result<AAstNode, SemanalyzerErr> {
-//        if call.children.len() < FnLet::MIN_ARGS_LEN {
-//            return Err(SemanalyzerErr::ArityMismatch {
-//                fn_name: FnLet::LEXEME,
-//                found: call.children.len(),
-//                span: call.span.clone(),
-//                kind: ArityMismatchKind::MoreEq(FnLet::MIX_ARGS_LEN),
-//            });
-//        }
-//
-//        // TODO
-//
-//        Err(SemanalyzerErr::UnknownFunction {
-//            span: Span { start: 0, end: 0 },
-//        })
-//    }
-//
-//    // ==================================================================
-//    // ANNOTATE LET CALL END
-//    // ==================================================================
-//
-//    // ==================================================================
-//    // ANNOTATE CALL START
-//    // ==================================================================
-//
-//    fn annotate_call(
-//        &mut self,
-//        call: &AstCall,
-//        symbol_table: &mut SymbolTable,
-//    ) -> Result<AAstNode, SemanalyzerErr> {
-//        match call.lexeme.as_str() {
-//            FnDefine::LEXEME => self.annotate_define_call(call, symbol_table),
-//            FnLet::LEXEME => self.annotate_let_call(call, symbol_table),
-//            _ => Err(SemanalyzerErr::UnknownFunction {
-//                span: call.span.clone(),
-//            }),
-//        }
-//    }
-//
-//    // ==================================================================
-//    // ANNOTATE CALL END
-//    // ==================================================================
-//
-//    // ==================================================================
-//    // PRIMITIVE ANNOTATIONS START
-//    //
-//    // Annotations for primitive values Number, String, Bool, Null,
-//    // Identifier which we can map almost 1:1 from AstNode to AAstNode.
-//    // ==================================================================
-//
-//    // ==================================================================
-//    // ANNOTATE IDENTIFIER REFERENCE START
-//    //
-//    // Annotates identifier references only.
-//    // It means that it captures only identifiers that are
-//    // already in scope and just referenced. For example:
-//    //
-//    // .define (PI 3.1415)
-//    // .let ([distance 43]
-//    //    .add (PI distance))
-//    //
-//    // This function takes care of `PI` and `distance` in .add
-//    // function call only. Resolution for identifier definition
-//    // has to be done in respective functions for handling
-//    // semantics for expressions that can define identifiers
-//    // like `.let` and `.define`.
-//    // ==================================================================
-//
-//    fn annotate_identifier_reference(
-//        &self,
-//        primitive: &AstPrimitive,
-//    ) -> Result<AAstNode, SemanalyzerErr> {
-//        self.scope_stack
-//            .resolve(&primitive.value)
-//            .map(|(symbol_id, depth)| AAstNode::SymbolRef {
-//                symbol_id,
-//                depth,
-//                span: primitive.span.clone(),
-//            })
-//            .ok_or_else(|| SemanalyzerErr::SymbolUndefined {
-//                span: primitive.span.clone(),
-//            })
-//    }
-//
-//    // ==================================================================
-//    // ANNOTATE IDENTIFIER REFERENCE END
-//    // ==================================================================
-//
-//    // ==================================================================
-//    // ANNOTATE INT START
-//    // ==================================================================
-//
-//    fn annotate_int(primitive: &AstPrimitive) -> Result<AAstNode, SemanalyzerErr> {
-//        Ok(AAstNode::Int {
-//            value: primitive.value.clone(),
-//            span: primitive.span.clone(),
-//        })
-//    }
-//
-//    // ==================================================================
-//    // ANNOTATE INT END
-//    // ==================================================================
-//
-//    // ==================================================================
-//    // ANNOTATE FLOAT START
-//    // ==================================================================
-//
-//    fn annotate_float(primitive: &AstPrimitive) -> Result<AAstNode, SemanalyzerErr> {
-//        Ok(AAstNode::Float {
-//            value: primitive.value.clone(),
-//            span: primitive.span.clone(),
-//        })
-//    }
-//
-//    // ==================================================================
-//    // ANNOTATE FLOAT END
-//    // ==================================================================
-//
-//    // ==================================================================
-//    // ANNOTATE STRING START
-//    // ==================================================================
-//
-//    fn annotate_string(primitive: &AstPrimitive) -> Result<AAstNode, SemanalyzerErr> {
-//        Ok(AAstNode::String {
-//            value: primitive.value.clone(),
-//            span: primitive.span.clone(),
-//        })
-//    }
-//
-//    // ==================================================================
-//    // ANNOTATE STRING END
-//    // ==================================================================
-//
-//    // ==================================================================
-//    // ANNOTATE BOOL START
-//    // ==================================================================
-//
-//    fn annotate_bool(primitive: &AstPrimitive) -> Result<AAstNode, SemanalyzerErr> {
-//        Ok(AAstNode::Bool {
-//            value: primitive.value == Keyword::TRUE,
-//            span: primitive.span.clone(),
-//        })
-//    }
-//
-//    // ==================================================================
-//    // ANNOTATE BOOL END
-//    // ==================================================================
-//
-//    // ==================================================================
-//    // ANNOTATE NULL START
-//    // ==================================================================
+pub mod aast;
+pub mod config;
+pub mod const_folder;
+pub mod data_types;
+pub mod scope_stack;
+pub mod symbol_table;
+
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
+
+use elise_ast::{AstCall, AstCompound, AstNode, AstPrimitive};
+use elise_data::{
+    resolution_path::{ResolutionPath, ResolutionPathSegment},
+    schema_resolver::{ResolvedSchema, SchemaDataType},
+};
+use elise_shared::{
+    shared_errors::errors_semanalyzer::{SemanalyzerDiag, SemanalyzerErr, SemanalyzerWarn},
+    shared_node_names::NodeName,
+    shared_temporal::{self, TemporalKind, TemporalUnit},
+    shared_types::{ArityMismatchKind, Keyword, Span},
+};
+
+use crate::{
+    aast::{AAstNode, CollectionOp, Conversion, ConversionMode, Op},
+    config::{
+        FnAggregate, FnArith, FnCmp, FnColl, FnCond, FnConv, FnDate, FnDefine, FnFn, FnGet, FnIf,
+        FnLet, FnNarrow, FnStr, FnThread, FnWhen, SlotName,
+    },
+    const_folder::ConstFolder,
+    data_types::{LangPrimitiveType, LangType},
+    scope_stack::ScopeStack,
+    symbol_table::{SymbolId, SymbolTable},
+};
+
+// ==================================================================
 //
-//    fn annotate_null(primitive: &AstPrimitive) -> Result<AAstNode, SemanalyzerErr> {
-//        Ok(AAstNode::Null {
-//            span: primitive.span.clone(),
-//        })
-//    }
+//  SEMANALYZER START
 //
-//    // ==================================================================
-//    // ANNOTATE BOOL END
-//    // ==================================================================
+// ==================================================================
+
+#[derive(Debug)]
+pub struct HIR {
+    pub symbol_table: SymbolTable,
+    pub aast: Vec<AAstNode>,
+}
+
+/// Result of analysis in recovering mode.
+#[derive(Debug)]
+pub struct RecoveredAnalysis {
+    // Nodes that contained errors are skipped.
+    pub hir: HIR,
+    // Every error and warning that was collected, in source order.
+    pub diagnostics: Vec<SemanalyzerDiag>,
+}
+
+/// Function that is being analyzed. Any symbol that resolves
+/// to a scope below the `boundary` is defined outside of the
+/// function and must be captured by it.
+struct FnFrame {
+    // Index of the function's own scope in the scope stack.
+    boundary: usize,
+    captures: Vec<SymbolId>,
+}
+
+/// Part of a destructured value that a pattern is bound to.
+struct PatternTarget {
+    // Hidden symbol the whole value is bound to.
+    source: SymbolId,
+    // Keys that access the part inside of the value.
+    keys: Vec<AAstNode>,
+    ty: LangType,
+    // Schema path of the part if the value comes from the data.
+    schema_path: Option<ResolutionPath>,
+    // The part itself if it's written as a literal.
+    literal: Option<AAstNode>,
+}
+
+/// What is known about a param of a function that is passed
+/// to a built-in before the function itself is analyzed.
+struct ParamHint {
+    ty: LangType,
+    // Schema path of the value if it comes from the data.
+    schema_path: Option<ResolutionPath>,
+}
+
+pub struct Harmony<'a> {
+    pub ast: &'a Vec<AstNode>,
+    pub schema: &'a ResolvedSchema,
+    pub scope_stack: ScopeStack,
+    // Functions we are currently inside of, innermost last.
+    fn_frames: Vec<FnFrame>,
+    // Symbols that were referenced at least once.
+    used: HashSet<SymbolId>,
+    warnings: Vec<SemanalyzerWarn>,
+    // Symbols bound to values of the data, so their keys
+    // can be checked against the data schema.
+    schema_paths: HashMap<SymbolId, ResolutionPath>,
+    // Types that symbols of unknown types are used as. Params
+    // take them once the body of their function is analyzed,
+    // a symbol used as different types stays unknown.
+    uses: HashMap<SymbolId, LangType>,
+}
+
+impl<'a> Harmony<'a> {
+    pub fn new(ast: &'a Vec<AstNode>, schema: &'a ResolvedSchema) -> Self {
+        // In order to have a global scope we push a new one
+        // before analyzing AST, so the first stack frame is
+        // our genesis scope.
+        let mut scope_stack = ScopeStack::new();
+        scope_stack.push();
+        Self {
+            ast,
+            schema,
+            scope_stack,
+            fn_frames: vec![],
+            used: HashSet::new(),
+            warnings: vec![],
+            schema_paths: HashMap::new(),
+            uses: HashMap::new(),
+        }
+    }
+
+    pub fn analyze(&mut self) -> Result<HIR, SemanalyzerErr> {
+        let mut symbol_table = SymbolTable::new();
+        let mut aast: Vec<AAstNode> = vec![];
+
+        for ast_node in self.ast {
+            let aast_node = self.annotate_ast_node(ast_node, &mut symbol_table)?;
+            aast.push(aast_node);
+        }
+
+        ConstFolder::new().fold(&mut aast)?;
+
+        Ok(HIR { symbol_table, aast })
+    }
+
+    /// Analyzes every top-level node even if some of them are
+    /// invalid, collecting errors along with warnings.
+    pub fn analyze_recovering(&mut self) -> RecoveredAnalysis {
+        let mut symbol_table = SymbolTable::new();
+        let mut aast: Vec<AAstNode> = vec![];
+        let mut diagnostics: Vec<SemanalyzerDiag> = vec![];
+        let mut const_folder = ConstFolder::new();
+
+        for ast_node in self.ast {
+            // Scopes are restored by the annotation itself,
+            // so the next node starts from a clean state.
+            let result = self
+                .annotate_ast_node(ast_node, &mut symbol_table)
+                .and_then(|mut aast_node| {
+                    const_folder.fold(std::slice::from_mut(&mut aast_node))?;
+                    Ok(aast_node)
+                });
+
+            match result {
+                Ok(aast_node) => aast.push(aast_node),
+                Err(err) => diagnostics.push(SemanalyzerDiag::Err(err)),
+            }
+        }
+
+        diagnostics.extend(self.warnings.drain(..).map(SemanalyzerDiag::Warn));
+        diagnostics.sort_by_key(|diag| diag.span().start);
+
+        RecoveredAnalysis {
+            hir: HIR { symbol_table, aast },
+            diagnostics,
+        }
+    }
+
+    fn warn_if_unused(
+        &mut self,
+        primitive: &AstPrimitive,
+        symbol_id: SymbolId,
+        variant: fn(Span) -> SemanalyzerWarn,
+    ) {
+        if !self.used.contains(&symbol_id) {
+            self.warnings.push(variant(primitive.span.clone()));
+        }
+    }
+
+    /// Must be called before the identifier is defined, so only
+    /// identifiers of the outer scopes are found.
+    fn warn_if_shadows(&mut self, primitive: &AstPrimitive) {
+        if self.scope_stack.resolve(&primitive.value).is_some() {
+            self.warnings.push(SemanalyzerWarn::Shadowing {
+                span: primitive.span.clone(),
+            });
+        }
+    }
+
+    /// Remembers the type a symbol of unknown type is used as,
+    /// only uses that expect exactly one type are counted.
+    fn note_use(&mut self, aast_node: &AAstNode, ty: &LangType, symbol_table: &SymbolTable) {
+        let AAstNode::SymbolRef { symbol_id, .. } = aast_node else {
+            return;
+        };
+
+        if *ty == LangType::Unknown
+            || symbol_table.symbols.get(symbol_id).unwrap().ty != LangType::Unknown
+        {
+            return;
+        }
+
+        match self.uses.get(symbol_id) {
+            Some(used_as) if used_as != ty => {
+                self.uses.insert(*symbol_id, LangType::Unknown);
+            }
+            Some(_) => {}
+            None => {
+                self.uses.insert(*symbol_id, ty.clone());
+            }
+        }
+    }
+
+    fn annotate_ast_node(
+        &mut self,
+        ast_node: &AstNode,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        match ast_node {
+            AstNode::Int(primitive) => Self::annotate_int(primitive),
+            AstNode::Float(primitive) => Self::annotate_float(primitive),
+            AstNode::String(primitive) => Self::annotate_string(primitive),
+            AstNode::Bool(primitive) => Self::annotate_bool(primitive),
+            AstNode::Null(primitive) => Self::annotate_null(primitive),
+            AstNode::Identifier(primitive) => {
+                self.annotate_identifier_reference(primitive, symbol_table)
+            }
+            AstNode::List(compound) => self.annotate_list(compound, symbol_table),
+            AstNode::Dict(compound) => self.annotate_dict(compound, symbol_table),
+            AstNode::Call(call) => self.annotate_call(call, symbol_table),
+            // Bare slot is the whole data, like `.get` without keys.
+            AstNode::Slot(primitive) => {
+                self.annotate_get_slot(primitive, &[], &primitive.span, symbol_table)
+            }
+            _ => Err(SemanalyzerErr::UnsupportedNode {
+                span: ast_node.span().clone(),
+            }),
+        }
+    }
+
+    // ==================================================================
+    // ANNOTATE DEFINE CALL START
+    //
+    // .define (Identifier LangPrimitiveType)
+    //
+    // 1. Has only 2 arguments;
+    // 2. First argument is always an identifier;
+    // 3. Second argument is always primitive type;
+    // 4. Never creates a new scope stack record;
+    // 5. Defines symbols in the current scope stack;
+    // 6. Does not remove any scope stack entries;
+    // ==================================================================
+
+    fn annotate_define_call(
+        &mut self,
+        call: &AstCall,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        if call.children.len() != FnDefine::ARGS_LEN {
+            return Err(SemanalyzerErr::ArityMismatch {
+                fn_name: FnDefine::LEXEME.into(),
+                found: call.children.len(),
+                span: call.span.clone(),
+                kind: ArityMismatchKind::Eq(FnDefine::ARGS_LEN),
+            });
+        }
+
+        let first_arg = &**call.children.first().unwrap();
+        let second_arg = &**call.children.last().unwrap();
+
+        let (ident_type, aast_node) = match second_arg {
+            AstNode::Int(prim) => (LangPrimitiveType::Int, Self::annotate_int(prim)?),
+            AstNode::Float(prim) => (LangPrimitiveType::Float, Self::annotate_float(prim)?),
+            AstNode::String(prim) => (LangPrimitiveType::String, Self::annotate_string(prim)?),
+            AstNode::Bool(prim) => (LangPrimitiveType::Bool, Self::annotate_bool(prim)?),
+            AstNode::Null(prim) => (LangPrimitiveType::Null, Self::annotate_null(prim)?),
+            _ => {
+                return Err(SemanalyzerErr::ArgTypeMismatch {
+                    fn_name: FnDefine::LEXEME.into(),
+                    position: 1,
+                    expected: NodeName::PRIMITIVE,
+                    found: second_arg.as_str(),
+                    span: second_arg.span().clone(),
+                });
+            }
+        };
+
+        let AstNode::Identifier(primitive) = first_arg else {
+            return Err(SemanalyzerErr::ArgKindMismatch {
+                fn_name: FnDefine::LEXEME,
+                position: 0,
+                expected: NodeName::IDENTIFIER,
+                found: first_arg.as_str(),
+                span: first_arg.span().clone(),
+            });
+        };
+
+        if self.scope_stack.resolve(&primitive.value).is_some() {
+            return Err(SemanalyzerErr::SymbolDuplicate {
+                span: call.span.clone(),
+            });
+        }
+
+        let symbol_id =
+            symbol_table.fresh(primitive.value.clone(), LangType::Primitive(ident_type));
+
+        self.scope_stack.define(primitive.value.clone(), symbol_id);
+
+        Ok(AAstNode::CallDefine {
+            symbol_id,
+            value: Box::new(aast_node),
+            span: call.span.clone(),
+        })
+    }
+
+    // ==================================================================
+    // ANNOTATE DEFINE CALL END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE LET CALL START
+    //
+    // .let ([(Pattern Expression)+] Expression+)
+    //
+    // 1. Min 2 arguments;
+    // 2. First argument is always a list;
+    // 3. Odd items in the list are always patterns: identifiers,
+    //    or lists and dicts that destructure the value;
+    // 4. Even items in the list are always expressions
+    //    that must be evaluated first;
+    // 5. The result of evaluation is always a result of
+    //    the last evaluated expression;
+    // 6. Creates its own scope stack when enters;
+    // 7. Removes its own scope stack when evaluation finishes;
+    // 8. Does not allow symbol re-bindings;
+    // 9. Can access outer scope;
+    // ==================================================================
+
+    fn annotate_let_call(
+        &mut self,
+        call: &AstCall,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        if call.children.len() < FnLet::MIN_ARGS_LEN {
+            return Err(SemanalyzerErr::ArityMismatch {
+                fn_name: FnLet::LEXEME.into(),
+                found: call.children.len(),
+                span: call.span.clone(),
+                kind: ArityMismatchKind::MoreEq(FnLet::MIN_ARGS_LEN),
+            });
+        }
+
+        let first_arg = &**call.children.first().unwrap();
+
+        let AstNode::List(list) = first_arg else {
+            return Err(SemanalyzerErr::ArgKindMismatch {
+                fn_name: FnLet::LEXEME,
+                position: 0,
+                expected: NodeName::LIST,
+                found: first_arg.as_str(),
+                span: first_arg.span().clone(),
+            });
+        };
+
+        if list.children.is_empty() {
+            return Err(SemanalyzerErr::BindingsEmpty {
+                span: list.span.clone(),
+            });
+        }
+
+        // Init expressions are resolved in the outer scope before
+        // the `.let` scope is pushed, so bindings of the same `.let`
+        // can't see each other.
+        let mut inits: Vec<(&AstNode, AAstNode)> = vec![];
+
+        for pair in list.children.chunks(2) {
+            let pattern = &*pair[0];
+
+            if !Self::is_pattern(pattern) {
+                return Err(SemanalyzerErr::ArgKindMismatch {
+                    fn_name: FnLet::LEXEME,
+                    position: 0,
+                    expected: NodeName::PATTERN,
+                    found: pattern.as_str(),
+                    span: pattern.span().clone(),
+                });
+            }
+
+            let Some(init) = pair.get(1) else {
+                return Err(SemanalyzerErr::BindingValueMissing {
+                    span: pattern.span().clone(),
+                });
+            };
+
+            inits.push((pattern, self.annotate_ast_node(init, symbol_table)?));
+        }
+
+        self.scope_stack.push();
+
+        let result = self.annotate_let_scope(call, inits, symbol_table);
+
+        // Scope must be removed even if the body is invalid.
+        self.scope_stack.pop();
+
+        result
+    }
+
+    fn annotate_let_scope(
+        &mut self,
+        call: &AstCall,
+        inits: Vec<(&AstNode, AAstNode)>,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        let mut bindings: Vec<(SymbolId, Box<AAstNode>)> = vec![];
+        let mut idents: Vec<(&AstPrimitive, SymbolId)> = vec![];
+
+        for (pattern, aast_node) in inits {
+            let AstNode::Identifier(primitive) = pattern else {
+                let ty = Self::type_of(&aast_node, symbol_table);
+                let schema_path = self.schema_path_of(&aast_node);
+                let source = Self::fresh_hidden(ty.clone(), symbol_table);
+                // Lengths of literal lists are known, even if they're nested.
+                let literal = matches!(aast_node, AAstNode::List { .. } | AAstNode::Dict { .. })
+                    .then(|| aast_node.clone());
+                bindings.push((source, Box::new(aast_node)));
+
+                self.bind_pattern(
+                    FnLet::LEXEME,
+                    0,
+                    pattern,
+                    PatternTarget {
+                        source,
+                        keys: vec![],
+                        ty,
+                        schema_path,
+                        literal,
+                    },
+                    &mut bindings,
+                    &mut idents,
+                    symbol_table,
+                )?;
+                continue;
+            };
+
+            // Shadowing of the outer scope is allowed, re-binding
+            // inside the same `.let` is not.
+            if self.scope_stack.resolve_local(&primitive.value).is_some() {
+                return Err(SemanalyzerErr::SymbolDuplicate {
+                    span: primitive.span.clone(),
+                });
+            }
+
+            self.warn_if_shadows(primitive);
+
+            let ty = Self::nullable_type_of(&aast_node, symbol_table);
+            let symbol_id = symbol_table.fresh(primitive.value.clone(), ty);
+
+            // Bound rows of the data are checked against the schema
+            // when they are accessed or destructured later.
+            if let Some(path) = self.schema_path_of(&aast_node) {
+                self.schema_paths.insert(symbol_id, path);
+            }
+
+            self.scope_stack.define(primitive.value.clone(), symbol_id);
+            bindings.push((symbol_id, Box::new(aast_node)));
+            idents.push((primitive, symbol_id));
+        }
+
+        let mut body: Vec<Box<AAstNode>> = vec![];
+
+        for ast_node in call.children.iter().skip(1) {
+            body.push(Box::new(self.annotate_ast_node(ast_node, symbol_table)?));
+        }
+
+        for (primitive, symbol_id) in idents {
+            self.warn_if_unused(primitive, symbol_id, |span| {
+                SemanalyzerWarn::UnusedBinding { span }
+            });
+        }
+
+        Ok(AAstNode::CallLet {
+            bindings,
+            body,
+            span: call.span.clone(),
+        })
+    }
+
+    // ==================================================================
+    // ANNOTATE LET CALL END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE FN CALL START
+    //
+    // .fn (Identifier? [Pattern*] Expression+)
+    //
+    // 1. Min 2 arguments, or 3 if the name is provided;
+    // 2. Optional first identifier is defined in the current
+    //    scope stack record and bound to the function;
+    // 3. Params list is always a list of patterns, params that
+    //    are destructured are bound by a `.let` around the body;
+    // 4. Params exist only in the function's scope stack record;
+    // 5. The rest of arguments is the body, the result of the
+    //    function is the result of the last expression;
+    // 6. Creates its own scope stack when enters;
+    // 7. Removes its own scope stack when analysis finishes;
+    // 8. Captures symbols of the enclosing scope stack records
+    //    except the global one;
+    // 9. Param types are given by the built-in the function is
+    //    passed to, otherwise a param takes the type it is used
+    //    as in the body: String for string functions and `.concat`,
+    //    Bool for conditions, or the param type of a called
+    //    function. Params used in other ways, or in conflicting
+    //    ways, stay Unknown and accept any argument;
+    // ==================================================================
+
+    fn annotate_fn_call(
+        &mut self,
+        call: &AstCall,
+        hints: &[ParamHint],
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        if call.children.len() < FnFn::MIN_ARGS_LEN {
+            return Err(SemanalyzerErr::ArityMismatch {
+                fn_name: FnFn::LEXEME.into(),
+                found: call.children.len(),
+                span: call.span.clone(),
+                kind: ArityMismatchKind::MoreEq(FnFn::MIN_ARGS_LEN),
+            });
+        }
+
+        let (name, params_pos) = match &**call.children.first().unwrap() {
+            AstNode::Identifier(primitive) => (Some(primitive), 1),
+            _ => (None, 0),
+        };
+
+        if name.is_some() && call.children.len() < FnFn::MIN_NAMED_ARGS_LEN {
+            return Err(SemanalyzerErr::ArityMismatch {
+                fn_name: FnFn::LEXEME.into(),
+                found: call.children.len(),
+                span: call.span.clone(),
+                kind: ArityMismatchKind::MoreEq(FnFn::MIN_NAMED_ARGS_LEN),
+            });
+        }
+
+        let params_arg = &*call.children[params_pos];
+
+        let AstNode::List(params_list) = params_arg else {
+            return Err(SemanalyzerErr::ArgKindMismatch {
+                fn_name: FnFn::LEXEME,
+                position: params_pos,
+                expected: NodeName::LIST,
+                found: params_arg.as_str(),
+                span: params_arg.span().clone(),
+            });
+        };
+
+        // Named function is defined before its body is analyzed,
+        // so the function can reference itself.
+        let symbol_id = match name {
+            Some(primitive) => {
+                if self.scope_stack.resolve_local(&primitive.value).is_some() {
+                    return Err(SemanalyzerErr::SymbolDuplicate {
+                        span: primitive.span.clone(),
+                    });
+                }
+
+                self.warn_if_shadows(primitive);
+
+                // Params are known already, the rest of the type
+                // is refined once the body is analyzed.
+                let ty = LangType::Function {
+                    params: vec![LangType::Unknown; params_list.children.len()],
+                    ret: Box::new(LangType::Unknown),
+                };
+                let symbol_id = symbol_table.fresh(primitive.value.clone(), ty);
+                self.scope_stack.define(primitive.value.clone(), symbol_id);
+                Some(symbol_id)
+            }
+            None => None,
+        };
+
+        self.scope_stack.push();
+        self.fn_frames.push(FnFrame {
+            boundary: self.scope_stack.scopes.len() - 1,
+            captures: vec![],
+        });
+
+        let result = self.annotate_fn_scope(
+            call,
+            symbol_id,
+            params_pos,
+            &params_list.children,
+            hints,
+            symbol_table,
+        );
+
+        // Scope must be removed even if the body is invalid.
+        self.fn_frames.pop();
+        self.scope_stack.pop();
+
+        let aast_node = result?;
+
+        if let Some(symbol_id) = symbol_id {
+            let ty = Self::type_of(&aast_node, symbol_table);
+            symbol_table.symbols.get_mut(&symbol_id).unwrap().ty = ty;
+        }
+
+        Ok(aast_node)
+    }
+
+    fn annotate_fn_scope(
+        &mut self,
+        call: &AstCall,
+        symbol_id: Option<SymbolId>,
+        params_pos: usize,
+        params_list: &[Box<AstNode>],
+        hints: &[ParamHint],
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        let mut params: Vec<SymbolId> = vec![];
+        let mut idents: Vec<(&AstPrimitive, SymbolId)> = vec![];
+        // Parts of destructured params.
+        let mut bindings: Vec<(SymbolId, Box<AAstNode>)> = vec![];
+
+        for (idx, param) in params_list.iter().enumerate() {
+            let hint = hints.get(idx);
+
+            let AstNode::Identifier(primitive) = &**param else {
+                if !Self::is_pattern(param) {
+                    return Err(SemanalyzerErr::ArgKindMismatch {
+                        fn_name: FnFn::LEXEME,
+                        position: params_pos,
+                        expected: NodeName::PATTERN,
+                        found: param.as_str(),
+                        span: param.span().clone(),
+                    });
+                }
+
+                let ty = hint.map_or(LangType::Unknown, |hint| hint.ty.clone());
+                let schema_path = hint.and_then(|hint| hint.schema_path.clone());
+                let source = Self::fresh_hidden(ty.clone(), symbol_table);
+                params.push(source);
+
+                self.bind_pattern(
+                    FnFn::LEXEME,
+                    params_pos,
+                    param,
+                    PatternTarget {
+                        source,
+                        keys: vec![],
+                        ty,
+                        schema_path,
+                        literal: None,
+                    },
+                    &mut bindings,
+                    &mut idents,
+                    symbol_table,
+                )?;
+                continue;
+            };
+
+            if self.scope_stack.resolve_local(&primitive.value).is_some() {
+                return Err(SemanalyzerErr::SymbolDuplicate {
+                    span: primitive.span.clone(),
+                });
+            }
+
+            self.warn_if_shadows(primitive);
+
+            // Param types are unknown until the param is used
+            // in a way that narrows its type down, unless the
+            // function is passed to a built-in that knows them.
+            let ty = hint.map_or(LangType::Unknown, |hint| hint.ty.clone());
+            let symbol_id = symbol_table.fresh(primitive.value.clone(), ty);
+
+            if let Some(path) = hint.and_then(|hint| hint.schema_path.clone()) {
+                self.schema_paths.insert(symbol_id, path);
+            }
+
+            self.scope_stack.define(primitive.value.clone(), symbol_id);
+            params.push(symbol_id);
+            idents.push((primitive, symbol_id));
+        }
+
+        let mut body: Vec<Box<AAstNode>> = vec![];
+
+        for ast_node in call.children.iter().skip(params_pos + 1) {
+            body.push(Box::new(self.annotate_ast_node(ast_node, symbol_table)?));
+        }
+
+        for (primitive, symbol_id) in idents {
+            self.warn_if_unused(primitive, symbol_id, |span| SemanalyzerWarn::UnusedParam {
+                span,
+            });
+
+            // The body is checked already, so the param takes
+            // the type it is used as only for calls of the function.
+            let descriptor = symbol_table.symbols.get_mut(&symbol_id).unwrap();
+
+            if descriptor.ty == LangType::Unknown
+                && let Some(ty) = self.uses.remove(&symbol_id)
+            {
+                descriptor.ty = ty;
+            }
+        }
+
+        if !bindings.is_empty() {
+            body = vec![Box::new(AAstNode::CallLet {
+                bindings,
+                body,
+                span: call.span.clone(),
+            })];
+        }
+
+        // Body is fully analyzed, so the list of captures is final.
+        let captures = self.fn_frames.last().unwrap().captures.clone();
+
+        Ok(AAstNode::CallFn {
+            symbol_id,
+            params,
+            captures,
+            body,
+            span: call.span.clone(),
+        })
+    }
+
+    // ==================================================================
+    // ANNOTATE FN CALL END
+    // ==================================================================
+
+    // ==================================================================
+    // BIND PATTERN START
+    //
+    // [Pattern*] | {(String Pattern)*}
+    //
+    // 1. List pattern destructures a list by indexes;
+    // 2. Dict pattern destructures a dict by keys;
+    // 3. Patterns can be nested;
+    // 4. Destructured value is bound to a hidden symbol, each
+    //    identifier of the pattern is bound to a `.get` of it;
+    // 5. Type of the value must match the pattern unless
+    //    it is unknown;
+    // 6. If the value comes from the data, keys of dict patterns
+    //    must be defined in the data schema;
+    // 7. If the value is a literal list, the list pattern must
+    //    have the same number of items;
+    // ==================================================================
+
+    fn is_pattern(ast_node: &AstNode) -> bool {
+        matches!(
+            ast_node,
+            AstNode::Identifier(_) | AstNode::List(_) | AstNode::Dict(_)
+        )
+    }
+
+    /// Symbol that holds a destructured value. It has no name,
+    /// so it can't be referenced from the source.
+    fn fresh_hidden(ty: LangType, symbol_table: &mut SymbolTable) -> SymbolId {
+        symbol_table.fresh(String::new(), ty)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn bind_pattern<'b>(
+        &mut self,
+        fn_name: &'static str,
+        position: usize,
+        pattern: &'b AstNode,
+        target: PatternTarget,
+        bindings: &mut Vec<(SymbolId, Box<AAstNode>)>,
+        idents: &mut Vec<(&'b AstPrimitive, SymbolId)>,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<(), SemanalyzerErr> {
+        match pattern {
+            AstNode::Identifier(primitive) => {
+                if self.scope_stack.resolve_local(&primitive.value).is_some() {
+                    return Err(SemanalyzerErr::SymbolDuplicate {
+                        span: primitive.span.clone(),
+                    });
+                }
+
+                self.warn_if_shadows(primitive);
+
+                let symbol_id = symbol_table.fresh(primitive.value.clone(), target.ty.clone());
+
+                if let Some(path) = target.schema_path {
+                    self.schema_paths.insert(symbol_id, path);
+                }
+
+                self.scope_stack.define(primitive.value.clone(), symbol_id);
+                bindings.push((
+                    symbol_id,
+                    Box::new(AAstNode::CallGet {
+                        target: Box::new(AAstNode::SymbolRef {
+                            symbol_id: target.source,
+                            span: primitive.span.clone(),
+                            // Hidden symbol is defined in the same scope.
+                            depth: 0,
+                        }),
+                        keys: target.keys.into_iter().map(Box::new).collect(),
+                        ty: target.ty,
+                        span: primitive.span.clone(),
+                    }),
+                ));
+                idents.push((primitive, symbol_id));
+            }
+            AstNode::List(compound) => {
+                let item_ty = match &target.ty {
+                    LangType::List(item_ty) => *item_ty.clone(),
+                    LangType::Unknown => LangType::Unknown,
+                    ty => {
+                        return Err(SemanalyzerErr::PatternTypeMismatch {
+                            expected: NodeName::LIST,
+                            found: ty.as_str(),
+                            span: compound.span.clone(),
+                        });
+                    }
+                };
+
+                let literal_items = match target.literal {
+                    Some(AAstNode::List { items, .. }) => Some(items),
+                    _ => None,
+                };
+
+                if let Some(items) = &literal_items
+                    && items.len() != compound.children.len()
+                {
+                    return Err(SemanalyzerErr::PatternLenMismatch {
+                        expected: compound.children.len(),
+                        found: items.len(),
+                        span: compound.span.clone(),
+                    });
+                }
+
+                // Any index resolves to the same schema path.
+                let schema_path = target.schema_path.map(|mut path| {
+                    path.push(ResolutionPathSegment::AbstractIndex);
+                    path
+                });
+
+                for (idx, item) in compound.children.iter().enumerate() {
+                    let mut keys = target.keys.clone();
+                    keys.push(AAstNode::Int {
+                        value: idx.to_string(),
+                        span: item.span().clone(),
+                    });
+
+                    let item_target = PatternTarget {
+                        source: target.source,
+                        keys,
+                        ty: match &schema_path {
+                            Some(path) => self.type_of_schema_path(path),
+                            None => item_ty.clone(),
+                        },
+                        schema_path: schema_path.clone(),
+                        literal: literal_items.as_ref().map(|items| (*items[idx]).clone()),
+                    };
+
+                    self.bind_pattern(
+                        fn_name,
+                        position,
+                        item,
+                        item_target,
+                        bindings,
+                        idents,
+                        symbol_table,
+                    )?;
+                }
+            }
+            AstNode::Dict(compound) => {
+                let value_ty = match &target.ty {
+                    LangType::Dict(value_ty) => *value_ty.clone(),
+                    LangType::Unknown => LangType::Unknown,
+                    ty => {
+                        return Err(SemanalyzerErr::PatternTypeMismatch {
+                            expected: NodeName::DICT,
+                            found: ty.as_str(),
+                            span: compound.span.clone(),
+                        });
+                    }
+                };
+
+                for ast_node in compound.children.iter() {
+                    // Parser guarantees that dict consists of pairs only.
+                    let AstNode::DictPair(pair) = &**ast_node else {
+                        return Err(SemanalyzerErr::UnsupportedNode {
+                            span: ast_node.span().clone(),
+                        });
+                    };
+
+                    let schema_path = match &target.schema_path {
+                        Some(path) => {
+                            let mut path = path.clone();
+                            path.push(ResolutionPathSegment::Field(pair.key.clone()));
+
+                            if !self.schema.resolved_schema.contains_key(&path) {
+                                return Err(SemanalyzerErr::SchemaPathUndefined {
+                                    path: path.as_str(),
+                                    span: pair.key_span.clone(),
+                                });
+                            }
+
+                            Some(path)
+                        }
+                        None => None,
+                    };
+
+                    let mut keys = target.keys.clone();
+                    keys.push(AAstNode::String {
+                        value: pair.key.clone(),
+                        span: pair.key_span.clone(),
+                    });
+
+                    let item_target = PatternTarget {
+                        source: target.source,
+                        keys,
+                        ty: match &schema_path {
+                            Some(path) => self.type_of_schema_path(path),
+                            None => value_ty.clone(),
+                        },
+                        schema_path,
+                        literal: match &target.literal {
+                            Some(AAstNode::Dict { pairs, .. }) => pairs
+                                .iter()
+                                .find(|(key, _)| *key == pair.key)
+                                .map(|(_, value)| (**value).clone()),
+                            _ => None,
+                        },
+                    };
+
+                    self.bind_pattern(
+                        fn_name,
+                        position,
+                        &pair.value,
+                        item_target,
+                        bindings,
+                        idents,
+                        symbol_table,
+                    )?;
+                }
+            }
+            ast_node => {
+                return Err(SemanalyzerErr::ArgKindMismatch {
+                    fn_name,
+                    position,
+                    expected: NodeName::PATTERN,
+                    found: ast_node.as_str(),
+                    span: ast_node.span().clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // ==================================================================
+    // BIND PATTERN END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE OP CALL START
+    //
+    // .add, .sub, .mul, .div, .mod (Expression Expression+)
+    // .neg (Expression)
+    // .eq, .neq, .lt, .lte, .gt, .gte (Expression Expression)
+    // .concat (Expression Expression+)
+    // .length, .trim, .upper, .lower (Expression)
+    // .substring (Expression Expression Expression?)
+    // .split, .join, .starts-with, .ends-with (Expression Expression)
+    // .matches, .extract, .extract-all (Expression Expression)
+    // .replace (Expression Expression Expression)
+    // .date-add, .date-diff, .date-trunc, .date-format (Expression Expression)
+    //
+    // 1. Arithmetic takes numbers only, the result is Float if
+    //    any of the arguments is Float, otherwise it's Int;
+    // 2. .eq and .neq take arguments of any type;
+    // 3. .lt, .lte, .gt and .gte take numbers, or values of the
+    //    same Date, Time, DateTime or Duration type;
+    // 4. The result of comparison is always Bool;
+    // 5. .concat takes strings only and results in String;
+    // 6. The rest of string functions take arguments of the
+    //    types listed in `signature_of_string_fn`, .substring
+    //    takes Int positions and .join takes a List of Strings;
+    // 7. Regular expressions given as String literals are
+    //    checked to be valid;
+    // 8. Date functions take a Date, Time or DateTime first, then
+    //    .date-add takes a Duration, .date-diff takes a value of
+    //    the same type and results in Duration, .date-trunc takes
+    //    a unit and .date-format takes a format, both as Strings;
+    // 9. Units and formats given as String literals are checked
+    //    to apply to the type of the value;
+    // ==================================================================
+
+    fn annotate_op_call(
+        &mut self,
+        call: &AstCall,
+        op: Op,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        let arity = match op {
+            Op::Add | Op::Sub | Op::Mul | Op::Div | Op::Mod => {
+                ArityMismatchKind::MoreEq(FnArith::MIN_ARGS_LEN)
+            }
+            Op::Neg => ArityMismatchKind::Eq(FnArith::NEG_ARGS_LEN),
+            Op::Eq | Op::Neq | Op::Lt | Op::Lte | Op::Gt | Op::Gte => {
+                ArityMismatchKind::Eq(FnCmp::ARGS_LEN)
+            }
+            Op::Concat => ArityMismatchKind::MoreEq(FnStr::CONCAT_MIN_ARGS_LEN),
+            Op::Length | Op::Trim | Op::Upper | Op::Lower => {
+                ArityMismatchKind::Eq(FnStr::UNARY_ARGS_LEN)
+            }
+            Op::Substring => ArityMismatchKind::Range((
+                FnStr::SUBSTRING_MIN_ARGS_LEN,
+                FnStr::SUBSTRING_MAX_ARGS_LEN,
+            )),
+            Op::Split
+            | Op::Join
+            | Op::StartsWith
+            | Op::EndsWith
+            | Op::Matches
+            | Op::Extract
+            | Op::ExtractAll => ArityMismatchKind::Eq(FnStr::BINARY_ARGS_LEN),
+            Op::Replace => ArityMismatchKind::Eq(FnStr::REPLACE_ARGS_LEN),
+            Op::DateAdd | Op::DateDiff | Op::DateTrunc | Op::DateFormat => {
+                ArityMismatchKind::Eq(FnDate::ARGS_LEN)
+            }
+        };
+
+        if !arity.accepts(call.children.len()) {
+            return Err(SemanalyzerErr::ArityMismatch {
+                fn_name: op.as_str().into(),
+                found: call.children.len(),
+                span: call.span.clone(),
+                kind: arity,
+            });
+        }
+
+        let mut args: Vec<Box<AAstNode>> = vec![];
+
+        for ast_node in call.children.iter() {
+            args.push(Box::new(self.annotate_ast_node(ast_node, symbol_table)?));
+        }
+
+        let ty = match op {
+            Op::Eq | Op::Neq => LangType::Primitive(LangPrimitiveType::Bool),
+            Op::Lt | Op::Lte | Op::Gt | Op::Gte => {
+                Self::check_ordered(op, &args, symbol_table)?;
+                LangType::Primitive(LangPrimitiveType::Bool)
+            }
+            Op::Concat => {
+                let ty = Self::type_of_strings(op, &args, symbol_table)?;
+
+                for arg in args.iter() {
+                    self.note_use(arg, &ty, symbol_table);
+                }
+
+                ty
+            }
+            Op::Add | Op::Sub | Op::Mul | Op::Div | Op::Mod | Op::Neg => {
+                Self::type_of_numbers(op, &args, symbol_table)?
+            }
+            Op::DateAdd | Op::DateDiff | Op::DateTrunc | Op::DateFormat => {
+                Self::type_of_date_fn(op, &args, symbol_table)?
+            }
+            _ => {
+                let ty = Self::type_of_string_fn(op, &args, symbol_table)?;
+                let (params, _) = Self::signature_of_string_fn(op);
+
+                for (param, arg) in params.iter().zip(args.iter()) {
+                    self.note_use(arg, param, symbol_table);
+                }
+
+                ty
+            }
+        };
+
+        Ok(AAstNode::CallOp {
+            op,
+            args,
+            ty,
+            span: call.span.clone(),
+        })
+    }
+
+    /// Checks that all arguments are numbers and derives the type
+    /// of the arithmetic result with Int to Float promotion.
+    fn type_of_numbers(
+        op: Op,
+        args: &[Box<AAstNode>],
+        symbol_table: &SymbolTable,
+    ) -> Result<LangType, SemanalyzerErr> {
+        let mut has_float = false;
+        let mut has_unknown = false;
+
+        for (position, arg) in args.iter().enumerate() {
+            match Self::type_of(arg, symbol_table) {
+                LangType::Primitive(LangPrimitiveType::Int) => {}
+                LangType::Primitive(LangPrimitiveType::Float) => has_float = true,
+                LangType::Unknown => has_unknown = true,
+                ty => {
+                    return Err(SemanalyzerErr::ArgTypeMismatch {
+                        fn_name: op.as_str().into(),
+                        position,
+                        expected: NodeName::NUMBER,
+                        found: ty.as_str(),
+                        span: arg.span().clone(),
+                    });
+                }
+            }
+        }
+
+        // Float wins anyway, but Int can turn into Float
+        // at runtime if any of the unknowns is Float.
+        let ty = if has_float {
+            LangType::Primitive(LangPrimitiveType::Float)
+        } else if has_unknown {
+            LangType::Unknown
+        } else {
+            LangType::Primitive(LangPrimitiveType::Int)
+        };
+
+        Ok(ty)
+    }
+
+    /// Checks that values are numbers, or that all of them are
+    /// of the same temporal type if any of them is.
+    fn check_ordered(
+        op: Op,
+        args: &[Box<AAstNode>],
+        symbol_table: &SymbolTable,
+    ) -> Result<(), SemanalyzerErr> {
+        let temporal = args
+            .iter()
+            .map(|arg| Self::type_of(arg, symbol_table))
+            .find(|ty| {
+                Self::temporal_kind(ty).is_some()
+                    || *ty == LangType::Primitive(LangPrimitiveType::Duration)
+            });
+
+        let Some(expected) = temporal else {
+            return Self::type_of_numbers(op, args, symbol_table).map(|_| ());
+        };
+
+        for (position, arg) in args.iter().enumerate() {
+            let ty = Self::type_of(arg, symbol_table);
+
+            if !expected.accepts(&ty) {
+                return Err(SemanalyzerErr::ArgTypeMismatch {
+                    fn_name: op.as_str().into(),
+                    position,
+                    expected: expected.as_str(),
+                    found: ty.as_str(),
+                    span: arg.span().clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn type_of_strings(
+        op: Op,
+        args: &[Box<AAstNode>],
+        symbol_table: &SymbolTable,
+    ) -> Result<LangType, SemanalyzerErr> {
+        for (position, arg) in args.iter().enumerate() {
+            match Self::type_of(arg, symbol_table) {
+                LangType::Primitive(LangPrimitiveType::String) | LangType::Unknown => {}
+                ty => {
+                    return Err(SemanalyzerErr::ArgTypeMismatch {
+                        fn_name: op.as_str().into(),
+                        position,
+                        expected: NodeName::STRING,
+                        found: ty.as_str(),
+                        span: arg.span().clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(LangType::Primitive(LangPrimitiveType::String))
+    }
+
+    /// Types of params and the result of a string function
+    /// other than `.concat`.
+    fn signature_of_string_fn(op: Op) -> (Vec<LangType>, LangType) {
+        let string = || LangType::Primitive(LangPrimitiveType::String);
+        let int = || LangType::Primitive(LangPrimitiveType::Int);
+        let bool = || LangType::Primitive(LangPrimitiveType::Bool);
+        let strings = || LangType::List(Box::new(string()));
+
+        match op {
+            Op::Length => (vec![string()], int()),
+            Op::Substring => (vec![string(), int(), int()], string()),
+            Op::Split => (vec![string(), string()], strings()),
+            Op::Join => (vec![strings(), string()], string()),
+            Op::Trim | Op::Upper | Op::Lower => (vec![string()], string()),
+            Op::StartsWith | Op::EndsWith | Op::Matches => (vec![string(), string()], bool()),
+            Op::Replace => (vec![string(), string(), string()], string()),
+            // Null if nothing matches, which unifies with anything.
+            Op::Extract => (vec![string(), string()], string()),
+            Op::ExtractAll => (vec![string(), string()], strings()),
+            op => unreachable!("{} is not a string function", op.as_str()),
+        }
+    }
+
+    fn type_of_string_fn(
+        op: Op,
+        args: &[Box<AAstNode>],
+        symbol_table: &SymbolTable,
+    ) -> Result<LangType, SemanalyzerErr> {
+        let (params, ret) = Self::signature_of_string_fn(op);
+
+        for (position, (param, arg)) in params.iter().zip(args.iter()).enumerate() {
+            let ty = Self::type_of(arg, symbol_table);
+
+            if !param.accepts(&ty) {
+                return Err(SemanalyzerErr::ArgTypeMismatch {
+                    fn_name: op.as_str().into(),
+                    position,
+                    expected: param.as_str(),
+                    found: ty.as_str(),
+                    span: arg.span().clone(),
+                });
+            }
+        }
+
+        // Patterns known in advance fail here instead of at runtime.
+        if let Op::Matches | Op::Extract | Op::ExtractAll = op
+            && let AAstNode::String { value, span } = &*args[1]
+            && Regex::new(value).is_err()
+        {
+            return Err(SemanalyzerErr::InvalidRegex { span: span.clone() });
+        }
+
+        Ok(ret)
+    }
+
+    fn temporal_kind(ty: &LangType) -> Option<TemporalKind> {
+        match ty {
+            LangType::Primitive(LangPrimitiveType::Date) => Some(TemporalKind::Date),
+            LangType::Primitive(LangPrimitiveType::Time) => Some(TemporalKind::Time),
+            LangType::Primitive(LangPrimitiveType::DateTime) => Some(TemporalKind::DateTime),
+            _ => None,
+        }
+    }
+
+    fn type_of_date_fn(
+        op: Op,
+        args: &[Box<AAstNode>],
+        symbol_table: &SymbolTable,
+    ) -> Result<LangType, SemanalyzerErr> {
+        let value_ty = Self::type_of(&args[0], symbol_table);
+        let arg_ty = Self::type_of(&args[1], symbol_table);
+        let kind = Self::temporal_kind(&value_ty);
+
+        let mismatch = |position: usize, expected: &'static str, found: &LangType| {
+            SemanalyzerErr::ArgTypeMismatch {
+                fn_name: op.as_str().into(),
+                position,
+                expected,
+                found: found.as_str(),
+                span: args[position].span().clone(),
+            }
+        };
+
+        if kind.is_none() && value_ty != LangType::Unknown {
+            return Err(mismatch(0, NodeName::TEMPORAL, &value_ty));
+        }
+
+        match op {
+            Op::DateAdd => {
+                let duration = LangType::Primitive(LangPrimitiveType::Duration);
+                if !duration.accepts(&arg_ty) {
+                    return Err(mismatch(1, NodeName::DURATION, &arg_ty));
+                }
+            }
+            Op::DateDiff => {
+                if Self::temporal_kind(&arg_ty).is_none() && arg_ty != LangType::Unknown {
+                    return Err(mismatch(1, NodeName::TEMPORAL, &arg_ty));
+                }
+                if !value_ty.accepts(&arg_ty) {
+                    return Err(mismatch(1, value_ty.as_str(), &arg_ty));
+                }
+            }
+            _ => {
+                let string = LangType::Primitive(LangPrimitiveType::String);
+                if !string.accepts(&arg_ty) {
+                    return Err(mismatch(1, NodeName::STRING, &arg_ty));
+                }
+            }
+        }
+
+        // Units and formats known in advance fail here instead of at
+        // runtime. Values of unknown types can be any of the kinds, so
+        // they are checked against DateTime, which has all the fields.
+        if let AAstNode::String { value, span } = &*args[1] {
+            let kind = kind.unwrap_or(TemporalKind::DateTime);
+
+            match op {
+                Op::DateTrunc
+                    if !TemporalUnit::from_name(value)
+                        .is_some_and(|unit| unit.applies_to(kind)) =>
+                {
+                    return Err(SemanalyzerErr::InvalidDateUnit { span: span.clone() });
+                }
+                Op::DateFormat if !shared_temporal::can_format(value, kind) => {
+                    return Err(SemanalyzerErr::InvalidDateFormat { span: span.clone() });
+                }
+                _ => {}
+            }
+        }
+
+        let ty = match op {
+            Op::DateDiff => LangType::Primitive(LangPrimitiveType::Duration),
+            Op::DateFormat => LangType::Primitive(LangPrimitiveType::String),
+            _ => value_ty,
+        };
+
+        Ok(ty)
+    }
+
+    // ==================================================================
+    // ANNOTATE OP CALL END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE COLLECTION CALL START
+    //
+    // .map, .filter, .sort-by, .flat-map (Expression Function)
+    // .group-by (Expression Function)
+    // .reduce (Expression Function Expression)
+    // .take, .drop (Expression Expression)
+    // .count, .count-distinct (Expression)
+    // .sum, .avg, .min, .max (Expression)
+    //
+    // 1. The first argument is always a List, including Lists
+    //    of the data;
+    // 2. Functions are called with an item of the List, .reduce
+    //    calls them with an accumulator and an item;
+    // 3. Params of inline functions get the type of the items,
+    //    items of the data get their type from the data schema,
+    //    so their keys are checked against it;
+    // 4. .map results in a List of function results, .flat-map
+    //    joins function results which must be Lists;
+    // 5. .filter function must return Bool;
+    // 6. .filter, .sort-by, .take and .drop keep the type of
+    //    the List, .take and .drop take an Int;
+    // 7. .reduce results in the type of the initial value
+    //    unified with the function result;
+    // 8. .count results in Int;
+    // 9. .group-by results in a List of groups, each group is
+    //    a List of items with equal function results;
+    // 10. .sum, .min and .max take a List of numbers and keep
+    //     their type, .avg results in Float, .count-distinct
+    //     results in Int;
+    // ==================================================================
+
+    fn annotate_collection_call(
+        &mut self,
+        call: &AstCall,
+        op: CollectionOp,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        let args_len = match op {
+            CollectionOp::Reduce => FnColl::REDUCE_ARGS_LEN,
+            CollectionOp::Count
+            | CollectionOp::CountDistinct
+            | CollectionOp::Sum
+            | CollectionOp::Avg
+            | CollectionOp::Min
+            | CollectionOp::Max => FnColl::LIST_ARGS_LEN,
+            _ => FnColl::ARGS_LEN,
+        };
+
+        if call.children.len() != args_len {
+            return Err(SemanalyzerErr::ArityMismatch {
+                fn_name: op.as_str().into(),
+                found: call.children.len(),
+                span: call.span.clone(),
+                kind: ArityMismatchKind::Eq(args_len),
+            });
+        }
+
+        let source = self.annotate_ast_node(&call.children[0], symbol_table)?;
+
+        let item_ty = match Self::type_of(&source, symbol_table) {
+            LangType::List(item_ty) => *item_ty,
+            LangType::Unknown => LangType::Unknown,
+            ty => {
+                return Err(SemanalyzerErr::ArgTypeMismatch {
+                    fn_name: op.as_str().into(),
+                    position: 0,
+                    expected: NodeName::LIST,
+                    found: ty.as_str(),
+                    span: source.span().clone(),
+                });
+            }
+        };
+
+        let item = ParamHint {
+            ty: item_ty.clone(),
+            schema_path: self.schema_path_of(&source).map(|mut path| {
+                path.push(ResolutionPathSegment::AbstractIndex);
+                path
+            }),
+        };
+        let list_ty = LangType::List(Box::new(item_ty));
+        let mut args: Vec<Box<AAstNode>> = vec![];
+
+        let ty = match op {
+            CollectionOp::Count => LangType::Primitive(LangPrimitiveType::Int),
+            CollectionOp::CountDistinct
+            | CollectionOp::Sum
+            | CollectionOp::Avg
+            | CollectionOp::Min
+            | CollectionOp::Max => Self::type_of_aggregated(op, 0, &item.ty, source.span())?,
+            CollectionOp::Take | CollectionOp::Drop => {
+                let count = self.annotate_ast_node(&call.children[1], symbol_table)?;
+                let count_ty = Self::type_of(&count, symbol_table);
+
+                if !LangType::Primitive(LangPrimitiveType::Int).accepts(&count_ty) {
+                    return Err(SemanalyzerErr::ArgTypeMismatch {
+                        fn_name: op.as_str().into(),
+                        position: 1,
+                        expected: NodeName::INT,
+                        found: count_ty.as_str(),
+                        span: count.span().clone(),
+                    });
+                }
+
+                args.push(Box::new(count));
+                list_ty
+            }
+            CollectionOp::Reduce => {
+                // Initial value defines the type of the accumulator.
+                let init = self.annotate_ast_node(&call.children[2], symbol_table)?;
+                let init_ty = Self::type_of(&init, symbol_table);
+                let acc = ParamHint {
+                    ty: init_ty.clone(),
+                    schema_path: None,
+                };
+
+                let callback =
+                    self.annotate_callback(op, &call.children[1], vec![acc, item], symbol_table)?;
+                let ret = Self::type_of_callback_ret(&callback, symbol_table);
+
+                let Some(ty) = init_ty.unify(&ret) else {
+                    return Err(SemanalyzerErr::CallbackReturnMismatch {
+                        fn_name: op.as_str(),
+                        expected: init_ty.as_str(),
+                        found: ret.as_str(),
+                        span: callback.span().clone(),
+                    });
+                };
+
+                args.push(Box::new(callback));
+                args.push(Box::new(init));
+                ty
+            }
+            _ => {
+                let callback =
+                    self.annotate_callback(op, &call.children[1], vec![item], symbol_table)?;
+                let ret = Self::type_of_callback_ret(&callback, symbol_table);
+
+                let ty = match (op, &ret) {
+                    (CollectionOp::Map, _) => LangType::List(Box::new(ret.clone())),
+                    (CollectionOp::SortBy, _) => list_ty,
+                    (CollectionOp::GroupBy, _) => LangType::List(Box::new(list_ty)),
+                    (
+                        CollectionOp::Filter,
+                        LangType::Primitive(LangPrimitiveType::Bool) | LangType::Unknown,
+                    ) => list_ty,
+                    (CollectionOp::FlatMap, LangType::List(_)) => ret.clone(),
+                    (CollectionOp::FlatMap, LangType::Unknown) => {
+                        LangType::List(Box::new(LangType::Unknown))
+                    }
+                    _ => {
+                        let expected = if op == CollectionOp::Filter {
+                            NodeName::BOOL
+                        } else {
+                            NodeName::LIST
+                        };
+
+                        return Err(SemanalyzerErr::CallbackReturnMismatch {
+                            fn_name: op.as_str(),
+                            expected,
+                            found: ret.as_str(),
+                            span: callback.span().clone(),
+                        });
+                    }
+                };
+
+                args.push(Box::new(callback));
+                ty
+            }
+        };
+
+        Ok(AAstNode::CallCollection {
+            op,
+            source: Box::new(source),
+            args,
+            ty,
+            span: call.span.clone(),
+        })
+    }
+
+    /// Annotates a function passed to a built-in. Params of an
+    /// inline `.fn` are typed by the hints, any function must
+    /// take exactly as many params as there are hints.
+    fn annotate_callback(
+        &mut self,
+        op: CollectionOp,
+        ast_node: &AstNode,
+        hints: Vec<ParamHint>,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        let callback = match ast_node {
+            AstNode::Call(call) if call.lexeme == FnFn::LEXEME => {
+                self.annotate_fn_call(call, &hints, symbol_table)?
+            }
+            ast_node => self.annotate_ast_node(ast_node, symbol_table)?,
+        };
+
+        match Self::type_of(&callback, symbol_table) {
+            LangType::Function { params, .. } if params.len() != hints.len() => {
+                Err(SemanalyzerErr::CallbackArityMismatch {
+                    fn_name: op.as_str(),
+                    expected: hints.len(),
+                    found: params.len(),
+                    span: callback.span().clone(),
+                })
+            }
+            LangType::Function { .. } | LangType::Unknown => Ok(callback),
+            ty => Err(SemanalyzerErr::ArgTypeMismatch {
+                fn_name: op.as_str().into(),
+                position: 1,
+                expected: NodeName::FUNCTION,
+                found: ty.as_str(),
+                span: callback.span().clone(),
+            }),
+        }
+    }
+
+    fn type_of_callback_ret(callback: &AAstNode, symbol_table: &SymbolTable) -> LangType {
+        match Self::type_of(callback, symbol_table) {
+            LangType::Function { ret, .. } => *ret,
+            _ => LangType::Unknown,
+        }
+    }
+
+    /// Type of an aggregation over values of the `ty` type.
+    /// Every aggregation except counting requires numbers.
+    fn type_of_aggregated(
+        op: CollectionOp,
+        position: usize,
+        ty: &LangType,
+        span: &Span,
+    ) -> Result<LangType, SemanalyzerErr> {
+        match (op, ty) {
+            (CollectionOp::Count | CollectionOp::CountDistinct, _) => {
+                Ok(LangType::Primitive(LangPrimitiveType::Int))
+            }
+            (
+                CollectionOp::Avg,
+                LangType::Primitive(LangPrimitiveType::Int | LangPrimitiveType::Float)
+                | LangType::Unknown,
+            ) => Ok(LangType::Primitive(LangPrimitiveType::Float)),
+            (
+                _,
+                LangType::Primitive(LangPrimitiveType::Int | LangPrimitiveType::Float)
+                | LangType::Unknown,
+            ) => Ok(ty.clone()),
+            _ => Err(SemanalyzerErr::ArgTypeMismatch {
+                fn_name: op.as_str().into(),
+                position,
+                expected: NodeName::NUMBER,
+                found: ty.as_str(),
+                span: span.clone(),
+            }),
+        }
+    }
+
+    // ==================================================================
+    // ANNOTATE COLLECTION CALL END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE AGGREGATE CALL START
+    //
+    // .aggregate (Expression [String*] {(String Aggregation)*})
+    //
+    // Aggregation is one of:
+    // .count ()
+    // .sum, .avg, .min, .max, .count-distinct (String)
+    //
+    // 1. The first argument is a List of Dicts, usually rows
+    //    of the data;
+    // 2. Rows are grouped by the values of the key fields, every
+    //    group results in a Dict of the key fields and the
+    //    aggregations under their names;
+    // 3. Fields are checked against the data schema if rows come
+    //    from the data, the types of the key fields are taken
+    //    from it;
+    // 4. Aggregated fields follow the rules of the same List
+    //    built-ins, .count counts the rows of a group;
+    // 5. The result is a List of Dicts, values of which are Unknown
+    //    unless the key fields and aggregations have the same type;
+    // ==================================================================
+
+    fn annotate_aggregate_call(
+        &mut self,
+        call: &AstCall,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        if call.children.len() != FnAggregate::ARGS_LEN {
+            return Err(SemanalyzerErr::ArityMismatch {
+                fn_name: FnAggregate::LEXEME.into(),
+                found: call.children.len(),
+                span: call.span.clone(),
+                kind: ArityMismatchKind::Eq(FnAggregate::ARGS_LEN),
+            });
+        }
+
+        let source = self.annotate_ast_node(&call.children[0], symbol_table)?;
+
+        let row_ty = match Self::type_of(&source, symbol_table) {
+            LangType::List(item_ty) => match *item_ty {
+                LangType::Dict(value_ty) => *value_ty,
+                LangType::Unknown => LangType::Unknown,
+                ty => {
+                    return Err(SemanalyzerErr::ArgTypeMismatch {
+                        fn_name: FnAggregate::LEXEME.into(),
+                        position: 0,
+                        expected: NodeName::DICT,
+                        found: ty.as_str(),
+                        span: source.span().clone(),
+                    });
+                }
+            },
+            LangType::Unknown => LangType::Unknown,
+            ty => {
+                return Err(SemanalyzerErr::ArgTypeMismatch {
+                    fn_name: FnAggregate::LEXEME.into(),
+                    position: 0,
+                    expected: NodeName::LIST,
+                    found: ty.as_str(),
+                    span: source.span().clone(),
+                });
+            }
+        };
+
+        let row_path = self.schema_path_of(&source).map(|mut path| {
+            path.push(ResolutionPathSegment::AbstractIndex);
+            path
+        });
+
+        let keys_arg = &*call.children[1];
+        let AstNode::List(keys_list) = keys_arg else {
+            return Err(SemanalyzerErr::ArgKindMismatch {
+                fn_name: FnAggregate::LEXEME,
+                position: 1,
+                expected: NodeName::LIST,
+                found: keys_arg.as_str(),
+                span: keys_arg.span().clone(),
+            });
+        };
+
+        let mut keys: Vec<String> = vec![];
+        let mut fields_ty: Vec<LangType> = vec![];
+
+        for ast_node in keys_list.children.iter() {
+            let key = Self::expect_field(FnAggregate::LEXEME, 1, ast_node)?;
+
+            fields_ty.push(self.type_of_row_field(row_path.as_ref(), &row_ty, key)?);
+            keys.push(key.value.clone());
+        }
+
+        let aggregations_arg = &*call.children[2];
+        let AstNode::Dict(aggregations_dict) = aggregations_arg else {
+            return Err(SemanalyzerErr::ArgKindMismatch {
+                fn_name: FnAggregate::LEXEME,
+                position: 2,
+                expected: NodeName::DICT,
+                found: aggregations_arg.as_str(),
+                span: aggregations_arg.span().clone(),
+            });
+        };
+
+        let mut aggregations: Vec<(String, CollectionOp, Option<String>)> = vec![];
+
+        for ast_node in aggregations_dict.children.iter() {
+            // Parser guarantees that dict consists of pairs only.
+            let AstNode::DictPair(pair) = &**ast_node else {
+                return Err(SemanalyzerErr::UnsupportedNode {
+                    span: ast_node.span().clone(),
+                });
+            };
+
+            let aggregation = match &*pair.value {
+                AstNode::Call(call) => CollectionOp::from_lexeme(&call.lexeme)
+                    .filter(|op| {
+                        matches!(
+                            op,
+                            CollectionOp::Count
+                                | CollectionOp::CountDistinct
+                                | CollectionOp::Sum
+                                | CollectionOp::Avg
+                                | CollectionOp::Min
+                                | CollectionOp::Max
+                        )
+                    })
+                    .map(|op| (op, call)),
+                _ => None,
+            };
+
+            let Some((op, aggregation_call)) = aggregation else {
+                return Err(SemanalyzerErr::ArgKindMismatch {
+                    fn_name: FnAggregate::LEXEME,
+                    position: 2,
+                    expected: NodeName::AGGREGATION,
+                    found: pair.value.as_str(),
+                    span: pair.value.span().clone(),
+                });
+            };
+
+            // `.count` counts rows, the rest read a field.
+            let args_len = if op == CollectionOp::Count {
+                0
+            } else {
+                FnAggregate::FIELD_ARGS_LEN
+            };
+
+            if aggregation_call.children.len() != args_len {
+                return Err(SemanalyzerErr::ArityMismatch {
+                    fn_name: op.as_str().into(),
+                    found: aggregation_call.children.len(),
+                    span: aggregation_call.span.clone(),
+                    kind: ArityMismatchKind::Eq(args_len),
+                });
+            }
+
+            let field = match aggregation_call.children.first() {
+                Some(ast_node) => {
+                    let field = Self::expect_field(op.as_str(), 0, ast_node)?;
+                    let field_ty = self.type_of_row_field(row_path.as_ref(), &row_ty, field)?;
+
+                    fields_ty.push(Self::type_of_aggregated(op, 0, &field_ty, &field.span)?);
+                    Some(field.value.clone())
+                }
+                None => {
+                    fields_ty.push(LangType::Primitive(LangPrimitiveType::Int));
+                    None
+                }
+            };
+
+            aggregations.push((pair.key.clone(), op, field));
+        }
+
+        let mut fields_ty = fields_ty.into_iter();
+        let value_ty = match fields_ty.next() {
+            Some(first) if fields_ty.all(|ty| ty == first) => first,
+            _ => LangType::Unknown,
+        };
+
+        Ok(AAstNode::CallAggregate {
+            source: Box::new(source),
+            keys,
+            aggregations,
+            ty: LangType::List(Box::new(LangType::Dict(Box::new(value_ty)))),
+            span: call.span.clone(),
+        })
+    }
+
+    /// Fields of rows are named by String literals.
+    fn expect_field<'b>(
+        fn_name: &'static str,
+        position: usize,
+        ast_node: &'b AstNode,
+    ) -> Result<&'b AstPrimitive, SemanalyzerErr> {
+        match ast_node {
+            AstNode::String(primitive) => Ok(primitive),
+            ast_node => Err(SemanalyzerErr::ArgKindMismatch {
+                fn_name,
+                position,
+                expected: NodeName::STRING,
+                found: ast_node.as_str(),
+                span: ast_node.span().clone(),
+            }),
+        }
+    }
+
+    /// Type of the `field` of a row. Rows of the data are checked
+    /// against the schema, the rest share the type of their values.
+    fn type_of_row_field(
+        &self,
+        row_path: Option<&ResolutionPath>,
+        row_ty: &LangType,
+        field: &AstPrimitive,
+    ) -> Result<LangType, SemanalyzerErr> {
+        let Some(row_path) = row_path else {
+            return Ok(row_ty.clone());
+        };
+
+        let mut path = row_path.clone();
+        path.push(ResolutionPathSegment::Field(field.value.clone()));
+
+        if !self.schema.resolved_schema.contains_key(&path) {
+            return Err(SemanalyzerErr::SchemaPathUndefined {
+                path: path.as_str(),
+                span: field.span.clone(),
+            });
+        }
+
+        Ok(self.type_of_schema_path(&path))
+    }
+
+    // ==================================================================
+    // ANNOTATE AGGREGATE CALL END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE CONVERT CALL START
+    //
+    // .to-int, .to-float, .to-str, .to-bool (Expression)
+    // .to-int-or, .to-float-or, ... (Expression Expression)
+    // .to-int-or-null, .to-float-or-null, ... (Expression)
+    //
+    // 1. The value must be of a primitive type, Null included,
+    //    or a union of primitive types. Dates, times and durations
+    //    convert only to String;
+    // 2. The result is of the target type;
+    // 3. A value that can't be converted fails the program,
+    //    `-or` variants result in the fallback instead, which
+    //    must be of the target type or Null, and `-or-null`
+    //    variants result in Null;
+    // 4. A conversion is nullable if it is an `-or-null` one,
+    //    or if its fallback can be Null;
+    // ==================================================================
+
+    fn annotate_convert_call(
+        &mut self,
+        call: &AstCall,
+        conversion: Conversion,
+        mode: ConversionMode,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        let args_len = match mode {
+            ConversionMode::Fallback => FnConv::FALLBACK_ARGS_LEN,
+            _ => FnConv::ARGS_LEN,
+        };
+
+        if call.children.len() != args_len {
+            return Err(SemanalyzerErr::ArityMismatch {
+                fn_name: conversion.as_str().into(),
+                found: call.children.len(),
+                span: call.span.clone(),
+                kind: ArityMismatchKind::Eq(args_len),
+            });
+        }
+
+        let value = self.annotate_ast_node(&call.children[0], symbol_table)?;
+        let value_ty = Self::type_of(&value, symbol_table);
+
+        // Converting a union of primitives narrows it down.
+        let is_primitive = |ty: &LangType| matches!(ty, LangType::Primitive(_) | LangType::Unknown);
+        let accepted = match &value_ty {
+            LangType::Union(alternatives) => alternatives.iter().all(is_primitive),
+            ty => is_primitive(ty),
+        };
+
+        if !accepted {
+            return Err(SemanalyzerErr::ArgTypeMismatch {
+                fn_name: conversion.as_str().into(),
+                position: 0,
+                expected: NodeName::PRIMITIVE,
+                found: value_ty.as_str(),
+                span: value.span().clone(),
+            });
+        }
+
+        // Alternatives of a union may convert, so only a value
+        // known to be a date, a time or a duration is an error.
+        let is_temporal = Self::temporal_kind(&value_ty).is_some()
+            || value_ty == LangType::Primitive(LangPrimitiveType::Duration);
+
+        if is_temporal && conversion != Conversion::Str {
+            return Err(SemanalyzerErr::ArgTypeMismatch {
+                fn_name: conversion.as_str().into(),
+                position: 0,
+                expected: NodeName::STRING,
+                found: value_ty.as_str(),
+                span: value.span().clone(),
+            });
+        }
+
+        let target = conversion.target();
+        let mut fallback = None;
+
+        if mode == ConversionMode::Fallback {
+            let node = self.annotate_ast_node(&call.children[1], symbol_table)?;
+            let ty = Self::type_of(&node, symbol_table);
+
+            if !target.accepts(&ty) && ty != LangType::Primitive(LangPrimitiveType::Null) {
+                return Err(SemanalyzerErr::ArgTypeMismatch {
+                    fn_name: conversion.as_str().into(),
+                    position: 1,
+                    expected: target.as_str(),
+                    found: ty.as_str(),
+                    span: node.span().clone(),
+                });
+            }
+
+            fallback = Some(Box::new(node));
+        }
+
+        let nullable = match (mode, &fallback) {
+            (ConversionMode::Null, _) => true,
+            (ConversionMode::Fallback, Some(fallback)) => {
+                Self::nullable_type_of(fallback, symbol_table).is_nullable()
+            }
+            _ => false,
+        };
+
+        Ok(AAstNode::CallConvert {
+            conversion,
+            mode,
+            value: Box::new(value),
+            fallback,
+            ty: target,
+            nullable,
+            span: call.span.clone(),
+        })
+    }
+
+    // ==================================================================
+    // ANNOTATE CONVERT CALL END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE NARROW CALL START
+    //
+    // .narrow (Expression String)
+    //
+    // 1. Exactly 2 arguments;
+    // 2. The first argument is a value of a union, or a value
+    //    of an unknown type;
+    // 3. The second argument is a literal name of the type the
+    //    value is asserted to be of, which must be one of the
+    //    alternatives of the union;
+    // 4. The result is of the asserted type, and if the value
+    //    comes from the data, its schema path continues with
+    //    the alternative;
+    // 5. A value of another alternative fails the program;
+    // ==================================================================
+
+    fn annotate_narrow_call(
+        &mut self,
+        call: &AstCall,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        if call.children.len() != FnNarrow::ARGS_LEN {
+            return Err(SemanalyzerErr::ArityMismatch {
+                fn_name: FnNarrow::LEXEME.into(),
+                found: call.children.len(),
+                span: call.span.clone(),
+                kind: ArityMismatchKind::Eq(FnNarrow::ARGS_LEN),
+            });
+        }
+
+        let value = self.annotate_ast_node(&call.children[0], symbol_table)?;
+        let alternative_arg = &*call.children[1];
+
+        let AstNode::String(alternative) = alternative_arg else {
+            return Err(SemanalyzerErr::ArgKindMismatch {
+                fn_name: FnNarrow::LEXEME,
+                position: 1,
+                expected: NodeName::STRING,
+                found: alternative_arg.as_str(),
+                span: alternative_arg.span().clone(),
+            });
+        };
+
+        let undefined = || SemanalyzerErr::UnionAlternativeUndefined {
+            alternative: alternative.value.clone(),
+            span: alternative.span.clone(),
+        };
+
+        let ty = match Self::type_of(&value, symbol_table) {
+            LangType::Union(alternatives) => alternatives
+                .into_iter()
+                .find(|ty| ty.as_str() == alternative.value)
+                .ok_or_else(undefined)?,
+            LangType::Unknown => {
+                Self::type_of_alternative(&alternative.value).ok_or_else(undefined)?
+            }
+            ty => {
+                return Err(SemanalyzerErr::ArgTypeMismatch {
+                    fn_name: FnNarrow::LEXEME.into(),
+                    position: 0,
+                    expected: NodeName::UNION,
+                    found: ty.as_str(),
+                    span: value.span().clone(),
+                });
+            }
+        };
+
+        Ok(AAstNode::CallNarrow {
+            value: Box::new(value),
+            alternative: alternative.value.clone(),
+            ty,
+            span: call.span.clone(),
+        })
+    }
+
+    /// Type a value of an unknown type can be narrowed to.
+    fn type_of_alternative(name: &str) -> Option<LangType> {
+        let ty = match name {
+            NodeName::INT => LangType::Primitive(LangPrimitiveType::Int),
+            NodeName::FLOAT => LangType::Primitive(LangPrimitiveType::Float),
+            NodeName::STRING => LangType::Primitive(LangPrimitiveType::String),
+            NodeName::BOOL => LangType::Primitive(LangPrimitiveType::Bool),
+            NodeName::DATE => LangType::Primitive(LangPrimitiveType::Date),
+            NodeName::TIME => LangType::Primitive(LangPrimitiveType::Time),
+            NodeName::DATETIME => LangType::Primitive(LangPrimitiveType::DateTime),
+            NodeName::DURATION => LangType::Primitive(LangPrimitiveType::Duration),
+            NodeName::LIST => LangType::List(Box::new(LangType::Unknown)),
+            NodeName::DICT => LangType::Dict(Box::new(LangType::Unknown)),
+            _ => return None,
+        };
+
+        Some(ty)
+    }
+
+    // ==================================================================
+    // ANNOTATE NARROW CALL END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE THREAD CALL START
+    //
+    // .-> (Expression Call*)
+    // .->> (Expression Call*)
+    //
+    // 1. Every argument after the first one must be a call;
+    // 2. The first argument is inserted as the first argument of
+    //    the next call for .->, or as the last one for .->>, the
+    //    call is inserted into the one after it and so on;
+    // 3. The rewritten calls keep their spans, so errors point at
+    //    the calls as they are written;
+    // ==================================================================
+
+    fn annotate_thread_call(
+        &mut self,
+        call: &AstCall,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        let fn_name = if call.lexeme == FnThread::FIRST {
+            FnThread::FIRST
+        } else {
+            FnThread::LAST
+        };
+
+        if call.children.len() < FnThread::MIN_ARGS_LEN {
+            return Err(SemanalyzerErr::ArityMismatch {
+                fn_name: fn_name.into(),
+                found: call.children.len(),
+                span: call.span.clone(),
+                kind: ArityMismatchKind::MoreEq(FnThread::MIN_ARGS_LEN),
+            });
+        }
+
+        let mut threaded = (*call.children[0]).clone();
+
+        for (position, ast_node) in call.children.iter().enumerate().skip(1) {
+            let AstNode::Call(step) = &**ast_node else {
+                return Err(SemanalyzerErr::ArgKindMismatch {
+                    fn_name,
+                    position,
+                    expected: NodeName::CALL,
+                    found: ast_node.as_str(),
+                    span: ast_node.span().clone(),
+                });
+            };
+
+            let mut step = step.clone();
+
+            if fn_name == FnThread::FIRST {
+                step.children.insert(0, Box::new(threaded));
+            } else {
+                step.children.push(Box::new(threaded));
+            }
+
+            threaded = AstNode::Call(step);
+        }
+
+        self.annotate_ast_node(&threaded, symbol_table)
+    }
+
+    // ==================================================================
+    // ANNOTATE THREAD CALL END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE CONDITIONAL CALL START
+    //
+    // .if (Expression Expression Expression)
+    // .cond ((Expression Expression)+ Expression?)
+    // .when (Expression Expression+)
+    //
+    // 1. Conditions must be Bool;
+    // 2. .if evaluates to one of its branches;
+    // 3. .cond takes pairs of a condition and a value, an odd
+    //    trailing argument is a default value;
+    // 4. .cond without a default and .when evaluate to Null if
+    //    no condition holds;
+    // 5. The result type is unified from the types of branches,
+    //    incompatible branch types are an error;
+    // 6. The result is nullable if any branch can be Null;
+    // ==================================================================
+
+    fn annotate_if_call(
+        &mut self,
+        call: &AstCall,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        if call.children.len() != FnIf::ARGS_LEN {
+            return Err(SemanalyzerErr::ArityMismatch {
+                fn_name: FnIf::LEXEME.into(),
+                found: call.children.len(),
+                span: call.span.clone(),
+                kind: ArityMismatchKind::Eq(FnIf::ARGS_LEN),
+            });
+        }
+
+        let cond = self.annotate_condition(FnIf::LEXEME, 0, &call.children[0], symbol_table)?;
+        let then_branch = self.annotate_ast_node(&call.children[1], symbol_table)?;
+        let else_branch = self.annotate_ast_node(&call.children[2], symbol_table)?;
+
+        let ty = Self::unify_branches(FnIf::LEXEME, [&then_branch, &else_branch], symbol_table)?;
+
+        Ok(AAstNode::CallIf {
+            cond: Box::new(cond),
+            then_branch: Box::new(then_branch),
+            else_branch: Box::new(else_branch),
+            ty,
+            span: call.span.clone(),
+        })
+    }
+
+    fn annotate_cond_call(
+        &mut self,
+        call: &AstCall,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        if call.children.len() < FnCond::MIN_ARGS_LEN {
+            return Err(SemanalyzerErr::ArityMismatch {
+                fn_name: FnCond::LEXEME.into(),
+                found: call.children.len(),
+                span: call.span.clone(),
+                kind: ArityMismatchKind::MoreEq(FnCond::MIN_ARGS_LEN),
+            });
+        }
+
+        let mut arms: Vec<(Box<AAstNode>, Box<AAstNode>)> = vec![];
+        let mut default = None;
+
+        for (idx, chunk) in call.children.chunks(2).enumerate() {
+            let position = idx * 2;
+
+            match chunk {
+                [cond, value] => {
+                    let cond =
+                        self.annotate_condition(FnCond::LEXEME, position, cond, symbol_table)?;
+                    let value = self.annotate_ast_node(value, symbol_table)?;
+                    arms.push((Box::new(cond), Box::new(value)));
+                }
+                [value] => default = Some(Box::new(self.annotate_ast_node(value, symbol_table)?)),
+                _ => unreachable!(),
+            }
+        }
+
+        let null = AAstNode::Null {
+            span: call.span.clone(),
+        };
+        let branches = arms
+            .iter()
+            .map(|(_, value)| &**value)
+            .chain([default.as_deref().unwrap_or(&null)]);
+
+        let ty = Self::unify_branches(FnCond::LEXEME, branches, symbol_table)?;
+
+        Ok(AAstNode::CallCond {
+            arms,
+            default,
+            ty,
+            span: call.span.clone(),
+        })
+    }
+
+    fn annotate_when_call(
+        &mut self,
+        call: &AstCall,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        if call.children.len() < FnWhen::MIN_ARGS_LEN {
+            return Err(SemanalyzerErr::ArityMismatch {
+                fn_name: FnWhen::LEXEME.into(),
+                found: call.children.len(),
+                span: call.span.clone(),
+                kind: ArityMismatchKind::MoreEq(FnWhen::MIN_ARGS_LEN),
+            });
+        }
+
+        let cond = self.annotate_condition(FnWhen::LEXEME, 0, &call.children[0], symbol_table)?;
+        let mut body: Vec<Box<AAstNode>> = vec![];
+
+        for ast_node in call.children.iter().skip(1) {
+            body.push(Box::new(self.annotate_ast_node(ast_node, symbol_table)?));
+        }
+
+        // Null unifies with anything, so the type is the
+        // type of the last expression that can be Null.
+        let ty = Self::nullable_type_of(body.last().unwrap(), symbol_table).nullable();
+
+        Ok(AAstNode::CallWhen {
+            cond: Box::new(cond),
+            body,
+            ty,
+            span: call.span.clone(),
+        })
+    }
+
+    fn annotate_condition(
+        &mut self,
+        fn_name: &'static str,
+        position: usize,
+        ast_node: &AstNode,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        let cond = self.annotate_ast_node(ast_node, symbol_table)?;
+
+        match Self::type_of(&cond, symbol_table) {
+            LangType::Primitive(LangPrimitiveType::Bool) | LangType::Unknown => {
+                self.note_use(
+                    &cond,
+                    &LangType::Primitive(LangPrimitiveType::Bool),
+                    symbol_table,
+                );
+                Ok(cond)
+            }
+            ty => Err(SemanalyzerErr::ArgTypeMismatch {
+                fn_name: fn_name.into(),
+                position,
+                expected: NodeName::BOOL,
+                found: ty.as_str(),
+                span: cond.span().clone(),
+            }),
+        }
+    }
+
+    fn unify_branches<'b>(
+        fn_name: &'static str,
+        branches: impl IntoIterator<Item = &'b AAstNode>,
+        symbol_table: &SymbolTable,
+    ) -> Result<LangType, SemanalyzerErr> {
+        let mut unified: Option<LangType> = None;
+
+        for branch in branches {
+            let ty = Self::nullable_type_of(branch, symbol_table);
+
+            unified = match unified {
+                None => Some(ty),
+                Some(unified) => match unified.unify(&ty) {
+                    Some(ty) => Some(ty),
+                    None => {
+                        return Err(SemanalyzerErr::BranchTypeMismatch {
+                            fn_name,
+                            expected: unified.as_str(),
+                            found: ty.as_str(),
+                            span: branch.span().clone(),
+                        });
+                    }
+                },
+            };
+        }
+
+        // Conditionals have at least one branch.
+        Ok(unified.unwrap())
+    }
+
+    // ==================================================================
+    // ANNOTATE CONDITIONAL CALL END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE GET CALL START
+    //
+    // .get (Expression Expression+)
+    //
+    // 1. Min 2 arguments, or 1 if the first argument is a slot;
+    // 2. First argument is a value to get the data from;
+    // 3. The rest of arguments are keys that are applied one by one,
+    //    Int keys for lists and String keys for dicts;
+    // 4. If the first argument is a slot, keys are resolved against
+    //    the data schema, so the String keys must be literals;
+    // 5. Accessing a path that is not defined in the data schema
+    //    is an error;
+    // 6. Slot without keys, inside or outside of `.get`, is the whole
+    //    data typed by the root of the schema;
+    // ==================================================================
+
+    fn annotate_get_call(
+        &mut self,
+        call: &AstCall,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        let min_args_len = match call.children.first().map(|child| &**child) {
+            Some(AstNode::Slot(_)) => FnGet::SLOT_MIN_ARGS_LEN,
+            _ => FnGet::MIN_ARGS_LEN,
+        };
+
+        if call.children.len() < min_args_len {
+            return Err(SemanalyzerErr::ArityMismatch {
+                fn_name: FnGet::LEXEME.into(),
+                found: call.children.len(),
+                span: call.span.clone(),
+                kind: ArityMismatchKind::MoreEq(min_args_len),
+            });
+        }
+
+        match &**call.children.first().unwrap() {
+            AstNode::Slot(primitive) => {
+                self.annotate_get_slot(primitive, &call.children[1..], &call.span, symbol_table)
+            }
+            target => {
+                let target = self.annotate_ast_node(target, symbol_table)?;
+                let mut keys: Vec<Box<AAstNode>> = vec![];
+
+                for ast_node in call.children.iter().skip(1) {
+                    keys.push(Box::new(self.annotate_ast_node(ast_node, symbol_table)?));
+                }
+
+                // Values of the data are checked against the schema
+                // the same way as `@data` itself.
+                let ty = match self.schema_path_of(&target) {
+                    Some(mut path) => {
+                        self.extend_schema_path(&mut path, &keys, symbol_table)?;
+                        self.type_of_schema_path(&path)
+                    }
+                    None => {
+                        let mut ty = Self::type_of(&target, symbol_table);
+
+                        for (idx, key) in keys.iter().enumerate() {
+                            ty = Self::type_of_key_access(ty, key, idx + 1, symbol_table)?;
+                        }
+
+                        ty
+                    }
+                };
+
+                Ok(AAstNode::CallGet {
+                    target: Box::new(target),
+                    keys,
+                    ty,
+                    span: call.span.clone(),
+                })
+            }
+        }
+    }
+
+    /// Type of the value that is accessed with the `key` inside
+    /// of the value of the `ty` type.
+    fn type_of_key_access(
+        ty: LangType,
+        key: &AAstNode,
+        position: usize,
+        symbol_table: &SymbolTable,
+    ) -> Result<LangType, SemanalyzerErr> {
+        let key_ty = Self::type_of(key, symbol_table);
+        let int = LangType::Primitive(LangPrimitiveType::Int);
+        let string = LangType::Primitive(LangPrimitiveType::String);
+
+        let (expected, item_ty) = match ty {
+            LangType::List(item_ty) => (int, *item_ty),
+            LangType::Dict(item_ty) => (string, *item_ty),
+            LangType::Unknown => return Ok(LangType::Unknown),
+            ty => {
+                // Container type is wrong, so we guess which
+                // one was intended by the type of the key.
+                let expected = if key_ty == string {
+                    NodeName::DICT
+                } else {
+                    NodeName::LIST
+                };
+
+                return Err(SemanalyzerErr::ArgTypeMismatch {
+                    fn_name: FnGet::LEXEME.into(),
+                    position: position - 1,
+                    expected,
+                    found: ty.as_str(),
+                    span: key.span().clone(),
+                });
+            }
+        };
+
+        if !expected.accepts(&key_ty) {
+            return Err(SemanalyzerErr::ArgTypeMismatch {
+                fn_name: FnGet::LEXEME.into(),
+                position,
+                expected: expected.as_str(),
+                found: key_ty.as_str(),
+                span: key.span().clone(),
+            });
+        }
+
+        Ok(item_ty)
+    }
+
+    /// Keys are resolved from the root of the schema, so a slot
+    /// without keys is typed by the whole data.
+    fn annotate_get_slot(
+        &mut self,
+        slot: &AstPrimitive,
+        key_nodes: &[Box<AstNode>],
+        span: &Span,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        if slot.value != SlotName::DATA {
+            return Err(SemanalyzerErr::SlotUnknown {
+                span: slot.span.clone(),
+            });
+        }
+
+        let mut keys: Vec<Box<AAstNode>> = vec![];
+
+        for ast_node in key_nodes {
+            keys.push(Box::new(self.annotate_ast_node(ast_node, symbol_table)?));
+        }
+
+        let mut path = ResolutionPath::new();
+        self.extend_schema_path(&mut path, &keys, symbol_table)?;
+
+        // Keys are checked one by one, so only the root itself
+        // can be missing here, if there is no data schema.
+        let Some(descriptor) = self.schema.resolved_schema.get(&path) else {
+            return Err(SemanalyzerErr::SchemaPathUndefined {
+                path: path.as_str(),
+                span: slot.span.clone(),
+            });
+        };
+
+        Ok(AAstNode::CallGetSlot {
+            slot: slot.value.clone(),
+            ty: self.type_of_schema_path(&path),
+            nullable: descriptor.nullable,
+            optional: descriptor.optional,
+            path,
+            keys,
+            span: span.clone(),
+        })
+    }
+
+    /// Applies keys of `.get` to the schema path one by one. String
+    /// literals are fields, any Int is an abstract index.
+    fn extend_schema_path(
+        &self,
+        path: &mut ResolutionPath,
+        keys: &[Box<AAstNode>],
+        symbol_table: &SymbolTable,
+    ) -> Result<(), SemanalyzerErr> {
+        let int = LangType::Primitive(LangPrimitiveType::Int);
+
+        for (idx, key) in keys.iter().enumerate() {
+            let key_ty = Self::type_of(key, symbol_table);
+
+            // Alternatives of a union have different paths, so the
+            // value must be narrowed before anything is accessed.
+            if let Some(descriptor) = self.schema.resolved_schema.get(path)
+                && descriptor.dtype == SchemaDataType::Union
+            {
+                return Err(SemanalyzerErr::UnionNotNarrowed {
+                    span: key.span().clone(),
+                });
+            }
+
+            let segment = match &**key {
+                AAstNode::String { value, .. } => ResolutionPathSegment::Field(value.clone()),
+                // Any index resolves to the same type.
+                _ if int.accepts(&key_ty) => ResolutionPathSegment::AbstractIndex,
+                _ => {
+                    return Err(SemanalyzerErr::ArgTypeMismatch {
+                        fn_name: FnGet::LEXEME.into(),
+                        position: idx + 1,
+                        expected: NodeName::INT,
+                        found: key_ty.as_str(),
+                        span: key.span().clone(),
+                    });
+                }
+            };
+
+            path.push(segment);
+
+            // Check every step in order to point at the exact key.
+            if !self.schema.resolved_schema.contains_key(path) {
+                return Err(SemanalyzerErr::SchemaPathUndefined {
+                    path: path.as_str(),
+                    span: key.span().clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Schema path of the value if it is known to come from the data.
+    fn schema_path_of(&self, aast_node: &AAstNode) -> Option<ResolutionPath> {
+        match aast_node {
+            AAstNode::CallGetSlot { path, .. } => Some(path.clone()),
+            AAstNode::SymbolRef { symbol_id, .. } => self.schema_paths.get(symbol_id).cloned(),
+            AAstNode::CallNarrow {
+                value, alternative, ..
+            } => self.schema_path_of(value).map(|mut path| {
+                path.push(ResolutionPathSegment::Assertion(alternative.clone()));
+                path
+            }),
+            // These keep the items of the source as they are.
+            AAstNode::CallCollection {
+                op:
+                    CollectionOp::Filter
+                    | CollectionOp::SortBy
+                    | CollectionOp::Take
+                    | CollectionOp::Drop,
+                source,
+                ..
+            } => self.schema_path_of(source),
+            _ => None,
+        }
+    }
+
+    /// Maps the schema type of the value at the `path` to the
+    /// language type. Path must exist in the schema.
+    fn type_of_schema_path(&self, path: &ResolutionPath) -> LangType {
+        let descriptor = self.schema.resolved_schema.get(path).unwrap();
+
+        match descriptor.dtype {
+            SchemaDataType::Int => LangType::Primitive(LangPrimitiveType::Int),
+            SchemaDataType::Float => LangType::Primitive(LangPrimitiveType::Float),
+            SchemaDataType::String => LangType::Primitive(LangPrimitiveType::String),
+            SchemaDataType::Bool => LangType::Primitive(LangPrimitiveType::Bool),
+            SchemaDataType::Date(_) => LangType::Primitive(LangPrimitiveType::Date),
+            SchemaDataType::Time(_) => LangType::Primitive(LangPrimitiveType::Time),
+            SchemaDataType::DateTime(_) => LangType::Primitive(LangPrimitiveType::DateTime),
+            SchemaDataType::Duration => LangType::Primitive(LangPrimitiveType::Duration),
+            SchemaDataType::ListAbstract | SchemaDataType::ListFixed(_) => {
+                let mut item_path = path.clone();
+                item_path.push(ResolutionPathSegment::AbstractIndex);
+                LangType::List(Box::new(self.type_of_schema_path(&item_path)))
+            }
+            SchemaDataType::Dict => {
+                // Direct children of the dict are its fields.
+                let mut fields_ty = self
+                    .schema
+                    .resolved_schema
+                    .keys()
+                    .filter(|key| key.len() == path.len() + 1 && key.starts_with(path))
+                    .map(|key| self.type_of_schema_path(key));
+
+                let ty = match fields_ty.next() {
+                    Some(first) if fields_ty.all(|ty| ty == first) => first,
+                    _ => LangType::Unknown,
+                };
+
+                LangType::Dict(Box::new(ty))
+            }
+            SchemaDataType::Union => {
+                let mut alternatives = self
+                    .schema
+                    .resolved_schema
+                    .keys()
+                    .filter(|key| key.len() == path.len() + 1 && key.starts_with(path))
+                    .collect::<Vec<_>>();
+
+                // HashMap doesn't keep any order.
+                alternatives.sort_by_key(|key| key.last().unwrap().as_str());
+
+                LangType::Union(
+                    alternatives
+                        .into_iter()
+                        .map(|key| self.type_of_schema_path(key))
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    // ==================================================================
+    // ANNOTATE GET CALL END
+    // ==================================================================
+
+    // ==================================================================
+    // TYPE OF START
+    //
+    // Derives a type of already annotated node. Used to register
+    // symbols that are bound to arbitrary expressions.
+    // ==================================================================
+
+    fn type_of(aast_node: &AAstNode, symbol_table: &SymbolTable) -> LangType {
+        Self::nullable_type_of(aast_node, symbol_table)
+            .non_null()
+            .clone()
+    }
+
+    /// Type of the node that keeps whether it can be Null, so it
+    /// goes along with symbols and results of branching.
+    fn nullable_type_of(aast_node: &AAstNode, symbol_table: &SymbolTable) -> LangType {
+        match aast_node {
+            AAstNode::Int { .. } => LangType::Primitive(LangPrimitiveType::Int),
+            AAstNode::Float { .. } => LangType::Primitive(LangPrimitiveType::Float),
+            AAstNode::String { .. } => LangType::Primitive(LangPrimitiveType::String),
+            AAstNode::Bool { .. } => LangType::Primitive(LangPrimitiveType::Bool),
+            AAstNode::Null { .. } => LangType::Primitive(LangPrimitiveType::Null),
+            AAstNode::CallDefine { value, .. } => Self::nullable_type_of(value, symbol_table),
+            // Params might have been narrowed down by the body.
+            AAstNode::CallFn { params, body, .. } => LangType::Function {
+                params: params
+                    .iter()
+                    .map(|symbol_id| symbol_table.symbols.get(symbol_id).unwrap().ty.clone())
+                    .collect(),
+                ret: Box::new(Self::nullable_type_of(body.last().unwrap(), symbol_table)),
+            },
+            AAstNode::Call { symbol_id, .. } => {
+                match &symbol_table.symbols.get(symbol_id).unwrap().ty {
+                    LangType::Function { ret, .. } => *ret.clone(),
+                    _ => LangType::Unknown,
+                }
+            }
+            AAstNode::CallGetSlot {
+                ty,
+                nullable,
+                optional,
+                ..
+            } if *nullable || *optional => ty.clone().nullable(),
+            AAstNode::CallConvert { ty, nullable, .. } if *nullable => ty.clone().nullable(),
+            AAstNode::CallOp { ty, .. }
+            | AAstNode::CallCollection { ty, .. }
+            | AAstNode::CallAggregate { ty, .. }
+            | AAstNode::CallConvert { ty, .. }
+            | AAstNode::CallNarrow { ty, .. }
+            | AAstNode::CallIf { ty, .. }
+            | AAstNode::CallCond { ty, .. }
+            | AAstNode::CallWhen { ty, .. }
+            | AAstNode::CallGet { ty, .. }
+            | AAstNode::CallGetSlot { ty, .. } => ty.clone(),
+            AAstNode::List { items, .. } => LangType::List(Box::new(Self::common_type_of(
+                items.iter().map(|item| &**item),
+                symbol_table,
+            ))),
+            AAstNode::Dict { pairs, .. } => LangType::Dict(Box::new(Self::common_type_of(
+                pairs.iter().map(|(_, value)| &**value),
+                symbol_table,
+            ))),
+            // `.let` evaluates to its last expression, and the
+            // body always has at least one expression.
+            AAstNode::CallLet { body, .. } => {
+                Self::nullable_type_of(body.last().unwrap(), symbol_table)
+            }
+            // Every reference has been resolved against the symbol
+            // table already, so the symbol must be there.
+            AAstNode::SymbolRef { symbol_id, .. } => {
+                symbol_table.symbols.get(symbol_id).unwrap().ty.clone()
+            }
+        }
+    }
+
+    fn common_type_of<'b>(
+        mut aast_nodes: impl Iterator<Item = &'b AAstNode>,
+        symbol_table: &SymbolTable,
+    ) -> LangType {
+        let Some(first) = aast_nodes.next() else {
+            return LangType::Unknown;
+        };
+
+        let ty = Self::type_of(first, symbol_table);
+
+        if aast_nodes.all(|aast_node| Self::type_of(aast_node, symbol_table) == ty) {
+            ty
+        } else {
+            LangType::Unknown
+        }
+    }
+
+    // ==================================================================
+    // TYPE OF END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE CALL START
+    // ==================================================================
+
+    fn annotate_call(
+        &mut self,
+        call: &AstCall,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        match call.lexeme.as_str() {
+            FnDefine::LEXEME => self.annotate_define_call(call, symbol_table),
+            FnLet::LEXEME => self.annotate_let_call(call, symbol_table),
+            FnFn::LEXEME => self.annotate_fn_call(call, &[], symbol_table),
+            FnGet::LEXEME => self.annotate_get_call(call, symbol_table),
+            FnIf::LEXEME => self.annotate_if_call(call, symbol_table),
+            FnCond::LEXEME => self.annotate_cond_call(call, symbol_table),
+            FnWhen::LEXEME => self.annotate_when_call(call, symbol_table),
+            FnAggregate::LEXEME => self.annotate_aggregate_call(call, symbol_table),
+            FnNarrow::LEXEME => self.annotate_narrow_call(call, symbol_table),
+            FnThread::FIRST | FnThread::LAST => self.annotate_thread_call(call, symbol_table),
+            lexeme => {
+                if let Some(op) = Op::from_lexeme(lexeme) {
+                    self.annotate_op_call(call, op, symbol_table)
+                } else if let Some(op) = CollectionOp::from_lexeme(lexeme) {
+                    self.annotate_collection_call(call, op, symbol_table)
+                } else if let Some((conversion, mode)) = Conversion::from_lexeme(lexeme) {
+                    self.annotate_convert_call(call, conversion, mode, symbol_table)
+                } else {
+                    self.annotate_symbol_call(call, symbol_table)
+                }
+            }
+        }
+    }
+
+    // ==================================================================
+    // ANNOTATE SYMBOL CALL START
+    //
+    // .identifier (Expression*)
+    //
+    // 1. Identifier must be bound to a function;
+    // 2. Number of arguments must match the number of params;
+    // 3. Types of arguments must match the types of params
+    //    if they are known, see rule 9 of `.fn`;
+    // 4. Calling an identifier of unknown type narrows its
+    //    type down to a function;
+    // ==================================================================
+
+    fn annotate_symbol_call(
+        &mut self,
+        call: &AstCall,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        let Some((symbol_id, depth)) = self.scope_stack.resolve(&call.lexeme) else {
+            return Err(SemanalyzerErr::UnknownFunction {
+                span: call.span.clone(),
+            });
+        };
+
+        self.used.insert(symbol_id);
+        self.capture(symbol_id, depth, symbol_table);
+
+        let mut args: Vec<Box<AAstNode>> = vec![];
+
+        for ast_node in call.children.iter() {
+            args.push(Box::new(self.annotate_ast_node(ast_node, symbol_table)?));
+        }
+
+        // Span of the callee name right after the dot.
+        let callee_span = Span {
+            start: call.span.start + 1,
+            end: call.span.start + 1 + call.lexeme.len(),
+        };
+
+        let descriptor = symbol_table.symbols.get(&symbol_id).unwrap();
+
+        match descriptor.ty.non_null().clone() {
+            LangType::Function { params, .. } => {
+                if params.len() != args.len() {
+                    return Err(SemanalyzerErr::ArityMismatch {
+                        fn_name: call.lexeme.clone().into(),
+                        kind: ArityMismatchKind::Eq(params.len()),
+                        found: args.len(),
+                        span: callee_span,
+                    });
+                }
+
+                for (position, (param, arg)) in params.iter().zip(args.iter()).enumerate() {
+                    let found = Self::type_of(arg, symbol_table);
+
+                    if !param.accepts(&found) {
+                        return Err(SemanalyzerErr::ArgTypeMismatch {
+                            fn_name: call.lexeme.clone().into(),
+                            position,
+                            expected: param.as_str(),
+                            found: found.as_str(),
+                            span: callee_span,
+                        });
+                    }
+
+                    self.note_use(arg, param, symbol_table);
+                }
+            }
+            LangType::Unknown => {
+                let ty = LangType::Function {
+                    params: vec![LangType::Unknown; args.len()],
+                    ret: Box::new(LangType::Unknown),
+                };
+                symbol_table.symbols.get_mut(&symbol_id).unwrap().ty = ty;
+            }
+            ty => {
+                return Err(SemanalyzerErr::NotCallable {
+                    found: ty.as_str(),
+                    span: callee_span,
+                });
+            }
+        }
+
+        Ok(AAstNode::Call {
+            symbol_id,
+            depth,
+            args,
+            span: call.span.clone(),
+        })
+    }
+
+    // ==================================================================
+    // ANNOTATE SYMBOL CALL END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE CALL END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE LIST START
+    // ==================================================================
+
+    fn annotate_list(
+        &mut self,
+        compound: &AstCompound,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        let mut items: Vec<Box<AAstNode>> = vec![];
+
+        for ast_node in compound.children.iter() {
+            items.push(Box::new(self.annotate_ast_node(ast_node, symbol_table)?));
+        }
+
+        Ok(AAstNode::List {
+            items,
+            span: compound.span.clone(),
+        })
+    }
+
+    // ==================================================================
+    // ANNOTATE LIST END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE DICT START
+    // ==================================================================
+
+    fn annotate_dict(
+        &mut self,
+        compound: &AstCompound,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        let mut pairs: Vec<(String, Box<AAstNode>)> = vec![];
+
+        for ast_node in compound.children.iter() {
+            // Parser guarantees that dict consists of pairs only.
+            let AstNode::DictPair(pair) = &**ast_node else {
+                return Err(SemanalyzerErr::UnsupportedNode {
+                    span: ast_node.span().clone(),
+                });
+            };
+
+            let value = self.annotate_ast_node(&pair.value, symbol_table)?;
+            pairs.push((pair.key.clone(), Box::new(value)));
+        }
+
+        Ok(AAstNode::Dict {
+            pairs,
+            span: compound.span.clone(),
+        })
+    }
+
+    // ==================================================================
+    // ANNOTATE DICT END
+    // ==================================================================
+
+    // ==================================================================
+    // PRIMITIVE ANNOTATIONS START
+    //
+    // Annotations for primitive values Number, String, Bool, Null,
+    // Identifier which we can map almost 1:1 from AstNode to AAstNode.
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE IDENTIFIER REFERENCE START
+    //
+    // Annotates identifier references only.
+    // It means that it captures only identifiers that are
+    // already in scope and just referenced. For example:
+    //
+    // .define (PI 3.1415)
+    // .let ([distance 43]
+    //    .add (PI distance))
+    //
+    // This function takes care of `PI` and `distance` in .add
+    // function call only. Resolution for identifier definition
+    // has to be done in respective functions for handling
+    // semantics for expressions that can define identifiers
+    // like `.let` and `.define`.
+    //
+    // References from inside of `.fn` bodies to the enclosing
+    // scopes are registered as captures of those functions.
+    // ==================================================================
+
+    fn annotate_identifier_reference(
+        &mut self,
+        primitive: &AstPrimitive,
+        symbol_table: &mut SymbolTable,
+    ) -> Result<AAstNode, SemanalyzerErr> {
+        let Some((symbol_id, depth)) = self.scope_stack.resolve(&primitive.value) else {
+            return Err(SemanalyzerErr::SymbolUndefined {
+                span: primitive.span.clone(),
+            });
+        };
+
+        self.used.insert(symbol_id);
+        self.capture(symbol_id, depth, symbol_table);
+
+        Ok(AAstNode::SymbolRef {
+            symbol_id,
+            depth,
+            span: primitive.span.clone(),
+        })
+    }
+
+    fn capture(&mut self, symbol_id: SymbolId, depth: usize, symbol_table: &mut SymbolTable) {
+        // Index of the scope the symbol is defined in.
+        let scope_idx = self.scope_stack.scopes.len() - 1 - depth;
+
+        // Globals live as long as the program does,
+        // so there is no need to capture them.
+        if scope_idx == 0 {
+            return;
+        }
+
+        // Every function between the reference and the definition
+        // must capture the symbol in order to pass it down.
+        for frame in self.fn_frames.iter_mut() {
+            if scope_idx < frame.boundary && !frame.captures.contains(&symbol_id) {
+                frame.captures.push(symbol_id);
+            }
+        }
+
+        if self
+            .fn_frames
+            .iter()
+            .any(|frame| scope_idx < frame.boundary)
+            && let Some(descriptor) = symbol_table.symbols.get_mut(&symbol_id)
+        {
+            descriptor.is_captured = true;
+        }
+    }
+
+    // ==================================================================
+    // ANNOTATE IDENTIFIER REFERENCE END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE INT START
+    // ==================================================================
+
+    fn annotate_int(primitive: &AstPrimitive) -> Result<AAstNode, SemanalyzerErr> {
+        Ok(AAstNode::Int {
+            value: primitive.value.clone(),
+            span: primitive.span.clone(),
+        })
+    }
+
+    // ==================================================================
+    // ANNOTATE INT END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE FLOAT START
+    // ==================================================================
+
+    fn annotate_float(primitive: &AstPrimitive) -> Result<AAstNode, SemanalyzerErr> {
+        Ok(AAstNode::Float {
+            value: primitive.value.clone(),
+            span: primitive.span.clone(),
+        })
+    }
+
+    // ==================================================================
+    // ANNOTATE FLOAT END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE STRING START
+    // ==================================================================
+
+    fn annotate_string(primitive: &AstPrimitive) -> Result<AAstNode, SemanalyzerErr> {
+        Ok(AAstNode::String {
+            value: primitive.value.clone(),
+            span: primitive.span.clone(),
+        })
+    }
+
+    // ==================================================================
+    // ANNOTATE STRING END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE BOOL START
+    // ==================================================================
+
+    fn annotate_bool(primitive: &AstPrimitive) -> Result<AAstNode, SemanalyzerErr> {
+        Ok(AAstNode::Bool {
+            value: primitive.value == Keyword::TRUE,
+            span: primitive.span.clone(),
+        })
+    }
+
+    // ==================================================================
+    // ANNOTATE BOOL END
+    // ==================================================================
+
+    // ==================================================================
+    // ANNOTATE NULL START
+    // ==================================================================
+
+    fn annotate_null(primitive: &AstPrimitive) -> Result<AAstNode, SemanalyzerErr> {
+        Ok(AAstNode::Null {
+            span: primitive.span.clone(),
+        })
+    }
+
+    // ==================================================================
+    // ANNOTATE NULL END
+    // ==================================================================
+
+    // ==================================================================
+    // PRIMITIVE ANNOTATIONS END
+    // ==================================================================
+}
+
+// ==================================================================
 //
-//    // ==================================================================
-//    // PRIMITIVE ANNOTATIONS END
-//    // ==================================================================
-//}
+//  SEMANALYZER END
 //
-//// ==================================================================
-////
-////  SEMANALYZER END
-////
-//// ==================================================================
+// ==================================================================
@@ -63,11 +63,20 @@ impl ScopeStack {
         }
         None
     }
+
+    /// Looks up an identifier in the current (innermost) scope only.
+    /// Used to forbid re-bindings within the same scope while still
+    /// allowing to shadow outer ones.
+    pub fn resolve_local(&self, name: &str) -> Option<SymbolId> {
+        self.scopes
+            .last()
+            .and_then(|scope| scope.bindings.get(name).copied())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{semanalyzer_scope_stack::ScopeStack, semanalyzer_symbol_table::SymbolId};
+    use crate::{scope_stack::ScopeStack, symbol_table::SymbolId};
 
     #[test]
     fn should_create_with_empty_scopes() {
@@ -134,4 +143,15 @@ mod tests {
         stack.push();
         assert_eq!(stack.resolve("name"), Some((SymbolId(1), 1)));
     }
+
+    #[test]
+    fn should_resolve_local_in_current_scope_only() {
+        let mut stack = ScopeStack::new();
+        stack.push();
+        stack.define("outer".to_string(), SymbolId(1));
+        stack.push();
+        stack.define("inner".to_string(), SymbolId(2));
+        assert_eq!(stack.resolve_local("outer"), None);
+        assert_eq!(stack.resolve_local("inner"), Some(SymbolId(2)));
+    }
 }
@@ -89,8 +89,8 @@ impl SymbolTable {
 #[cfg(test)]
 mod tests {
     use crate::{
-        semanalyzer_data_types::{LangPrimitiveType, LangType},
-        semanalyzer_symbol_table::{SymbolId, SymbolTable},
+        data_types::{LangPrimitiveType, LangType},
+        symbol_table::{SymbolId, SymbolTable},
     };
 
     #[test]
@@ -113,6 +113,6 @@ mod tests {
         let descriptor = table.symbols.get(&id).unwrap();
         assert_eq!(descriptor.name, "name".to_string());
         assert_eq!(descriptor.ty, LangType::Primitive(LangPrimitiveType::Int));
-        assert_eq!(descriptor.is_captured, false);
+        assert!(!descriptor.is_captured);
     }
 }
@@ -1,15 +1,90 @@
+// Every test file includes this module but uses only some of the helpers.
+#![allow(dead_code)]
+
 use std::collections::HashMap;
 
 use elise_ast::AstNode;
-use elise_data::binder::DataBindingTable;
+use elise_data::schema_resolver::ResolvedSchema;
 use elise_parser::Prelude;
+use elise_semanalyzer::{
+    Harmony,
+    aast::AAstNode,
+    data_types::{LangPrimitiveType, LangType},
+    symbol_table::SymbolId,
+};
+use elise_shared::shared_errors::errors_semanalyzer::SemanalyzerErr;
 
 pub fn parse(source_code: &str) -> Vec<AstNode> {
-    Prelude::new(&source_code.as_bytes()).parse().unwrap()
+    Prelude::new(source_code.as_bytes()).parse().unwrap()
+}
+
+pub fn empty_schema() -> ResolvedSchema {
+    ResolvedSchema {
+        resolved_schema: HashMap::new(),
+    }
+}
+
+// Last top-level node of the analyzed source.
+pub fn analyze(source_code: &str, schema: &ResolvedSchema) -> Result<AAstNode, SemanalyzerErr> {
+    let ast = parse(source_code);
+    let mut hir = Harmony::new(&ast, schema).analyze()?;
+
+    Ok(hir.aast.pop().unwrap())
 }
 
-pub fn empty_data_bindings() -> DataBindingTable {
-    DataBindingTable {
-        table: HashMap::new(),
+// Type of the last top-level node, which must be a typed call.
+pub fn ty_of(source_code: &str, schema: &ResolvedSchema) -> LangType {
+    match analyze(source_code, schema).unwrap() {
+        AAstNode::CallOp { ty, .. }
+        | AAstNode::CallGet { ty, .. }
+        | AAstNode::CallGetSlot { ty, .. }
+        | AAstNode::CallNarrow { ty, .. }
+        | AAstNode::CallConvert { ty, .. }
+        | AAstNode::CallCollection { ty, .. }
+        | AAstNode::CallAggregate { ty, .. } => ty,
+        aast_node => panic!("Expected typed call, found {aast_node:?}"),
     }
 }
+
+// Literal operands are folded, so the type is taken
+// from a symbol bound to the expression.
+pub fn ty_of_bound(source_code: &str) -> LangType {
+    let ast = parse(&format!(".let([x {source_code}] x)"));
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    hir.symbol_table
+        .symbols
+        .get(&SymbolId(0))
+        .unwrap()
+        .ty
+        .clone()
+}
+
+pub fn int() -> LangType {
+    LangType::Primitive(LangPrimitiveType::Int)
+}
+
+pub fn float() -> LangType {
+    LangType::Primitive(LangPrimitiveType::Float)
+}
+
+pub fn bool() -> LangType {
+    LangType::Primitive(LangPrimitiveType::Bool)
+}
+
+pub fn string() -> LangType {
+    LangType::Primitive(LangPrimitiveType::String)
+}
+
+pub fn list_of(ty: LangType) -> LangType {
+    LangType::List(Box::new(ty))
+}
+
+pub fn dict_of(ty: LangType) -> LangType {
+    LangType::Dict(Box::new(ty))
+}
+
+pub fn nullable(ty: LangType) -> LangType {
+    LangType::Nullable(Box::new(ty))
+}
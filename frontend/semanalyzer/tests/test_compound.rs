@@ -0,0 +1,85 @@
+use elise_semanalyzer::{
+    Harmony,
+    aast::AAstNode,
+    data_types::{LangPrimitiveType, LangType},
+    symbol_table::SymbolId,
+};
+use elise_shared::shared_types::Span;
+
+use crate::common::{empty_schema, parse};
+
+mod common;
+
+#[test]
+fn test_list() {
+    let ast = parse("[1, 2]");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    assert!(hir.symbol_table.symbols.is_empty());
+    assert_eq!(
+        hir.aast,
+        vec![AAstNode::List {
+            items: vec![
+                Box::new(AAstNode::Int {
+                    value: "1".to_string(),
+                    span: Span { start: 1, end: 2 }
+                }),
+                Box::new(AAstNode::Int {
+                    value: "2".to_string(),
+                    span: Span { start: 4, end: 5 }
+                }),
+            ],
+            span: Span { start: 0, end: 6 }
+        }]
+    );
+}
+
+#[test]
+fn test_dict() {
+    let ast = parse(r#"{"a" true}"#);
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    assert_eq!(
+        hir.aast,
+        vec![AAstNode::Dict {
+            pairs: vec![(
+                "a".to_string(),
+                Box::new(AAstNode::Bool {
+                    value: true,
+                    span: Span { start: 5, end: 9 }
+                })
+            )],
+            span: Span { start: 0, end: 10 }
+        }]
+    );
+}
+
+#[test]
+fn test_types_of_compounds() {
+    let ast = parse(r#".let([a [1 2], b [1 "x"], c {"k" 1.5}, d {"k" 1, "j" null}, e []] a)"#);
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let ty = |id: u32| {
+        hir.symbol_table
+            .symbols
+            .get(&SymbolId(id))
+            .unwrap()
+            .ty
+            .clone()
+    };
+
+    assert_eq!(
+        ty(0),
+        LangType::List(Box::new(LangType::Primitive(LangPrimitiveType::Int)))
+    );
+    assert_eq!(ty(1), LangType::List(Box::new(LangType::Unknown)));
+    assert_eq!(
+        ty(2),
+        LangType::Dict(Box::new(LangType::Primitive(LangPrimitiveType::Float)))
+    );
+    assert_eq!(ty(3), LangType::Dict(Box::new(LangType::Unknown)));
+    assert_eq!(ty(4), LangType::List(Box::new(LangType::Unknown)));
+}
@@ -0,0 +1,237 @@
+use elise_semanalyzer::{Harmony, aast::AAstNode};
+use elise_shared::{shared_errors::errors_semanalyzer::SemanalyzerErr, shared_types::Span};
+
+use crate::common::{empty_schema, parse};
+
+mod common;
+
+fn fold(src: &str) -> AAstNode {
+    let ast = parse(src);
+    let schema = empty_schema();
+    let mut hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    hir.aast.pop().unwrap()
+}
+
+fn int(value: &str, start: usize, end: usize) -> AAstNode {
+    AAstNode::Int {
+        value: value.to_string(),
+        span: Span { start, end },
+    }
+}
+
+fn float(value: &str, start: usize, end: usize) -> AAstNode {
+    AAstNode::Float {
+        value: value.to_string(),
+        span: Span { start, end },
+    }
+}
+
+fn bool(value: bool, start: usize, end: usize) -> AAstNode {
+    AAstNode::Bool {
+        value,
+        span: Span { start, end },
+    }
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_folds_int_arithmetic_with_call_span() {
+    assert_eq!(fold(".add(1 2 3)"), int("6", 0, 11));
+    assert_eq!(fold(".sub(10 2 3)"), int("5", 0, 12));
+    assert_eq!(fold(".mul(2 -3)"), int("-6", 0, 10));
+    assert_eq!(fold(".div(7 2)"), int("3", 0, 9));
+    assert_eq!(fold(".mod(7 2)"), int("1", 0, 9));
+    assert_eq!(fold(".neg(7)"), int("-7", 0, 7));
+}
+
+#[test]
+fn test_folds_float_arithmetic() {
+    assert_eq!(fold(".add(1 0.5)"), float("1.5", 0, 11));
+    assert_eq!(fold(".div(1.0 4)"), float("0.25", 0, 11));
+    // Float stays Float even if it has no fraction.
+    assert_eq!(fold(".mul(2 1.5)"), float("3.0", 0, 11));
+}
+
+#[test]
+fn test_folds_nested_ops() {
+    assert_eq!(fold(".add(1 .mul(2 3))"), int("7", 0, 17));
+}
+
+#[test]
+fn test_folds_comparisons() {
+    assert_eq!(fold(".lt(1 2)"), bool(true, 0, 8));
+    assert_eq!(fold(".gte(1 1.5)"), bool(false, 0, 11));
+    assert_eq!(fold(".eq(1 1.0)"), bool(true, 0, 10));
+    assert_eq!(fold(r#".eq("a" "a")"#), bool(true, 0, 12));
+    assert_eq!(fold(".eq(null null)"), bool(true, 0, 14));
+    assert_eq!(fold(".neq(1 null)"), bool(true, 0, 12));
+}
+
+#[test]
+fn test_folds_string_concatenation() {
+    assert_eq!(
+        fold(r#".concat("a" "b" "c")"#),
+        AAstNode::String {
+            value: "abc".to_string(),
+            span: Span { start: 0, end: 20 },
+        }
+    );
+}
+
+#[test]
+fn test_folds_if_with_literal_condition_into_taken_branch() {
+    assert_eq!(fold(".if(.gt(2 1) 1 2)"), int("1", 13, 14));
+    assert_eq!(fold(".if(false 1 2)"), int("2", 12, 13));
+}
+
+#[test]
+fn test_does_not_fold_never_taken_branch() {
+    assert_eq!(fold(".if(true 1 .div(1 0))"), int("1", 9, 10));
+}
+
+#[test]
+fn test_folds_cond_arms() {
+    assert_eq!(fold(".cond(false 1 true 2 3)"), int("2", 19, 20));
+    assert_eq!(
+        fold(".cond(false 1)"),
+        AAstNode::Null {
+            span: Span { start: 0, end: 14 }
+        }
+    );
+}
+
+#[test]
+fn test_drops_false_arms_of_cond() {
+    let node = fold(".fn([c] .cond(false 1 c 2 3))");
+
+    let AAstNode::CallFn { body, .. } = node else {
+        panic!("Expected CallFn");
+    };
+    assert!(matches!(
+        &*body[0],
+        AAstNode::CallCond { arms, default: Some(_), .. } if arms.len() == 1
+    ));
+}
+
+#[test]
+fn test_folds_when_with_literal_condition() {
+    assert_eq!(fold(".when(true 1)"), int("1", 11, 12));
+    assert_eq!(
+        fold(".when(false 1)"),
+        AAstNode::Null {
+            span: Span { start: 0, end: 14 }
+        }
+    );
+}
+
+#[test]
+fn test_inlines_define_constants() {
+    assert_eq!(fold(".define(X 2) X"), int("2", 13, 14));
+    assert_eq!(fold(".define(X 2) .mul(X X)"), int("4", 13, 22));
+}
+
+#[test]
+fn test_inlines_define_constants_inside_fn() {
+    let node = fold(".define(X 2) .fn([a] .add(a X))");
+
+    let AAstNode::CallFn { body, .. } = node else {
+        panic!("Expected CallFn");
+    };
+    let AAstNode::CallOp { args, .. } = &*body[0] else {
+        panic!("Expected CallOp");
+    };
+    assert_eq!(*args[1], int("2", 28, 29));
+}
+
+#[test]
+fn test_does_not_fold_unknown_operands() {
+    let node = fold(".fn([a] .add(a 1))");
+
+    let AAstNode::CallFn { body, .. } = node else {
+        panic!("Expected CallFn");
+    };
+    assert!(matches!(*body[0], AAstNode::CallOp { .. }));
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_division_by_zero_with_call_span() {
+    let ast = parse(".add(1 .div(4 .sub(2 2)))");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::DivisionByZero {
+            span: Span { start: 7, end: 24 }
+        }
+    );
+}
+
+#[test]
+fn test_returns_division_by_zero_for_mod_and_floats() {
+    let schema = empty_schema();
+
+    for src in [".mod(1 0)", ".div(1.5 0)", ".div(1 0.0)"] {
+        let ast = parse(src);
+        let result = Harmony::new(&ast, &schema).analyze();
+
+        assert!(matches!(result, Err(SemanalyzerErr::DivisionByZero { .. })));
+    }
+}
+
+#[test]
+fn test_returns_arithmetic_overflow() {
+    let ast = parse(".add(9223372036854775807 1)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::ArithmeticOverflow {
+            span: Span { start: 0, end: 27 }
+        }
+    );
+}
+
+#[test]
+fn test_returns_arithmetic_overflow_for_neg_and_floats() {
+    let schema = empty_schema();
+
+    for src in [
+        ".neg(-9223372036854775808)",
+        ".mul(1e308 10)",
+        ".div(-9223372036854775808 -1)",
+    ] {
+        let ast = parse(src);
+        let result = Harmony::new(&ast, &schema).analyze();
+
+        assert!(matches!(
+            result,
+            Err(SemanalyzerErr::ArithmeticOverflow { .. })
+        ));
+    }
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
@@ -0,0 +1,318 @@
+use elise_data::schema_resolver::{ResolvedSchema, SchemaResolver};
+use elise_semanalyzer::{
+    HIR, Harmony, aast::AAstNode, config::FnLet, data_types::LangType, symbol_table::SymbolId,
+};
+use elise_shared::{
+    shared_errors::errors_semanalyzer::{SemanalyzerDiag, SemanalyzerErr, SemanalyzerWarn},
+    shared_node_names::NodeName,
+    shared_types::Span,
+};
+
+use crate::common::{empty_schema, int, parse, string};
+
+mod common;
+
+fn schema() -> ResolvedSchema {
+    let ast = parse(
+        r#"
+.schema(
+    .list(
+        .dict(
+            "name" .string()
+            "age"  .int())))
+"#,
+    );
+    SchemaResolver::new(&ast).resolve().unwrap()
+}
+
+// Same rows nested in a field of the data.
+fn nested_schema() -> ResolvedSchema {
+    let ast = parse(
+        r#"
+.schema(
+    .dict(
+        "rows" .list(.dict(
+                   "name" .string()
+                   "age"  .int()))))
+"#,
+    );
+    SchemaResolver::new(&ast).resolve().unwrap()
+}
+
+fn analyze(src: &str, schema: &ResolvedSchema) -> Result<HIR, SemanalyzerErr> {
+    let ast = parse(src);
+    Harmony::new(&ast, schema).analyze()
+}
+
+fn ty_of_symbol(hir: &HIR, name: &str) -> LangType {
+    hir.symbol_table
+        .symbols
+        .values()
+        .find(|descriptor| descriptor.name == name)
+        .unwrap()
+        .ty
+        .clone()
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_binds_list_pattern_to_gets_of_hidden_symbol() {
+    let hir = analyze(".let([[a b] [1 2]] .add(a b))", &schema()).unwrap();
+
+    assert_eq!(ty_of_symbol(&hir, "a"), int());
+    assert_eq!(ty_of_symbol(&hir, "b"), int());
+
+    let AAstNode::CallLet { bindings, .. } = &hir.aast[0] else {
+        panic!("Expected CallLet");
+    };
+    assert_eq!(bindings.len(), 3);
+    assert!(matches!(*bindings[0].1, AAstNode::List { .. }));
+    assert_eq!(
+        bindings[2],
+        (
+            SymbolId(2),
+            Box::new(AAstNode::CallGet {
+                target: Box::new(AAstNode::SymbolRef {
+                    symbol_id: SymbolId(0),
+                    span: Span { start: 9, end: 10 },
+                    depth: 0,
+                }),
+                keys: vec![Box::new(AAstNode::Int {
+                    value: "1".to_string(),
+                    span: Span { start: 9, end: 10 },
+                })],
+                ty: int(),
+                span: Span { start: 9, end: 10 },
+            })
+        )
+    );
+}
+
+#[test]
+fn test_binds_dict_pattern_by_keys() {
+    let hir = analyze(
+        r#".let([{"x" a "y" b} {"x" 1 "y" 2}] .add(a b))"#,
+        &schema(),
+    )
+    .unwrap();
+
+    assert_eq!(ty_of_symbol(&hir, "a"), int());
+    assert_eq!(ty_of_symbol(&hir, "b"), int());
+}
+
+#[test]
+fn test_binds_nested_patterns_with_key_chain() {
+    let hir = analyze(r#".let([[{"a" x}] [{"a" "s"}]] x)"#, &schema()).unwrap();
+
+    assert_eq!(ty_of_symbol(&hir, "x"), string());
+
+    let AAstNode::CallLet { bindings, .. } = &hir.aast[0] else {
+        panic!("Expected CallLet");
+    };
+    let AAstNode::CallGet { keys, .. } = &*bindings[1].1 else {
+        panic!("Expected CallGet");
+    };
+    assert!(matches!(*keys[0], AAstNode::Int { .. }));
+    assert!(matches!(*keys[1], AAstNode::String { .. }));
+}
+
+#[test]
+fn test_types_patterns_of_data_by_schema() {
+    let hir = analyze(
+        r#".let([[{"name" n "age" a}] @data] .concat(n "!"))"#,
+        &schema(),
+    )
+    .unwrap();
+
+    assert_eq!(ty_of_symbol(&hir, "n"), string());
+    assert_eq!(ty_of_symbol(&hir, "a"), int());
+
+    let hir = analyze(
+        r#".let([[{"name" n "age" a}] .get(@data "rows")] .concat(n "!"))"#,
+        &nested_schema(),
+    )
+    .unwrap();
+
+    assert_eq!(ty_of_symbol(&hir, "n"), string());
+    assert_eq!(ty_of_symbol(&hir, "a"), int());
+}
+
+#[test]
+fn test_types_destructured_params_by_hints() {
+    let hir = analyze(r#".map(@data .fn([{"name" n}] n))"#, &schema()).unwrap();
+
+    assert!(matches!(
+        &hir.aast[0],
+        AAstNode::CallCollection { ty, .. } if *ty == LangType::List(Box::new(string()))
+    ));
+
+    let hir = analyze(
+        r#".map(.get(@data "rows") .fn([{"name" n}] n))"#,
+        &nested_schema(),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        &hir.aast[0],
+        AAstNode::CallCollection { ty, .. } if *ty == LangType::List(Box::new(string()))
+    ));
+}
+
+#[test]
+fn test_wraps_body_of_fn_with_destructured_params_in_let() {
+    let hir = analyze(".fn([[a b] c] .add(a b c))", &schema()).unwrap();
+
+    let AAstNode::CallFn { params, body, .. } = &hir.aast[0] else {
+        panic!("Expected CallFn");
+    };
+    assert_eq!(params.len(), 2);
+    assert_eq!(body.len(), 1);
+    assert!(matches!(
+        &*body[0],
+        AAstNode::CallLet { bindings, body, .. } if bindings.len() == 2 && body.len() == 1
+    ));
+}
+
+#[test]
+fn test_warns_about_unused_pattern_bindings() {
+    let ast = parse(".let([[a b] [1 2]] a)");
+    let schema = empty_schema();
+
+    assert_eq!(
+        Harmony::new(&ast, &schema).analyze_recovering().diagnostics,
+        vec![SemanalyzerDiag::Warn(SemanalyzerWarn::UnusedBinding {
+            span: Span { start: 9, end: 10 }
+        })]
+    );
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_schema_path_undefined_for_unknown_key() {
+    assert!(matches!(
+        analyze(r#".map(@data .fn([{"zip" z}] z))"#, &schema()),
+        Err(SemanalyzerErr::SchemaPathUndefined {
+            span: Span { start: 17, end: 22 },
+            ..
+        })
+    ));
+    assert!(matches!(
+        analyze(
+            r#".map(.get(@data "rows") .fn([{"zip" z}] z))"#,
+            &nested_schema()
+        ),
+        Err(SemanalyzerErr::SchemaPathUndefined {
+            span: Span { start: 30, end: 35 },
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_schema_path_undefined_for_unknown_key_of_bound_row() {
+    assert!(matches!(
+        analyze(
+            r#".let([r .get(@data 0)] .let([{"zip" z} r] z))"#,
+            &schema()
+        ),
+        Err(SemanalyzerErr::SchemaPathUndefined { .. })
+    ));
+    assert!(matches!(
+        analyze(
+            r#".let([r .get(@data "rows" 0)] .let([{"zip" z} r] z))"#,
+            &nested_schema()
+        ),
+        Err(SemanalyzerErr::SchemaPathUndefined { .. })
+    ));
+}
+
+#[test]
+fn test_returns_pattern_type_mismatch_if_value_is_not_list() {
+    assert_eq!(
+        analyze(".let([[a] 1] a)", &schema()).unwrap_err(),
+        SemanalyzerErr::PatternTypeMismatch {
+            expected: NodeName::LIST,
+            found: NodeName::INT,
+            span: Span { start: 6, end: 9 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_pattern_type_mismatch_if_value_is_not_dict() {
+    assert!(matches!(
+        analyze(r#".let([{"a" a} [1]] a)"#, &schema()),
+        Err(SemanalyzerErr::PatternTypeMismatch {
+            expected: NodeName::DICT,
+            found: NodeName::LIST,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_pattern_len_mismatch_if_literal_list_differs() {
+    assert_eq!(
+        analyze(".let([[a b c] [1 2]] a)", &schema()).unwrap_err(),
+        SemanalyzerErr::PatternLenMismatch {
+            expected: 3,
+            found: 2,
+            span: Span { start: 6, end: 13 },
+        }
+    );
+    assert_eq!(
+        analyze(r#".let([{"p" [x]} {"p" [1 2]}] x)"#, &schema()).unwrap_err(),
+        SemanalyzerErr::PatternLenMismatch {
+            expected: 1,
+            found: 2,
+            span: Span { start: 11, end: 14 },
+        }
+    );
+    assert!(analyze(".let([[a [b]] [1 [2]]] .add(a b))", &schema()).is_ok());
+}
+
+#[test]
+fn test_returns_arg_kind_mismatch_if_pattern_item_is_not_pattern() {
+    assert_eq!(
+        analyze(".let([[a 1] [1 2]] a)", &schema()).unwrap_err(),
+        SemanalyzerErr::ArgKindMismatch {
+            fn_name: FnLet::LEXEME,
+            position: 0,
+            expected: NodeName::PATTERN,
+            found: NodeName::INT,
+            span: Span { start: 9, end: 10 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_symbol_duplicate_if_pattern_repeats_identifier() {
+    assert_eq!(
+        analyze(".let([[a a] [1 2]] a)", &schema()).unwrap_err(),
+        SemanalyzerErr::SymbolDuplicate {
+            span: Span { start: 9, end: 10 }
+        }
+    );
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
@@ -0,0 +1,139 @@
+use elise_semanalyzer::Harmony;
+use elise_shared::{
+    shared_errors::errors_semanalyzer::{
+        SemanalyzerDiag, SemanalyzerErr, SemanalyzerWarn, Severity,
+    },
+    shared_types::Span,
+};
+
+use crate::common::{empty_schema, parse};
+
+mod common;
+
+fn diagnostics(src: &str) -> Vec<SemanalyzerDiag> {
+    let ast = parse(src);
+    let schema = empty_schema();
+
+    Harmony::new(&ast, &schema).analyze_recovering().diagnostics
+}
+
+fn span(start: usize, end: usize) -> Span {
+    Span { start, end }
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_reports_nothing_for_valid_program() {
+    assert_eq!(diagnostics(".let([a 1] .fn([b] .add(a b)))"), vec![]);
+}
+
+#[test]
+fn test_warns_about_unused_let_binding() {
+    assert_eq!(
+        diagnostics(".let([a 1 b 2] a)"),
+        vec![SemanalyzerDiag::Warn(SemanalyzerWarn::UnusedBinding {
+            span: span(10, 11)
+        })]
+    );
+}
+
+#[test]
+fn test_warns_about_unused_fn_param() {
+    assert_eq!(
+        diagnostics(".fn([a b] a)"),
+        vec![SemanalyzerDiag::Warn(SemanalyzerWarn::UnusedParam {
+            span: span(7, 8)
+        })]
+    );
+}
+
+#[test]
+fn test_counts_calls_as_usages() {
+    assert_eq!(diagnostics(".fn([f] .f())"), vec![]);
+}
+
+#[test]
+fn test_warns_about_shadowing() {
+    assert_eq!(
+        diagnostics(".define(X 1) .let([X 2] .fn([X] X))"),
+        vec![
+            SemanalyzerDiag::Warn(SemanalyzerWarn::Shadowing { span: span(19, 20) }),
+            SemanalyzerDiag::Warn(SemanalyzerWarn::UnusedBinding { span: span(19, 20) }),
+            SemanalyzerDiag::Warn(SemanalyzerWarn::Shadowing { span: span(29, 30) }),
+        ]
+    );
+}
+
+#[test]
+fn test_warns_about_shadowing_by_named_fn() {
+    assert_eq!(
+        diagnostics(".fn([f] .let([x 1] .fn(f [] x)))"),
+        vec![
+            SemanalyzerDiag::Warn(SemanalyzerWarn::UnusedParam { span: span(5, 6) }),
+            SemanalyzerDiag::Warn(SemanalyzerWarn::Shadowing { span: span(23, 24) }),
+        ]
+    );
+}
+
+#[test]
+fn test_keeps_valid_nodes_after_recovery() {
+    let ast = parse(".let([a 1] b) .add(1 2)");
+    let schema = empty_schema();
+    let recovered = Harmony::new(&ast, &schema).analyze_recovering();
+
+    assert_eq!(recovered.hir.aast.len(), 1);
+}
+
+#[test]
+fn test_fail_fast_analysis_ignores_warnings() {
+    let ast = parse(".let([a 1] 2)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(result.is_ok());
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_collects_errors_of_every_top_level_node() {
+    assert_eq!(
+        diagnostics(".add(1 a) .div(1 0) .sub(b 1)"),
+        vec![
+            SemanalyzerDiag::Err(SemanalyzerErr::SymbolUndefined { span: span(7, 8) }),
+            SemanalyzerDiag::Err(SemanalyzerErr::DivisionByZero { span: span(10, 19) }),
+            SemanalyzerDiag::Err(SemanalyzerErr::SymbolUndefined { span: span(25, 26) }),
+        ]
+    );
+}
+
+#[test]
+fn test_sorts_errors_and_warnings_in_source_order() {
+    let diags = diagnostics(".fn([a] 1) .add(1 b)");
+
+    assert_eq!(
+        diags.iter().map(|diag| diag.severity()).collect::<Vec<_>>(),
+        vec![Severity::Warning, Severity::Error]
+    );
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
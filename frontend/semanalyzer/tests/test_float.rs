@@ -1,17 +1,17 @@
-use elise_semanalyzer::{Harmony, semanalyzer_aast::AAstNode};
+use elise_semanalyzer::{Harmony, aast::AAstNode};
 use elise_shared::shared_types::Span;
 
-use crate::common::{empty_data_bindings, parse};
+use crate::common::{empty_schema, parse};
 
 mod common;
 
 #[test]
 fn test_floats() {
     let ast = parse("-1.2e2, -3.34, 1.5e-2, 5.6, 3e5, 94E2");
-    let data_bindings = empty_data_bindings();
-    let hir = Harmony::new(&ast, &data_bindings).analyze().unwrap();
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
 
-    assert_eq!(hir.symbol_table.symbols.is_empty(), true);
+    assert!(hir.symbol_table.symbols.is_empty());
     assert_eq!(
         hir.aast,
         vec![
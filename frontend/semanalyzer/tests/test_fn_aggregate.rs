@@ -0,0 +1,314 @@
+use elise_data::schema_resolver::{ResolvedSchema, SchemaResolver};
+use elise_semanalyzer::{
+    Harmony,
+    aast::{AAstNode, CollectionOp},
+    config::{FnAggregate, FnColl},
+    data_types::LangType,
+};
+use elise_shared::{
+    shared_errors::errors_semanalyzer::SemanalyzerErr,
+    shared_node_names::NodeName,
+    shared_types::{ArityMismatchKind, Span},
+};
+
+use crate::common::{analyze, dict_of, empty_schema, float, int, list_of, parse, ty_of};
+
+mod common;
+
+fn schema() -> ResolvedSchema {
+    let ast = parse(
+        r#"
+.schema(
+    .list(
+        .dict(
+            "country" .string()
+            "city"    .string()
+            "amount"  .int()
+            "price"   .float())))
+"#,
+    );
+    SchemaResolver::new(&ast).resolve().unwrap()
+}
+
+// Same rows nested in a field of the data.
+fn nested_schema() -> ResolvedSchema {
+    let ast = parse(
+        r#"
+.schema(
+    .dict(
+        "rows" .list(.dict(
+                   "country" .string()
+                   "city"    .string()
+                   "amount"  .int()
+                   "price"   .float()))))
+"#,
+    );
+    SchemaResolver::new(&ast).resolve().unwrap()
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_types_sum_min_and_max_by_item_type() {
+    assert_eq!(
+        ty_of(r#".sum(.map(@data .fn([r] .get(r "amount"))))"#, &schema()),
+        int()
+    );
+    assert_eq!(
+        ty_of(
+            r#".sum(.map(.get(@data "rows") .fn([r] .get(r "amount"))))"#,
+            &nested_schema()
+        ),
+        int()
+    );
+    assert_eq!(ty_of(".min([1.5 2.5])", &schema()), float());
+    assert_eq!(ty_of(".max([1 2])", &schema()), int());
+}
+
+#[test]
+fn test_types_avg_as_float() {
+    assert_eq!(ty_of(".avg([1 2])", &schema()), float());
+}
+
+#[test]
+fn test_types_count_distinct_as_int() {
+    assert_eq!(ty_of(r#".count-distinct(["a" "b" "a"])"#, &schema()), int());
+}
+
+#[test]
+fn test_types_group_by_as_list_of_groups() {
+    assert_eq!(
+        ty_of(".group-by([1 2 3] .fn([x] .mod(x 2)))", &schema()),
+        list_of(list_of(int()))
+    );
+}
+
+#[test]
+fn test_types_aggregate_by_schema_of_grouped_fields() {
+    assert_eq!(
+        ty_of(
+            r#".aggregate(@data ["amount"] {"n" .count() "top" .max("amount")})"#,
+            &schema()
+        ),
+        list_of(dict_of(int()))
+    );
+    assert_eq!(
+        ty_of(
+            r#".aggregate(@data [] {"avg" .avg("amount") "max" .max("price")})"#,
+            &schema()
+        ),
+        list_of(dict_of(float()))
+    );
+    assert_eq!(
+        ty_of(
+            r#".aggregate(.get(@data "rows") ["amount"] {"n" .count() "top" .max("amount")})"#,
+            &nested_schema()
+        ),
+        list_of(dict_of(int()))
+    );
+}
+
+#[test]
+fn test_types_aggregate_with_mixed_fields_as_unknown_values() {
+    assert_eq!(
+        ty_of(
+            r#".aggregate(@data ["country"] {"total" .sum("amount")})"#,
+            &schema()
+        ),
+        list_of(dict_of(LangType::Unknown))
+    );
+}
+
+#[test]
+fn test_types_aggregate_of_rows_not_from_data_by_their_values() {
+    assert_eq!(
+        ty_of(
+            r#".aggregate([{"a" 1} {"a" 2}] ["a"] {"s" .sum("a")})"#,
+            &schema()
+        ),
+        list_of(dict_of(int()))
+    );
+}
+
+#[test]
+fn test_annotates_aggregate_call() {
+    let node = analyze(
+        r#".aggregate(@data ["country" "city"] {"n" .count() "s" .sum("price")})"#,
+        &schema(),
+    )
+    .unwrap();
+
+    let AAstNode::CallAggregate {
+        keys, aggregations, ..
+    } = node
+    else {
+        panic!("Expected CallAggregate");
+    };
+    assert_eq!(keys, vec!["country".to_string(), "city".to_string()]);
+    assert_eq!(
+        aggregations,
+        vec![
+            ("n".to_string(), CollectionOp::Count, None),
+            (
+                "s".to_string(),
+                CollectionOp::Sum,
+                Some("price".to_string())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_keeps_data_rows_through_filter_before_aggregate() {
+    assert!(
+        analyze(
+            r#".aggregate(.filter(.get(@data) .fn([r] .gt(.get(r "amount") 0)))
+                          ["country"] {"n" .count()})"#,
+            &schema(),
+        )
+        .is_ok()
+    );
+    assert!(
+        analyze(
+            r#".aggregate(.filter(.get(@data "rows") .fn([r] .gt(.get(r "amount") 0)))
+                          ["country"] {"n" .count()})"#,
+            &nested_schema(),
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn test_accepts_unknown_rows() {
+    let ast = parse(r#".fn([rows] .aggregate(rows ["a"] {"s" .sum("b")}))"#);
+    let schema = empty_schema();
+
+    assert!(Harmony::new(&ast, &schema).analyze().is_ok());
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_arg_type_mismatch_if_summed_items_are_not_numbers() {
+    assert_eq!(
+        analyze(r#".sum(["a"])"#, &schema()).unwrap_err(),
+        SemanalyzerErr::ArgTypeMismatch {
+            fn_name: FnColl::SUM.into(),
+            position: 0,
+            expected: NodeName::NUMBER,
+            found: NodeName::STRING,
+            span: Span { start: 5, end: 10 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_schema_path_undefined_for_unknown_key_field() {
+    assert!(matches!(
+        analyze(r#".aggregate(@data ["zip"] {"n" .count()})"#, &schema()),
+        Err(SemanalyzerErr::SchemaPathUndefined {
+            span: Span { start: 18, end: 23 },
+            ..
+        })
+    ));
+    assert!(matches!(
+        analyze(
+            r#".aggregate(.get(@data "rows") ["zip"] {"n" .count()})"#,
+            &nested_schema()
+        ),
+        Err(SemanalyzerErr::SchemaPathUndefined {
+            span: Span { start: 31, end: 36 },
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_aggregated_field_is_not_number() {
+    assert!(matches!(
+        analyze(r#".aggregate(@data [] {"s" .avg("city")})"#, &schema()),
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            expected: NodeName::NUMBER,
+            found: NodeName::STRING,
+            ..
+        }) if fn_name == FnColl::AVG
+    ));
+}
+
+#[test]
+fn test_returns_arg_kind_mismatch_if_value_is_not_aggregation() {
+    assert!(matches!(
+        analyze(
+            r#".aggregate(@data [] {"s" .map([] .fn([x] x))})"#,
+            &schema()
+        ),
+        Err(SemanalyzerErr::ArgKindMismatch {
+            fn_name: FnAggregate::LEXEME,
+            position: 2,
+            expected: NodeName::AGGREGATION,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_arg_kind_mismatch_if_keys_are_not_list() {
+    assert!(matches!(
+        analyze(r#".aggregate(@data "country" {"n" .count()})"#, &schema()),
+        Err(SemanalyzerErr::ArgKindMismatch {
+            fn_name: FnAggregate::LEXEME,
+            position: 1,
+            expected: NodeName::LIST,
+            found: NodeName::STRING,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_arity_mismatch_if_count_takes_field() {
+    assert!(matches!(
+        analyze(r#".aggregate(@data [] {"n" .count("city")})"#, &schema()),
+        Err(SemanalyzerErr::ArityMismatch {
+            fn_name,
+            kind: ArityMismatchKind::Eq(0),
+            found: 1,
+            ..
+        }) if fn_name == FnColl::COUNT
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_rows_are_not_dicts() {
+    assert!(matches!(
+        analyze(r#".aggregate([1 2] [] {"n" .count()})"#, &schema()),
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            position: 0,
+            expected: NodeName::DICT,
+            found: NodeName::INT,
+            ..
+        }) if fn_name == FnAggregate::LEXEME
+    ));
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
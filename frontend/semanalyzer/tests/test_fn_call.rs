@@ -0,0 +1,302 @@
+use elise_semanalyzer::{
+    Harmony,
+    aast::AAstNode,
+    data_types::{LangPrimitiveType, LangType},
+    symbol_table::SymbolId,
+};
+use elise_shared::{
+    shared_errors::errors_semanalyzer::SemanalyzerErr,
+    shared_node_names::NodeName,
+    shared_types::{ArityMismatchKind, Span},
+};
+
+use crate::common::{empty_schema, parse};
+
+mod common;
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_calls_named_fn() {
+    let ast = parse(".fn(f [x] x) .f(1)");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    assert_eq!(
+        hir.aast[1],
+        AAstNode::Call {
+            symbol_id: SymbolId(0),
+            depth: 0,
+            args: vec![Box::new(AAstNode::Int {
+                value: "1".to_string(),
+                span: Span { start: 16, end: 17 }
+            })],
+            span: Span { start: 13, end: 18 }
+        }
+    );
+}
+
+#[test]
+fn test_calls_fn_bound_with_let() {
+    let ast = parse(".let([f .fn([x y] x)] .f(1 2))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallLet { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallLet");
+    };
+    assert!(matches!(
+        *body[0],
+        AAstNode::Call {
+            symbol_id: SymbolId(2),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_derives_type_of_call_from_fn_result() {
+    let ast = parse(r#".fn(f [] "x") .let([r .f()] r)"#);
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    assert_eq!(
+        hir.symbol_table.symbols.get(&SymbolId(1)).unwrap().ty,
+        LangType::Primitive(LangPrimitiveType::String)
+    );
+}
+
+#[test]
+fn test_narrows_called_param_to_fn() {
+    let ast = parse(".fn(apply [g x] .g(x))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let g = LangType::Function {
+        params: vec![LangType::Unknown],
+        ret: Box::new(LangType::Unknown),
+    };
+
+    assert_eq!(hir.symbol_table.symbols.get(&SymbolId(1)).unwrap().ty, g);
+    assert_eq!(
+        hir.symbol_table.symbols.get(&SymbolId(0)).unwrap().ty,
+        LangType::Function {
+            params: vec![g, LangType::Unknown],
+            ret: Box::new(LangType::Unknown),
+        }
+    );
+}
+
+#[test]
+fn test_infers_param_types_from_their_uses() {
+    let ast = parse(".fn(f [s c x] .if(c .upper(s) x))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let LangType::Function { params, .. } = &hir.symbol_table.symbols.get(&SymbolId(0)).unwrap().ty
+    else {
+        panic!("Expected function");
+    };
+
+    assert_eq!(
+        params,
+        &vec![
+            LangType::Primitive(LangPrimitiveType::String),
+            LangType::Primitive(LangPrimitiveType::Bool),
+            LangType::Unknown,
+        ]
+    );
+}
+
+#[test]
+fn test_accepts_fn_as_arg() {
+    let ast = parse(".fn(apply [g x] .g(x)) .apply(.fn([y] y) 1)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_allows_recursive_call() {
+    let ast = parse(".fn(f [x] .f(x))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallFn { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallFn");
+    };
+    assert!(matches!(
+        *body[0],
+        AAstNode::Call {
+            symbol_id: SymbolId(0),
+            depth: 1,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_captures_callee() {
+    let ast = parse(".let([g .fn([] 1)] .fn([] .g()))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallLet { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallLet");
+    };
+    assert!(matches!(
+        &*body[0],
+        AAstNode::CallFn { captures, .. } if *captures == vec![SymbolId(0)]
+    ));
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_arity_mismatch_with_callee_span() {
+    let ast = parse(".fn(f [x] x) .f(1 2)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::ArityMismatch {
+            fn_name: "f".into(),
+            kind: ArityMismatchKind::Eq(1),
+            found: 2,
+            span: Span { start: 14, end: 15 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_with_callee_span() {
+    let ast = parse(".fn(apply [g x] .g(x)) .apply(1 2)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::ArgTypeMismatch {
+            fn_name: "apply".into(),
+            position: 0,
+            expected: NodeName::FUNCTION,
+            found: NodeName::INT,
+            span: Span { start: 24, end: 29 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_fn_arity_differs() {
+    let ast = parse(".fn(apply [g] .g(1)) .apply(.fn([a b] a))");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            position: 0,
+            expected: NodeName::FUNCTION,
+            found: NodeName::FUNCTION,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_primitive_arg_differs() {
+    let ast = parse(".fn(shout [s] .upper(s)) .shout(1)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::ArgTypeMismatch {
+            fn_name: "shout".into(),
+            position: 0,
+            expected: NodeName::STRING,
+            found: NodeName::INT,
+            span: Span { start: 26, end: 31 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_param_is_used_as_condition() {
+    let ast = parse(".fn(pick [c] .if(c 1 2)) .pick(\"yes\")");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            expected: NodeName::BOOL,
+            found: NodeName::STRING,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_not_callable_if_symbol_is_not_fn() {
+    let ast = parse(".define(PI 3.14) .PI()");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::NotCallable {
+            found: NodeName::FLOAT,
+            span: Span { start: 18, end: 20 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_not_callable_if_symbol_is_list() {
+    let ast = parse(".let([xs [1 2]] .xs())");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::NotCallable {
+            found: NodeName::LIST,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_unknown_function_if_not_defined() {
+    let ast = parse(".nope(1)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::UnknownFunction { .. })
+    ));
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
@@ -0,0 +1,276 @@
+use elise_data::schema_resolver::{ResolvedSchema, SchemaResolver};
+use elise_semanalyzer::{
+    Harmony,
+    aast::{AAstNode, CollectionOp},
+    config::FnColl,
+    data_types::LangType,
+};
+use elise_shared::{
+    shared_errors::errors_semanalyzer::SemanalyzerErr,
+    shared_node_names::NodeName,
+    shared_types::{ArityMismatchKind, Span},
+};
+
+use crate::common::{analyze, empty_schema, float, int, list_of, parse, string, ty_of};
+
+mod common;
+
+fn schema() -> ResolvedSchema {
+    let ast = parse(
+        r#"
+.schema(
+    .list(
+        .dict(
+            "name"  .string()
+            "age"   .int()
+            "score" .float()
+            "tags"  .list(.string()))))
+"#,
+    );
+    SchemaResolver::new(&ast).resolve().unwrap()
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_types_map_over_data_rows_by_schema() {
+    assert_eq!(
+        ty_of(r#".map(@data .fn([row] .get(row "age")))"#, &schema()),
+        list_of(int())
+    );
+}
+
+#[test]
+fn test_types_map_over_list_by_item_type() {
+    assert_eq!(
+        ty_of(".map([1 2] .fn([x] .mul(x 1.5)))", &schema()),
+        list_of(float())
+    );
+}
+
+#[test]
+fn test_keeps_data_rows_through_filter() {
+    assert_eq!(
+        ty_of(
+            r#".map(.filter(@data .fn([r] .gt(.get(r "age") 18)))
+                    .fn([r] .get(r "name")))"#,
+            &schema(),
+        ),
+        list_of(string())
+    );
+}
+
+#[test]
+fn test_types_reduce_by_init_and_callback() {
+    assert_eq!(
+        ty_of(
+            r#".reduce(@data .fn([acc row] .add(acc .get(row "score"))) 0)"#,
+            &schema()
+        ),
+        float()
+    );
+}
+
+#[test]
+fn test_keeps_list_type_through_sort_take_and_drop() {
+    assert_eq!(
+        ty_of(".drop(.take(.sort-by([3 1 2] .fn([x] x)) 2) 1)", &schema()),
+        list_of(int())
+    );
+}
+
+#[test]
+fn test_types_count_as_int() {
+    assert_eq!(ty_of(r#".count(@data)"#, &schema()), int());
+}
+
+#[test]
+fn test_types_data_rows_with_or_without_get() {
+    let rows = analyze("@data", &schema()).unwrap();
+    let got_rows = analyze(".get(@data)", &schema()).unwrap();
+
+    for node in [rows, got_rows] {
+        assert!(matches!(
+            node,
+            AAstNode::CallGetSlot { ref path, ref ty, .. }
+                if path.len() == 1
+                    && matches!(ty, LangType::List(row_ty) if matches!(**row_ty, LangType::Dict(_)))
+        ));
+    }
+}
+
+#[test]
+fn test_types_flat_map_by_callback_items() {
+    assert_eq!(
+        ty_of(r#".flat-map(@data .fn([row] .get(row "tags")))"#, &schema()),
+        list_of(string())
+    );
+}
+
+#[test]
+fn test_annotates_collection_call() {
+    let node = analyze(".map([1] .fn([x] x))", &schema()).unwrap();
+
+    assert!(matches!(
+        node,
+        AAstNode::CallCollection {
+            op: CollectionOp::Map,
+            ref args,
+            span: Span { start: 0, end: 20 },
+            ..
+        } if matches!(*args[0], AAstNode::CallFn { .. })
+    ));
+}
+
+#[test]
+fn test_accepts_function_bound_to_symbol() {
+    assert!(analyze(".let([f .fn([x] x)] .map([1] f))", &schema()).is_ok());
+}
+
+#[test]
+fn test_accepts_unknown_list() {
+    let ast = parse(".fn([xs] .count(xs))");
+    let schema = empty_schema();
+
+    assert!(Harmony::new(&ast, &schema).analyze().is_ok());
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_arity_mismatch() {
+    assert!(matches!(
+        analyze(".map([1])", &schema()),
+        Err(SemanalyzerErr::ArityMismatch {
+            fn_name,
+            kind: ArityMismatchKind::Eq(FnColl::ARGS_LEN),
+            found: 1,
+            ..
+        }) if fn_name == FnColl::MAP
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_source_is_not_list() {
+    assert_eq!(
+        analyze(".count(1)", &schema()).unwrap_err(),
+        SemanalyzerErr::ArgTypeMismatch {
+            fn_name: FnColl::COUNT.into(),
+            position: 0,
+            expected: NodeName::LIST,
+            found: NodeName::INT,
+            span: Span { start: 7, end: 8 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_callback_is_not_function() {
+    assert!(matches!(
+        analyze(".map([1] 2)", &schema()),
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            position: 1,
+            expected: NodeName::FUNCTION,
+            found: NodeName::INT,
+            ..
+        }) if fn_name == FnColl::MAP
+    ));
+}
+
+#[test]
+fn test_returns_callback_arity_mismatch() {
+    assert_eq!(
+        analyze(".map([1] .fn([a b] a))", &schema()).unwrap_err(),
+        SemanalyzerErr::CallbackArityMismatch {
+            fn_name: FnColl::MAP,
+            expected: 1,
+            found: 2,
+            span: Span { start: 9, end: 21 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_callback_return_mismatch_if_filter_returns_not_bool() {
+    assert!(matches!(
+        analyze(".filter([1] .fn([x] x))", &schema()),
+        Err(SemanalyzerErr::CallbackReturnMismatch {
+            fn_name: FnColl::FILTER,
+            expected: NodeName::BOOL,
+            found: NodeName::INT,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_callback_return_mismatch_if_reduce_changes_type() {
+    assert!(matches!(
+        analyze(r#".reduce([1] .fn([acc x] .concat("a" "b")) 0)"#, &schema()),
+        Err(SemanalyzerErr::CallbackReturnMismatch {
+            fn_name: FnColl::REDUCE,
+            expected: NodeName::INT,
+            found: NodeName::STRING,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_take_count_is_not_int() {
+    assert!(matches!(
+        analyze(r#".take([1] "a")"#, &schema()),
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            position: 1,
+            expected: NodeName::INT,
+            ..
+        }) if fn_name == FnColl::TAKE
+    ));
+}
+
+#[test]
+fn test_returns_schema_path_undefined_for_unknown_row_field() {
+    assert!(matches!(
+        analyze(r#".map(@data .fn([row] .get(row "zip")))"#, &schema()),
+        Err(SemanalyzerErr::SchemaPathUndefined {
+            span: Span { start: 30, end: 35 },
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_schema_path_undefined_for_data_without_schema() {
+    let ast = parse(".count(@data)");
+    let schema = empty_schema();
+
+    assert!(matches!(
+        Harmony::new(&ast, &schema).analyze(),
+        Err(SemanalyzerErr::SchemaPathUndefined {
+            span: Span { start: 7, end: 12 },
+            ..
+        })
+    ));
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
@@ -0,0 +1,224 @@
+use elise_data::schema_resolver::{ResolvedSchema, SchemaResolver};
+use elise_semanalyzer::{
+    Harmony,
+    aast::{AAstNode, Conversion, ConversionMode},
+    config::FnConv,
+    data_types::LangType,
+};
+use elise_shared::{
+    shared_errors::errors_semanalyzer::SemanalyzerErr,
+    shared_node_names::NodeName,
+    shared_types::{ArityMismatchKind, Span},
+};
+
+use crate::common::{analyze, bool, empty_schema, float, int, parse, string};
+
+mod common;
+
+fn schema() -> ResolvedSchema {
+    let ast = parse(
+        r#"
+.schema(
+    .dict(
+        "zip"  .int()
+        "code" .nullable(.string())
+        "day"  .date()
+        "took" .duration()))
+"#,
+    );
+    SchemaResolver::new(&ast).resolve().unwrap()
+}
+
+// Type and nullability of the conversion, which can be
+// the last expression of `.let`.
+fn convert_of(src: &str) -> (LangType, bool) {
+    let node = match analyze(src, &schema()).unwrap() {
+        AAstNode::CallLet { mut body, .. } => *body.pop().unwrap(),
+        node => node,
+    };
+
+    match node {
+        AAstNode::CallConvert { ty, nullable, .. } => (ty, nullable),
+        aast_node => panic!("Expected CallConvert, found {aast_node:?}"),
+    }
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_annotates_strict_conversion() {
+    let node = analyze(r#".to-str(.get(@data "zip"))"#, &schema()).unwrap();
+
+    assert!(matches!(
+        node,
+        AAstNode::CallConvert {
+            conversion: Conversion::Str,
+            mode: ConversionMode::Strict,
+            fallback: None,
+            nullable: false,
+            span: Span { start: 0, end: 26 },
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_types_conversions_by_target() {
+    assert_eq!(convert_of(r#".to-int("1")"#).0, int());
+    assert_eq!(convert_of(".to-float(1)").0, float());
+    assert_eq!(convert_of(".to-str(true)").0, string());
+    assert_eq!(convert_of(r#".to-bool("true")"#).0, bool());
+}
+
+#[test]
+fn test_tracks_nullable_conversions() {
+    assert_eq!(convert_of(".to-str-or-null(1)"), (string(), true));
+    assert_eq!(convert_of(r#".to-str-or(1 "")"#), (string(), false));
+    assert_eq!(convert_of(".to-str-or(1 null)"), (string(), true));
+    assert_eq!(
+        convert_of(r#".to-str-or(1 .get(@data "code"))"#),
+        (string(), true)
+    );
+    assert_eq!(
+        convert_of(".to-str-or(1 .to-str-or-null(2))"),
+        (string(), true)
+    );
+}
+
+#[test]
+fn test_tracks_nullable_fallbacks_by_their_types() {
+    assert_eq!(
+        convert_of(r#".let([c .get(@data "code")] .to-str-or(1 c))"#),
+        (string(), true)
+    );
+    assert_eq!(
+        convert_of(r#".let([c "a"] .to-str-or(1 c))"#),
+        (string(), false)
+    );
+    assert_eq!(
+        convert_of(r#".to-str-or(1 .if(.lt(1 2) "a" null))"#),
+        (string(), true)
+    );
+    assert_eq!(
+        convert_of(r#".to-str-or(1 .when(.lt(1 2) "a"))"#),
+        (string(), true)
+    );
+}
+
+#[test]
+fn test_converts_temporal_values_to_str() {
+    assert_eq!(
+        convert_of(r#".to-str(.get(@data "day"))"#),
+        (string(), false)
+    );
+    assert_eq!(
+        convert_of(r#".to-str(.get(@data "took"))"#),
+        (string(), false)
+    );
+}
+
+#[test]
+fn test_accepts_nullable_values() {
+    assert!(analyze(r#".to-int-or(.get(@data "code") 0)"#, &schema()).is_ok());
+}
+
+#[test]
+fn test_accepts_unknown_values() {
+    let ast = parse(".fn([x] .to-float(x))");
+    let schema = empty_schema();
+
+    assert!(Harmony::new(&ast, &schema).analyze().is_ok());
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_arity_mismatch_if_fallback_is_missing() {
+    assert!(matches!(
+        analyze(".to-int-or(1)", &schema()),
+        Err(SemanalyzerErr::ArityMismatch {
+            fn_name,
+            kind: ArityMismatchKind::Eq(FnConv::FALLBACK_ARGS_LEN),
+            found: 1,
+            ..
+        }) if fn_name == FnConv::TO_INT
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_value_is_not_primitive() {
+    assert_eq!(
+        analyze(".to-str([1])", &schema()).unwrap_err(),
+        SemanalyzerErr::ArgTypeMismatch {
+            fn_name: FnConv::TO_STR.into(),
+            position: 0,
+            expected: NodeName::PRIMITIVE,
+            found: NodeName::LIST,
+            span: Span { start: 8, end: 11 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_temporal_value_is_not_converted_to_str() {
+    assert_eq!(
+        analyze(r#".to-int(.get(@data "day"))"#, &schema()).unwrap_err(),
+        SemanalyzerErr::ArgTypeMismatch {
+            fn_name: FnConv::TO_INT.into(),
+            position: 0,
+            expected: NodeName::STRING,
+            found: NodeName::DATE,
+            span: Span { start: 8, end: 25 },
+        }
+    );
+    assert!(matches!(
+        analyze(r#".to-float-or-null(.get(@data "took"))"#, &schema()),
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            found: NodeName::DURATION,
+            ..
+        }) if fn_name == FnConv::TO_FLOAT
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_fallback_is_not_target_type() {
+    assert!(matches!(
+        analyze(r#".to-int-or("a" "0")"#, &schema()),
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            position: 1,
+            expected: NodeName::INT,
+            found: NodeName::STRING,
+            ..
+        }) if fn_name == FnConv::TO_INT
+    ));
+}
+
+#[test]
+fn test_returns_unknown_function_for_unknown_conversion() {
+    assert!(matches!(
+        analyze(".to-list(1)", &schema()),
+        Err(SemanalyzerErr::UnknownFunction { .. })
+    ));
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
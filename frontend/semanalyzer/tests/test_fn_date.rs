@@ -0,0 +1,254 @@
+use elise_data::schema_resolver::{ResolvedSchema, SchemaResolver};
+use elise_semanalyzer::{
+    Harmony,
+    aast::{AAstNode, Op},
+    config::{FnCmp, FnDate},
+    data_types::{LangPrimitiveType, LangType},
+};
+use elise_shared::{
+    shared_errors::errors_semanalyzer::SemanalyzerErr,
+    shared_node_names::NodeName,
+    shared_types::{ArityMismatchKind, Span},
+};
+
+use crate::common::{analyze, empty_schema, parse, ty_of};
+
+mod common;
+
+fn schema() -> ResolvedSchema {
+    let ast = parse(
+        r#"
+.schema(
+    .dict(
+        "day"   .date("%d.%m.%Y")
+        "at"    .datetime()
+        "opens" .time()
+        "took"  .duration()
+        "when"  .union(.date() .int())))
+"#,
+    );
+    SchemaResolver::new(&ast).resolve().unwrap()
+}
+
+fn primitive(ty: LangPrimitiveType) -> LangType {
+    LangType::Primitive(ty)
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_types_data_fields_by_temporal_schema_types() {
+    let inputs = vec![
+        ("day", LangPrimitiveType::Date),
+        ("at", LangPrimitiveType::DateTime),
+        ("opens", LangPrimitiveType::Time),
+        ("took", LangPrimitiveType::Duration),
+    ];
+
+    for (field, ty) in inputs {
+        let node = analyze(&format!(r#".get(@data "{field}")"#), &schema()).unwrap();
+        assert!(
+            matches!(node, AAstNode::CallGetSlot { ty: found, .. } if found == primitive(ty.clone())),
+            "{field}"
+        );
+    }
+}
+
+#[test]
+fn test_types_date_fns() {
+    assert_eq!(
+        ty_of(
+            r#".date-add(.get(@data "day") .get(@data "took"))"#,
+            &schema()
+        ),
+        primitive(LangPrimitiveType::Date)
+    );
+    assert_eq!(
+        ty_of(
+            r#".date-diff(.get(@data "at") .get(@data "at"))"#,
+            &schema()
+        ),
+        primitive(LangPrimitiveType::Duration)
+    );
+    assert_eq!(
+        ty_of(r#".date-trunc(.get(@data "at") "week")"#, &schema()),
+        primitive(LangPrimitiveType::DateTime)
+    );
+    assert_eq!(
+        ty_of(r#".date-format(.get(@data "opens") "%H:%M")"#, &schema()),
+        primitive(LangPrimitiveType::String)
+    );
+}
+
+#[test]
+fn test_annotates_date_fn_without_folding() {
+    let node = analyze(r#".date-trunc(.get(@data "day") "month")"#, &schema()).unwrap();
+
+    assert!(matches!(
+        node,
+        AAstNode::CallOp {
+            op: Op::DateTrunc,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_compares_values_of_same_temporal_type() {
+    assert_eq!(
+        ty_of(r#".lt(.get(@data "day") .get(@data "day"))"#, &schema()),
+        primitive(LangPrimitiveType::Bool)
+    );
+    assert_eq!(
+        ty_of(
+            r#".gte(.get(@data "took") .date-diff(.get(@data "at") .get(@data "at")))"#,
+            &schema()
+        ),
+        primitive(LangPrimitiveType::Bool)
+    );
+}
+
+#[test]
+fn test_narrows_union_to_temporal_alternative() {
+    assert_eq!(
+        ty_of(
+            r#".date-format(.narrow(.get(@data "when") "Date") "%Y")"#,
+            &schema()
+        ),
+        primitive(LangPrimitiveType::String)
+    );
+}
+
+#[test]
+fn test_checks_unknown_values_at_runtime() {
+    let ast = parse(r#".fn([a b] .date-format(.date-add(a b) "%d.%m.%Y %H:%M"))"#);
+    let schema = empty_schema();
+
+    assert!(Harmony::new(&ast, &schema).analyze().is_ok());
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_arity_mismatch_for_date_fn() {
+    assert!(matches!(
+        analyze(r#".date-trunc(.get(@data "day"))"#, &schema()),
+        Err(SemanalyzerErr::ArityMismatch {
+            fn_name,
+            kind: ArityMismatchKind::Eq(FnDate::ARGS_LEN),
+            found: 1,
+            ..
+        }) if fn_name == FnDate::TRUNC
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_value_is_not_temporal() {
+    assert_eq!(
+        analyze(
+            r#".date-add(.get(@data "took") .get(@data "took"))"#,
+            &schema()
+        )
+        .unwrap_err(),
+        SemanalyzerErr::ArgTypeMismatch {
+            fn_name: FnDate::ADD.into(),
+            position: 0,
+            expected: NodeName::TEMPORAL,
+            found: NodeName::DURATION,
+            span: Span { start: 10, end: 28 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_added_value_is_not_duration() {
+    assert!(matches!(
+        analyze(r#".date-add(.get(@data "day") 1)"#, &schema()),
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            position: 1,
+            expected: NodeName::DURATION,
+            found: NodeName::INT,
+            ..
+        }) if fn_name == FnDate::ADD
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_diff_of_different_types() {
+    assert!(matches!(
+        analyze(
+            r#".date-diff(.get(@data "day") .get(@data "at"))"#,
+            &schema()
+        ),
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            position: 1,
+            expected: NodeName::DATE,
+            found: NodeName::DATETIME,
+            ..
+        }) if fn_name == FnDate::DIFF
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_compared_with_other_type() {
+    assert!(matches!(
+        analyze(r#".lt(1 .get(@data "day"))"#, &schema()),
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            position: 0,
+            expected: NodeName::DATE,
+            found: NodeName::INT,
+            ..
+        }) if fn_name == FnCmp::LT
+    ));
+}
+
+#[test]
+fn test_returns_invalid_date_unit_for_literal_unit() {
+    assert_eq!(
+        analyze(r#".date-trunc(.get(@data "day") "hour")"#, &schema()).unwrap_err(),
+        SemanalyzerErr::InvalidDateUnit {
+            span: Span { start: 30, end: 36 }
+        }
+    );
+    assert!(matches!(
+        analyze(r#".date-trunc(.get(@data "opens") "quarter")"#, &schema()),
+        Err(SemanalyzerErr::InvalidDateUnit { .. })
+    ));
+}
+
+#[test]
+fn test_returns_invalid_date_format_for_literal_format() {
+    assert_eq!(
+        analyze(r#".date-format(.get(@data "opens") "%Y")"#, &schema()).unwrap_err(),
+        SemanalyzerErr::InvalidDateFormat {
+            span: Span { start: 33, end: 37 }
+        }
+    );
+    assert!(matches!(
+        analyze(r#".date-format(.get(@data "at") "%q")"#, &schema()),
+        Err(SemanalyzerErr::InvalidDateFormat { .. })
+    ));
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
@@ -1,17 +1,17 @@
 use elise_semanalyzer::{
     Harmony,
-    semanalyzer_aast::AAstNode,
-    semanalyzer_config::FnDefine,
-    semanalyzer_data_types::{LangPrimitiveType, LangType},
-    semanalyzer_symbol_table::{SymbolDescriptor, SymbolId},
+    aast::AAstNode,
+    config::FnDefine,
+    data_types::{LangPrimitiveType, LangType},
+    symbol_table::{SymbolDescriptor, SymbolId},
 };
 use elise_shared::{
-    shared_errors::errors_semanalyzer::{ArityMismatchKind, SemanalyzerErr},
+    shared_errors::errors_semanalyzer::SemanalyzerErr,
     shared_node_names::NodeName,
-    shared_types::Span,
+    shared_types::{ArityMismatchKind, Span},
 };
 
-use crate::common::{empty_data_bindings, parse};
+use crate::common::{empty_schema, parse};
 
 mod common;
 
@@ -24,8 +24,8 @@ mod common;
 #[test]
 fn test_defines_int() {
     let ast = parse(".define(AD 3)");
-    let data_bindings = empty_data_bindings();
-    let hir = Harmony::new(&ast, &data_bindings).analyze().unwrap();
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
 
     assert_eq!(
         *hir.symbol_table.symbols.get(&SymbolId(0)).unwrap(),
@@ -52,8 +52,8 @@ fn test_defines_int() {
 #[test]
 fn test_defines_float() {
     let ast = parse(".define(PI 3.1415)");
-    let data_bindings = empty_data_bindings();
-    let hir = Harmony::new(&ast, &data_bindings).analyze().unwrap();
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
 
     assert_eq!(
         *hir.symbol_table.symbols.get(&SymbolId(0)).unwrap(),
@@ -80,8 +80,8 @@ fn test_defines_float() {
 #[test]
 fn test_defines_string() {
     let ast = parse(r#".define(NAME "Carl")"#);
-    let data_bindings = empty_data_bindings();
-    let hir = Harmony::new(&ast, &data_bindings).analyze().unwrap();
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
 
     assert_eq!(
         *hir.symbol_table.symbols.get(&SymbolId(0)).unwrap(),
@@ -108,8 +108,8 @@ fn test_defines_string() {
 #[test]
 fn test_defines_bool_true() {
     let ast = parse(".define(OPEN true)");
-    let data_bindings = empty_data_bindings();
-    let hir = Harmony::new(&ast, &data_bindings).analyze().unwrap();
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
 
     assert_eq!(
         *hir.symbol_table.symbols.get(&SymbolId(0)).unwrap(),
@@ -137,8 +137,8 @@ fn test_defines_bool_true() {
 #[test]
 fn test_defines_bool_false() {
     let ast = parse(".define(OPEN false)");
-    let data_bindings = empty_data_bindings();
-    let hir = Harmony::new(&ast, &data_bindings).analyze().unwrap();
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
 
     assert_eq!(
         *hir.symbol_table.symbols.get(&SymbolId(0)).unwrap(),
@@ -178,62 +178,60 @@ fn test_defines_bool_false() {
 #[test]
 fn test_returns_arity_mismatch_if_no_args() {
     let ast = parse(".define()");
-    let data_bindings = empty_data_bindings();
-    let result = Harmony::new(&ast, &data_bindings).analyze();
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
 
     assert!(matches!(
         result,
         Err(SemanalyzerErr::ArityMismatch {
-            fn_name: FnDefine::LEXEME,
-            expected: FnDefine::ARGS_LEN,
+            fn_name,
             found: 0,
-            kind: ArityMismatchKind::Eq,
+            kind: ArityMismatchKind::Eq(FnDefine::ARGS_LEN),
             ..
-        })
+        }) if fn_name == FnDefine::LEXEME
     ));
 }
 
 #[test]
 fn test_returns_arity_mismatch_if_more_than_2_args() {
     let ast = parse(".define(PI, 2, 3)");
-    let data_bindings = empty_data_bindings();
-    let result = Harmony::new(&ast, &data_bindings).analyze();
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
 
     assert!(matches!(
         result,
         Err(SemanalyzerErr::ArityMismatch {
-            fn_name: FnDefine::LEXEME,
-            expected: FnDefine::ARGS_LEN,
+            fn_name,
             found: 3,
-            kind: ArityMismatchKind::Eq,
+            kind: ArityMismatchKind::Eq(FnDefine::ARGS_LEN),
             ..
-        })
+        }) if fn_name == FnDefine::LEXEME
     ));
 }
 
 #[test]
 fn test_returns_arg_type_mismatch_if_defines_non_primitive() {
     let ast = parse(".define(PI [1, 2])");
-    let data_bindings = empty_data_bindings();
-    let result = Harmony::new(&ast, &data_bindings).analyze();
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
 
     assert!(matches!(
         result,
         Err(SemanalyzerErr::ArgTypeMismatch {
-            fn_name: FnDefine::LEXEME,
+            fn_name,
             position: 1,
             expected: NodeName::PRIMITIVE,
             found: NodeName::LIST,
             ..
-        })
+        }) if fn_name == FnDefine::LEXEME
     ));
 }
 
 #[test]
 fn test_returns_arg_kind_mismatch_if_first_arg_is_not_identifier() {
     let ast = parse(".define(false 2)");
-    let data_bindings = empty_data_bindings();
-    let result = Harmony::new(&ast, &data_bindings).analyze();
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
     assert!(matches!(
         result,
         Err(SemanalyzerErr::ArgKindMismatch {
@@ -249,8 +247,8 @@ fn test_returns_arg_kind_mismatch_if_first_arg_is_not_identifier() {
 #[test]
 fn test_returns_symbol_duplicate_if_already_defined() {
     let ast = parse(".define(PI 3.14) .define(PI 3.1415)");
-    let data_bindings = empty_data_bindings();
-    let result = Harmony::new(&ast, &data_bindings).analyze();
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
     assert!(matches!(
         result,
         Err(SemanalyzerErr::SymbolDuplicate { .. })
@@ -0,0 +1,397 @@
+use elise_semanalyzer::{
+    Harmony,
+    aast::AAstNode,
+    config::FnFn,
+    data_types::LangType,
+    symbol_table::{SymbolDescriptor, SymbolId},
+};
+use elise_shared::{
+    shared_errors::errors_semanalyzer::SemanalyzerErr,
+    shared_node_names::NodeName,
+    shared_types::{ArityMismatchKind, Span},
+};
+
+use crate::common::{empty_schema, parse};
+
+mod common;
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_creates_anonymous_fn() {
+    let ast = parse(".fn([x] x)");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    assert_eq!(
+        *hir.symbol_table.symbols.get(&SymbolId(0)).unwrap(),
+        SymbolDescriptor {
+            name: "x".to_string(),
+            ty: LangType::Unknown,
+            is_captured: false,
+        }
+    );
+
+    assert_eq!(
+        hir.aast,
+        vec![AAstNode::CallFn {
+            symbol_id: None,
+            params: vec![SymbolId(0)],
+            captures: vec![],
+            body: vec![Box::new(AAstNode::SymbolRef {
+                symbol_id: SymbolId(0),
+                span: Span { start: 8, end: 9 },
+                depth: 0,
+            })],
+            span: Span { start: 0, end: 10 }
+        }]
+    );
+}
+
+#[test]
+fn test_creates_fn_without_params() {
+    let ast = parse(".fn([] 1)");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    assert!(hir.symbol_table.symbols.is_empty());
+    assert!(matches!(
+        &hir.aast[0],
+        AAstNode::CallFn { params, body, .. } if params.is_empty() && body.len() == 1
+    ));
+}
+
+#[test]
+fn test_defines_named_fn_in_enclosing_scope() {
+    let ast = parse(".fn(my-fn [x] x) my-fn");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    assert_eq!(
+        *hir.symbol_table.symbols.get(&SymbolId(0)).unwrap(),
+        SymbolDescriptor {
+            name: "my-fn".to_string(),
+            ty: LangType::Function {
+                params: vec![LangType::Unknown],
+                ret: Box::new(LangType::Unknown),
+            },
+            is_captured: false,
+        }
+    );
+
+    assert!(matches!(
+        &hir.aast[0],
+        AAstNode::CallFn {
+            symbol_id: Some(SymbolId(0)),
+            params,
+            ..
+        } if *params == vec![SymbolId(1)]
+    ));
+    assert!(matches!(
+        hir.aast[1],
+        AAstNode::SymbolRef {
+            symbol_id: SymbolId(0),
+            depth: 0,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_binds_fn_with_let() {
+    let ast = parse(".let([my-fn .fn([x] x)] my-fn)");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    // Param is registered first since init is analyzed before binding.
+    let descriptor = hir.symbol_table.symbols.get(&SymbolId(1)).unwrap();
+    assert_eq!(descriptor.name, "my-fn".to_string());
+    assert_eq!(
+        descriptor.ty,
+        LangType::Function {
+            params: vec![LangType::Unknown],
+            ret: Box::new(LangType::Unknown),
+        }
+    );
+}
+
+#[test]
+fn test_captures_outer_symbols() {
+    let ast = parse(r#".let([prefix "a"] .fn([row] prefix row))"#);
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallLet { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallLet");
+    };
+    let AAstNode::CallFn { captures, body, .. } = &*body[0] else {
+        panic!("Expected CallFn");
+    };
+
+    assert_eq!(*captures, vec![SymbolId(0)]);
+    assert!(matches!(
+        *body[0],
+        AAstNode::SymbolRef {
+            symbol_id: SymbolId(0),
+            depth: 1,
+            ..
+        }
+    ));
+    assert!(
+        hir.symbol_table
+            .symbols
+            .get(&SymbolId(0))
+            .unwrap()
+            .is_captured
+    );
+    assert!(
+        !hir.symbol_table
+            .symbols
+            .get(&SymbolId(1))
+            .unwrap()
+            .is_captured
+    );
+}
+
+#[test]
+fn test_captures_once_per_symbol() {
+    let ast = parse(".let([a 1] .fn([] a a))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallLet { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallLet");
+    };
+    assert!(matches!(
+        &*body[0],
+        AAstNode::CallFn { captures, .. } if *captures == vec![SymbolId(0)]
+    ));
+}
+
+#[test]
+fn test_captures_through_nested_fns() {
+    let ast = parse(".let([a 1] .fn([] .fn([b] a b)))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallLet { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallLet");
+    };
+    let AAstNode::CallFn { captures, body, .. } = &*body[0] else {
+        panic!("Expected CallFn");
+    };
+    // Outer function must capture `a` in order to pass it down.
+    assert_eq!(*captures, vec![SymbolId(0)]);
+
+    let AAstNode::CallFn { captures, .. } = &*body[0] else {
+        panic!("Expected CallFn");
+    };
+    assert_eq!(*captures, vec![SymbolId(0)]);
+}
+
+#[test]
+fn test_does_not_capture_outer_fn_params_in_outer_fn() {
+    let ast = parse(".fn([a] .fn([] a))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallFn { captures, body, .. } = &hir.aast[0] else {
+        panic!("Expected CallFn");
+    };
+    assert!(captures.is_empty());
+
+    let AAstNode::CallFn { captures, .. } = &*body[0] else {
+        panic!("Expected CallFn");
+    };
+    assert_eq!(*captures, vec![SymbolId(0)]);
+}
+
+#[test]
+fn test_does_not_capture_globals() {
+    let ast = parse(".define(PI 3.14) .fn([] PI)");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    assert!(matches!(
+        &hir.aast[1],
+        AAstNode::CallFn { captures, .. } if captures.is_empty()
+    ));
+    assert!(
+        !hir.symbol_table
+            .symbols
+            .get(&SymbolId(0))
+            .unwrap()
+            .is_captured
+    );
+}
+
+#[test]
+fn test_allows_named_fn_to_reference_itself() {
+    let ast = parse(".fn(f [x] f)");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallFn { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallFn");
+    };
+    assert!(matches!(
+        *body[0],
+        AAstNode::SymbolRef {
+            symbol_id: SymbolId(0),
+            depth: 1,
+            ..
+        }
+    ));
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_arity_mismatch_if_no_body() {
+    let ast = parse(".fn([])");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArityMismatch {
+            fn_name,
+            found: 1,
+            kind: ArityMismatchKind::MoreEq(FnFn::MIN_ARGS_LEN),
+            ..
+        }) if fn_name == FnFn::LEXEME
+    ));
+}
+
+#[test]
+fn test_returns_arity_mismatch_if_named_without_body() {
+    let ast = parse(".fn(f [x])");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArityMismatch {
+            fn_name,
+            found: 2,
+            kind: ArityMismatchKind::MoreEq(FnFn::MIN_NAMED_ARGS_LEN),
+            ..
+        }) if fn_name == FnFn::LEXEME
+    ));
+}
+
+#[test]
+fn test_returns_arg_kind_mismatch_if_params_is_not_list() {
+    let ast = parse(".fn(1 2)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArgKindMismatch {
+            fn_name: FnFn::LEXEME,
+            position: 0,
+            expected: NodeName::LIST,
+            found: NodeName::INT,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_arg_kind_mismatch_if_named_params_is_not_list() {
+    let ast = parse(".fn(f x 1)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArgKindMismatch {
+            fn_name: FnFn::LEXEME,
+            position: 1,
+            expected: NodeName::LIST,
+            found: NodeName::IDENTIFIER,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_arg_kind_mismatch_if_param_is_not_pattern() {
+    let ast = parse(".fn([x 1] x)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArgKindMismatch {
+            fn_name: FnFn::LEXEME,
+            position: 0,
+            expected: NodeName::PATTERN,
+            found: NodeName::INT,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_symbol_duplicate_if_params_repeat() {
+    let ast = parse(".fn([x x] x)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::SymbolDuplicate {
+            span: Span { start: 7, end: 8 }
+        }
+    );
+}
+
+#[test]
+fn test_returns_symbol_duplicate_if_name_already_defined() {
+    let ast = parse(".define(F 1) .fn(F [] 1)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::SymbolDuplicate {
+            span: Span { start: 17, end: 18 }
+        }
+    );
+}
+
+#[test]
+fn test_returns_symbol_undefined_if_param_referenced_outside() {
+    let ast = parse(".fn([x] x) x");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::SymbolUndefined {
+            span: Span { start: 11, end: 12 }
+        }
+    );
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
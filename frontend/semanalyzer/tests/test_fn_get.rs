@@ -0,0 +1,335 @@
+use elise_data::{
+    resolution_path::{ResolutionPath, ResolutionPathSegment::*},
+    schema_resolver::{ResolvedSchema, SchemaResolver},
+};
+use elise_semanalyzer::{
+    Harmony,
+    aast::AAstNode,
+    config::FnGet,
+    data_types::{LangPrimitiveType, LangType},
+    symbol_table::SymbolId,
+};
+use elise_shared::{
+    shared_errors::errors_semanalyzer::SemanalyzerErr,
+    shared_node_names::NodeName,
+    shared_types::{ArityMismatchKind, Span},
+};
+
+use crate::common::{empty_schema, parse, string};
+
+mod common;
+
+fn schema() -> ResolvedSchema {
+    let ast = parse(
+        r#"
+.schema(
+    .dict(
+        "name"    .string()
+        "age"     .nullable(.int())
+        "nick"    .optional(.string())
+        "address" .dict(
+                      "street" .string()
+                      "house"  .int())
+        "tags"    .list(.string())))
+"#,
+    );
+    SchemaResolver::new(&ast).resolve().unwrap()
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_gets_data_field() {
+    let ast = parse(r#".get(@data "name")"#);
+    let schema = schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    assert_eq!(
+        hir.aast,
+        vec![AAstNode::CallGetSlot {
+            slot: "data".to_string(),
+            path: ResolutionPath::with_segments(vec![Field("name".to_string())]),
+            keys: vec![Box::new(AAstNode::String {
+                value: "name".to_string(),
+                span: Span { start: 11, end: 17 }
+            })],
+            ty: string(),
+            nullable: false,
+            optional: false,
+            span: Span { start: 0, end: 18 }
+        }]
+    );
+}
+
+#[test]
+fn test_gets_nested_data_field() {
+    let ast = parse(r#".get(@data "address" "house")"#);
+    let schema = schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    assert!(matches!(
+        &hir.aast[0],
+        AAstNode::CallGetSlot { path, ty, .. }
+            if *path == ResolutionPath::with_segments(vec![
+                Field("address".to_string()),
+                Field("house".to_string()),
+            ]) && *ty == LangType::Primitive(LangPrimitiveType::Int)
+    ));
+}
+
+#[test]
+fn test_keeps_nullable_and_optional_flags() {
+    let ast = parse(r#".get(@data "age") .get(@data "nick")"#);
+    let schema = schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    assert!(matches!(
+        hir.aast[0],
+        AAstNode::CallGetSlot {
+            nullable: true,
+            optional: false,
+            ..
+        }
+    ));
+    assert!(matches!(
+        hir.aast[1],
+        AAstNode::CallGetSlot {
+            nullable: false,
+            optional: true,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_gets_data_list_item_by_any_index() {
+    let ast = parse(r#".get(@data "tags" 0) .fn([i] .get(@data "tags" i))"#);
+    let schema = schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let path = ResolutionPath::with_segments(vec![Field("tags".to_string()), AbstractIndex]);
+
+    assert!(matches!(
+        &hir.aast[0],
+        AAstNode::CallGetSlot { path: p, ty, .. } if *p == path && *ty == string()
+    ));
+
+    let AAstNode::CallFn { body, .. } = &hir.aast[1] else {
+        panic!("Expected CallFn");
+    };
+    assert!(matches!(
+        &*body[0],
+        AAstNode::CallGetSlot { path: p, .. } if *p == path
+    ));
+}
+
+#[test]
+fn test_types_data_compounds() {
+    let ast = parse(r#".let([a .get(@data "address"), t .get(@data "tags")] a)"#);
+    let schema = schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    // Fields of the address have different types.
+    assert_eq!(
+        hir.symbol_table.symbols.get(&SymbolId(0)).unwrap().ty,
+        LangType::Dict(Box::new(LangType::Unknown))
+    );
+    assert_eq!(
+        hir.symbol_table.symbols.get(&SymbolId(1)).unwrap().ty,
+        LangType::List(Box::new(string()))
+    );
+}
+
+#[test]
+fn test_gets_list_item() {
+    let ast = parse(".let([xs [1 2]] .get(xs 0))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallLet { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallLet");
+    };
+    assert!(matches!(
+        &*body[0],
+        AAstNode::CallGet { ty, .. } if *ty == LangType::Primitive(LangPrimitiveType::Int)
+    ));
+}
+
+#[test]
+fn test_gets_nested_dict_value() {
+    let ast = parse(r#".get({"a" {"b" "x"}} "a" "b")"#);
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    assert!(matches!(
+        &hir.aast[0],
+        AAstNode::CallGet { ty, keys, .. } if *ty == string() && keys.len() == 2
+    ));
+}
+
+#[test]
+fn test_gets_unknown_from_unknown() {
+    let ast = parse(".fn([x] .get(x 0 \"a\"))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallFn { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallFn");
+    };
+    assert!(matches!(
+        &*body[0],
+        AAstNode::CallGet {
+            ty: LangType::Unknown,
+            ..
+        }
+    ));
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_arity_mismatch_if_no_keys() {
+    let ast = parse(".get([1 2])");
+    let schema = schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArityMismatch {
+            fn_name,
+            found: 1,
+            kind: ArityMismatchKind::MoreEq(FnGet::MIN_ARGS_LEN),
+            ..
+        }) if fn_name == FnGet::LEXEME
+    ));
+}
+
+#[test]
+fn test_returns_schema_path_undefined_with_key_span() {
+    let ast = parse(r#".get(@data "address" "zip")"#);
+    let schema = schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::SchemaPathUndefined {
+            path: r#"[Root, Field("address"), Field("zip")]"#.to_string(),
+            span: Span { start: 21, end: 26 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_schema_path_undefined_if_indexing_dict() {
+    let ast = parse(r#".get(@data "address" 0 "street")"#);
+    let schema = schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::SchemaPathUndefined {
+            path: r#"[Root, Field("address"), AbstractIndex]"#.to_string(),
+            span: Span { start: 21, end: 22 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_schema_path_undefined_if_schema_is_empty() {
+    let ast = parse(r#".get(@data "name")"#);
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::SchemaPathUndefined { .. })
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_data_key_is_invalid() {
+    let ast = parse(".get(@data true)");
+    let schema = schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::ArgTypeMismatch {
+            fn_name: FnGet::LEXEME.into(),
+            position: 1,
+            expected: NodeName::INT,
+            found: NodeName::BOOL,
+            span: Span { start: 11, end: 15 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_slot_unknown() {
+    let ast = parse(r#".get(@nope "name")"#);
+    let schema = schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::SlotUnknown {
+            span: Span { start: 5, end: 10 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_list_key_is_not_int() {
+    let ast = parse(r#".get([1 2] "a")"#);
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            position: 1,
+            expected: NodeName::INT,
+            found: NodeName::STRING,
+            ..
+        }) if fn_name == FnGet::LEXEME
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_target_is_not_compound() {
+    let ast = parse(".get(1 0)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            position: 0,
+            expected: NodeName::LIST,
+            found: NodeName::INT,
+            ..
+        }) if fn_name == FnGet::LEXEME
+    ));
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
@@ -0,0 +1,313 @@
+use elise_semanalyzer::{
+    Harmony,
+    aast::AAstNode,
+    config::{FnCond, FnIf, FnWhen},
+    data_types::LangType,
+    symbol_table::SymbolId,
+};
+use elise_shared::{
+    shared_errors::errors_semanalyzer::SemanalyzerErr,
+    shared_node_names::NodeName,
+    shared_types::{ArityMismatchKind, Span},
+};
+
+use crate::common::{empty_schema, float, int, nullable, parse, string, ty_of_bound};
+
+mod common;
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_annotates_if() {
+    let ast = parse(".fn([c] .if(c 1 2))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallFn { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallFn");
+    };
+    assert_eq!(
+        *body[0],
+        AAstNode::CallIf {
+            cond: Box::new(AAstNode::SymbolRef {
+                symbol_id: SymbolId(0),
+                span: Span { start: 12, end: 13 },
+                depth: 0,
+            }),
+            then_branch: Box::new(AAstNode::Int {
+                value: "1".to_string(),
+                span: Span { start: 14, end: 15 }
+            }),
+            else_branch: Box::new(AAstNode::Int {
+                value: "2".to_string(),
+                span: Span { start: 16, end: 17 }
+            }),
+            ty: int(),
+            span: Span { start: 8, end: 18 }
+        }
+    );
+}
+
+#[test]
+fn test_unifies_numeric_branches_to_float() {
+    assert_eq!(ty_of_bound(".if(.lt(1 2) 1 2.5)"), float());
+}
+
+#[test]
+fn test_unifies_null_branch_with_other_branch_to_nullable() {
+    assert_eq!(ty_of_bound(r#".if(true null "a")"#), nullable(string()));
+    assert_eq!(ty_of_bound(r#".if(true "a" null)"#), nullable(string()));
+    assert_eq!(
+        ty_of_bound(".if(true null .if(false 1 2.5))"),
+        nullable(float())
+    );
+}
+
+#[test]
+fn test_keeps_nullability_of_bound_symbols() {
+    let ast = parse(r#".fn([c] .let([s .when(c "a")] .if(c s "b")))"#);
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallFn { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallFn");
+    };
+    assert!(matches!(
+        &*body[0],
+        AAstNode::CallLet { body, .. }
+            if matches!(&*body[0], AAstNode::CallIf { ty, .. } if *ty == nullable(string()))
+    ));
+}
+
+#[test]
+fn test_accepts_nullable_value_where_its_type_is_expected() {
+    let ast = parse(".let([n .if(true 1 null)] .add(n 1))");
+    let schema = empty_schema();
+
+    assert!(Harmony::new(&ast, &schema).analyze().is_ok());
+}
+
+#[test]
+fn test_unifies_unknown_branch_to_unknown() {
+    let ast = parse(r#".fn([c x] .if(c x "a"))"#);
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallFn { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallFn");
+    };
+    assert!(matches!(
+        *body[0],
+        AAstNode::CallIf {
+            ty: LangType::Unknown,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_accepts_unknown_condition() {
+    let ast = parse(".fn([ok] .if(ok 1 2))");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_annotates_cond_with_default() {
+    let ast = parse(r#".fn([n] .cond(.lt(n 2) "a" .gt(n 5) "b" "c"))"#);
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallFn { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallFn");
+    };
+    assert!(matches!(
+        &*body[0],
+        AAstNode::CallCond { arms, default: Some(_), ty, .. }
+            if arms.len() == 2 && *ty == string()
+    ));
+}
+
+#[test]
+fn test_annotates_cond_without_default() {
+    let ast = parse(r#".fn([c] .cond(c "a"))"#);
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallFn { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallFn");
+    };
+    assert!(matches!(
+        &*body[0],
+        AAstNode::CallCond { arms, default: None, ty, .. }
+            if arms.len() == 1 && *ty == nullable(string())
+    ));
+}
+
+#[test]
+fn test_annotates_when_with_type_of_last_expression() {
+    let ast = parse(r#".fn([c] .when(c 1 "a"))"#);
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallFn { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallFn");
+    };
+    assert!(matches!(
+        &*body[0],
+        AAstNode::CallWhen { body, ty, .. } if body.len() == 2 && *ty == nullable(string())
+    ));
+}
+
+#[test]
+fn test_unifies_list_branches_by_item_type() {
+    assert_eq!(
+        ty_of_bound(".if(true [1] [2 3])"),
+        LangType::List(Box::new(int()))
+    );
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_arity_mismatch_if_if_has_no_else() {
+    let ast = parse(".if(true 1)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArityMismatch {
+            fn_name,
+            kind: ArityMismatchKind::Eq(FnIf::ARGS_LEN),
+            found: 2,
+            ..
+        }) if fn_name == FnIf::LEXEME
+    ));
+}
+
+#[test]
+fn test_returns_arity_mismatch_if_cond_has_one_arg() {
+    let ast = parse(".cond(true)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArityMismatch {
+            fn_name,
+            kind: ArityMismatchKind::MoreEq(FnCond::MIN_ARGS_LEN),
+            found: 1,
+            ..
+        }) if fn_name == FnCond::LEXEME
+    ));
+}
+
+#[test]
+fn test_returns_arity_mismatch_if_when_has_no_body() {
+    let ast = parse(".when(true)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArityMismatch {
+            fn_name,
+            kind: ArityMismatchKind::MoreEq(FnWhen::MIN_ARGS_LEN),
+            found: 1,
+            ..
+        }) if fn_name == FnWhen::LEXEME
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_condition_is_not_bool() {
+    let ast = parse(".if(1 2 3)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::ArgTypeMismatch {
+            fn_name: FnIf::LEXEME.into(),
+            position: 0,
+            expected: NodeName::BOOL,
+            found: NodeName::INT,
+            span: Span { start: 4, end: 5 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_with_cond_arm_position() {
+    let ast = parse(r#".cond(true 1 "x" 2)"#);
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            position: 2,
+            expected: NodeName::BOOL,
+            found: NodeName::STRING,
+            ..
+        }) if fn_name == FnCond::LEXEME
+    ));
+}
+
+#[test]
+fn test_returns_branch_type_mismatch() {
+    let ast = parse(r#".if(true 1 "a")"#);
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::BranchTypeMismatch {
+            fn_name: FnIf::LEXEME,
+            expected: NodeName::INT,
+            found: NodeName::STRING,
+            span: Span { start: 11, end: 14 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_branch_type_mismatch_for_cond_default() {
+    let ast = parse(r#".cond(true 1 false 2.5 "a")"#);
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::BranchTypeMismatch {
+            fn_name: FnCond::LEXEME,
+            expected: NodeName::FLOAT,
+            found: NodeName::STRING,
+            ..
+        })
+    ));
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
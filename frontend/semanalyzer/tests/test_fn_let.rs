@@ -1,7 +1,17 @@
-use elise_semanalyzer::{Harmony, semanalyzer_config::FnLet};
-use elise_shared::shared_errors::errors_semanalyzer::{ArityMismatchKind, SemanalyzerErr};
+use elise_semanalyzer::{
+    Harmony,
+    aast::AAstNode,
+    config::FnLet,
+    data_types::{LangPrimitiveType, LangType},
+    symbol_table::SymbolId,
+};
+use elise_shared::{
+    shared_errors::errors_semanalyzer::SemanalyzerErr,
+    shared_node_names::NodeName,
+    shared_types::{ArityMismatchKind, Span},
+};
 
-use crate::common::{empty_data_bindings, parse};
+use crate::common::{empty_schema, parse};
 
 mod common;
 
@@ -11,7 +21,176 @@ mod common;
 //
 // ==================================================================
 
-// TODO
+#[test]
+fn test_binds_and_references_symbol() {
+    let ast = parse(".let([a 1] a)");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let descriptor = hir.symbol_table.symbols.get(&SymbolId(0)).unwrap();
+    assert_eq!(descriptor.name, "a".to_string());
+    assert_eq!(descriptor.ty, LangType::Primitive(LangPrimitiveType::Int));
+
+    assert_eq!(
+        hir.aast,
+        vec![AAstNode::CallLet {
+            bindings: vec![(
+                SymbolId(0),
+                Box::new(AAstNode::Int {
+                    value: "1".to_string(),
+                    span: Span { start: 8, end: 9 }
+                })
+            )],
+            body: vec![Box::new(AAstNode::SymbolRef {
+                symbol_id: SymbolId(0),
+                span: Span { start: 11, end: 12 },
+                depth: 0,
+            })],
+            span: Span { start: 0, end: 13 }
+        }]
+    );
+}
+
+#[test]
+fn test_gives_each_binding_fresh_symbol() {
+    let ast = parse(r#".let([a 1, b "x"] b a)"#);
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    assert_eq!(hir.symbol_table.symbols.len(), 2);
+    assert_eq!(
+        hir.symbol_table.symbols.get(&SymbolId(1)).unwrap().ty,
+        LangType::Primitive(LangPrimitiveType::String)
+    );
+
+    let AAstNode::CallLet { bindings, body, .. } = &hir.aast[0] else {
+        panic!("Expected CallLet");
+    };
+    let ids: Vec<SymbolId> = bindings.iter().map(|(id, _)| *id).collect();
+    assert_eq!(ids, vec![SymbolId(0), SymbolId(1)]);
+    assert!(matches!(
+        *body[0],
+        AAstNode::SymbolRef {
+            symbol_id: SymbolId(1),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_accesses_outer_scope() {
+    let ast = parse(".define(PI 3.14) .let([a PI] .let([b a] PI))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    // `a` takes the type of `PI`.
+    assert_eq!(
+        hir.symbol_table.symbols.get(&SymbolId(1)).unwrap().ty,
+        LangType::Primitive(LangPrimitiveType::Float)
+    );
+
+    // Constants are inlined by the folding wherever they are
+    // referenced, nested scopes included.
+    let AAstNode::CallLet { bindings, body, .. } = &hir.aast[1] else {
+        panic!("Expected CallLet");
+    };
+    assert!(matches!(
+        &*bindings[0].1,
+        AAstNode::Float { value, .. } if value == "3.14"
+    ));
+    let AAstNode::CallLet { body, .. } = &*body[0] else {
+        panic!("Expected CallLet");
+    };
+    assert!(matches!(
+        &*body[0],
+        AAstNode::Float { value, .. } if value == "3.14"
+    ));
+}
+
+#[test]
+fn test_accesses_outer_let_scope() {
+    let ast = parse(".let([pi 3.14] .let([a pi] .let([b a] pi)))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    // `a` takes the type of `pi`.
+    assert_eq!(
+        hir.symbol_table.symbols.get(&SymbolId(1)).unwrap().ty,
+        LangType::Primitive(LangPrimitiveType::Float)
+    );
+
+    let AAstNode::CallLet { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallLet");
+    };
+    let AAstNode::CallLet { body, .. } = &*body[0] else {
+        panic!("Expected CallLet");
+    };
+    let AAstNode::CallLet { bindings, body, .. } = &*body[0] else {
+        panic!("Expected CallLet");
+    };
+    assert!(matches!(
+        *bindings[0].1,
+        AAstNode::SymbolRef {
+            symbol_id: SymbolId(1),
+            depth: 0,
+            ..
+        }
+    ));
+    assert!(matches!(
+        *body[0],
+        AAstNode::SymbolRef {
+            symbol_id: SymbolId(0),
+            depth: 2,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_resolves_inits_before_scope_is_pushed() {
+    // Inner `a` init refers to the outer `a`.
+    let ast = parse(".let([a 1] .let([a a] a))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallLet { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallLet");
+    };
+    let AAstNode::CallLet { bindings, body, .. } = &*body[0] else {
+        panic!("Expected CallLet");
+    };
+    assert_eq!(bindings[0].0, SymbolId(1));
+    assert!(matches!(
+        *bindings[0].1,
+        AAstNode::SymbolRef {
+            symbol_id: SymbolId(0),
+            depth: 0,
+            ..
+        }
+    ));
+    assert!(matches!(
+        *body[0],
+        AAstNode::SymbolRef {
+            symbol_id: SymbolId(1),
+            depth: 0,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_pops_scope_on_exit() {
+    let ast = parse(".let([a 1] a) a");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::SymbolUndefined {
+            span: Span { start: 14, end: 15 }
+        }
+    );
+}
 
 // ==================================================================
 //
@@ -28,39 +207,127 @@ mod common;
 #[test]
 fn test_returns_arity_mismatch_if_no_args() {
     let ast = parse(".let()");
-    let data_bindings = empty_data_bindings();
-    let result = Harmony::new(&ast, &data_bindings).analyze();
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
 
     assert!(matches!(
         result,
         Err(SemanalyzerErr::ArityMismatch {
-            fn_name: FnLet::LEXEME,
-            expected: FnLet::MIN_ARGS_LEN,
+            fn_name,
             found: 0,
-            kind: ArityMismatchKind::MoreEq,
+            kind: ArityMismatchKind::MoreEq(FnLet::MIN_ARGS_LEN),
             ..
-        })
+        }) if fn_name == FnLet::LEXEME
     ));
 }
 
 #[test]
 fn test_returns_arity_mismatch_if_1_arg() {
     let ast = parse(".let([])");
-    let data_bindings = empty_data_bindings();
-    let result = Harmony::new(&ast, &data_bindings).analyze();
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
 
     assert!(matches!(
         result,
         Err(SemanalyzerErr::ArityMismatch {
-            fn_name: FnLet::LEXEME,
-            expected: FnLet::MIN_ARGS_LEN,
+            fn_name,
             found: 1,
-            kind: ArityMismatchKind::MoreEq,
+            kind: ArityMismatchKind::MoreEq(FnLet::MIN_ARGS_LEN),
+            ..
+        }) if fn_name == FnLet::LEXEME
+    ));
+}
+
+#[test]
+fn test_returns_arg_kind_mismatch_if_first_arg_is_not_list() {
+    let ast = parse(".let(a 1)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArgKindMismatch {
+            fn_name: FnLet::LEXEME,
+            position: 0,
+            expected: NodeName::LIST,
+            found: NodeName::IDENTIFIER,
             ..
         })
     ));
 }
 
+#[test]
+fn test_returns_arg_kind_mismatch_if_binding_is_not_pattern() {
+    let ast = parse(".let([1 2] 1)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArgKindMismatch {
+            fn_name: FnLet::LEXEME,
+            position: 0,
+            expected: NodeName::PATTERN,
+            found: NodeName::INT,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_bindings_empty_if_no_bindings() {
+    let ast = parse(".let([] 1)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::BindingsEmpty {
+            span: Span { start: 5, end: 7 }
+        }
+    );
+}
+
+#[test]
+fn test_returns_binding_value_missing_if_odd_bindings() {
+    let ast = parse(".let([a 1 b] a)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::BindingValueMissing {
+            span: Span { start: 10, end: 11 }
+        }
+    );
+}
+
+#[test]
+fn test_returns_symbol_duplicate_if_rebound_in_same_let() {
+    let ast = parse(".let([a 1, a 2] a)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::SymbolDuplicate {
+            span: Span { start: 11, end: 12 }
+        }
+    );
+}
+
+#[test]
+fn test_returns_symbol_undefined_if_init_refers_to_sibling() {
+    let ast = parse(".let([a 1, b a] b)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::SymbolUndefined { .. })
+    ));
+}
+
 // ==================================================================
 //
 //  ERROR CASES END
@@ -0,0 +1,184 @@
+use elise_data::schema_resolver::{ResolvedSchema, SchemaResolver};
+use elise_semanalyzer::{Harmony, config::FnNarrow, data_types::LangType};
+use elise_shared::{
+    shared_errors::errors_semanalyzer::SemanalyzerErr,
+    shared_node_names::NodeName,
+    shared_types::{ArityMismatchKind, Span},
+};
+
+use crate::common::{analyze, empty_schema, int, parse, string, ty_of};
+
+mod common;
+
+fn schema() -> ResolvedSchema {
+    let ast = parse(
+        r#"
+.schema(
+    .dict(
+        "id"   .union(.int() .string())
+        "meta" .union(.dict("a" .int()) .list(.int()))
+        "rows" .list(.dict("code" .union(.int() .string())))))
+"#,
+    );
+    SchemaResolver::new(&ast).resolve().unwrap()
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_types_union_of_data_by_alternatives() {
+    assert_eq!(
+        ty_of(r#".get(@data "id")"#, &schema()),
+        LangType::Union(vec![int(), string()])
+    );
+}
+
+#[test]
+fn test_narrows_union_to_alternative() {
+    assert_eq!(
+        ty_of(r#".narrow(.get(@data "id") "Int")"#, &schema()),
+        int()
+    );
+    assert!(analyze(r#".add(.narrow(.get(@data "id") "Int") 1)"#, &schema()).is_ok());
+}
+
+#[test]
+fn test_continues_schema_path_of_narrowed_value() {
+    assert_eq!(
+        ty_of(r#".get(.narrow(.get(@data "meta") "Dict") "a")"#, &schema()),
+        int()
+    );
+}
+
+#[test]
+fn test_narrows_union_of_bound_rows() {
+    assert_eq!(
+        ty_of(
+            r#".map(.get(@data "rows") .fn([r] .narrow(.get(r "code") "String")))"#,
+            &schema()
+        ),
+        LangType::List(Box::new(string()))
+    );
+}
+
+#[test]
+fn test_converts_union_of_primitives() {
+    assert_eq!(ty_of(r#".to-int(.get(@data "id"))"#, &schema()), int());
+}
+
+#[test]
+fn test_narrows_unknown_value_to_any_kind() {
+    let ast = parse(r#".fn([x] .narrow(x "List"))"#);
+    let schema = empty_schema();
+
+    assert!(Harmony::new(&ast, &schema).analyze().is_ok());
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_arg_type_mismatch_if_union_is_used_as_alternative() {
+    assert_eq!(
+        analyze(r#".add(.get(@data "id") 1)"#, &schema()).unwrap_err(),
+        SemanalyzerErr::ArgTypeMismatch {
+            fn_name: "add".into(),
+            position: 0,
+            expected: NodeName::NUMBER,
+            found: NodeName::UNION,
+            span: Span { start: 5, end: 21 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_union_not_narrowed_if_key_is_applied_to_union() {
+    assert_eq!(
+        analyze(r#".get(@data "meta" "a")"#, &schema()).unwrap_err(),
+        SemanalyzerErr::UnionNotNarrowed {
+            span: Span { start: 18, end: 21 }
+        }
+    );
+}
+
+#[test]
+fn test_returns_schema_path_undefined_for_unknown_key_of_alternative() {
+    assert!(matches!(
+        analyze(
+            r#".get(.narrow(.get(@data "meta") "Dict") "zip")"#,
+            &schema()
+        ),
+        Err(SemanalyzerErr::SchemaPathUndefined { .. })
+    ));
+}
+
+#[test]
+fn test_returns_union_alternative_undefined() {
+    assert_eq!(
+        analyze(r#".narrow(.get(@data "id") "Bool")"#, &schema()).unwrap_err(),
+        SemanalyzerErr::UnionAlternativeUndefined {
+            alternative: "Bool".to_string(),
+            span: Span { start: 25, end: 31 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_value_is_not_union() {
+    assert!(matches!(
+        analyze(r#".narrow(1 "Int")"#, &schema()),
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            position: 0,
+            expected: NodeName::UNION,
+            found: NodeName::INT,
+            ..
+        }) if fn_name == FnNarrow::LEXEME
+    ));
+}
+
+#[test]
+fn test_returns_arg_kind_mismatch_if_alternative_is_not_literal() {
+    assert!(matches!(
+        analyze(r#".narrow(.get(@data "id") 1)"#, &schema()),
+        Err(SemanalyzerErr::ArgKindMismatch {
+            fn_name: FnNarrow::LEXEME,
+            position: 1,
+            expected: NodeName::STRING,
+            found: NodeName::INT,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_returns_arity_mismatch() {
+    assert!(matches!(
+        analyze(r#".narrow(.get(@data "id"))"#, &schema()),
+        Err(SemanalyzerErr::ArityMismatch {
+            fn_name,
+            kind: ArityMismatchKind::Eq(FnNarrow::ARGS_LEN),
+            found: 1,
+            ..
+        }) if fn_name == FnNarrow::LEXEME
+    ));
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
@@ -0,0 +1,307 @@
+use elise_semanalyzer::{
+    Harmony,
+    aast::{AAstNode, Op},
+    config::{FnArith, FnCmp, FnStr},
+    data_types::LangType,
+    symbol_table::SymbolId,
+};
+use elise_shared::{
+    shared_errors::errors_semanalyzer::SemanalyzerErr,
+    shared_node_names::NodeName,
+    shared_types::{ArityMismatchKind, Span},
+};
+
+use crate::common::{bool, empty_schema, float, int, parse, string, ty_of_bound};
+
+mod common;
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_annotates_op() {
+    let ast = parse(".fn([a] .add(a 2))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallFn { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallFn");
+    };
+    assert_eq!(
+        *body[0],
+        AAstNode::CallOp {
+            op: Op::Add,
+            args: vec![
+                Box::new(AAstNode::SymbolRef {
+                    symbol_id: SymbolId(0),
+                    span: Span { start: 13, end: 14 },
+                    depth: 0,
+                }),
+                Box::new(AAstNode::Int {
+                    value: "2".to_string(),
+                    span: Span { start: 15, end: 16 }
+                }),
+            ],
+            ty: LangType::Unknown,
+            span: Span { start: 8, end: 17 }
+        }
+    );
+}
+
+#[test]
+fn test_keeps_int_for_int_arithmetic() {
+    assert_eq!(ty_of_bound(".sub(3 2 1)"), int());
+    assert_eq!(ty_of_bound(".mul(3 2)"), int());
+    assert_eq!(ty_of_bound(".div(3 2)"), int());
+    assert_eq!(ty_of_bound(".mod(3 2)"), int());
+}
+
+#[test]
+fn test_promotes_to_float_if_any_arg_is_float() {
+    assert_eq!(ty_of_bound(".add(1 2.5)"), float());
+    assert_eq!(ty_of_bound(".mul(1.5 2 3)"), float());
+    assert_eq!(ty_of_bound(".div(1 2.0)"), float());
+}
+
+#[test]
+fn test_promotes_nested_arithmetic() {
+    assert_eq!(ty_of_bound(".add(1 .mul(2 0.5))"), float());
+    assert_eq!(ty_of_bound(".add(1 .mul(2 3))"), int());
+}
+
+#[test]
+fn test_negates_number() {
+    assert_eq!(ty_of_bound(".neg(1)"), int());
+    assert_eq!(ty_of_bound(".neg(1.5)"), float());
+}
+
+#[test]
+fn test_returns_unknown_if_arg_is_unknown() {
+    let ast = parse(".fn([x] .add(x 1))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallFn { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallFn");
+    };
+    assert!(matches!(
+        *body[0],
+        AAstNode::CallOp {
+            op: Op::Add,
+            ty: LangType::Unknown,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_float_wins_over_unknown() {
+    let ast = parse(".fn([x] .add(x 1.5))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    let AAstNode::CallFn { body, .. } = &hir.aast[0] else {
+        panic!("Expected CallFn");
+    };
+    assert!(matches!(
+        &*body[0],
+        AAstNode::CallOp { ty, .. } if *ty == float()
+    ));
+}
+
+#[test]
+fn test_compares_numbers() {
+    assert_eq!(ty_of_bound(".lt(1 2)"), bool());
+    assert_eq!(ty_of_bound(".lte(1 2.5)"), bool());
+    assert_eq!(ty_of_bound(".gt(1.5 2)"), bool());
+    assert_eq!(ty_of_bound(".gte(1 2)"), bool());
+}
+
+#[test]
+fn test_compares_any_types_for_equality() {
+    assert_eq!(ty_of_bound(r#".eq("a" "b")"#), bool());
+    assert_eq!(ty_of_bound(".neq(1 null)"), bool());
+    assert_eq!(ty_of_bound(".eq([1] [1])"), bool());
+}
+
+#[test]
+fn test_concatenates_strings() {
+    assert_eq!(ty_of_bound(r#".concat("a" "b" "c")"#), string());
+}
+
+#[test]
+fn test_types_symbol_bound_to_op() {
+    let ast = parse(".let([x .add(1 2.0)] .let([y .gt(x 1)] y))");
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+
+    assert_eq!(
+        hir.symbol_table.symbols.get(&SymbolId(0)).unwrap().ty,
+        float()
+    );
+    assert_eq!(
+        hir.symbol_table.symbols.get(&SymbolId(1)).unwrap().ty,
+        bool()
+    );
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_arity_mismatch_if_arithmetic_has_one_arg() {
+    let ast = parse(".add(1)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::ArityMismatch {
+            fn_name: FnArith::ADD.into(),
+            kind: ArityMismatchKind::MoreEq(FnArith::MIN_ARGS_LEN),
+            found: 1,
+            span: Span { start: 0, end: 7 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_arity_mismatch_if_neg_has_two_args() {
+    let ast = parse(".neg(1 2)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArityMismatch {
+            fn_name,
+            kind: ArityMismatchKind::Eq(FnArith::NEG_ARGS_LEN),
+            found: 2,
+            ..
+        }) if fn_name == FnArith::NEG
+    ));
+}
+
+#[test]
+fn test_returns_arity_mismatch_if_comparison_has_three_args() {
+    let ast = parse(".lt(1 2 3)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArityMismatch {
+            fn_name,
+            kind: ArityMismatchKind::Eq(FnCmp::ARGS_LEN),
+            found: 3,
+            ..
+        }) if fn_name == FnCmp::LT
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_arithmetic_gets_string() {
+    let ast = parse(r#".add(1 2 "3")"#);
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::ArgTypeMismatch {
+            fn_name: FnArith::ADD.into(),
+            position: 2,
+            expected: NodeName::NUMBER,
+            found: NodeName::STRING,
+            span: Span { start: 9, end: 12 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_ordering_gets_bool() {
+    let ast = parse(".gte(true 1)");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            position: 0,
+            expected: NodeName::NUMBER,
+            found: NodeName::BOOL,
+            ..
+        }) if fn_name == FnCmp::GTE
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_neg_gets_list() {
+    let ast = parse(".neg([1])");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            position: 0,
+            expected: NodeName::NUMBER,
+            found: NodeName::LIST,
+            ..
+        }) if fn_name == FnArith::NEG
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_concat_gets_number() {
+    let ast = parse(r#".concat("a" 1)"#);
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert_eq!(
+        result.unwrap_err(),
+        SemanalyzerErr::ArgTypeMismatch {
+            fn_name: FnStr::CONCAT.into(),
+            position: 1,
+            expected: NodeName::STRING,
+            found: NodeName::INT,
+            span: Span { start: 12, end: 13 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_nested_op_is_bool() {
+    let ast = parse(".add(1 .lt(1 2))");
+    let schema = empty_schema();
+    let result = Harmony::new(&ast, &schema).analyze();
+
+    assert!(matches!(
+        result,
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            position: 1,
+            found: NodeName::BOOL,
+            ..
+        }) if fn_name == FnArith::ADD
+    ));
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
@@ -0,0 +1,142 @@
+use elise_semanalyzer::{
+    aast::{AAstNode, Op},
+    config::FnStr,
+    data_types::LangType,
+};
+use elise_shared::{
+    shared_errors::errors_semanalyzer::SemanalyzerErr,
+    shared_node_names::NodeName,
+    shared_types::{ArityMismatchKind, Span},
+};
+
+use crate::common::{analyze, bool, empty_schema, int, string, ty_of};
+
+mod common;
+
+fn strings() -> LangType {
+    LangType::List(Box::new(string()))
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_annotates_string_fn_without_folding() {
+    let node = analyze(r#".upper("a")"#, &empty_schema()).unwrap();
+
+    assert!(matches!(
+        node,
+        AAstNode::CallOp {
+            op: Op::Upper,
+            span: Span { start: 0, end: 11 },
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_types_string_fns() {
+    assert_eq!(ty_of(r#".length("añ")"#, &empty_schema()), int());
+    assert_eq!(ty_of(r#".substring("abc" 1)"#, &empty_schema()), string());
+    assert_eq!(ty_of(r#".substring("abc" 1 2)"#, &empty_schema()), string());
+    assert_eq!(ty_of(r#".split("a,b" ",")"#, &empty_schema()), strings());
+    assert_eq!(ty_of(r#".join(["a" "b"] ",")"#, &empty_schema()), string());
+    assert_eq!(ty_of(r#".trim(" a ")"#, &empty_schema()), string());
+    assert_eq!(ty_of(r#".lower("A")"#, &empty_schema()), string());
+    assert_eq!(ty_of(r#".starts-with("ab" "a")"#, &empty_schema()), bool());
+    assert_eq!(ty_of(r#".ends-with("ab" "b")"#, &empty_schema()), bool());
+    assert_eq!(
+        ty_of(r#".replace("ab" "a" "c")"#, &empty_schema()),
+        string()
+    );
+}
+
+#[test]
+fn test_types_regex_fns() {
+    assert_eq!(ty_of(r#".matches("a1" "\\d")"#, &empty_schema()), bool());
+    assert_eq!(ty_of(r#".extract("a1" "\\d")"#, &empty_schema()), string());
+    assert_eq!(
+        ty_of(r#".extract-all("a1" "\\d")"#, &empty_schema()),
+        strings()
+    );
+}
+
+#[test]
+fn test_accepts_unknown_args() {
+    assert!(analyze(".fn([s p] .matches(s p))", &empty_schema()).is_ok());
+    assert!(analyze(r#".fn([xs] .join(xs ","))"#, &empty_schema()).is_ok());
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_arity_mismatch_for_substring() {
+    assert!(matches!(
+        analyze(r#".substring("abc")"#, &empty_schema()),
+        Err(SemanalyzerErr::ArityMismatch {
+            fn_name,
+            kind: ArityMismatchKind::Range((
+                FnStr::SUBSTRING_MIN_ARGS_LEN,
+                FnStr::SUBSTRING_MAX_ARGS_LEN
+            )),
+            found: 1,
+            ..
+        }) if fn_name == FnStr::SUBSTRING
+    ));
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_for_substring_position() {
+    assert_eq!(
+        analyze(r#".substring("abc" "1")"#, &empty_schema()).unwrap_err(),
+        SemanalyzerErr::ArgTypeMismatch {
+            fn_name: FnStr::SUBSTRING.into(),
+            position: 1,
+            expected: NodeName::INT,
+            found: NodeName::STRING,
+            span: Span { start: 17, end: 20 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_arg_type_mismatch_if_joined_items_are_not_strings() {
+    assert!(matches!(
+        analyze(r#".join([1 2] ",")"#, &empty_schema()),
+        Err(SemanalyzerErr::ArgTypeMismatch {
+            fn_name,
+            position: 0,
+            expected: NodeName::LIST,
+            ..
+        }) if fn_name == FnStr::JOIN
+    ));
+}
+
+#[test]
+fn test_returns_invalid_regex_for_literal_pattern() {
+    assert_eq!(
+        analyze(r#".matches("a" "(")"#, &empty_schema()).unwrap_err(),
+        SemanalyzerErr::InvalidRegex {
+            span: Span { start: 13, end: 16 }
+        }
+    );
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
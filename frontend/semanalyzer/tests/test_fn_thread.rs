@@ -0,0 +1,165 @@
+use elise_semanalyzer::{
+    Harmony,
+    aast::{AAstNode, Op},
+    config::FnThread,
+};
+use elise_shared::{
+    shared_errors::errors_semanalyzer::SemanalyzerErr,
+    shared_node_names::NodeName,
+    shared_types::{ArityMismatchKind, Span},
+};
+
+use crate::common::{empty_schema, parse};
+
+mod common;
+
+fn analyze(src: &str) -> Result<AAstNode, SemanalyzerErr> {
+    let ast = parse(src);
+    let schema = empty_schema();
+    let mut hir = Harmony::new(&ast, &schema).analyze()?;
+
+    Ok(hir.aast.pop().unwrap())
+}
+
+// Literal operands are folded, so the threaded value is a param.
+fn fn_body(src: &str) -> AAstNode {
+    let AAstNode::CallFn { mut body, .. } = analyze(src).unwrap() else {
+        panic!("Expected CallFn");
+    };
+
+    *body.pop().unwrap()
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_threads_into_first_arg() {
+    let node = fn_body(".fn([x] .->(x .add(1) .mul(2)))");
+
+    let AAstNode::CallOp {
+        op: Op::Mul,
+        args,
+        span,
+        ..
+    } = node
+    else {
+        panic!("Expected .mul");
+    };
+    assert_eq!(span, Span { start: 22, end: 29 });
+    assert!(matches!(
+        &*args[0],
+        AAstNode::CallOp {
+            op: Op::Add,
+            span: Span { start: 14, end: 21 },
+            args,
+            ..
+        } if matches!(*args[0], AAstNode::SymbolRef { .. })
+    ));
+}
+
+#[test]
+fn test_threads_into_last_arg() {
+    let node = fn_body(r#".fn([s] .->>(s .concat("a")))"#);
+
+    let AAstNode::CallOp {
+        op: Op::Concat,
+        args,
+        ..
+    } = node
+    else {
+        panic!("Expected .concat");
+    };
+    assert!(matches!(*args[0], AAstNode::String { .. }));
+    assert!(matches!(*args[1], AAstNode::SymbolRef { .. }));
+}
+
+#[test]
+fn test_evaluates_to_expression_without_calls() {
+    assert_eq!(
+        analyze(".->(1)").unwrap(),
+        AAstNode::Int {
+            value: "1".to_string(),
+            span: Span { start: 4, end: 5 },
+        }
+    );
+}
+
+#[test]
+fn test_folds_threaded_literals() {
+    assert_eq!(
+        analyze(".->(1 .add(2) .mul(3))").unwrap(),
+        AAstNode::Int {
+            value: "9".to_string(),
+            span: Span { start: 14, end: 21 },
+        }
+    );
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_errors_at_original_spans() {
+    assert_eq!(
+        analyze(r#".->(1 .concat("a"))"#).unwrap_err(),
+        SemanalyzerErr::ArgTypeMismatch {
+            fn_name: "concat".into(),
+            position: 0,
+            expected: NodeName::STRING,
+            found: NodeName::INT,
+            span: Span { start: 4, end: 5 },
+        }
+    );
+    assert_eq!(
+        analyze(".->(1 .f())").unwrap_err(),
+        SemanalyzerErr::UnknownFunction {
+            span: Span { start: 6, end: 10 }
+        }
+    );
+}
+
+#[test]
+fn test_returns_arg_kind_mismatch_if_step_is_not_call() {
+    assert_eq!(
+        analyze(".->>(1 2)").unwrap_err(),
+        SemanalyzerErr::ArgKindMismatch {
+            fn_name: FnThread::LAST,
+            position: 1,
+            expected: NodeName::CALL,
+            found: NodeName::INT,
+            span: Span { start: 7, end: 8 },
+        }
+    );
+}
+
+#[test]
+fn test_returns_arity_mismatch_without_expression() {
+    assert!(matches!(
+        analyze(".->()"),
+        Err(SemanalyzerErr::ArityMismatch {
+            fn_name,
+            kind: ArityMismatchKind::MoreEq(FnThread::MIN_ARGS_LEN),
+            found: 0,
+            ..
+        }) if fn_name == FnThread::FIRST
+    ));
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
@@ -1,16 +1,16 @@
-use elise_semanalyzer::{Harmony, semanalyzer_aast::AAstNode};
+use elise_semanalyzer::{Harmony, aast::AAstNode};
 use elise_shared::shared_types::Span;
 
-use crate::common::{empty_data_bindings, parse};
+use crate::common::{empty_schema, parse};
 
 mod common;
 
 #[test]
 fn test_bool() {
     let ast = parse("true, false");
-    let data_bindings = empty_data_bindings();
-    let hir = Harmony::new(&ast, &data_bindings).analyze().unwrap();
-    assert_eq!(hir.symbol_table.symbols.is_empty(), true);
+    let schema = empty_schema();
+    let hir = Harmony::new(&ast, &schema).analyze().unwrap();
+    assert!(hir.symbol_table.symbols.is_empty());
     assert_eq!(
         hir.aast,
         vec![
@@ -0,0 +1,294 @@
+//! Implementations of `Instruction::GroupBy`, `Instruction::Aggregate`
+//! and `Instruction::GroupAggregate`. Groups are found in a hash table
+//! by their keys, so the items are read once, and groups keep the
+//! order of their first items.
+//!
+//! 1. Keys are equal if they are values of the same type that are
+//!    equal, so `1` and `1.0` make different groups, and Null is
+//!    a key of its own;
+//! 2. Sum, Avg, Min and Max take numbers and skip Nulls;
+//! 3. Sum is an Int if all the numbers are Ints and the total fits
+//!    into it, otherwise a Float. Min and Max keep the type of the
+//!    number they found;
+//! 4. Sum, Count and CountDistinct of no values are 0, Avg, Min and
+//!    Max of them are Null;
+//! 5. Missing fields of rows are read as Null, the same way as
+//!    absent optional columns;
+
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+
+use elise_bytecode::{AggregateFn, AggregateSpec};
+use elise_shared::shared_errors::errors_vm::VmErr;
+
+use crate::value::Value;
+
+/// Hashable form of a value. Floats are kept as their bits with
+/// `-0.0` turned into `0.0`, so equal Floats have equal keys.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+enum Key {
+    Int(i64),
+    Float(u64),
+    String(String),
+    Bool(bool),
+    Null,
+    Date(i64),
+    Time(i64),
+    DateTime(i64),
+    Duration(i64),
+    List(Vec<Key>),
+    Dict(Vec<(String, Key)>),
+}
+
+impl Key {
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::Int(int) => Key::Int(*int),
+            Value::Float(float) => Key::Float((float + 0.0).to_bits()),
+            Value::String(string) => Key::String(string.clone()),
+            Value::Bool(bool) => Key::Bool(*bool),
+            Value::Null => Key::Null,
+            Value::Date(days) => Key::Date(*days),
+            Value::Time(secs) => Key::Time(*secs),
+            Value::DateTime(secs) => Key::DateTime(*secs),
+            Value::Duration(secs) => Key::Duration(*secs),
+            Value::List(items) => Key::List(items.iter().map(Key::of).collect()),
+            Value::Dict(fields) => Key::Dict(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), Key::of(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Aggregation of the values read so far.
+enum Running {
+    Count(i64),
+    CountDistinct(HashSet<Key>),
+    // Ints are added up apart from Floats, so the total of Ints
+    // stays exact. i128 doesn't overflow for any List that fits
+    // into memory.
+    Sum {
+        ints: i128,
+        floats: f64,
+        has_float: bool,
+    },
+    Avg {
+        total: f64,
+        count: i64,
+    },
+    Min(Option<Value>),
+    Max(Option<Value>),
+}
+
+impl Running {
+    fn new(aggregate_fn: AggregateFn) -> Self {
+        match aggregate_fn {
+            AggregateFn::Count => Running::Count(0),
+            AggregateFn::CountDistinct => Running::CountDistinct(HashSet::new()),
+            AggregateFn::Sum => Running::Sum {
+                ints: 0,
+                floats: 0.0,
+                has_float: false,
+            },
+            AggregateFn::Avg => Running::Avg {
+                total: 0.0,
+                count: 0,
+            },
+            AggregateFn::Min => Running::Min(None),
+            AggregateFn::Max => Running::Max(None),
+        }
+    }
+
+    fn update(&mut self, value: &Value) -> Result<(), VmErr> {
+        match self {
+            Running::Count(count) => *count += 1,
+            Running::CountDistinct(seen) => {
+                seen.insert(Key::of(value));
+            }
+            _ if matches!(value, Value::Null) => {}
+            Running::Sum {
+                ints,
+                floats,
+                has_float,
+            } => match value {
+                Value::Int(int) => *ints += *int as i128,
+                Value::Float(float) => {
+                    *floats += float;
+                    *has_float = true;
+                }
+                value => return Err(not_a_number(AggregateFn::Sum, value)),
+            },
+            Running::Avg { total, count } => {
+                *total += as_float(value).ok_or_else(|| not_a_number(AggregateFn::Avg, value))?;
+                *count += 1;
+            }
+            Running::Min(found) => keep(found, value, AggregateFn::Min, Ordering::Less)?,
+            Running::Max(found) => keep(found, value, AggregateFn::Max, Ordering::Greater)?,
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Value {
+        match self {
+            Running::Count(count) => Value::Int(count),
+            Running::CountDistinct(seen) => Value::Int(seen.len() as i64),
+            Running::Sum {
+                ints,
+                floats,
+                has_float,
+            } => match i64::try_from(ints) {
+                Ok(ints) if !has_float => Value::Int(ints),
+                _ => Value::Float(ints as f64 + floats),
+            },
+            Running::Avg { count: 0, .. } => Value::Null,
+            Running::Avg { total, count } => Value::Float(total / count as f64),
+            Running::Min(found) | Running::Max(found) => found.unwrap_or(Value::Null),
+        }
+    }
+}
+
+/// Groups of the items with equal keys, `keys` go along with
+/// `items` by their positions.
+pub fn group_by(items: Vec<Value>, keys: &[Value]) -> Value {
+    let mut groups: Vec<Vec<Value>> = vec![];
+    let mut idx_by_key: HashMap<Key, usize> = HashMap::new();
+
+    for (item, key) in items.into_iter().zip(keys) {
+        let idx = *idx_by_key.entry(Key::of(key)).or_insert_with(|| {
+            groups.push(vec![]);
+            groups.len() - 1
+        });
+        groups[idx].push(item);
+    }
+
+    Value::List(groups.into_iter().map(Value::List).collect())
+}
+
+pub fn aggregate(items: &[Value], aggregate_fn: AggregateFn) -> Result<Value, VmErr> {
+    let mut running = Running::new(aggregate_fn);
+
+    for item in items {
+        running.update(item)?;
+    }
+
+    Ok(running.finish())
+}
+
+/// Dicts of the key fields of the groups of rows followed by
+/// their aggregations, in the order of the spec.
+pub fn group_aggregate(rows: &[Value], spec: &AggregateSpec) -> Result<Value, VmErr> {
+    let mut groups: Vec<(Vec<Value>, Vec<Running>)> = vec![];
+    let mut idx_by_key: HashMap<Vec<Key>, usize> = HashMap::new();
+
+    for row in rows {
+        let Value::Dict(fields) = row else {
+            return Err(VmErr::InvalidAggregateRow {
+                found: row.as_str(),
+            });
+        };
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(field_name, _)| field_name == name)
+                .map_or(Value::Null, |(_, value)| value.clone())
+        };
+
+        let key_values: Vec<Value> = spec.keys.iter().map(|key| field(key)).collect();
+        let key = key_values.iter().map(Key::of).collect();
+
+        let idx = *idx_by_key.entry(key).or_insert_with(|| {
+            let running = spec
+                .aggregations
+                .iter()
+                .map(|(_, aggregate_fn, _)| Running::new(*aggregate_fn))
+                .collect();
+            groups.push((key_values, running));
+            groups.len() - 1
+        });
+
+        for ((_, _, read), running) in spec.aggregations.iter().zip(&mut groups[idx].1) {
+            // Count reads no field, any value counts the row.
+            let value = read.as_deref().map_or(Value::Bool(true), field);
+            running.update(&value)?;
+        }
+    }
+
+    let result = groups
+        .into_iter()
+        .map(|(key_values, running)| {
+            let keys = spec.keys.iter().cloned().zip(key_values);
+            let aggregations = spec
+                .aggregations
+                .iter()
+                .zip(running)
+                .map(|((name, _, _), running)| (name.clone(), running.finish()));
+
+            Value::Dict(keys.chain(aggregations).collect())
+        })
+        .collect();
+
+    Ok(Value::List(result))
+}
+
+fn as_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int(int) => Some(*int as f64),
+        Value::Float(float) => Some(*float),
+        _ => None,
+    }
+}
+
+// Ints are compared as they are, so the ones beyond the precision
+// of Floats keep their order.
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Int(a), Value::Int(b)) => Some(a.cmp(b)),
+        (a, b) => as_float(a)?.partial_cmp(&as_float(b)?),
+    }
+}
+
+fn keep(
+    found: &mut Option<Value>,
+    value: &Value,
+    aggregate_fn: AggregateFn,
+    wanted: Ordering,
+) -> Result<(), VmErr> {
+    if as_float(value).is_none() {
+        return Err(not_a_number(aggregate_fn, value));
+    }
+
+    let replaces = match found {
+        Some(current) => compare(value, current) == Some(wanted),
+        None => true,
+    };
+
+    if replaces {
+        *found = Some(value.clone());
+    }
+
+    Ok(())
+}
+
+fn not_a_number(aggregate_fn: AggregateFn, value: &Value) -> VmErr {
+    VmErr::InvalidAggregateValue {
+        aggregation: aggregate_name(aggregate_fn),
+        found: value.as_str(),
+    }
+}
+
+fn aggregate_name(aggregate_fn: AggregateFn) -> &'static str {
+    match aggregate_fn {
+        AggregateFn::Count => "count",
+        AggregateFn::CountDistinct => "count-distinct",
+        AggregateFn::Sum => "sum",
+        AggregateFn::Avg => "avg",
+        AggregateFn::Min => "min",
+        AggregateFn::Max => "max",
+    }
+}
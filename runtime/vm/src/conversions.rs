@@ -0,0 +1,121 @@
+//! Implementation of `Instruction::Convert`.
+//!
+//! 1. Int converts from a Float without a fraction, a String of
+//!    digits with an optional sign, and a Bool as 1 or 0;
+//! 2. Float converts from an Int, a String of a finite number,
+//!    and a Bool as 1.0 or 0.0;
+//! 3. Str converts from any primitive value but Null, Floats
+//!    always keep their fraction, like "3.0", dates and times are
+//!    written in their default formats and durations in ISO 8601;
+//! 4. Bool converts from "true" and "false", and from numbers
+//!    equal to 1 or 0;
+//! 5. Null, Lists and Dicts never convert;
+
+use elise_bytecode::{ConvertTo, OnFailure};
+use elise_shared::{
+    shared_errors::{errors_csv_binder::PosInfo, errors_vm::VmErr},
+    shared_node_names::NodeName,
+    shared_temporal,
+    shared_types::Span,
+};
+
+use crate::value::Value;
+
+/// Converts the value, or handles the failure as the instruction
+/// says. `fallback` is the value popped for `OnFailure::Fallback`,
+/// `cell` is the position of the value in the data if it was read
+/// from there, so a failure points at the data along with the code.
+/// Values don't keep their positions yet, so it's None until they do.
+pub fn convert(
+    value: Value,
+    to: ConvertTo,
+    on_failure: OnFailure,
+    fallback: Option<Value>,
+    cell: Option<&PosInfo>,
+    span: &Span,
+) -> Result<Value, VmErr> {
+    if let Some(converted) = try_convert(&value, to) {
+        return Ok(converted);
+    }
+
+    match on_failure {
+        OnFailure::Fallback => Ok(fallback.unwrap_or(Value::Null)),
+        OnFailure::Null => Ok(Value::Null),
+        OnFailure::Fail => Err(VmErr::ConversionFailed {
+            expected: target_name(to),
+            found: describe(&value),
+            cell: cell.cloned(),
+            span: span.clone(),
+        }),
+    }
+}
+
+fn try_convert(value: &Value, to: ConvertTo) -> Option<Value> {
+    let converted = match (to, value) {
+        (ConvertTo::Int, Value::Int(int)) => Value::Int(*int),
+        // The range of i64 can't be checked in Float precisely,
+        // 2^63 is the first Float out of it.
+        (ConvertTo::Int, Value::Float(float))
+            if float.fract() == 0.0 && float.abs() < 9_223_372_036_854_775_808.0 =>
+        {
+            Value::Int(*float as i64)
+        }
+        (ConvertTo::Int, Value::String(string)) => Value::Int(string.parse().ok()?),
+        (ConvertTo::Int, Value::Bool(bool)) => Value::Int(*bool as i64),
+
+        (ConvertTo::Float, Value::Int(int)) => Value::Float(*int as f64),
+        (ConvertTo::Float, Value::Float(float)) => Value::Float(*float),
+        (ConvertTo::Float, Value::String(string)) => {
+            let float: f64 = string.parse().ok()?;
+            if !float.is_finite() {
+                return None;
+            }
+            Value::Float(float)
+        }
+        (ConvertTo::Float, Value::Bool(bool)) => Value::Float(*bool as i64 as f64),
+
+        (ConvertTo::Str, Value::Int(int)) => Value::String(int.to_string()),
+        (ConvertTo::Str, Value::Float(float)) => Value::String(format!("{float:?}")),
+        (ConvertTo::Str, Value::String(string)) => Value::String(string.clone()),
+        (ConvertTo::Str, Value::Bool(bool)) => Value::String(bool.to_string()),
+        (ConvertTo::Str, Value::Date(_) | Value::Time(_) | Value::DateTime(_)) => {
+            let (kind, value) = value.temporal()?;
+            Value::String(shared_temporal::format(value, kind.default_format(), kind)?)
+        }
+        (ConvertTo::Str, Value::Duration(secs)) => {
+            Value::String(shared_temporal::format_duration(*secs))
+        }
+
+        (ConvertTo::Bool, Value::Bool(bool)) => Value::Bool(*bool),
+        (ConvertTo::Bool, Value::String(string)) => Value::Bool(string.parse().ok()?),
+        (ConvertTo::Bool, Value::Int(int @ (0 | 1))) => Value::Bool(*int == 1),
+        (ConvertTo::Bool, Value::Float(float)) if *float == 0.0 || *float == 1.0 => {
+            Value::Bool(*float == 1.0)
+        }
+
+        _ => return None,
+    };
+
+    Some(converted)
+}
+
+fn target_name(to: ConvertTo) -> &'static str {
+    match to {
+        ConvertTo::Int => NodeName::INT,
+        ConvertTo::Float => NodeName::FLOAT,
+        ConvertTo::Str => NodeName::STRING,
+        ConvertTo::Bool => NodeName::BOOL,
+    }
+}
+
+// Primitive values are shown as they are, since the value is
+// what the user needs to find in the data.
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Int(int) => int.to_string(),
+        Value::Float(float) => format!("{float:?}"),
+        Value::String(string) => format!("\"{string}\""),
+        Value::Bool(bool) => bool.to_string(),
+        value => value.as_str().to_string(),
+    }
+}
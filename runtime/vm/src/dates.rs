@@ -0,0 +1,35 @@
+//! Implementations of `Instruction::Date`. Values are Dates, Times
+//! and DateTimes given by their kind and the integer they are kept
+//! as, see `shared_temporal`. Durations are counted in seconds.
+
+use elise_shared::{
+    shared_errors::errors_vm::VmErr,
+    shared_temporal::{self, TemporalKind, TemporalUnit},
+};
+
+/// Dates result in the day the sum falls on counting from their
+/// midnights, Times wrap around midnight.
+pub fn add(kind: TemporalKind, value: i64, duration: i64) -> i64 {
+    shared_temporal::add(value, duration, kind)
+}
+
+/// Duration from `to` to `from`, negative if `from` is earlier.
+pub fn diff(kind: TemporalKind, from: i64, to: i64) -> i64 {
+    shared_temporal::diff(from, to, kind)
+}
+
+/// Start of the "year", "month", "week", "day", "hour" or "minute"
+/// the value is in. Weeks start on Monday.
+pub fn trunc(kind: TemporalKind, value: i64, unit: &str) -> Result<i64, VmErr> {
+    TemporalUnit::from_name(unit)
+        .and_then(|temporal_unit| shared_temporal::truncate(value, temporal_unit, kind))
+        .ok_or_else(|| VmErr::InvalidDateUnit {
+            unit: unit.to_string(),
+        })
+}
+
+pub fn format(kind: TemporalKind, value: i64, format: &str) -> Result<String, VmErr> {
+    shared_temporal::format(value, format, kind).ok_or_else(|| VmErr::InvalidDateFormat {
+        format: format.to_string(),
+    })
+}
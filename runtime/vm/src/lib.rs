@@ -1 +1,12 @@
-// TODO
+//! # Sonata — VM
+//!
+//! Executes a bytecode `Chunk` produced by Maestro against the data.
+//!
+//! So far only the built-in functions behind dedicated instructions
+//! are implemented, the dispatch loop is yet to come.
+
+pub mod aggregation;
+pub mod conversions;
+pub mod dates;
+pub mod strings;
+pub mod value;
@@ -0,0 +1,124 @@
+//! Implementations of `Instruction::Str`. Strings are kept as UTF-8
+//! the same way the parser reads them, so lengths and positions are
+//! counted in characters, and no function splits a character apart.
+
+use std::collections::HashMap;
+
+use elise_shared::shared_errors::errors_vm::VmErr;
+use regex::{Captures, Regex};
+
+pub fn length(s: &str) -> i64 {
+    s.chars().count() as i64
+}
+
+/// Characters from `start` up to `end` exclusive, or up to the end
+/// of the String if `end` is absent. Positions are clamped to the
+/// String, so the result is empty if they are out of it.
+pub fn substring(s: &str, start: i64, end: Option<i64>) -> String {
+    let len = length(s);
+    let start = start.clamp(0, len) as usize;
+    let end = end.unwrap_or(len).clamp(0, len) as usize;
+
+    if start >= end {
+        return String::new();
+    }
+
+    s.chars().skip(start).take(end - start).collect()
+}
+
+/// An empty separator splits the String into characters.
+pub fn split(s: &str, separator: &str) -> Vec<String> {
+    if separator.is_empty() {
+        return s.chars().map(String::from).collect();
+    }
+
+    s.split(separator).map(String::from).collect()
+}
+
+pub fn join(items: &[String], separator: &str) -> String {
+    items.join(separator)
+}
+
+/// Removes Unicode whitespace from both ends.
+pub fn trim(s: &str) -> String {
+    s.trim().to_string()
+}
+
+pub fn upper(s: &str) -> String {
+    s.to_uppercase()
+}
+
+pub fn lower(s: &str) -> String {
+    s.to_lowercase()
+}
+
+pub fn starts_with(s: &str, prefix: &str) -> bool {
+    s.starts_with(prefix)
+}
+
+pub fn ends_with(s: &str, suffix: &str) -> bool {
+    s.ends_with(suffix)
+}
+
+/// Replaces every occurrence. An empty `from` matches nothing,
+/// so the String is kept as it is.
+pub fn replace(s: &str, from: &str, to: &str) -> String {
+    if from.is_empty() {
+        return s.to_string();
+    }
+
+    s.replace(from, to)
+}
+
+/// Compiled regular expressions by their patterns, so a pattern
+/// used inside of a loop is compiled once.
+#[derive(Default)]
+pub struct Regexes {
+    compiled: HashMap<String, Regex>,
+}
+
+impl Regexes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the pattern matches anywhere in the String.
+    pub fn matches(&mut self, s: &str, pattern: &str) -> Result<bool, VmErr> {
+        Ok(self.get(pattern)?.is_match(s))
+    }
+
+    /// The first match, or its first group if the pattern has any.
+    pub fn extract(&mut self, s: &str, pattern: &str) -> Result<Option<String>, VmErr> {
+        Ok(self
+            .get(pattern)?
+            .captures(s)
+            .and_then(|captures| Self::extracted(&captures)))
+    }
+
+    /// Every match the same way as `extract` takes the first one.
+    pub fn extract_all(&mut self, s: &str, pattern: &str) -> Result<Vec<String>, VmErr> {
+        Ok(self
+            .get(pattern)?
+            .captures_iter(s)
+            .filter_map(|captures| Self::extracted(&captures))
+            .collect())
+    }
+
+    fn get(&mut self, pattern: &str) -> Result<&Regex, VmErr> {
+        if !self.compiled.contains_key(pattern) {
+            let regex = Regex::new(pattern).map_err(|_| VmErr::InvalidRegex {
+                pattern: pattern.to_string(),
+            })?;
+            self.compiled.insert(pattern.to_string(), regex);
+        }
+
+        Ok(&self.compiled[pattern])
+    }
+
+    // A group that didn't take part in the match extracts nothing.
+    fn extracted(captures: &Captures) -> Option<String> {
+        let group = if captures.len() > 1 { 1 } else { 0 };
+
+        captures.get(group).map(|found| found.as_str().to_string())
+    }
+}
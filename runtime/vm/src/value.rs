@@ -0,0 +1,54 @@
+use elise_shared::{shared_node_names::NodeName, shared_temporal::TemporalKind};
+
+/// Runtime value. Dicts keep the order of their keys. Temporal
+/// values are kept as described in `shared_temporal`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    String(String),
+    Bool(bool),
+    Null,
+    Date(i64),
+    Time(i64),
+    DateTime(i64),
+    Duration(i64),
+    List(Vec<Value>),
+    Dict(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Value::Int(_) => NodeName::INT,
+            Value::Float(_) => NodeName::FLOAT,
+            Value::String(_) => NodeName::STRING,
+            Value::Bool(_) => NodeName::BOOL,
+            Value::Null => NodeName::NULL,
+            Value::Date(_) => NodeName::DATE,
+            Value::Time(_) => NodeName::TIME,
+            Value::DateTime(_) => NodeName::DATETIME,
+            Value::Duration(_) => NodeName::DURATION,
+            Value::List(_) => NodeName::LIST,
+            Value::Dict(_) => NodeName::DICT,
+        }
+    }
+
+    /// Kind of a Date, Time or DateTime together with its value.
+    pub fn temporal(&self) -> Option<(TemporalKind, i64)> {
+        match self {
+            Value::Date(days) => Some((TemporalKind::Date, *days)),
+            Value::Time(secs) => Some((TemporalKind::Time, *secs)),
+            Value::DateTime(secs) => Some((TemporalKind::DateTime, *secs)),
+            _ => None,
+        }
+    }
+
+    pub fn from_temporal(kind: TemporalKind, value: i64) -> Self {
+        match kind {
+            TemporalKind::Date => Value::Date(value),
+            TemporalKind::Time => Value::Time(value),
+            TemporalKind::DateTime => Value::DateTime(value),
+        }
+    }
+}
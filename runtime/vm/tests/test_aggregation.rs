@@ -0,0 +1,259 @@
+use elise_bytecode::{AggregateFn, AggregateSpec};
+use elise_shared::shared_errors::errors_vm::VmErr;
+use elise_vm::{aggregation, value::Value};
+
+fn string(value: &str) -> Value {
+    Value::String(value.to_string())
+}
+
+fn row(fields: &[(&str, Value)]) -> Value {
+    Value::Dict(
+        fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect(),
+    )
+}
+
+fn rows() -> Vec<Value> {
+    vec![
+        row(&[("country", string("FR")), ("amount", Value::Int(10))]),
+        row(&[("country", string("DE")), ("amount", Value::Float(2.5))]),
+        row(&[("country", string("FR")), ("amount", Value::Null)]),
+        row(&[("country", Value::Null), ("amount", Value::Int(1))]),
+        row(&[("country", string("FR")), ("amount", Value::Int(5))]),
+    ]
+}
+
+fn spec(keys: &[&str]) -> AggregateSpec {
+    AggregateSpec {
+        keys: keys.iter().map(|key| key.to_string()).collect(),
+        aggregations: vec![
+            ("orders".to_string(), AggregateFn::Count, None),
+            (
+                "total".to_string(),
+                AggregateFn::Sum,
+                Some("amount".to_string()),
+            ),
+            (
+                "top".to_string(),
+                AggregateFn::Max,
+                Some("amount".to_string()),
+            ),
+        ],
+    }
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_groups_by_keys_in_order_of_first_items() {
+    let items = vec![Value::Int(1), Value::Int(2), Value::Int(3), Value::Int(4)];
+    let keys = vec![string("b"), string("a"), string("b"), Value::Null];
+
+    assert_eq!(
+        aggregation::group_by(items, &keys),
+        Value::List(vec![
+            Value::List(vec![Value::Int(1), Value::Int(3)]),
+            Value::List(vec![Value::Int(2)]),
+            Value::List(vec![Value::Int(4)]),
+        ])
+    );
+}
+
+#[test]
+fn test_keeps_keys_of_different_types_apart() {
+    let items = vec![Value::Int(1), Value::Int(2), Value::Int(3)];
+    let keys = vec![Value::Int(1), Value::Float(1.0), Value::Float(-0.0)];
+
+    assert_eq!(
+        aggregation::group_by(items, &keys),
+        Value::List(vec![
+            Value::List(vec![Value::Int(1)]),
+            Value::List(vec![Value::Int(2)]),
+            Value::List(vec![Value::Int(3)]),
+        ])
+    );
+}
+
+#[test]
+fn test_aggregates_numbers_skipping_nulls() {
+    let items = vec![Value::Int(4), Value::Null, Value::Float(1.5), Value::Int(1)];
+    let cases = vec![
+        (AggregateFn::Count, Value::Int(4)),
+        (AggregateFn::CountDistinct, Value::Int(4)),
+        (AggregateFn::Sum, Value::Float(6.5)),
+        (AggregateFn::Avg, Value::Float(6.5 / 3.0)),
+        (AggregateFn::Min, Value::Int(1)),
+        (AggregateFn::Max, Value::Int(4)),
+    ];
+
+    for (aggregate_fn, expected) in cases {
+        assert_eq!(
+            aggregation::aggregate(&items, aggregate_fn),
+            Ok(expected),
+            "{aggregate_fn:?}"
+        );
+    }
+}
+
+#[test]
+fn test_sums_ints_exactly() {
+    let items = vec![Value::Int(i64::MAX), Value::Int(1), Value::Int(-2)];
+    assert_eq!(
+        aggregation::aggregate(&items, AggregateFn::Sum),
+        Ok(Value::Int(i64::MAX - 1))
+    );
+
+    let items = vec![Value::Int(i64::MAX), Value::Int(1)];
+    assert_eq!(
+        aggregation::aggregate(&items, AggregateFn::Sum),
+        Ok(Value::Float(i64::MAX as f64 + 1.0))
+    );
+}
+
+#[test]
+fn test_aggregates_empty_list() {
+    let cases = vec![
+        (AggregateFn::Count, Value::Int(0)),
+        (AggregateFn::CountDistinct, Value::Int(0)),
+        (AggregateFn::Sum, Value::Int(0)),
+        (AggregateFn::Avg, Value::Null),
+        (AggregateFn::Min, Value::Null),
+        (AggregateFn::Max, Value::Null),
+    ];
+
+    for (aggregate_fn, expected) in cases {
+        assert_eq!(
+            aggregation::aggregate(&[], aggregate_fn),
+            Ok(expected),
+            "{aggregate_fn:?}"
+        );
+    }
+}
+
+#[test]
+fn test_counts_distinct_values_of_any_type() {
+    let items = vec![
+        string("a"),
+        Value::Null,
+        string("a"),
+        Value::Null,
+        Value::List(vec![Value::Int(1)]),
+        Value::List(vec![Value::Int(1)]),
+    ];
+    assert_eq!(
+        aggregation::aggregate(&items, AggregateFn::CountDistinct),
+        Ok(Value::Int(3))
+    );
+}
+
+#[test]
+fn test_aggregates_groups_of_rows() {
+    assert_eq!(
+        aggregation::group_aggregate(&rows(), &spec(&["country"])),
+        Ok(Value::List(vec![
+            row(&[
+                ("country", string("FR")),
+                ("orders", Value::Int(3)),
+                ("total", Value::Int(15)),
+                ("top", Value::Int(10)),
+            ]),
+            row(&[
+                ("country", string("DE")),
+                ("orders", Value::Int(1)),
+                ("total", Value::Float(2.5)),
+                ("top", Value::Float(2.5)),
+            ]),
+            row(&[
+                ("country", Value::Null),
+                ("orders", Value::Int(1)),
+                ("total", Value::Int(1)),
+                ("top", Value::Int(1)),
+            ]),
+        ]))
+    );
+}
+
+#[test]
+fn test_aggregates_all_rows_into_one_group_without_keys() {
+    assert_eq!(
+        aggregation::group_aggregate(&rows(), &spec(&[])),
+        Ok(Value::List(vec![row(&[
+            ("orders", Value::Int(5)),
+            ("total", Value::Float(18.5)),
+            ("top", Value::Int(10)),
+        ])]))
+    );
+}
+
+#[test]
+fn test_reads_missing_fields_as_null() {
+    let rows = vec![row(&[("amount", Value::Null)]), row(&[])];
+
+    assert_eq!(
+        aggregation::group_aggregate(&rows, &spec(&["country"])),
+        Ok(Value::List(vec![row(&[
+            ("country", Value::Null),
+            ("orders", Value::Int(2)),
+            ("total", Value::Int(0)),
+            ("top", Value::Null),
+        ])]))
+    );
+}
+
+#[test]
+fn test_aggregates_no_rows_into_no_groups() {
+    assert_eq!(
+        aggregation::group_aggregate(&[], &spec(&["country"])),
+        Ok(Value::List(vec![]))
+    );
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_error_if_aggregated_value_is_not_number() {
+    assert_eq!(
+        aggregation::aggregate(&[Value::Int(1), string("2")], AggregateFn::Sum),
+        Err(VmErr::InvalidAggregateValue {
+            aggregation: "sum",
+            found: "String",
+        })
+    );
+    assert_eq!(
+        aggregation::aggregate(&[Value::Bool(true)], AggregateFn::Min),
+        Err(VmErr::InvalidAggregateValue {
+            aggregation: "min",
+            found: "Bool",
+        })
+    );
+}
+
+#[test]
+fn test_returns_error_if_row_is_not_dict() {
+    assert_eq!(
+        aggregation::group_aggregate(&[Value::Int(1)], &spec(&["country"])),
+        Err(VmErr::InvalidAggregateRow { found: "Int" })
+    );
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
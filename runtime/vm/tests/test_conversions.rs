@@ -0,0 +1,182 @@
+use elise_bytecode::{ConvertTo, OnFailure};
+use elise_shared::{
+    shared_errors::{errors_csv_binder::PosInfo, errors_vm::VmErr},
+    shared_types::Span,
+};
+use elise_vm::{conversions::convert, value::Value};
+
+fn span() -> Span {
+    Span { start: 0, end: 10 }
+}
+
+fn strict(value: Value, to: ConvertTo) -> Result<Value, VmErr> {
+    convert(value, to, OnFailure::Fail, None, None, &span())
+}
+
+fn string(value: &str) -> Value {
+    Value::String(value.to_string())
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_converts_to_int() {
+    assert_eq!(strict(string("-42"), ConvertTo::Int), Ok(Value::Int(-42)));
+    assert_eq!(strict(Value::Float(3.0), ConvertTo::Int), Ok(Value::Int(3)));
+    assert_eq!(strict(Value::Bool(true), ConvertTo::Int), Ok(Value::Int(1)));
+}
+
+#[test]
+fn test_converts_to_float() {
+    assert_eq!(
+        strict(string("1e3"), ConvertTo::Float),
+        Ok(Value::Float(1000.0))
+    );
+    assert_eq!(
+        strict(Value::Int(2), ConvertTo::Float),
+        Ok(Value::Float(2.0))
+    );
+}
+
+#[test]
+fn test_converts_to_str_keeping_float_fraction() {
+    assert_eq!(strict(Value::Float(3.0), ConvertTo::Str), Ok(string("3.0")));
+    assert_eq!(strict(Value::Int(7), ConvertTo::Str), Ok(string("7")));
+    assert_eq!(
+        strict(Value::Bool(false), ConvertTo::Str),
+        Ok(string("false"))
+    );
+}
+
+#[test]
+fn test_converts_temporal_values_to_str_in_default_formats() {
+    assert_eq!(
+        strict(Value::Date(19_723), ConvertTo::Str),
+        Ok(string("2024-01-01"))
+    );
+    assert_eq!(
+        strict(Value::DateTime(-1), ConvertTo::Str),
+        Ok(string("1969-12-31T23:59:59"))
+    );
+    assert_eq!(
+        strict(Value::Time(45_000), ConvertTo::Str),
+        Ok(string("12:30:00"))
+    );
+    assert_eq!(
+        strict(Value::Duration(90_000), ConvertTo::Str),
+        Ok(string("P1DT1H"))
+    );
+}
+
+#[test]
+fn test_converts_to_bool() {
+    assert_eq!(
+        strict(string("true"), ConvertTo::Bool),
+        Ok(Value::Bool(true))
+    );
+    assert_eq!(
+        strict(Value::Int(0), ConvertTo::Bool),
+        Ok(Value::Bool(false))
+    );
+}
+
+#[test]
+fn test_results_in_fallback_or_null_on_failure() {
+    assert_eq!(
+        convert(
+            string("n/a"),
+            ConvertTo::Int,
+            OnFailure::Fallback,
+            Some(Value::Int(0)),
+            None,
+            &span()
+        ),
+        Ok(Value::Int(0))
+    );
+    assert_eq!(
+        convert(
+            Value::Null,
+            ConvertTo::Str,
+            OnFailure::Null,
+            None,
+            None,
+            &span()
+        ),
+        Ok(Value::Null)
+    );
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_conversion_failed_with_span() {
+    assert_eq!(
+        strict(string("12a"), ConvertTo::Int),
+        Err(VmErr::ConversionFailed {
+            expected: "Int",
+            found: "\"12a\"".to_string(),
+            cell: None,
+            span: span(),
+        })
+    );
+}
+
+#[test]
+fn test_returns_conversion_failed_with_data_cell() {
+    let cell = PosInfo { row: 2, col: 1 };
+
+    assert_eq!(
+        convert(
+            string("n/a"),
+            ConvertTo::Float,
+            OnFailure::Fail,
+            None,
+            Some(&cell),
+            &span()
+        ),
+        Err(VmErr::ConversionFailed {
+            expected: "Float",
+            found: "\"n/a\"".to_string(),
+            cell: Some(PosInfo { row: 2, col: 1 }),
+            span: span(),
+        })
+    );
+}
+
+#[test]
+fn test_fails_to_convert_out_of_range_and_lossy_values() {
+    for (value, to) in [
+        (Value::Float(1.5), ConvertTo::Int),
+        (Value::Float(1e19), ConvertTo::Int),
+        (string("9223372036854775808"), ConvertTo::Int),
+        (string("inf"), ConvertTo::Float),
+        (Value::Int(2), ConvertTo::Bool),
+        (Value::Null, ConvertTo::Str),
+        (Value::List(vec![]), ConvertTo::Str),
+    ] {
+        assert!(matches!(
+            strict(value, to),
+            Err(VmErr::ConversionFailed { .. })
+        ));
+    }
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
@@ -0,0 +1,195 @@
+use elise_shared::{
+    shared_errors::errors_vm::VmErr,
+    shared_temporal::{self, TemporalKind},
+};
+use elise_vm::{dates, value::Value};
+
+const DAY: i64 = 86_400;
+
+fn date(value: &str) -> i64 {
+    shared_temporal::parse(value, "%Y-%m-%d", TemporalKind::Date).unwrap()
+}
+
+fn datetime(value: &str) -> i64 {
+    shared_temporal::parse(value, "%Y-%m-%d %H:%M:%S", TemporalKind::DateTime).unwrap()
+}
+
+fn time(value: &str) -> i64 {
+    shared_temporal::parse(value, "%H:%M:%S", TemporalKind::Time).unwrap()
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_adds_durations_across_months_and_years() {
+    assert_eq!(
+        dates::add(TemporalKind::Date, date("2024-02-28"), DAY),
+        date("2024-02-29")
+    );
+    assert_eq!(
+        dates::add(TemporalKind::Date, date("2023-12-31"), 2 * DAY),
+        date("2024-01-02")
+    );
+    assert_eq!(
+        dates::add(
+            TemporalKind::DateTime,
+            datetime("2024-03-31 23:30:00"),
+            -3_600
+        ),
+        datetime("2024-03-31 22:30:00")
+    );
+}
+
+#[test]
+fn test_adds_to_date_from_its_midnight() {
+    assert_eq!(
+        dates::add(TemporalKind::Date, date("2024-01-01"), DAY + 3_600),
+        date("2024-01-02")
+    );
+    assert_eq!(
+        dates::add(TemporalKind::Date, date("2024-03-31"), -3_600),
+        date("2024-03-30")
+    );
+}
+
+#[test]
+fn test_wraps_time_around_midnight() {
+    assert_eq!(
+        dates::add(TemporalKind::Time, time("23:30:00"), 3_600),
+        time("00:30:00")
+    );
+    assert_eq!(
+        dates::add(TemporalKind::Time, time("00:30:00"), -3_600),
+        time("23:30:00")
+    );
+}
+
+#[test]
+fn test_diffs_values_into_durations() {
+    assert_eq!(
+        dates::diff(TemporalKind::Date, date("2024-03-01"), date("2024-02-01")),
+        29 * DAY
+    );
+    assert_eq!(
+        dates::diff(
+            TemporalKind::DateTime,
+            datetime("2024-01-01 00:00:00"),
+            datetime("2024-01-01 12:00:00")
+        ),
+        -12 * 3_600
+    );
+}
+
+#[test]
+fn test_truncates_to_units() {
+    let at = datetime("2024-05-16 13:45:30");
+    let inputs = vec![
+        ("year", "2024-01-01 00:00:00"),
+        ("month", "2024-05-01 00:00:00"),
+        // 2024-05-16 is a Thursday.
+        ("week", "2024-05-13 00:00:00"),
+        ("day", "2024-05-16 00:00:00"),
+        ("hour", "2024-05-16 13:00:00"),
+        ("minute", "2024-05-16 13:45:00"),
+    ];
+
+    for (unit, truncated) in inputs {
+        assert_eq!(
+            dates::trunc(TemporalKind::DateTime, at, unit),
+            Ok(datetime(truncated)),
+            "{unit}"
+        );
+    }
+
+    assert_eq!(
+        dates::trunc(TemporalKind::Date, date("1969-12-31"), "week"),
+        Ok(date("1969-12-29"))
+    );
+    assert_eq!(
+        dates::trunc(TemporalKind::Time, time("13:45:30"), "hour"),
+        Ok(time("13:00:00"))
+    );
+}
+
+#[test]
+fn test_formats_values() {
+    assert_eq!(
+        dates::format(TemporalKind::Date, date("2024-01-05"), "%d/%m/%Y"),
+        Ok("05/01/2024".to_string())
+    );
+    assert_eq!(
+        dates::format(TemporalKind::Time, time("07:05:00"), "%H:%M (100%%)"),
+        Ok("07:05 (100%)".to_string())
+    );
+    assert_eq!(
+        dates::format(
+            TemporalKind::DateTime,
+            datetime("1969-12-31 23:59:59"),
+            "%Y-%m-%dT%H:%M:%S"
+        ),
+        Ok("1969-12-31T23:59:59".to_string())
+    );
+}
+
+#[test]
+fn test_keeps_kind_of_temporal_values() {
+    let value = Value::from_temporal(TemporalKind::Time, 60);
+
+    assert_eq!(value, Value::Time(60));
+    assert_eq!(value.temporal(), Some((TemporalKind::Time, 60)));
+    assert_eq!(Value::Duration(60).temporal(), None);
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_invalid_date_unit() {
+    assert_eq!(
+        dates::trunc(TemporalKind::Date, 0, "hour"),
+        Err(VmErr::InvalidDateUnit {
+            unit: "hour".to_string()
+        })
+    );
+    assert_eq!(
+        dates::trunc(TemporalKind::DateTime, 0, "quarter"),
+        Err(VmErr::InvalidDateUnit {
+            unit: "quarter".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_returns_invalid_date_format() {
+    assert_eq!(
+        dates::format(TemporalKind::Time, 0, "%Y"),
+        Err(VmErr::InvalidDateFormat {
+            format: "%Y".to_string()
+        })
+    );
+    assert_eq!(
+        dates::format(TemporalKind::Date, 0, "%"),
+        Err(VmErr::InvalidDateFormat {
+            format: "%".to_string()
+        })
+    );
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
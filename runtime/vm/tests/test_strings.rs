@@ -0,0 +1,117 @@
+use elise_shared::shared_errors::errors_vm::VmErr;
+use elise_vm::strings::{self, Regexes};
+
+// ==================================================================
+//
+//  SUCCESS CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_counts_length_in_chars() {
+    assert_eq!(strings::length("añ😀"), 3);
+    assert_eq!(strings::length(""), 0);
+}
+
+#[test]
+fn test_takes_substring_by_chars() {
+    assert_eq!(strings::substring("привет", 1, Some(3)), "ри");
+    assert_eq!(strings::substring("привет", 4, None), "ет");
+}
+
+#[test]
+fn test_clamps_substring_positions() {
+    assert_eq!(strings::substring("abc", -1, Some(10)), "abc");
+    assert_eq!(strings::substring("abc", 2, Some(1)), "");
+    assert_eq!(strings::substring("abc", 5, None), "");
+}
+
+#[test]
+fn test_splits_and_joins() {
+    assert_eq!(strings::split("a,b,,c", ","), vec!["a", "b", "", "c"]);
+    assert_eq!(strings::split("añ", ""), vec!["a", "ñ"]);
+    assert_eq!(
+        strings::join(&["a".to_string(), "b".to_string()], ", "),
+        "a, b"
+    );
+}
+
+#[test]
+fn test_trims_unicode_whitespace() {
+    assert_eq!(strings::trim("\u{3000} a b\n"), "a b");
+}
+
+#[test]
+fn test_changes_case_of_non_ascii_chars() {
+    assert_eq!(strings::upper("straße"), "STRASSE");
+    assert_eq!(strings::lower("ÀÉ"), "àé");
+}
+
+#[test]
+fn test_checks_prefix_and_suffix() {
+    assert!(strings::starts_with("élan", "é"));
+    assert!(strings::ends_with("élan", "an"));
+    assert!(!strings::ends_with("élan", "é"));
+}
+
+#[test]
+fn test_replaces_every_occurrence() {
+    assert_eq!(strings::replace("a-b-c", "-", "+"), "a+b+c");
+    assert_eq!(strings::replace("abc", "", "+"), "abc");
+}
+
+#[test]
+fn test_matches_and_extracts_by_regex() {
+    let mut regexes = Regexes::new();
+
+    assert!(regexes.matches("order 42", r"\d+").unwrap());
+    assert_eq!(
+        regexes.extract("order 42", r"\d+").unwrap(),
+        Some("42".to_string())
+    );
+    assert_eq!(regexes.extract("order", r"\d+").unwrap(), None);
+}
+
+#[test]
+fn test_extracts_first_group_if_pattern_has_groups() {
+    let mut regexes = Regexes::new();
+
+    assert_eq!(
+        regexes.extract("key=välue", r"=(\w+)").unwrap(),
+        Some("välue".to_string())
+    );
+    assert_eq!(
+        regexes.extract_all("a1 b2 c", r"([a-z])\d").unwrap(),
+        vec!["a", "b"]
+    );
+}
+
+// ==================================================================
+//
+//  SUCCESS CASES END
+//
+// ==================================================================
+
+// ==================================================================
+//
+//  ERROR CASES START
+//
+// ==================================================================
+
+#[test]
+fn test_returns_invalid_regex() {
+    let mut regexes = Regexes::new();
+
+    assert_eq!(
+        regexes.matches("a", "(").unwrap_err(),
+        VmErr::InvalidRegex {
+            pattern: "(".to_string()
+        }
+    );
+}
+
+// ==================================================================
+//
+//  ERROR CASES END
+//
+// ==================================================================
@@ -1,3 +1,5 @@
 pub mod shared_errors;
+pub mod shared_json;
 pub mod shared_node_names;
+pub mod shared_temporal;
 pub mod shared_types;
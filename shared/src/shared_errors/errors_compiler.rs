@@ -0,0 +1,6 @@
+use crate::shared_types::Span;
+
+#[derive(Debug, PartialEq)]
+pub enum CompilerErr {
+    UnsupportedNode { span: Span },
+}
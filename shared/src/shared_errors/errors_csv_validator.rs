@@ -0,0 +1,35 @@
+use crate::shared_errors::errors_csv_binder::PosInfo;
+
+#[derive(Debug, PartialEq)]
+pub enum CsvValidatorErr {
+    // Rows of CSV are items of the root list, each of them
+    // is a dict of columns.
+    InvalRowSchema,
+    MissingCol {
+        col: String,
+    },
+    MissingTypeDefinition {
+        pos: PosInfo,
+        col: String,
+    },
+    TypeMismatch {
+        pos: PosInfo,
+        col: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+    ConstraintViolation {
+        pos: PosInfo,
+        col: String,
+        value: String,
+        constraint: String,
+    },
+    // Value of a date, time or duration column
+    // can't be read with the format of the column.
+    FormatMismatch {
+        pos: PosInfo,
+        col: String,
+        value: String,
+        format: String,
+    },
+}
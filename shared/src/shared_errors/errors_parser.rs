@@ -14,6 +14,32 @@ pub enum ParserErr {
     InvalNum(ParserErrInfo),
     InvalStr(ParserErrInfo),
     UntermStr(ParserErrInfo),
+    InvalUnicodeEsc(ParserErrInfo),
+    InvalHexEsc(ParserErrInfo),
+    UntermComment(ParserErrInfo),
     InvalDictPair(ParserErrInfo),
     InvalFnName(ParserErrInfo),
 }
+
+impl ParserErr {
+    pub fn info(&self) -> &ParserErrInfo {
+        use ParserErr::*;
+
+        match self {
+            UnexpTok(info)
+            | UnexpEoFile(info)
+            | UnexpEoList(info)
+            | UnexpEoDict(info)
+            | UnexpEoFn(info)
+            | UnexpDictKey(info)
+            | InvalNum(info)
+            | InvalStr(info)
+            | UntermStr(info)
+            | InvalUnicodeEsc(info)
+            | InvalHexEsc(info)
+            | UntermComment(info)
+            | InvalDictPair(info)
+            | InvalFnName(info) => info,
+        }
+    }
+}
@@ -1,8 +1,17 @@
+use crate::shared_errors::errors_parser::ParserErr;
 use crate::shared_types::{ArityMismatchKind, Span};
 
+#[derive(Debug, PartialEq)]
+pub enum SchemaImportErr {
+    Unreadable { reason: String },
+    // Every error of the imported file.
+    Parser(Vec<ParserErr>),
+}
+
 #[derive(Debug, PartialEq)]
 pub enum SchemaResolverErr {
     Empty,
+    MissingRoot,
     Unexp {
         span: Span,
     },
@@ -32,4 +41,48 @@ pub enum SchemaResolverErr {
     NoUnionOfUnion {
         span: Span,
     },
+    InvalConstraint {
+        span: Span,
+    },
+    InvalPattern {
+        pattern: String,
+        span: Span,
+    },
+    // Format of a date or a time can't be used to read its values.
+    InvalFormat {
+        format: String,
+        span: Span,
+    },
+    // Alternatives of a union are the same temporal
+    // type with different formats.
+    FormatConflict {
+        span: Span,
+    },
+    TypeRedefinition {
+        name: String,
+        span: Span,
+    },
+    TypeUndefined {
+        name: String,
+        span: Span,
+    },
+    TypeCycle {
+        name: String,
+        span: Span,
+    },
+    Import {
+        path: String,
+        err: SchemaImportErr,
+        span: Span,
+    },
+    ImportCycle {
+        path: String,
+        span: Span,
+    },
+    // Error in the imported file at the path, so its
+    // span points to the source code of that file.
+    InFile {
+        path: String,
+        err: Box<SchemaResolverErr>,
+    },
 }
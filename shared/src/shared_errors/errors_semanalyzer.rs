@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use crate::shared_types::{ArityMismatchKind, Span};
 
 #[derive(Debug, PartialEq)]
@@ -8,14 +10,16 @@ pub enum SemanalyzerErr {
     SymbolDuplicate {
         span: Span,
     },
+    // `fn_name` is either the name of a built-in or of
+    // the symbol a called function is bound to.
     ArityMismatch {
-        fn_name: &'static str,
+        fn_name: Cow<'static, str>,
         kind: ArityMismatchKind,
         found: usize,
         span: Span,
     },
     ArgTypeMismatch {
-        fn_name: &'static str,
+        fn_name: Cow<'static, str>,
         position: usize,
         expected: &'static str,
         found: &'static str,
@@ -28,6 +32,80 @@ pub enum SemanalyzerErr {
         found: &'static str,
         span: Span,
     },
+    BindingsEmpty {
+        span: Span,
+    },
+    BindingValueMissing {
+        span: Span,
+    },
+    // Value can't be destructured with the pattern.
+    PatternTypeMismatch {
+        expected: &'static str,
+        found: &'static str,
+        span: Span,
+    },
+    // Literal list has a different number of items than
+    // the list pattern it's destructured with.
+    PatternLenMismatch {
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+    SlotUnknown {
+        span: Span,
+    },
+    SchemaPathUndefined {
+        path: String,
+        span: Span,
+    },
+    BranchTypeMismatch {
+        fn_name: &'static str,
+        expected: &'static str,
+        found: &'static str,
+        span: Span,
+    },
+    // Function passed to a built-in takes a wrong number of params.
+    CallbackArityMismatch {
+        fn_name: &'static str,
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+    CallbackReturnMismatch {
+        fn_name: &'static str,
+        expected: &'static str,
+        found: &'static str,
+        span: Span,
+    },
+    DivisionByZero {
+        span: Span,
+    },
+    ArithmeticOverflow {
+        span: Span,
+    },
+    InvalidRegex {
+        span: Span,
+    },
+    // Format of a date function can't be used for its value.
+    InvalidDateFormat {
+        span: Span,
+    },
+    // Unit of .date-trunc is unknown or doesn't apply to its value.
+    InvalidDateUnit {
+        span: Span,
+    },
+    // Value of a union is used before it is narrowed down.
+    UnionNotNarrowed {
+        span: Span,
+    },
+    UnionAlternativeUndefined {
+        alternative: String,
+        span: Span,
+    },
+    NotCallable {
+        found: &'static str,
+        span: Span,
+    },
     UnknownFunction {
         span: Span,
     },
@@ -35,3 +113,84 @@ pub enum SemanalyzerErr {
         span: Span,
     },
 }
+
+impl SemanalyzerErr {
+    pub fn span(&self) -> &Span {
+        use SemanalyzerErr::*;
+
+        match self {
+            SymbolUndefined { span }
+            | SymbolDuplicate { span }
+            | ArityMismatch { span, .. }
+            | ArgTypeMismatch { span, .. }
+            | ArgKindMismatch { span, .. }
+            | BindingsEmpty { span }
+            | BindingValueMissing { span }
+            | PatternTypeMismatch { span, .. }
+            | PatternLenMismatch { span, .. }
+            | SlotUnknown { span }
+            | SchemaPathUndefined { span, .. }
+            | BranchTypeMismatch { span, .. }
+            | CallbackArityMismatch { span, .. }
+            | CallbackReturnMismatch { span, .. }
+            | DivisionByZero { span }
+            | ArithmeticOverflow { span }
+            | InvalidRegex { span }
+            | InvalidDateFormat { span }
+            | InvalidDateUnit { span }
+            | UnionNotNarrowed { span }
+            | UnionAlternativeUndefined { span, .. }
+            | NotCallable { span, .. }
+            | UnknownFunction { span }
+            | UnsupportedNode { span } => span,
+        }
+    }
+}
+
+/// Issues that don't prevent the program from being compiled.
+#[derive(Debug, PartialEq)]
+pub enum SemanalyzerWarn {
+    UnusedBinding { span: Span },
+    UnusedParam { span: Span },
+    // Identifier hides the one defined in an outer scope.
+    Shadowing { span: Span },
+}
+
+impl SemanalyzerWarn {
+    pub fn span(&self) -> &Span {
+        use SemanalyzerWarn::*;
+
+        match self {
+            UnusedBinding { span } | UnusedParam { span } | Shadowing { span } => span,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Anything the semantic analyzer reports about the program.
+#[derive(Debug, PartialEq)]
+pub enum SemanalyzerDiag {
+    Err(SemanalyzerErr),
+    Warn(SemanalyzerWarn),
+}
+
+impl SemanalyzerDiag {
+    pub fn severity(&self) -> Severity {
+        match self {
+            SemanalyzerDiag::Err(_) => Severity::Error,
+            SemanalyzerDiag::Warn(_) => Severity::Warning,
+        }
+    }
+
+    pub fn span(&self) -> &Span {
+        match self {
+            SemanalyzerDiag::Err(err) => err.span(),
+            SemanalyzerDiag::Warn(warn) => warn.span(),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{shared_errors::errors_csv_binder::PosInfo, shared_types::Span};
+
+#[derive(Debug, PartialEq)]
+pub enum VmErr {
+    // Pattern built at runtime is not a valid regular expression.
+    InvalidRegex {
+        pattern: String,
+    },
+    // Format built at runtime can't be used for the value.
+    InvalidDateFormat {
+        format: String,
+    },
+    // Unit built at runtime is unknown or doesn't apply to the value.
+    InvalidDateUnit {
+        unit: String,
+    },
+    // Aggregated value is not a number.
+    InvalidAggregateValue {
+        aggregation: &'static str,
+        found: &'static str,
+    },
+    // Grouped row is not a Dict.
+    InvalidAggregateRow {
+        found: &'static str,
+    },
+    // Value can't be converted and the conversion has no fallback.
+    // `cell` is the row and column of the data the value was read
+    // from, if it comes from the data as it is.
+    ConversionFailed {
+        expected: &'static str,
+        found: String,
+        cell: Option<PosInfo>,
+        span: Span,
+    },
+}
@@ -1,25 +1,38 @@
 pub mod errors_common;
+pub mod errors_compiler;
 pub mod errors_csv_binder;
 pub mod errors_csv_parser;
+pub mod errors_csv_validator;
 pub mod errors_parser;
 pub mod errors_schema_resolver;
 pub mod errors_semanalyzer;
+pub mod errors_vm;
 
+use errors_compiler::CompilerErr;
 use errors_csv_binder::CsvBinderErr;
 use errors_csv_parser::CsvParserErr;
+use errors_csv_validator::CsvValidatorErr;
 use errors_parser::ParserErr;
 use errors_schema_resolver::SchemaResolverErr;
-use errors_semanalyzer::SemanalyzerErr;
+use errors_semanalyzer::SemanalyzerDiag;
+use errors_vm::VmErr;
 
 use crate::shared_errors::errors_common::CommonErr;
 
 #[derive(Debug, PartialEq)]
 pub enum LangErr {
     Common(CommonErr),
-    ParserSource(ParserErr),
-    ParserSchema(ParserErr),
+    // Parser collects every error it can recover from,
+    // so there is always at least one error in the list.
+    ParserSource(Vec<ParserErr>),
+    ParserSchema(Vec<ParserErr>),
     SchemaResolver(SchemaResolverErr),
-    SemanticAnalyzer(SemanalyzerErr),
+    // Every diagnostic of the analysis, warnings included, as
+    // they are reported together with errors.
+    SemanticAnalyzer(Vec<SemanalyzerDiag>),
+    Compiler(CompilerErr),
     CsvParser(CsvParserErr),
     CsvBinder(CsvBinderErr),
+    CsvValidator(CsvValidatorErr),
+    Vm(VmErr),
 }
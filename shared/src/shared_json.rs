@@ -0,0 +1,218 @@
+//! # JSON module
+//!
+//! Minimal JSON writer for dumping compiler artifacts (AST,
+//! resolved schema) for external tools. We only need to write
+//! JSON, never read it, so there is no need for a full blown
+//! serialization library.
+
+use crate::shared_types::Span;
+
+/// Version of the dump format. Must be bumped on any
+/// breaking change of the dumped structure.
+///
+/// 1. AST and resolved schema;
+/// 2. Schema constraints are objects instead of strings;
+pub const DUMP_VERSION: i64 = 2;
+
+/// Kinds of dumped documents.
+pub struct DumpKind;
+impl DumpKind {
+    pub const AST: &'static str = "ast";
+    pub const SCHEMA: &'static str = "schema";
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Json>),
+    // Vector instead of a map in order to keep keys order stable.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn str(value: &str) -> Self {
+        Json::String(value.to_string())
+    }
+
+    pub fn object(pairs: Vec<(&str, Json)>) -> Self {
+        Json::Object(
+            pairs
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    /// Top level document of any dump. Always starts with
+    /// the format version and the kind of the document.
+    pub fn document(kind: &str, key: &str, value: Json) -> Self {
+        Json::object(vec![
+            ("version", Json::Int(DUMP_VERSION)),
+            ("kind", Json::str(kind)),
+            (key, value),
+        ])
+    }
+
+    /// Single line JSON.
+    pub fn to_compact(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out, None, 0);
+        out
+    }
+
+    /// Multi line JSON indented with two spaces.
+    pub fn to_pretty(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out, Some(2), 0);
+        out
+    }
+
+    fn write(&self, out: &mut String, indent: Option<usize>, depth: usize) {
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+            Json::Int(value) => out.push_str(&value.to_string()),
+            // JSON has no NaN and infinities.
+            Json::Float(value) if !value.is_finite() => out.push_str("null"),
+            Json::Float(value) => out.push_str(&format!("{value:?}")),
+            Json::String(value) => Self::write_str(out, value),
+            Json::Array(items) => {
+                Self::write_seq(out, '[', ']', items, indent, depth, |out, item| {
+                    item.write(out, indent, depth + 1)
+                })
+            }
+            Json::Object(pairs) => {
+                Self::write_seq(out, '{', '}', pairs, indent, depth, |out, (key, value)| {
+                    Self::write_str(out, key);
+                    out.push(':');
+                    if indent.is_some() {
+                        out.push(' ');
+                    }
+                    value.write(out, indent, depth + 1);
+                })
+            }
+        }
+    }
+
+    fn write_seq<T>(
+        out: &mut String,
+        open: char,
+        close: char,
+        items: &[T],
+        indent: Option<usize>,
+        depth: usize,
+        write_item: impl Fn(&mut String, &T),
+    ) {
+        out.push(open);
+
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            if let Some(indent) = indent {
+                out.push('\n');
+                out.push_str(&" ".repeat(indent * (depth + 1)));
+            }
+            write_item(out, item);
+        }
+
+        if let Some(indent) = indent
+            && !items.is_empty()
+        {
+            out.push('\n');
+            out.push_str(&" ".repeat(indent * depth));
+        }
+
+        out.push(close);
+    }
+
+    fn write_str(out: &mut String, value: &str) {
+        out.push('"');
+        for c in value.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+    }
+}
+
+impl From<&Span> for Json {
+    fn from(span: &Span) -> Self {
+        Json::object(vec![
+            ("start", Json::Int(span.start as i64)),
+            ("end", Json::Int(span.end as i64)),
+        ])
+    }
+}
+
+// ==================================================================
+//
+// TESTS START
+//
+// ==================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{shared_json::Json, shared_types::Span};
+
+    #[test]
+    fn should_write_compact() {
+        let json = Json::object(vec![
+            ("a", Json::Null),
+            ("b", Json::Array(vec![Json::Bool(true), Json::Int(-1)])),
+            ("c", Json::from(&Span { start: 1, end: 2 })),
+            ("d", Json::Array(vec![])),
+        ]);
+        assert_eq!(
+            json.to_compact(),
+            r#"{"a":null,"b":[true,-1],"c":{"start":1,"end":2},"d":[]}"#
+        );
+    }
+
+    #[test]
+    fn should_write_pretty() {
+        let json = Json::object(vec![
+            ("a", Json::Array(vec![Json::Int(1), Json::Object(vec![])])),
+            ("b", Json::str("x")),
+        ]);
+        assert_eq!(
+            json.to_pretty(),
+            "{\n  \"a\": [\n    1,\n    {}\n  ],\n  \"b\": \"x\"\n}"
+        );
+    }
+
+    #[test]
+    fn should_write_floats() {
+        let json = Json::Array(vec![
+            Json::Float(1.0),
+            Json::Float(-0.5),
+            Json::Float(f64::NAN),
+        ]);
+        assert_eq!(json.to_compact(), "[1.0,-0.5,null]");
+    }
+
+    #[test]
+    fn should_escape_strings() {
+        assert_eq!(
+            Json::str("a\"b\\c\nd\te\u{1}😄").to_compact(),
+            r#""a\"b\\c\nd\te\u0001😄""#
+        );
+    }
+}
+
+// ==================================================================
+//
+// TESTS END
+//
+// ==================================================================
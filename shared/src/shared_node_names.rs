@@ -5,6 +5,10 @@ impl NodeName {
     pub const FLOAT: &'static str = "Float";
     pub const STRING: &'static str = "String";
     pub const BOOL: &'static str = "Bool";
+    pub const DATE: &'static str = "Date";
+    pub const TIME: &'static str = "Time";
+    pub const DATETIME: &'static str = "DateTime";
+    pub const DURATION: &'static str = "Duration";
     pub const NULL: &'static str = "Null";
     pub const LIST: &'static str = "List";
     pub const DICT: &'static str = "Dict";
@@ -14,7 +18,28 @@ impl NodeName {
     pub const CALL: &'static str = "Call";
     pub const SYMBOL: &'static str = "Symbol";
     pub const PRIMITIVE: &'static str = "Primitive";
+    // Either Int or Float.
+    pub const NUMBER: &'static str = "Number";
+    // Either Date, Time or DateTime.
+    pub const TEMPORAL: &'static str = "Temporal";
+    pub const FUNCTION: &'static str = "Function";
+    // Aggregation call inside of `.aggregate`.
+    pub const AGGREGATION: &'static str = "Aggregation";
+    // Identifier, or List or Dict of patterns inside of bindings.
+    pub const PATTERN: &'static str = "Pattern";
+    pub const UNKNOWN: &'static str = "Unknown";
     pub const CALL_DEFINE: &'static str = "CallDefine";
     pub const CALL_LET: &'static str = "CallLet";
-    // pub const UNION: &'static str = "Union";
+    pub const CALL_FN: &'static str = "CallFn";
+    pub const CALL_GET: &'static str = "CallGet";
+    pub const CALL_OP: &'static str = "CallOp";
+    pub const CALL_COLLECTION: &'static str = "CallCollection";
+    pub const CALL_AGGREGATE: &'static str = "CallAggregate";
+    pub const CALL_CONVERT: &'static str = "CallConvert";
+    pub const CALL_NARROW: &'static str = "CallNarrow";
+    pub const CALL_IF: &'static str = "CallIf";
+    pub const CALL_COND: &'static str = "CallCond";
+    pub const CALL_WHEN: &'static str = "CallWhen";
+    pub const CALL_GET_SLOT: &'static str = "CallGetSlot";
+    pub const UNION: &'static str = "Union";
 }
@@ -0,0 +1,583 @@
+//! # Temporal values
+//!
+//! Dates, times and durations shared by the data layer, which reads
+//! them from the data, and the VM, which computes with them. There
+//! are no time zones, so every value is a plain integer:
+//!
+//! 1. Date     - days since 1970-01-01;
+//! 2. Time     - seconds since midnight;
+//! 3. DateTime - seconds since 1970-01-01T00:00:00;
+//! 4. Duration - seconds, written in ISO 8601 as PnDTnHnMnS;
+//!
+//! Durations have no years and months since their length varies.
+//!
+//! Formats of dates and times are a subset of strftime:
+//!
+//! %Y - year of 4 digits;
+//! %m - month, %d - day of the month;
+//! %H - hour of 24, %M - minute, %S - second;
+//! %% - the percent sign itself;
+//!
+//! All fields but the year take exactly 2 digits, both when read
+//! and written. Every field can be used only once in a format.
+
+const SECS_PER_DAY: i64 = 86_400;
+const SECS_PER_HOUR: i64 = 3_600;
+const SECS_PER_MINUTE: i64 = 60;
+
+/// How durations are written, used in error reports.
+pub const DURATION_FORMAT: &str = "PnDTnHnMnS";
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TemporalKind {
+    Date,
+    Time,
+    DateTime,
+}
+
+impl TemporalKind {
+    pub fn default_format(&self) -> &'static str {
+        match self {
+            TemporalKind::Date => "%Y-%m-%d",
+            TemporalKind::Time => "%H:%M:%S",
+            TemporalKind::DateTime => "%Y-%m-%dT%H:%M:%S",
+        }
+    }
+
+    // Fields values of the kind have.
+    fn fields(&self) -> &'static [char] {
+        match self {
+            TemporalKind::Date => &['Y', 'm', 'd'],
+            TemporalKind::Time => &['H', 'M', 'S'],
+            TemporalKind::DateTime => &['Y', 'm', 'd', 'H', 'M', 'S'],
+        }
+    }
+
+    // Fields a value of the kind can't be read without,
+    // seconds default to 0.
+    fn required_fields(&self) -> &'static [char] {
+        match self {
+            TemporalKind::Date => &['Y', 'm', 'd'],
+            TemporalKind::Time => &['H', 'M'],
+            TemporalKind::DateTime => &['Y', 'm', 'd', 'H', 'M'],
+        }
+    }
+}
+
+/// Unit temporal values are truncated to. Weeks start on Monday.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TemporalUnit {
+    Year,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+}
+
+impl TemporalUnit {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let unit = match name {
+            "year" => TemporalUnit::Year,
+            "month" => TemporalUnit::Month,
+            "week" => TemporalUnit::Week,
+            "day" => TemporalUnit::Day,
+            "hour" => TemporalUnit::Hour,
+            "minute" => TemporalUnit::Minute,
+            _ => return None,
+        };
+
+        Some(unit)
+    }
+
+    /// Whether values of the kind can be truncated to the unit.
+    pub fn applies_to(&self, kind: TemporalKind) -> bool {
+        match self {
+            TemporalUnit::Year | TemporalUnit::Month | TemporalUnit::Week | TemporalUnit::Day => {
+                kind != TemporalKind::Time
+            }
+            TemporalUnit::Hour | TemporalUnit::Minute => kind != TemporalKind::Date,
+        }
+    }
+}
+
+// ==================================================================
+//
+// FORMATS START
+//
+// ==================================================================
+
+#[derive(Debug, PartialEq)]
+enum FormatItem {
+    Literal(char),
+    Field(char),
+}
+
+/// Splits the format into literals and fields. None if the
+/// format has an unknown or a repeated field.
+fn compile_format(format: &str) -> Option<Vec<FormatItem>> {
+    let mut items = vec![];
+    let mut chars = format.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            items.push(FormatItem::Literal(c));
+            continue;
+        }
+
+        match chars.next()? {
+            '%' => items.push(FormatItem::Literal('%')),
+            field @ ('Y' | 'm' | 'd' | 'H' | 'M' | 'S') => {
+                if items.contains(&FormatItem::Field(field)) {
+                    return None;
+                }
+                items.push(FormatItem::Field(field));
+            }
+            _ => return None,
+        }
+    }
+
+    Some(items)
+}
+
+fn fields_of(items: &[FormatItem]) -> impl Iterator<Item = char> + '_ {
+    items.iter().filter_map(|item| match item {
+        FormatItem::Field(field) => Some(*field),
+        FormatItem::Literal(_) => None,
+    })
+}
+
+/// Whether values of the kind can be read with the format,
+/// which needs every field the value can't do without.
+pub fn can_parse(format: &str, kind: TemporalKind) -> bool {
+    compile_format(format).is_some_and(|items| {
+        fields_of(&items).all(|field| kind.fields().contains(&field))
+            && kind
+                .required_fields()
+                .iter()
+                .all(|field| items.contains(&FormatItem::Field(*field)))
+    })
+}
+
+/// Whether values of the kind can be written with the format.
+pub fn can_format(format: &str, kind: TemporalKind) -> bool {
+    compile_format(format)
+        .is_some_and(|items| fields_of(&items).all(|field| kind.fields().contains(&field)))
+}
+
+/// Reads a value of the kind, None if the value doesn't
+/// match the format or is not a valid date or time. Fields
+/// must be padded, so "2024-1-5" doesn't match "%Y-%m-%d".
+pub fn parse(value: &str, format: &str, kind: TemporalKind) -> Option<i64> {
+    if !can_parse(format, kind) {
+        return None;
+    }
+
+    let (mut year, mut month, mut day) = (1970, 1, 1);
+    let (mut hour, mut minute, mut second) = (0, 0, 0);
+    let mut rest = value;
+
+    for item in compile_format(format)? {
+        match item {
+            FormatItem::Literal(c) => rest = rest.strip_prefix(c)?,
+            FormatItem::Field(field) => {
+                let len = if field == 'Y' { 4 } else { 2 };
+
+                if rest
+                    .bytes()
+                    .take(len)
+                    .take_while(u8::is_ascii_digit)
+                    .count()
+                    < len
+                {
+                    return None;
+                }
+
+                let number: i64 = rest[..len].parse().ok()?;
+                rest = &rest[len..];
+
+                match field {
+                    'Y' => year = number,
+                    'm' => month = number,
+                    'd' => day = number,
+                    'H' => hour = number,
+                    'M' => minute = number,
+                    _ => second = number,
+                }
+            }
+        }
+    }
+
+    let valid = rest.is_empty()
+        && (1..=12).contains(&month)
+        && (1..=days_in_month(year, month)).contains(&day)
+        && (0..24).contains(&hour)
+        && (0..60).contains(&minute)
+        && (0..60).contains(&second);
+
+    if !valid {
+        return None;
+    }
+
+    let days = days_from_civil(year, month, day);
+    let secs = hour * SECS_PER_HOUR + minute * SECS_PER_MINUTE + second;
+
+    Some(match kind {
+        TemporalKind::Date => days,
+        TemporalKind::Time => secs,
+        TemporalKind::DateTime => days * SECS_PER_DAY + secs,
+    })
+}
+
+/// Writes a value of the kind, None if the format
+/// can't be used for the kind.
+pub fn format(value: i64, format: &str, kind: TemporalKind) -> Option<String> {
+    if !can_format(format, kind) {
+        return None;
+    }
+
+    let (days, secs) = match kind {
+        TemporalKind::Date => (value, 0),
+        TemporalKind::Time => (0, value),
+        TemporalKind::DateTime => (
+            value.div_euclid(SECS_PER_DAY),
+            value.rem_euclid(SECS_PER_DAY),
+        ),
+    };
+    let (year, month, day) = civil_from_days(days);
+
+    let mut written = String::new();
+
+    for item in compile_format(format)? {
+        match item {
+            FormatItem::Literal(c) => written.push(c),
+            FormatItem::Field('Y') => written.push_str(&format!("{year:04}")),
+            FormatItem::Field(field) => {
+                let number = match field {
+                    'm' => month,
+                    'd' => day,
+                    'H' => secs / SECS_PER_HOUR,
+                    'M' => secs % SECS_PER_HOUR / SECS_PER_MINUTE,
+                    _ => secs % SECS_PER_MINUTE,
+                };
+                written.push_str(&format!("{number:02}"));
+            }
+        }
+    }
+
+    Some(written)
+}
+
+// ==================================================================
+//
+// FORMATS END
+//
+// ==================================================================
+
+// ==================================================================
+//
+// DURATIONS START
+//
+// ==================================================================
+
+/// Reads a duration like "P1DT2H30M" or "-PT15M" into seconds.
+/// Weeks are allowed as "P2W".
+pub fn parse_duration(value: &str) -> Option<i64> {
+    let (sign, value) = match value.strip_prefix('-') {
+        Some(value) => (-1, value),
+        None => (1, value),
+    };
+    let value = value.strip_prefix('P')?;
+
+    let (date_part, time_part) = match value.split_once('T') {
+        // Time designator must be followed by some time.
+        Some((_, "")) => return None,
+        Some((date_part, time_part)) => (date_part, Some(time_part)),
+        None => (value, None),
+    };
+
+    let mut secs = 0i64;
+    let mut has_components = false;
+
+    for (part, units) in [
+        (
+            date_part,
+            &[('W', 7 * SECS_PER_DAY), ('D', SECS_PER_DAY)][..],
+        ),
+        (
+            time_part.unwrap_or(""),
+            &[('H', SECS_PER_HOUR), ('M', SECS_PER_MINUTE), ('S', 1)][..],
+        ),
+    ] {
+        let mut rest = part;
+
+        // Components must go in the order of their units.
+        for (designator, unit_secs) in units {
+            let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+
+            if len == 0 || !rest[len..].starts_with(*designator) {
+                continue;
+            }
+
+            let number: i64 = rest[..len].parse().ok()?;
+            secs = secs.checked_add(number.checked_mul(*unit_secs)?)?;
+            has_components = true;
+            rest = &rest[len + 1..];
+        }
+
+        if !rest.is_empty() {
+            return None;
+        }
+    }
+
+    has_components.then_some(sign * secs)
+}
+
+/// Writes seconds as a duration, the inverse of `parse_duration`
+/// that never uses weeks.
+pub fn format_duration(secs: i64) -> String {
+    let mut written = String::from(if secs < 0 { "-P" } else { "P" });
+    let secs = secs.unsigned_abs();
+
+    let days = secs / SECS_PER_DAY as u64;
+    let hours = secs % SECS_PER_DAY as u64 / SECS_PER_HOUR as u64;
+    let minutes = secs % SECS_PER_HOUR as u64 / SECS_PER_MINUTE as u64;
+    let seconds = secs % SECS_PER_MINUTE as u64;
+
+    if days > 0 {
+        written.push_str(&format!("{days}D"));
+    }
+
+    if hours > 0 || minutes > 0 || seconds > 0 || days == 0 {
+        written.push('T');
+
+        if hours > 0 {
+            written.push_str(&format!("{hours}H"));
+        }
+        if minutes > 0 {
+            written.push_str(&format!("{minutes}M"));
+        }
+        if seconds > 0 || (hours == 0 && minutes == 0) {
+            written.push_str(&format!("{seconds}S"));
+        }
+    }
+
+    written
+}
+
+// ==================================================================
+//
+// DURATIONS END
+//
+// ==================================================================
+
+// ==================================================================
+//
+// ARITHMETIC START
+//
+// ==================================================================
+
+/// Adds the duration to the value. Dates are taken as their
+/// midnights and the result is the date the sum falls on, so
+/// 2024-03-31 - PT1H is 2024-03-30. Times wrap around midnight.
+pub fn add(value: i64, duration: i64, kind: TemporalKind) -> i64 {
+    match kind {
+        TemporalKind::Date => value.saturating_add(duration.div_euclid(SECS_PER_DAY)),
+        TemporalKind::Time => (value + duration.rem_euclid(SECS_PER_DAY)) % SECS_PER_DAY,
+        TemporalKind::DateTime => value.saturating_add(duration),
+    }
+}
+
+/// Duration from `to` to `from`, negative if `from` is earlier.
+pub fn diff(from: i64, to: i64, kind: TemporalKind) -> i64 {
+    match kind {
+        TemporalKind::Date => (from - to).saturating_mul(SECS_PER_DAY),
+        TemporalKind::Time | TemporalKind::DateTime => from.saturating_sub(to),
+    }
+}
+
+/// Truncates the value to the start of the unit, None if the
+/// unit doesn't apply to the kind.
+pub fn truncate(value: i64, unit: TemporalUnit, kind: TemporalKind) -> Option<i64> {
+    if !unit.applies_to(kind) {
+        return None;
+    }
+
+    let truncate_days = |days: i64| {
+        let (year, month, _) = civil_from_days(days);
+
+        match unit {
+            TemporalUnit::Year => days_from_civil(year, 1, 1),
+            TemporalUnit::Month => days_from_civil(year, month, 1),
+            // 1970-01-01 is a Thursday, the fourth day of its week.
+            TemporalUnit::Week => days - (days + 3).rem_euclid(7),
+            _ => days,
+        }
+    };
+    let truncate_secs = |secs: i64| match unit {
+        TemporalUnit::Hour => secs - secs.rem_euclid(SECS_PER_HOUR),
+        TemporalUnit::Minute => secs - secs.rem_euclid(SECS_PER_MINUTE),
+        _ => secs - secs.rem_euclid(SECS_PER_DAY),
+    };
+
+    Some(match (kind, unit) {
+        (TemporalKind::Date, _) => truncate_days(value),
+        (TemporalKind::Time, _)
+        | (_, TemporalUnit::Day | TemporalUnit::Hour | TemporalUnit::Minute) => {
+            truncate_secs(value)
+        }
+        (TemporalKind::DateTime, _) => truncate_days(value.div_euclid(SECS_PER_DAY)) * SECS_PER_DAY,
+    })
+}
+
+// ==================================================================
+//
+// ARITHMETIC END
+//
+// ==================================================================
+
+// ==================================================================
+//
+// CALENDAR START
+//
+// Conversions between days and proleptic Gregorian dates, see
+// https://howardhinnant.github.io/date_algorithms.html
+//
+// ==================================================================
+
+pub fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    // Years start in March, so leap days are at their ends.
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+pub fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// ==================================================================
+//
+// CALENDAR END
+//
+// ==================================================================
+
+// ==================================================================
+//
+// TESTS START
+//
+// ==================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::shared_temporal::{
+        TemporalKind, add, civil_from_days, days_from_civil, format, format_duration, parse,
+        parse_duration,
+    };
+
+    #[test]
+    fn should_convert_days_and_dates_both_ways() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+
+        for days in [-719_468, -1, 0, 59, 11_016, 19_723, 2_932_896] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn should_read_and_write_with_format() {
+        let date = parse("29.02.2024", "%d.%m.%Y", TemporalKind::Date).unwrap();
+        assert_eq!(
+            format(date, "%Y-%m-%d", TemporalKind::Date).unwrap(),
+            "2024-02-29"
+        );
+
+        let datetime = parse("2024-01-05 07:05", "%Y-%m-%d %H:%M", TemporalKind::DateTime).unwrap();
+        assert_eq!(
+            format(datetime, "%Y-%m-%dT%H:%M:%S", TemporalKind::DateTime).unwrap(),
+            "2024-01-05T07:05:00"
+        );
+    }
+
+    #[test]
+    fn should_not_read_fields_without_padding() {
+        assert_eq!(parse("2024-1-5", "%Y-%m-%d", TemporalKind::Date), None);
+        assert_eq!(parse("7:5:0", "%H:%M:%S", TemporalKind::Time), None);
+        assert_eq!(parse("24-01-05", "%Y-%m-%d", TemporalKind::Date), None);
+        assert_eq!(parse("2024-001-05", "%Y-%m-%d", TemporalKind::Date), None);
+    }
+
+    #[test]
+    fn should_add_durations_to_dates_by_days_they_fall_on() {
+        let date = parse("2024-03-31", "%Y-%m-%d", TemporalKind::Date).unwrap();
+        let day_before = parse("2024-03-30", "%Y-%m-%d", TemporalKind::Date).unwrap();
+        let day_after = parse("2024-04-01", "%Y-%m-%d", TemporalKind::Date).unwrap();
+
+        assert_eq!(add(date, -3_600, TemporalKind::Date), day_before);
+        assert_eq!(add(date, -86_400, TemporalKind::Date), day_before);
+        assert_eq!(add(date, -86_401, TemporalKind::Date), day_before - 1);
+        assert_eq!(add(date, 3_600, TemporalKind::Date), date);
+        assert_eq!(add(date, 86_400, TemporalKind::Date), day_after);
+    }
+
+    #[test]
+    fn should_not_read_invalid_values() {
+        assert_eq!(parse("2023-02-29", "%Y-%m-%d", TemporalKind::Date), None);
+        assert_eq!(parse("24:00:00", "%H:%M:%S", TemporalKind::Time), None);
+        assert_eq!(parse("2024-01-05x", "%Y-%m-%d", TemporalKind::Date), None);
+        assert_eq!(parse("12:30", "%H:%M", TemporalKind::Date), None);
+    }
+
+    #[test]
+    fn should_read_and_write_durations() {
+        assert_eq!(parse_duration("P1DT2H30M"), Some(95_400));
+        assert_eq!(parse_duration("-PT15M"), Some(-900));
+        assert_eq!(parse_duration("P2W"), Some(1_209_600));
+        assert_eq!(parse_duration("P"), None);
+        assert_eq!(parse_duration("PT"), None);
+        assert_eq!(parse_duration("PT1S2M"), None);
+        assert_eq!(parse_duration("P1Y"), None);
+
+        assert_eq!(format_duration(95_400), "P1DT2H30M");
+        assert_eq!(format_duration(-900), "-PT15M");
+        assert_eq!(format_duration(86_400), "P1D");
+        assert_eq!(format_duration(0), "PT0S");
+    }
+}
+
+// ==================================================================
+//
+// TESTS END
+//
+// ==================================================================
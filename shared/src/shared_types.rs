@@ -12,10 +12,14 @@ impl Keyword {
     pub const TRUE: &str = "true";
     pub const FALSE: &str = "false";
     pub const NULL: &str = "null";
+    // Names of threading calls, the only call names
+    // that are not identifiers.
+    pub const THREAD_FIRST: &str = "->";
+    pub const THREAD_LAST: &str = "->>";
 }
 
 /// Determines what exactly expected arguments number means.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ArityMismatchKind {
     Eq(usize),
     MoreEq(usize),
@@ -35,4 +39,16 @@ impl ArityMismatchKind {
             ArityMismatchKind::Range((min, max)) => format!(">={min}, <={max}"),
         }
     }
+
+    /// Whether the `found` number of arguments satisfies the requirement.
+    pub fn accepts(&self, found: usize) -> bool {
+        match self {
+            ArityMismatchKind::Eq(n) => found == *n,
+            ArityMismatchKind::MoreEq(n) => found >= *n,
+            ArityMismatchKind::LessEq(n) => found <= *n,
+            ArityMismatchKind::More(n) => found > *n,
+            ArityMismatchKind::Less(n) => found < *n,
+            ArityMismatchKind::Range((min, max)) => found >= *min && found <= *max,
+        }
+    }
 }